@@ -0,0 +1,165 @@
+/// Sizes every migration by how far the islands have drifted apart, in place of the `MigrantCount`. Islands that have
+/// diverged exchange more individuals, to share what they have found, and islands that have become alike exchange
+/// fewer, to keep what diversity is left.
+///
+/// Before each migration the world measures `World::island_distances`. An island's divergence is how much further its
+/// individuals are, on average, from those of the other islands than from each other: 0.0 when the other islands are
+/// no more different than its own individuals, and approaching 1.0 as they grow apart. The island then sends
+/// `min_migrants` plus that fraction of the range up to `max_migrants`. The measurement compares every pair of
+/// individuals in the world, and it is only as meaningful as the `Genetics::distance` of the genetics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveMigration {
+    /// The number of individuals an island sends when it is no more different from the other islands than from itself
+    ///
+    /// Default: 1
+    pub min_migrants: usize,
+
+    /// The number of individuals an island sends when it has diverged completely. Must not be less than
+    /// `min_migrants` or more than `individuals_per_island`.
+    ///
+    /// Default: 5
+    pub max_migrants: usize,
+}
+
+impl Default for AdaptiveMigration {
+    fn default() -> Self {
+        AdaptiveMigration {
+            min_migrants: 1,
+            max_migrants: 5,
+        }
+    }
+}
+
+impl AdaptiveMigration {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.min_migrants <= self.max_migrants
+    }
+
+    /// Returns the number of individuals `island` should send, given the distances measured by
+    /// `World::island_distances`. An island whose distance to the others cannot be measured sends `max_migrants`.
+    pub(crate) fn migrants(&self, distances: &[Vec<Option<f64>>], island: usize) -> usize {
+        let between: Vec<f64> = distances[island]
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != island)
+            .filter_map(|(_, distance)| *distance)
+            .collect();
+        let divergence = if between.is_empty() {
+            1.0
+        } else {
+            let between = between.iter().sum::<f64>() / between.len() as f64;
+            let within = distances[island][island].unwrap_or(0.0);
+            if between > 0.0 {
+                (1.0 - within / between).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        let range = (self.max_migrants - self.min_migrants) as f64;
+        self.min_migrants + (range * divergence).round() as usize
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn diverged_islands_send_more_migrants() {
+        let adaptive = AdaptiveMigration {
+            min_migrants: 1,
+            max_migrants: 9,
+        };
+        let distances = vec![
+            vec![Some(2.0), Some(2.0), Some(2.0)],
+            vec![Some(2.0), Some(1.0), Some(4.0)],
+            vec![Some(2.0), Some(4.0), None],
+        ];
+
+        // Island 0 is as far from the others as from itself, and island 1 is three times as far
+        assert_eq!(1, adaptive.migrants(&distances, 0));
+        assert_eq!(6, adaptive.migrants(&distances, 1));
+        assert_eq!(9, adaptive.migrants(&distances, 2));
+        assert_eq!(9, adaptive.migrants(&[vec![Some(1.0)]], 0));
+    }
+
+    #[test]
+    fn adaptive_migration_follows_island_distances() {
+        // Ids are handed out island by island, so measured by id the islands are far apart
+        struct IdDistance(CountingGenetics);
+
+        impl Genetics for IdDistance {
+            fn random_individual(&self, rng: &mut rand::rngs::StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut rand::rngs::StdRng, individual: u64, points: usize) -> u64 {
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(
+                &self,
+                rng: &mut rand::rngs::StdRng,
+                a: u64,
+                b: u64,
+                points: usize,
+            ) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn distance(&self, a: u64, b: u64) -> f64 {
+                a.abs_diff(b) as f64
+            }
+        }
+
+        let adaptive = AdaptiveMigration {
+            min_migrants: 1,
+            max_migrants: 5,
+        };
+        let requested = |log: &[MigrationRecord]| -> Vec<usize> {
+            log.iter().map(|record| record.requested).collect()
+        };
+
+        // Under the discrete distance of CountingGenetics the islands are no further apart than their own individuals
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(6)
+            .with_generations_between_migrations(1)
+            .with_migrating_individuals(3)
+            .with_adaptive_migration(adaptive.clone())
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        assert_eq!(vec![1, 1], requested(world.migration_log()));
+
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(IdDistance(CountingGenetics::default()))
+            .build()
+            .unwrap();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(6)
+            .with_generations_between_migrations(1)
+            .with_migrating_individuals(3)
+            .with_adaptive_migration(adaptive);
+        builder.add_island("a", Box::new(IdScoreEngine));
+        builder.add_island("b", Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(1).unwrap();
+        assert_eq!(vec![3, 3], requested(world.migration_log()));
+
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_adaptive_migration(AdaptiveMigration::default())
+                .build(),
+            Err(GeneticError::InvalidAdaptiveMigration)
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::Genetics;
+
+/// Collects the best migrants from every island without ever evaluating them. Each migrant is offered to the archive
+/// with the score its home island gave it. An archive holds at most `capacity` individuals, and keeps only the best of
+/// any individuals that are closer together than `min_distance` according to `Genetics::distance`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Archive {
+    capacity: usize,
+    min_distance: f64,
+    members: Vec<ArchivedIndividual>,
+}
+
+/// An individual stored in an archive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArchivedIndividual {
+    /// The individual
+    pub id: u64,
+
+    /// The score given by the island the individual emigrated from
+    pub score: u64,
+
+    /// The score on the scale shared by every island, according to the world's `ScoreNormalization`. Members are
+    /// ranked by this score.
+    pub normalized_score: f64,
+
+    /// The index of the island the individual emigrated from
+    pub island: usize,
+
+    /// The generation count of the world when the individual was archived
+    pub generation: usize,
+}
+
+impl Archive {
+    pub(crate) fn new(capacity: usize, min_distance: f64) -> Archive {
+        Archive {
+            capacity,
+            min_distance,
+            members: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of individuals in the archive
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of individuals in the archive
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns true if nothing has been archived
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Returns the archived individuals, most fit first
+    pub fn members(&self) -> &[ArchivedIndividual] {
+        &self.members
+    }
+
+    /// Returns the most fit archived individual
+    pub fn best(&self) -> Option<&ArchivedIndividual> {
+        self.members.first()
+    }
+
+    /// Removes every member
+    pub(crate) fn clear(&mut self) {
+        self.members.clear();
+    }
+
+    /// Offers an individual to the archive. Returns true if it was archived.
+    pub(crate) fn offer<G: Genetics>(
+        &mut self,
+        candidate: ArchivedIndividual,
+        genetics: &G,
+    ) -> bool {
+        // The candidate must beat every member it is too similar to, and then replaces them
+        let near: Vec<usize> = self
+            .members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| genetics.distance(member.id, candidate.id) < self.min_distance)
+            .map(|(index, _)| index)
+            .collect();
+        if near
+            .iter()
+            .any(|&index| self.members[index].normalized_score >= candidate.normalized_score)
+        {
+            return false;
+        }
+        for &index in near.iter().rev() {
+            self.members.remove(index);
+        }
+
+        // When full, the candidate must beat the least fit member
+        if self.members.len() >= self.capacity {
+            match self.members.last() {
+                Some(worst) if worst.normalized_score < candidate.normalized_score => {
+                    self.members.pop();
+                }
+                _ => return false,
+            }
+        }
+
+        let position = self
+            .members
+            .partition_point(|member| member.normalized_score >= candidate.normalized_score);
+        self.members.insert(position, candidate);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::CountingGenetics;
+    use crate::*;
+
+    fn archived(id: u64, score: u64) -> ArchivedIndividual {
+        ArchivedIndividual {
+            id,
+            score,
+            normalized_score: score as f64,
+            island: 0,
+            generation: 0,
+        }
+    }
+
+    #[test]
+    fn archive_keeps_the_best_unique_individuals() {
+        let genetics = CountingGenetics::default();
+        let mut archive = Archive::new(3, 0.5);
+
+        assert!(archive.offer(archived(1, 10), &genetics));
+        assert!(archive.offer(archived(2, 30), &genetics));
+        assert!(archive.offer(archived(3, 20), &genetics));
+
+        // The same individual is only archived once, and a full archive rejects anything less fit than its worst
+        assert!(!archive.offer(archived(2, 30), &genetics));
+        assert!(!archive.offer(archived(4, 5), &genetics));
+        assert!(archive.offer(archived(5, 25), &genetics));
+
+        let ids: Vec<u64> = archive.members().iter().map(|member| member.id).collect();
+        assert_eq!(vec![2, 5, 3], ids);
+    }
+
+    #[test]
+    fn archive_limits_are_exclusive() {
+        let genetics = CountingGenetics::default();
+
+        // An archive with no capacity takes nothing
+        let mut empty = Archive::new(0, 0.5);
+        assert!(!empty.offer(archived(1, 10), &genetics));
+        assert!(empty.is_empty() && empty.best().is_none());
+
+        // A full archive only takes a candidate that beats its worst member, not one that ties it
+        let mut full = Archive::new(2, 0.5);
+        assert!(full.offer(archived(1, 10), &genetics));
+        assert!(full.offer(archived(2, 20), &genetics));
+        assert!(!full.offer(archived(3, 10), &genetics));
+        assert!(full.offer(archived(4, 11), &genetics));
+        assert_eq!(
+            vec![2, 4],
+            full.members()
+                .iter()
+                .map(|member| member.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn similar_candidates_replace_every_member_they_beat() {
+        // Every two individuals are 1.0 apart, so a minimum distance above that makes them all too similar
+        let genetics = CountingGenetics::default();
+        let mut archive = Archive::new(3, 1.5);
+        assert!(archive.offer(archived(1, 10), &genetics));
+        assert!(!archive.offer(archived(2, 10), &genetics));
+        assert!(archive.offer(archived(3, 30), &genetics));
+        assert_eq!(1, archive.len());
+        assert_eq!(3, archive.best().unwrap().id);
+
+        // At exactly the minimum distance individuals are no longer too similar
+        let mut archive = Archive::new(3, 1.0);
+        assert!(archive.offer(archived(1, 30), &genetics));
+        assert!(archive.offer(archived(2, 10), &genetics));
+        assert_eq!(2, archive.len());
+    }
+}
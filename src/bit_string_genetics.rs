@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::index::sample;
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{Genetics, IndividualStore};
+
+/// How `BitStringGenetics` combines two parents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitStringCrossover {
+    /// The strings are cut at k random positions, where k is the number of crossover points chosen by the engine, and
+    /// the child takes the segments between the cuts from each parent in turn
+    #[default]
+    KPoint,
+
+    /// Every bit of the child is taken from either parent with equal probability
+    Uniform,
+}
+
+/// A ready-made `Genetics` for fixed-length bit strings, such as OneMax or knapsack problems. Mutation flips as many
+/// distinct bits as the engine's mutation points, and the distance between two individuals is the number of bits they
+/// differ in. The bit strings are kept in an `IndividualStore`, which island engines can read through `store`.
+pub struct BitStringGenetics {
+    length: usize,
+    crossover: BitStringCrossover,
+    store: Arc<IndividualStore<Vec<bool>>>,
+}
+
+impl BitStringGenetics {
+    /// Creates genetics for bit strings of the given length. The engine's `max_individual_points` is ignored.
+    pub fn new(length: usize) -> Self {
+        BitStringGenetics {
+            length,
+            crossover: BitStringCrossover::default(),
+            store: Arc::new(IndividualStore::new()),
+        }
+    }
+
+    pub fn with_crossover(mut self, crossover: BitStringCrossover) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// Returns the number of bits in every individual
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns a handle to the store of bit strings, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<bool>>> {
+        self.store.clone()
+    }
+
+    /// Returns the bits of an individual, or None if it is not stored
+    pub fn bits(&self, id: u64) -> Option<Arc<Vec<bool>>> {
+        self.store.get(id)
+    }
+
+    /// Stores a bit string created outside of the genetic operators, such as a seed individual, and returns its id.
+    /// Returns None if it does not have the configured length.
+    pub fn insert(&self, bits: Vec<bool>) -> Option<u64> {
+        (bits.len() == self.length).then(|| self.store.insert(bits))
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<bool>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+}
+
+impl Genetics for BitStringGenetics {
+    fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> u64 {
+        self.store
+            .insert((0..self.length).map(|_| rng.random()).collect())
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        for bit in sample(rng, self.length, points.min(self.length)) {
+            child[bit] = !child[bit];
+        }
+        self.store.insert(child)
+    }
+
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let child = match self.crossover {
+            BitStringCrossover::KPoint => {
+                // Cut positions are between bits, so there are length - 1 of them
+                let cuts = self.length.saturating_sub(1);
+                let mut from_a = vec![false; self.length];
+                for cut in sample(rng, cuts, points.min(cuts)) {
+                    from_a[cut + 1] = true;
+                }
+                let mut take_a = true;
+                a.iter()
+                    .zip(b.iter())
+                    .zip(from_a)
+                    .map(|((&a, &b), switch)| {
+                        take_a ^= switch;
+                        if take_a {
+                            a
+                        } else {
+                            b
+                        }
+                    })
+                    .collect()
+            }
+            BitStringCrossover::Uniform => a
+                .iter()
+                .zip(b.iter())
+                .map(|(&a, &b)| if rng.random() { a } else { b })
+                .collect(),
+        };
+        self.store.insert(child)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        a.iter().zip(b.iter()).filter(|(a, b)| a != b).count() as f64
+    }
+
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |bits| hash_genome(&*bits))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let bits = self.store.get(individual)?;
+        Some(
+            bits.iter()
+                .map(|&bit| if bit { '1' } else { '0' })
+                .collect(),
+        )
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let bits = genome
+            .chars()
+            .map(|c| match c {
+                '0' => Some(false),
+                '1' => Some(true),
+                _ => None,
+            })
+            .collect::<Option<Vec<bool>>>()?;
+        self.insert(bits)
+    }
+
+    fn individual_from_point(&self, point: &[f64]) -> Option<u64> {
+        self.insert(point.iter().map(|&x| x >= 0.5).collect())
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    // Scores a bit string by its number of ones
+    struct OneMaxEngine {
+        store: Arc<IndividualStore<Vec<bool>>>,
+    }
+
+    impl IslandEngine for OneMaxEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            self.store
+                .get(id)
+                .unwrap()
+                .iter()
+                .filter(|&&bit| bit)
+                .count() as u64
+        }
+    }
+
+    #[test]
+    fn bit_strings_are_mutated_and_crossed_over() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let genetics = BitStringGenetics::new(8);
+        let zeros = genetics.decode("00000000").unwrap();
+        let ones = genetics.decode("11111111").unwrap();
+        assert_eq!(None, genetics.decode("0101"));
+
+        let mutant = genetics.mutate(&mut rng, zeros, 3);
+        assert_eq!(3.0, genetics.distance(zeros, mutant));
+
+        // A single cut gives a run of one parent followed by a run of the other
+        let child = genetics.crossover(&mut rng, zeros, ones, 1);
+        let encoded = genetics.encode(child).unwrap();
+        assert!(encoded.starts_with('0') && encoded.ends_with('1'));
+        assert_eq!(1, encoded.matches("01").count());
+
+        let genetics = genetics.with_crossover(BitStringCrossover::Uniform);
+        let child = genetics.crossover(&mut rng, zeros, ones, 1);
+        assert_eq!(
+            8.0,
+            genetics.distance(zeros, child) + genetics.distance(ones, child)
+        );
+    }
+
+    #[test]
+    fn one_max_is_solved() {
+        let genetics = BitStringGenetics::new(16);
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(20)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(OneMaxEngine { store }));
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(50).unwrap();
+        assert_eq!(Some(16), world.best_score());
+    }
+
+    #[test]
+    fn snapshots_of_collected_individuals_are_refused() {
+        let build = |garbage_collection: bool| {
+            let genetics = BitStringGenetics::new(16);
+            let store = genetics.store();
+            let engine = GeneticEngineBuilder::default()
+                .seed(1234)
+                .genetics(genetics)
+                .build()
+                .unwrap();
+            let mut world = WorldBuilder::new()
+                .with_genetic_engine(engine)
+                .with_individuals_per_island(20)
+                .with_migrating_individuals(1)
+                .with_garbage_collection(garbage_collection);
+            world.add_island(
+                "a",
+                Box::new(OneMaxEngine {
+                    store: store.clone(),
+                }),
+            );
+            (world.build().unwrap(), store)
+        };
+
+        // By default the genomes of older generations are collected, and a snapshot of them is refused
+        let (mut world, store) = build(true);
+        let checkpoint =
+            std::env::temp_dir().join(format!("collected-checkpoint-{}", std::process::id()));
+        world.run_n_generations(2).unwrap();
+        let snapshot = world.snapshot();
+        world.checkpoint_to(&checkpoint).unwrap();
+        world.restore(&snapshot).unwrap();
+        world.run_n_generations(3).unwrap();
+        let population = world.get_island(0).unwrap().snapshot().individuals;
+        assert!(matches!(
+            world.restore(&snapshot),
+            Err(GeneticError::MissingIndividual(id)) if !store.contains(id)
+        ));
+        assert!(matches!(
+            world.resume_from(&checkpoint),
+            Err(GeneticError::MissingIndividual(_))
+        ));
+        std::fs::remove_file(&checkpoint).unwrap();
+        assert_eq!(5, world.generation_count());
+        assert_eq!(
+            population,
+            world.get_island(0).unwrap().snapshot().individuals
+        );
+        world.run_n_generations(1).unwrap();
+
+        // Without garbage collection, the genomes of the snapshot are still stored
+        let (mut world, store) = build(false);
+        world.run_n_generations(2).unwrap();
+        let snapshot = world.snapshot();
+        world.run_n_generations(3).unwrap();
+        world.restore(&snapshot).unwrap();
+        assert!(snapshot.islands[0]
+            .individuals
+            .iter()
+            .all(|&id| store.contains(id)));
+        world.run_n_generations(1).unwrap();
+    }
+}
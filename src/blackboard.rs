@@ -0,0 +1,150 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::MaybeSend;
+
+#[cfg(not(feature = "send"))]
+type Entry = Box<dyn Any>;
+
+#[cfg(feature = "send")]
+type Entry = Box<dyn Any + Send>;
+
+/// A typed store shared by every island of a world. Each entry is keyed by its type, so a value can be found again by
+/// asking for the same type, and islands never need to agree on names. Wrap values in a newtype to store more than one
+/// value of the same underlying type.
+///
+/// Island engines read the blackboard through `IslandEngine::read_blackboard` before each of their generations and can
+/// publish to it through `IslandEngine::write_blackboard` afterwards. The driver of the world has access through
+/// `World::blackboard` and `World::blackboard_mut`.
+#[derive(Default)]
+pub struct Blackboard {
+    entries: HashMap<TypeId, Entry>,
+}
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a value, returning the value of the same type that it replaced, if any
+    pub fn insert<T: Any + MaybeSend>(&mut self, value: T) -> Option<T> {
+        self.entries
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .map(|previous| *previous.downcast::<T>().unwrap())
+    }
+
+    /// Returns the stored value of type T, if any
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.entries
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// Returns the stored value of type T for modification, if any
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.entries
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut::<T>())
+    }
+
+    /// Returns the stored value of type T, inserting the default value first if there is none
+    pub fn get_or_default<T: Any + MaybeSend + Default>(&mut self) -> &mut T {
+        self.entries
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .downcast_mut::<T>()
+            .unwrap()
+    }
+
+    /// Removes and returns the stored value of type T, if any
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        self.entries
+            .remove(&TypeId::of::<T>())
+            .map(|value| *value.downcast::<T>().unwrap())
+    }
+
+    /// Returns true if a value of type T is stored
+    pub fn contains<T: Any>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the number of stored values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is stored
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every stored value
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::Blackboard;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Threshold(u64);
+
+    #[test]
+    fn values_are_keyed_by_type() {
+        let mut blackboard = Blackboard::new();
+        assert_eq!(None, blackboard.insert(Threshold(5)));
+        blackboard.insert(vec![1u64, 2, 3]);
+
+        assert_eq!(Some(&Threshold(5)), blackboard.get::<Threshold>());
+        assert_eq!(Some(Threshold(5)), blackboard.insert(Threshold(7)));
+        blackboard.get_or_default::<Vec<u64>>().push(4);
+        assert_eq!(Some(&vec![1, 2, 3, 4]), blackboard.get::<Vec<u64>>());
+        assert!(blackboard.get::<String>().is_none());
+
+        assert_eq!(Some(Threshold(7)), blackboard.remove::<Threshold>());
+        assert_eq!(1, blackboard.len());
+    }
+
+    #[test]
+    fn islands_share_data_through_the_blackboard() {
+        #[derive(Default)]
+        struct BestSeen(u64);
+
+        // Publishes a value for the other island, and remembers what the blackboard held before it ran
+        struct Sharing(Arc<Mutex<Vec<u64>>>);
+
+        impl IslandEngine for Sharing {
+            fn read_blackboard(&mut self, blackboard: &Blackboard) {
+                let seen = blackboard.get::<BestSeen>().map_or(0, |best| best.0);
+                self.0.lock().unwrap().push(seen);
+            }
+
+            fn write_blackboard(&mut self, blackboard: &mut Blackboard) {
+                let best = blackboard.get_or_default::<BestSeen>();
+                best.0 = best.0.max(7);
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+        }
+
+        let seen = Arc::new(Mutex::new(vec![]));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(2)
+            .with_elite_individuals(0)
+            .with_migrating_individuals(0);
+        builder.add_island("a", Box::new(Sharing(seen.clone())));
+        builder.add_island("b", Box::new(Sharing(seen.clone())));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(2).unwrap();
+
+        assert_eq!(vec![0, 7, 7, 7], *seen.lock().unwrap());
+        assert_eq!(7, world.blackboard().get::<BestSeen>().unwrap().0);
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{GeneticError, Genetics, IndividualStore, Primitive};
+
+/// A node of a `CartesianGenome`: a function applied to the values at other addresses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CartesianNode {
+    /// The index of the function
+    pub function: usize,
+
+    /// The addresses the function reads, one for each argument of the function with the highest arity. Only the first
+    /// `arity` are read.
+    pub connections: Vec<usize>,
+}
+
+/// The genome of a Cartesian genetic program: a grid of nodes stored column by column, and the addresses of the
+/// program's outputs. Addresses count the program inputs first, then the nodes, so node `n` has the address
+/// `inputs + n`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CartesianGenome {
+    /// The nodes of the grid, column by column
+    pub nodes: Vec<CartesianNode>,
+
+    /// The address each output of the program is read from
+    pub outputs: Vec<usize>,
+}
+
+/// A ready-made `Genetics` for Cartesian genetic programming. Programs are fixed-size grids of nodes kept in an
+/// `IndividualStore`, each node computing one of the function `Primitive`s from the program inputs or from the nodes in
+/// up to `levels_back` earlier columns, which keeps every program an acyclic graph. Nodes that no output depends on
+/// are inactive, so programs can grow and shrink without the genome changing size, and without the bloat of trees.
+/// Island engines read the genomes through `store` and can run them with `evaluate`.
+///
+/// Mutation is the standard point mutation: as many genes as the engine's mutation points, chosen from every function,
+/// connection and output gene, are given new random values. Cartesian programs are usually evolved by mutation alone,
+/// with the engine's crossover rate set to 0; crossover is a one-point crossover of the node grids that keeps the
+/// outputs of the first parent. Every genome has the same size, so the engine's `max_individual_points` is not used.
+pub struct CartesianGenetics {
+    functions: Vec<Primitive>,
+    inputs: usize,
+    outputs: usize,
+    rows: usize,
+    columns: usize,
+    levels_back: usize,
+    max_arity: usize,
+    store: Arc<IndividualStore<CartesianGenome>>,
+}
+
+impl CartesianGenetics {
+    /// Creates genetics for programs of `inputs` inputs and `outputs` outputs with a single row of `columns` nodes, any
+    /// of which can connect to any earlier node. Returns an error if there is no input, output, column or function, or
+    /// if a function name is empty, repeated or contains whitespace.
+    pub fn new(
+        functions: Vec<Primitive>,
+        inputs: usize,
+        outputs: usize,
+        columns: usize,
+    ) -> Result<Self, GeneticError> {
+        let mut names = HashSet::new();
+        let valid_functions = functions.iter().all(|function| {
+            !function.name.is_empty()
+                && !function
+                    .name
+                    .contains(|c: char| c.is_whitespace() || c == ';')
+                && names.insert(function.name.as_str())
+        });
+        if inputs == 0 || outputs == 0 || columns == 0 || functions.is_empty() || !valid_functions {
+            return Err(GeneticError::InvalidCartesianGrid);
+        }
+
+        Ok(CartesianGenetics {
+            max_arity: functions
+                .iter()
+                .map(|function| function.arity)
+                .max()
+                .unwrap(),
+            functions,
+            inputs,
+            outputs,
+            rows: 1,
+            columns,
+            levels_back: columns,
+            store: Arc::new(IndividualStore::new()),
+        })
+    }
+
+    /// Sets the number of nodes in each column
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    /// Sets how many columns back a node can connect to
+    pub fn with_levels_back(mut self, levels_back: usize) -> Self {
+        self.levels_back = levels_back.max(1);
+        self
+    }
+
+    /// Returns a function the nodes refer to
+    pub fn function(&self, index: usize) -> &Primitive {
+        &self.functions[index]
+    }
+
+    /// Returns a handle to the store of genomes, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<CartesianGenome>> {
+        self.store.clone()
+    }
+
+    /// Returns the genome of an individual, or None if it is not stored
+    pub fn program(&self, id: u64) -> Option<Arc<CartesianGenome>> {
+        self.store.get(id)
+    }
+
+    /// Returns, for each node, whether an output depends on it
+    pub fn active_nodes(&self, genome: &CartesianGenome) -> Vec<bool> {
+        let mut active = vec![false; genome.nodes.len()];
+        for &output in genome.outputs.iter() {
+            if output >= self.inputs {
+                active[output - self.inputs] = true;
+            }
+        }
+        for node in (0..genome.nodes.len()).rev() {
+            if active[node] {
+                for &connection in self.connections(&genome.nodes[node]) {
+                    if connection >= self.inputs {
+                        active[connection - self.inputs] = true;
+                    }
+                }
+            }
+        }
+        active
+    }
+
+    /// Runs a program on the given inputs and returns its outputs. Only active nodes are computed, by calling `apply`
+    /// with the index of the node's function and the values of its arguments.
+    pub fn evaluate<T, F>(&self, genome: &CartesianGenome, inputs: &[T], mut apply: F) -> Vec<T>
+    where
+        T: Clone + Default,
+        F: FnMut(usize, &[T]) -> T,
+    {
+        let mut values: Vec<T> = inputs.to_vec();
+        values.resize(self.inputs + genome.nodes.len(), T::default());
+        let active = self.active_nodes(genome);
+        let mut arguments = Vec::with_capacity(self.max_arity);
+        for (node, cartesian_node) in genome.nodes.iter().enumerate() {
+            if active[node] {
+                arguments.clear();
+                arguments.extend(
+                    self.connections(cartesian_node)
+                        .iter()
+                        .map(|&connection| values[connection].clone()),
+                );
+                values[self.inputs + node] = apply(cartesian_node.function, &arguments);
+            }
+        }
+        genome
+            .outputs
+            .iter()
+            .map(|&output| values[output].clone())
+            .collect()
+    }
+
+    fn connections<'a>(&self, node: &'a CartesianNode) -> &'a [usize] {
+        &node.connections[..self.functions[node.function].arity]
+    }
+
+    fn node_count(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    // Returns the number of addresses a node in the column can connect to
+    fn reachable(&self, column: usize) -> usize {
+        self.inputs + self.rows * column.min(self.levels_back)
+    }
+
+    fn random_connection(&self, rng: &mut StdRng, node: usize) -> usize {
+        let column = node / self.rows;
+        let address = rng.random_range(0..self.reachable(column));
+        if address < self.inputs {
+            address
+        } else {
+            address + self.rows * column.saturating_sub(self.levels_back)
+        }
+    }
+
+    fn is_valid_connection(&self, node: usize, address: usize) -> bool {
+        let column = node / self.rows;
+        let first_node = self.inputs + self.rows * column.saturating_sub(self.levels_back);
+        address < self.inputs || (first_node..self.inputs + self.rows * column).contains(&address)
+    }
+
+    fn random_output(&self, rng: &mut StdRng) -> usize {
+        rng.random_range(0..self.inputs + self.node_count())
+    }
+
+    fn genome(&self, id: u64) -> Arc<CartesianGenome> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    fn parse_node(&self, node: usize, text: &str) -> Option<CartesianNode> {
+        let mut words = text.split_whitespace();
+        let name = words.next()?;
+        let function = self
+            .functions
+            .iter()
+            .position(|function| function.name == name)?;
+        let mut connections = vec![0; self.max_arity];
+        for connection in connections.iter_mut().take(self.functions[function].arity) {
+            *connection = words.next()?.parse().ok()?;
+            if !self.is_valid_connection(node, *connection) {
+                return None;
+            }
+        }
+        if words.next().is_some() {
+            return None;
+        }
+        Some(CartesianNode {
+            function,
+            connections,
+        })
+    }
+}
+
+impl Genetics for CartesianGenetics {
+    fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> u64 {
+        let nodes = (0..self.node_count())
+            .map(|node| CartesianNode {
+                function: rng.random_range(0..self.functions.len()),
+                connections: (0..self.max_arity)
+                    .map(|_| self.random_connection(rng, node))
+                    .collect(),
+            })
+            .collect();
+        let outputs = (0..self.outputs).map(|_| self.random_output(rng)).collect();
+        self.store.insert(CartesianGenome { nodes, outputs })
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        let node_genes = 1 + self.max_arity;
+        for _ in 0..points {
+            let gene = rng.random_range(0..self.node_count() * node_genes + self.outputs);
+            if gene >= self.node_count() * node_genes {
+                child.outputs[gene - self.node_count() * node_genes] = self.random_output(rng);
+                continue;
+            }
+            let (node, position) = (gene / node_genes, gene % node_genes);
+            if position == 0 {
+                child.nodes[node].function = rng.random_range(0..self.functions.len());
+            } else {
+                child.nodes[node].connections[position - 1] = self.random_connection(rng, node);
+            }
+        }
+        self.store.insert(child)
+    }
+
+    // The engine's crossover points are not used
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let cut = rng.random_range(0..=self.node_count());
+        let mut child = (*a).clone();
+        child.nodes[cut..].clone_from_slice(&b.nodes[cut..]);
+        self.store.insert(child)
+    }
+
+    // The number of genes that differ
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let nodes: usize = a
+            .nodes
+            .iter()
+            .zip(b.nodes.iter())
+            .map(|(a, b)| {
+                let connections = a.connections.iter().zip(b.connections.iter());
+                usize::from(a.function != b.function) + connections.filter(|(a, b)| a != b).count()
+            })
+            .sum();
+        let outputs = a
+            .outputs
+            .iter()
+            .zip(b.outputs.iter())
+            .filter(|(a, b)| a != b)
+            .count();
+        (nodes + outputs) as f64
+    }
+
+    // Genomes are written as their nodes followed by their outputs, such as `add 0 1; mul 2 0; -> 3`
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.nodes.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |genome| hash_genome(&*genome))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let genome = self.store.get(individual)?;
+        let mut text = String::new();
+        for node in genome.nodes.iter() {
+            text.push_str(&self.functions[node.function].name);
+            for connection in self.connections(node) {
+                text.push_str(&format!(" {}", connection));
+            }
+            text.push_str("; ");
+        }
+        text.push_str("->");
+        for output in genome.outputs.iter() {
+            text.push_str(&format!(" {}", output));
+        }
+        Some(text)
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let (nodes, outputs) = genome.split_once("->")?;
+        let nodes: Vec<&str> = nodes
+            .split(';')
+            .map(str::trim)
+            .filter(|node| !node.is_empty())
+            .collect();
+        if nodes.len() != self.node_count() {
+            return None;
+        }
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(node, text)| self.parse_node(node, text))
+            .collect::<Option<Vec<CartesianNode>>>()?;
+        let outputs = outputs
+            .split_whitespace()
+            .map(|output| {
+                output
+                    .parse()
+                    .ok()
+                    .filter(|&output| output < self.inputs + nodes.len())
+            })
+            .collect::<Option<Vec<usize>>>()?;
+        if outputs.len() != self.outputs {
+            return None;
+        }
+        Some(self.store.insert(CartesianGenome { nodes, outputs }))
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::seq::IndexedRandom;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    fn arithmetic(columns: usize) -> CartesianGenetics {
+        let functions = vec![Primitive::function("add", 2), Primitive::function("mul", 2)];
+        CartesianGenetics::new(functions, 2, 1, columns).unwrap()
+    }
+
+    fn apply(function: usize, arguments: &[i64]) -> i64 {
+        match function {
+            0 => arguments[0].saturating_add(arguments[1]),
+            _ => arguments[0].saturating_mul(arguments[1]),
+        }
+    }
+
+    // Scores a program of the inputs x and 1 by how closely it computes x * x + 1
+    struct RegressionEngine {
+        genetics: CartesianGenetics,
+        store: Arc<IndividualStore<CartesianGenome>>,
+    }
+
+    impl IslandEngine for RegressionEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let genome = self.store.get(id).unwrap();
+            let error: u64 = (0..5)
+                .map(|x| self.genetics.evaluate(&genome, &[x, 1], apply)[0].abs_diff(x * x + 1))
+                .fold(0, u64::saturating_add);
+            1000u64.saturating_sub(error)
+        }
+    }
+
+    #[test]
+    fn only_active_nodes_are_evaluated() {
+        let genetics = arithmetic(4).with_rows(2).with_levels_back(1);
+        let program = genetics
+            .decode("mul 0 0; add 0 1; add 2 1; mul 3 3; add 4 5; add 0 0; mul 0 0; mul 0 1; -> 4")
+            .unwrap();
+        let genome = (*genetics.program(program).unwrap()).clone();
+        assert_eq!(
+            vec![true, false, true, false, false, false, false, false],
+            genetics.active_nodes(&genome)
+        );
+        assert_eq!(vec![10], genetics.evaluate(&genome, &[3, 1], apply));
+
+        // Nodes may only connect to the inputs and the column before their own
+        let with_node = |node: usize, text: &str| {
+            let mut nodes = ["add 0 0"; 8];
+            nodes[node] = text;
+            format!("{}; -> 2", nodes.join("; "))
+        };
+        assert!(genetics.decode(&with_node(4, "mul 4 5")).is_some());
+        assert_eq!(None, genetics.decode(&with_node(1, "mul 0 2")));
+        assert_eq!(None, genetics.decode(&with_node(4, "mul 2 0")));
+
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut individuals = vec![program];
+        for _ in 0..100 {
+            let a = *individuals.choose(&mut rng).unwrap();
+            let b = genetics.random_individual(&mut rng, 1);
+            individuals.push(genetics.crossover(&mut rng, a, b, 1));
+            individuals.push(genetics.mutate(&mut rng, a, 3));
+        }
+        for id in individuals {
+            let decoded = genetics.decode(&genetics.encode(id).unwrap()).unwrap();
+            assert_eq!(0.0, genetics.distance(id, decoded));
+        }
+    }
+
+    #[test]
+    fn a_polynomial_is_found() {
+        let genetics = arithmetic(10);
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .crossover_rate(0)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(20)
+            .with_migrating_individuals(1);
+        world.add_island(
+            "a",
+            Box::new(RegressionEngine {
+                genetics: arithmetic(10),
+                store,
+            }),
+        );
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(30).unwrap();
+        assert_eq!(Some(1000), world.best_score());
+    }
+}
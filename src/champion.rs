@@ -0,0 +1,56 @@
+/// The most fit individual of a world at some point in time, and where it lives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Champion {
+    /// The index of the island the individual was found on
+    pub island: usize,
+
+    /// The individual
+    pub id: u64,
+
+    /// The score the island engine gave the individual
+    pub score: u64,
+
+    /// The validation score the island engine gave the individual, if it validates. See
+    /// `IslandEngine::validation_score`.
+    pub validation_score: Option<u64>,
+
+    /// The score on the scale shared by every island, according to the world's `ScoreNormalization`
+    pub normalized_score: f64,
+
+    /// The normalized score multiplied by the weight of the island. The champion is the individual with the highest
+    /// weighted score.
+    pub weighted_score: f64,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn island_weights_decide_the_champion() {
+        let mut world = world_builder(&["important", "other"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_island_weight("important", 3.0)
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+
+        // Id 3 on the important island outweighs id 7 on the other island
+        let champion = world.most_fit_individual().unwrap();
+        assert_eq!(
+            (0, 3, 9.0),
+            (champion.island, champion.id, champion.weighted_score)
+        );
+        assert_eq!(1.0, world.island_weight(1));
+
+        let result = world_builder(&["a"])
+            .with_island_weight("missing", 2.0)
+            .build();
+        assert!(
+            matches!(result, Err(GeneticError::InvalidIslandWeight(name)) if name == "missing")
+        );
+    }
+}
@@ -0,0 +1,129 @@
+/// The champions of a world in a form that can be kept as the result of a run. Produced as JSON by
+/// `World::export_champions`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChampionExport {
+    /// The generation count of the world when the champions were exported
+    pub generation: usize,
+
+    /// The index in `champions` of the world's most fit individual, or None if no island has been sorted
+    pub best: Option<usize>,
+
+    /// The most fit individual of every sorted island in island order, followed by the members of the archive, most
+    /// fit first
+    pub champions: Vec<ExportedChampion>,
+}
+
+/// Where an `ExportedChampion` was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ChampionSource {
+    /// The most fit individual of an island's current generation
+    Island,
+
+    /// A member of the world's archive
+    Archive,
+}
+
+/// One individual of a `ChampionExport`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedChampion {
+    /// Where the individual was found
+    pub source: ChampionSource,
+
+    /// The name of the island the individual lives on, or emigrated from if it was archived
+    pub island: String,
+
+    /// The id of the individual in the process that exported it
+    pub id: u64,
+
+    /// The score the island engine gave the individual
+    pub score: u64,
+
+    /// The validation score the island engine gave the individual. Always None for archived individuals.
+    pub validation_score: Option<u64>,
+
+    /// The score on the scale shared by every island, according to the world's `ScoreNormalization`
+    pub normalized_score: f64,
+
+    /// The genome written by the codec given to `World::export_champions`, or None if it could not encode the
+    /// individual
+    pub genome: Option<String>,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn champions_are_exported_with_their_genomes() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_archive(2, 0.5)
+            .build()
+            .unwrap();
+        world.run_n_generations(3).unwrap();
+
+        let mut data = vec![];
+        let export = world
+            .export_champions(&mut data, |id| {
+                (id % 2 == 0).then(|| format!("genome {}", id))
+            })
+            .unwrap();
+        assert_eq!(
+            export,
+            serde_json::from_slice::<ChampionExport>(&data).unwrap()
+        );
+        assert_eq!(3, export.generation);
+        assert_eq!(4, export.champions.len());
+
+        let best = &export.champions[export.best.unwrap()];
+        assert_eq!(world.most_fit_individual().unwrap().id, best.id);
+        assert_eq!(
+            ("a", ChampionSource::Island),
+            (
+                export.champions[0].island.as_str(),
+                export.champions[0].source
+            )
+        );
+        let archived = &export.champions[2];
+        assert_eq!(ChampionSource::Archive, archived.source);
+        assert_eq!(world.archive().unwrap().best().unwrap().id, archived.id);
+        for champion in export.champions.iter() {
+            assert_eq!(
+                (champion.id % 2 == 0).then(|| format!("genome {}", champion.id)),
+                champion.genome
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn worlds_without_champions_export_an_empty_list() {
+        let world = world_builder(&["a", "b"]).build().unwrap();
+        let mut data = vec![];
+        let export = world
+            .export_champions(&mut data, |_| unreachable!())
+            .unwrap();
+        assert_eq!((0, None), (export.generation, export.best));
+        assert!(export.champions.is_empty());
+
+        // A writer that fails is reported rather than losing the export silently
+        struct FullDisk;
+        impl std::io::Write for FullDisk {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::StorageFull.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(matches!(
+            world.export_champions(FullDisk, |_| None),
+            Err(GeneticError::Io(_))
+        ));
+    }
+}
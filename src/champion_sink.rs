@@ -0,0 +1,170 @@
+use crate::{Champion, MaybeSend};
+
+#[cfg(not(feature = "send"))]
+type Callback = Box<dyn FnMut(&Champion)>;
+
+#[cfg(feature = "send")]
+type Callback = Box<dyn FnMut(&Champion) + Send>;
+
+/// Receives the world's champion each time it improves, so that it can be saved while the run continues instead of
+/// only at the end. The callback is given the `Champion`, and typically looks up the individual in the user's own
+/// storage and writes it out with the user's own codec.
+///
+/// After every generation the world compares its champion with the last one given to the sink, by weighted score.
+/// Improvements that arrive while the sink is throttled are not lost: once the throttle allows it, the champion of
+/// that generation is exported if it still beats the last export.
+pub struct ChampionSink {
+    callback: Callback,
+
+    /// The fewest generations between two exports. Zero or one exports every improvement as soon as it happens.
+    ///
+    /// Default: 0
+    pub min_generations_between_exports: usize,
+
+    /// The amount the weighted score must rise above the last export before the champion is exported again
+    ///
+    /// Default: 0.0
+    pub min_improvement: f64,
+
+    // The generation count and weighted score of the last export
+    last_export: Option<(usize, f64)>,
+}
+
+impl ChampionSink {
+    pub fn new<F: FnMut(&Champion) + MaybeSend + 'static>(callback: F) -> Self {
+        ChampionSink {
+            callback: Box::new(callback),
+            min_generations_between_exports: 0,
+            min_improvement: 0.0,
+            last_export: None,
+        }
+    }
+
+    pub fn with_min_generations_between_exports(mut self, generations: usize) -> Self {
+        self.min_generations_between_exports = generations;
+        self
+    }
+
+    pub fn with_min_improvement(mut self, improvement: f64) -> Self {
+        self.min_improvement = improvement;
+        self
+    }
+
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.min_improvement >= 0.0 && self.min_improvement.is_finite()
+    }
+
+    /// Forgets the last export, so that the next champion is exported whatever its score
+    pub(crate) fn reset(&mut self) {
+        self.last_export = None;
+    }
+
+    /// Passes the champion to the callback unless it is not enough of an improvement or the sink is throttled
+    pub(crate) fn offer(&mut self, generation: usize, champion: &Champion) {
+        if let Some((last_generation, last_score)) = self.last_export {
+            if champion.weighted_score <= last_score
+                || champion.weighted_score - last_score < self.min_improvement
+                || generation < last_generation + self.min_generations_between_exports
+            {
+                return;
+            }
+        }
+
+        self.export(generation, champion);
+    }
+
+    /// Passes the champion to the callback if it beats the last export, ignoring the throttle
+    pub(crate) fn flush(&mut self, generation: usize, champion: &Champion) {
+        if self
+            .last_export
+            .is_none_or(|(_, last_score)| champion.weighted_score > last_score)
+        {
+            self.export(generation, champion);
+        }
+    }
+
+    fn export(&mut self, generation: usize, champion: &Champion) {
+        (self.callback)(champion);
+        self.last_export = Some((generation, champion.weighted_score));
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn champion_sink_receives_throttled_improvements() {
+        let exported = Arc::new(Mutex::new(vec![]));
+        let sink_exported = exported.clone();
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_champion_sink(
+                ChampionSink::new(move |champion| {
+                    sink_exported.lock().unwrap().push(champion.score)
+                })
+                .with_min_generations_between_exports(3),
+            )
+            .build()
+            .unwrap();
+
+        let mut best_scores = vec![];
+        for _ in 0..10 {
+            world.run_n_generations(1).unwrap();
+            best_scores.push(world.most_fit_individual().unwrap().score);
+        }
+
+        // Every export is a strict improvement, at least three generations after the previous one
+        let exported = exported.lock().unwrap();
+        assert_eq!(best_scores[0], exported[0]);
+        assert!(exported.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(exported.len() <= 4);
+        assert!(exported.iter().all(|score| best_scores.contains(score)));
+    }
+
+    fn champion(weighted_score: f64) -> Champion {
+        Champion {
+            island: 0,
+            id: weighted_score as u64,
+            score: weighted_score as u64,
+            validation_score: None,
+            normalized_score: weighted_score,
+            weighted_score,
+        }
+    }
+
+    #[test]
+    fn thresholds_export_champions_that_exactly_meet_them() {
+        let exported = Arc::new(Mutex::new(vec![]));
+        let sink_exported = exported.clone();
+        let mut sink =
+            ChampionSink::new(move |champion| sink_exported.lock().unwrap().push(champion.id))
+                .with_min_generations_between_exports(2)
+                .with_min_improvement(5.0);
+
+        sink.offer(0, &champion(10.0));
+        sink.offer(1, &champion(20.0));
+        sink.offer(2, &champion(14.0));
+        sink.offer(3, &champion(15.0));
+
+        // Flushing ignores the throttle but still needs a better champion, until the sink is reset
+        sink.offer(4, &champion(16.0));
+        sink.flush(4, &champion(16.0));
+        sink.flush(5, &champion(16.0));
+        sink.reset();
+        sink.offer(5, &champion(1.0));
+        assert_eq!(vec![10, 15, 16, 1], *exported.lock().unwrap());
+
+        for improvement in [-1.0, f64::NAN, f64::INFINITY] {
+            let result = world_builder(&["a"])
+                .with_champion_sink(ChampionSink::new(|_| {}).with_min_improvement(improvement))
+                .build();
+            assert!(matches!(result, Err(GeneticError::InvalidChampionSink)));
+        }
+    }
+}
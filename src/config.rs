@@ -0,0 +1,233 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{
+    GeneticEngineBuilder, GeneticError, Genetics, Initialization, MigrationAlgorithm,
+    SelectionCurve, WorldBuilder,
+};
+
+/// The settings of an experiment that can be changed without recompiling, read from a TOML or JSON file by
+/// `WorldBuilder::from_config` and `GeneticEngineBuilder::from_config`. Every setting is optional; anything left out
+/// keeps the builder's default. Unknown settings are an error so that a misspelled name is not silently ignored. Each
+/// setting replaces the builder field of the same name.
+///
+/// ```toml
+/// individuals_per_island = 200
+/// elite_individuals_per_generation = 4
+/// migration_algorithm = { Cyclical = 2 }
+/// select_as_parent = "PreferenceForFit"
+///
+/// [genetic_engine]
+/// seed = 1234
+/// mutation_rate = 2
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldConfig {
+    pub individuals_per_island: Option<usize>,
+    pub elite_individuals_per_generation: Option<usize>,
+    pub generations_between_migrations: Option<usize>,
+    pub number_of_individuals_migrating: Option<usize>,
+    pub migration_algorithm: Option<MigrationAlgorithm>,
+    pub clone_migrated_individuals: Option<bool>,
+    pub select_for_migration: Option<SelectionCurve>,
+    pub select_as_parent: Option<SelectionCurve>,
+    pub select_as_elite: Option<SelectionCurve>,
+    pub initialization: Option<Initialization>,
+
+    /// The settings of the genetic engine
+    pub genetic_engine: Option<GeneticEngineConfig>,
+}
+
+/// The settings of a genetic engine in a `WorldConfig`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneticEngineConfig {
+    pub seed: Option<u64>,
+    pub mutation_rate: Option<u8>,
+    pub crossover_rate: Option<u8>,
+    pub reproduction_rate: Option<u8>,
+    pub max_mutation_points: Option<u8>,
+    pub max_crossover_points: Option<u8>,
+    pub max_individual_points: Option<usize>,
+}
+
+impl WorldConfig {
+    /// Reads a config file. Files ending in `.json` are read as JSON, and all others as TOML.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<WorldConfig, GeneticError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            serde_json::from_str(&text)
+                .map_err(|error| GeneticError::InvalidConfig(error.to_string()))
+        } else {
+            toml::from_str(&text).map_err(|error| GeneticError::InvalidConfig(error.to_string()))
+        }
+    }
+
+    /// Applies every world setting in the config to the builder
+    pub fn apply_to_world<G: Genetics>(&self, mut builder: WorldBuilder<G>) -> WorldBuilder<G> {
+        if let Some(count) = self.individuals_per_island {
+            builder.individuals_per_island = count;
+        }
+        if let Some(count) = self.elite_individuals_per_generation {
+            builder.elite_individuals_per_generation = count;
+        }
+        if let Some(generations) = self.generations_between_migrations {
+            builder.generations_between_migrations = generations;
+        }
+        if let Some(count) = self.number_of_individuals_migrating {
+            builder.number_of_individuals_migrating = count;
+        }
+        if let Some(algorithm) = &self.migration_algorithm {
+            builder.migration_algorithm = algorithm.clone();
+        }
+        if let Some(clone) = self.clone_migrated_individuals {
+            builder.clone_migrated_individuals = clone;
+        }
+        if let Some(curve) = self.select_for_migration {
+            builder.select_for_migration = curve;
+        }
+        if let Some(curve) = self.select_as_parent {
+            builder.select_as_parent = curve;
+        }
+        if let Some(curve) = self.select_as_elite {
+            builder.select_as_elite = curve;
+        }
+        if let Some(initialization) = self.initialization {
+            builder.initialization = initialization;
+        }
+
+        builder
+    }
+
+    /// Applies every genetic engine setting in the config to the builder
+    pub fn apply_to_genetic_engine<G: Genetics>(
+        &self,
+        mut builder: GeneticEngineBuilder<G>,
+    ) -> GeneticEngineBuilder<G> {
+        let Some(engine) = &self.genetic_engine else {
+            return builder;
+        };
+        if let Some(seed) = engine.seed {
+            builder.seed = Some(seed);
+        }
+        if let Some(rate) = engine.mutation_rate {
+            builder.mutation_rate = rate;
+        }
+        if let Some(rate) = engine.crossover_rate {
+            builder.crossover_rate = rate;
+        }
+        if let Some(rate) = engine.reproduction_rate {
+            builder.reproduction_rate = rate;
+        }
+        if let Some(points) = engine.max_mutation_points {
+            builder.max_mutation_points = points;
+        }
+        if let Some(points) = engine.max_crossover_points {
+            builder.max_crossover_points = points;
+        }
+        if let Some(points) = engine.max_individual_points {
+            builder.max_individual_points = points;
+        }
+
+        builder
+    }
+}
+
+impl<G: Genetics> WorldBuilder<G> {
+    /// Creates a builder with the world settings of a config file. See `WorldConfig` for the format. The islands and
+    /// genetic engine still have to be added in code.
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self, GeneticError> {
+        Ok(WorldConfig::load(path)?.apply_to_world(WorldBuilder::new()))
+    }
+}
+
+impl<G: Genetics> GeneticEngineBuilder<G> {
+    /// Creates a builder with the `[genetic_engine]` settings of a config file. See `WorldConfig` for the format. The
+    /// genetics implementation still has to be set in code.
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self, GeneticError> {
+        Ok(WorldConfig::load(path)?.apply_to_genetic_engine(GeneticEngineBuilder::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::CountingGenetics;
+    use crate::*;
+
+    #[test]
+    fn builders_load_toml_and_json_configs() {
+        let directory = std::env::temp_dir().join(format!("config-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let toml_path = directory.join("experiment.toml");
+        std::fs::write(
+            &toml_path,
+            "individuals_per_island = 20\nmigration_algorithm = { Cyclical = 2 }\nselect_as_parent = \"Fair\"\n\n\
+             [genetic_engine]\nmutation_rate = 4\n",
+        )
+        .unwrap();
+        let json_path = directory.join("experiment.json");
+        std::fs::write(
+            &json_path,
+            r#"{"elite_individuals_per_generation": 3, "typo": 1}"#,
+        )
+        .unwrap();
+
+        let world = WorldBuilder::<CountingGenetics>::from_config(&toml_path).unwrap();
+        assert_eq!(20, world.individuals_per_island);
+        assert_eq!(MigrationAlgorithm::Cyclical(2), world.migration_algorithm);
+        assert_eq!(SelectionCurve::Fair, world.select_as_parent);
+        let engine = GeneticEngineBuilder::<CountingGenetics>::from_config(&toml_path).unwrap();
+        assert_eq!(4, engine.mutation_rate);
+        assert_eq!(9, engine.crossover_rate);
+        assert!(matches!(
+            WorldBuilder::<CountingGenetics>::from_config(&json_path),
+            Err(GeneticError::InvalidConfig(_))
+        ));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn empty_missing_and_out_of_range_configs() {
+        let directory =
+            std::env::temp_dir().join(format!("config-edge-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        // An empty file keeps every default
+        let empty_path = directory.join("empty.toml");
+        std::fs::write(&empty_path, "").unwrap();
+        assert_eq!(
+            WorldConfig::default(),
+            WorldConfig::load(&empty_path).unwrap()
+        );
+        let world = WorldBuilder::<CountingGenetics>::from_config(&empty_path).unwrap();
+        assert_eq!(
+            WorldBuilder::<CountingGenetics>::new().individuals_per_island,
+            world.individuals_per_island
+        );
+
+        assert!(matches!(
+            WorldConfig::load(directory.join("missing.toml")),
+            Err(GeneticError::Io(_))
+        ));
+
+        // Rates must fit their type, and engine settings are checked for typos too
+        for text in [
+            "[genetic_engine]\nmutation_rate = 256\n",
+            "[genetic_engine]\nmutation = 2\n",
+        ] {
+            let path = directory.join("invalid.toml");
+            std::fs::write(&path, text).unwrap();
+            assert!(matches!(
+                GeneticEngineBuilder::<CountingGenetics>::from_config(&path),
+                Err(GeneticError::InvalidConfig(_))
+            ));
+        }
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
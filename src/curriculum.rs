@@ -0,0 +1,184 @@
+/// One stage of a curriculum. Stages are registered in order with `WorldBuilder::with_curriculum_stage`, and the world
+/// starts in the first one. When a stage is entered, its rate overrides are applied to the genetic engine and every
+/// island engine is told through `IslandEngine::curriculum_stage_started`, so that it can switch to harder test cases
+/// or a different fitness function.
+///
+/// Rates left as None keep whatever the engine was using in the previous stage. While a warmup or hypermutation burst
+/// is active, the new rates take effect when it ends.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurriculumStage {
+    /// A name for the stage, passed to the island engines and observers
+    pub name: String,
+
+    /// When the world moves on to the next stage. The last stage never ends, and every other stage must have a
+    /// criterion.
+    pub advance_when: Option<StageAdvancement>,
+
+    /// Replaces the mutation rate of the genetic engine from the start of the stage
+    pub mutation_rate: Option<u8>,
+
+    /// Replaces the crossover rate of the genetic engine from the start of the stage
+    pub crossover_rate: Option<u8>,
+}
+
+impl CurriculumStage {
+    /// Creates a stage that keeps the current rates and never ends
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        CurriculumStage {
+            name: name.into(),
+            advance_when: None,
+            mutation_rate: None,
+            crossover_rate: None,
+        }
+    }
+
+    pub fn with_advancement(mut self, advancement: StageAdvancement) -> Self {
+        self.advance_when = Some(advancement);
+        self
+    }
+
+    pub fn with_mutation_rate(mut self, rate: u8) -> Self {
+        self.mutation_rate = Some(rate);
+        self
+    }
+
+    pub fn with_crossover_rate(mut self, rate: u8) -> Self {
+        self.crossover_rate = Some(rate);
+        self
+    }
+}
+
+/// The criterion that ends a curriculum stage. It is checked after every generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StageAdvancement {
+    /// The stage ends after this many generations. Must be greater than zero.
+    Generations(usize),
+
+    /// The stage ends after a generation whose best score reaches this value
+    Score(u64),
+}
+
+impl StageAdvancement {
+    /// Returns true if a stage that has run `generations` generations, the latest having the given best score, is
+    /// complete
+    pub(crate) fn is_met(&self, generations: usize, best_score: Option<u64>) -> bool {
+        match self {
+            StageAdvancement::Generations(limit) => generations >= *limit,
+            StageAdvancement::Score(threshold) => {
+                best_score.is_some_and(|score| score >= *threshold)
+            }
+        }
+    }
+}
+
+/// Returns true if every stage but the last has an end, and no stage ends after zero generations
+pub(crate) fn is_valid_curriculum(stages: &[CurriculumStage]) -> bool {
+    let leading = stages.len().saturating_sub(1);
+    stages[..leading]
+        .iter()
+        .all(|stage| stage.advance_when.is_some())
+        && stages
+            .iter()
+            .all(|stage| stage.advance_when != Some(StageAdvancement::Generations(0)))
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn curriculum_stages_advance_and_are_recorded() {
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+        impl WorldObserver for Recorder {
+            fn curriculum_stage_started(&mut self, generation: usize, _: usize, name: &str) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", generation, name));
+            }
+        }
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_curriculum_stage(
+                CurriculumStage::new("easy")
+                    .with_advancement(StageAdvancement::Generations(2))
+                    .with_mutation_rate(2),
+            )
+            .with_curriculum_stage(
+                CurriculumStage::new("hard")
+                    .with_advancement(StageAdvancement::Score(0))
+                    .with_mutation_rate(7),
+            )
+            .with_curriculum_stage(CurriculumStage::new("final"))
+            .with_observer(Recorder(events.clone()))
+            .build()
+            .unwrap();
+        assert_eq!(Some(0), world.curriculum_stage());
+        assert_eq!(2, world.genetic_engine().mutation_rate());
+
+        world.run_n_generations(4).unwrap();
+        let stages: Vec<Option<usize>> = world
+            .stats_history()
+            .iter()
+            .map(|stats| stats.curriculum_stage)
+            .collect();
+        assert_eq!(vec![Some(0), Some(0), Some(1), Some(2)], stages);
+        assert_eq!(vec!["0 easy", "2 hard", "3 final"], *events.lock().unwrap());
+        assert_eq!(7, world.genetic_engine().mutation_rate());
+
+        // Only the last stage may run forever
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_curriculum_stage(CurriculumStage::new("stuck"))
+                .with_curriculum_stage(CurriculumStage::new("unreachable"))
+                .build(),
+            Err(GeneticError::InvalidCurriculum)
+        ));
+    }
+
+    #[test]
+    fn stages_end_exactly_at_their_criterion() {
+        assert!(!StageAdvancement::Generations(3).is_met(2, Some(100)));
+        assert!(StageAdvancement::Generations(3).is_met(3, None));
+        assert!(!StageAdvancement::Score(10).is_met(100, Some(9)));
+        assert!(StageAdvancement::Score(10).is_met(1, Some(10)));
+        assert!(!StageAdvancement::Score(0).is_met(1, None));
+
+        // No stage may end after zero generations, not even the last, and an empty curriculum is no curriculum
+        assert!(is_valid_curriculum(&[]));
+        assert!(!is_valid_curriculum(&[
+            CurriculumStage::new("only").with_advancement(StageAdvancement::Generations(0))
+        ]));
+        let world = world_builder(&["a"]).build().unwrap();
+        assert_eq!(None, world.curriculum_stage());
+    }
+
+    #[test]
+    fn stages_without_rates_keep_the_previous_rates() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_curriculum_stage(
+                CurriculumStage::new("first")
+                    .with_advancement(StageAdvancement::Generations(1))
+                    .with_mutation_rate(3)
+                    .with_crossover_rate(4),
+            )
+            .with_curriculum_stage(CurriculumStage::new("second").with_crossover_rate(6))
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+        assert_eq!(Some(1), world.curriculum_stage());
+        assert_eq!(3, world.genetic_engine().mutation_rate());
+        assert_eq!(6, world.genetic_engine().crossover_rate());
+    }
+}
@@ -0,0 +1,186 @@
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use rand::rngs::StdRng; // cspell:disable-line
+
+/// One random decision made by the world or its genetic engine, as recorded by `GeneticEngine::record_decisions`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decision {
+    /// The generation count of the world when the decision was made
+    pub generation: usize,
+
+    /// What was being decided
+    pub kind: DecisionKind,
+
+    /// The outcome. For `DecisionKind::Operator` this is 0 for mutation, 1 for crossover, 2 for reproduction and 3
+    /// onwards for the registered `VariationOperator`s in order; for selections it is the
+    /// index of the chosen individual in the island's current generation; and for destinations it is the index of the
+    /// destination island.
+    pub value: usize,
+}
+
+/// The kinds of random decisions that are recorded in a decision log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecisionKind {
+    /// Whether a child is bred by mutation, crossover, reproduction or a registered `VariationOperator`
+    Operator,
+
+    /// The choice of an elite on the island
+    Elite { island: usize },
+
+    /// The choice of a parent on the island
+    Parent { island: usize },
+
+    /// The choice of an emigrant on the island
+    Emigrant { island: usize },
+
+    /// The island a migrant from this island is sent to, when the migration algorithm picks destinations at random
+    Destination { island: usize },
+}
+
+// Whether decisions are being recorded, replayed or neither
+#[derive(Debug, Default)]
+pub(crate) enum DecisionLog {
+    #[default]
+    Off,
+    Recording(Vec<Decision>),
+    Replaying(Peekable<IntoIter<Decision>>),
+}
+
+impl DecisionLog {
+    /// Makes a decision by calling `draw`. When replaying, the value of the next logged decision is used instead if
+    /// it is of the same kind and `accept` allows it, but `draw` is still called so that the random number generator
+    /// advances exactly as it did when the log was recorded.
+    pub(crate) fn decide<Draw, Accept>(
+        &mut self,
+        rng: &mut StdRng,
+        generation: usize,
+        kind: DecisionKind,
+        draw: Draw,
+        accept: Accept,
+    ) -> usize
+    where
+        Draw: FnOnce(&mut StdRng) -> usize,
+        Accept: Fn(usize) -> bool,
+    {
+        let value = draw(rng);
+        match self {
+            DecisionLog::Off => value,
+            DecisionLog::Recording(decisions) => {
+                decisions.push(Decision {
+                    generation,
+                    kind,
+                    value,
+                });
+                value
+            }
+            DecisionLog::Replaying(decisions) => decisions
+                .next_if(|decision| decision.kind == kind)
+                .map(|decision| decision.value)
+                .filter(|&logged| accept(logged))
+                .unwrap_or(value),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn recorded_decisions_replay_the_run() {
+        let build = || {
+            world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_elite_individuals(1)
+                .with_migrating_individuals(1)
+                .with_migration_algorithm(MigrationAlgorithm::CompletelyRandom)
+                .with_generations_between_migrations(1)
+                .build()
+                .unwrap()
+        };
+        let mut recorded = build();
+        recorded.genetic_engine_mut().record_decisions();
+        recorded.run_n_generations(3).unwrap();
+        let log = recorded.genetic_engine_mut().take_decisions();
+        for kind in [
+            DecisionKind::Operator,
+            DecisionKind::Elite { island: 1 },
+            DecisionKind::Parent { island: 0 },
+            DecisionKind::Emigrant { island: 1 },
+            DecisionKind::Destination { island: 0 },
+        ] {
+            assert!(log.iter().any(|decision| decision.kind == kind));
+        }
+        // The last migration happens once the third generation is complete
+        assert_eq!(3, log.last().unwrap().generation);
+
+        let mut replayed = build();
+        replayed.genetic_engine_mut().replay_decisions(log.clone());
+        replayed.run_n_generations(3).unwrap();
+        assert_eq!(recorded.snapshot(), replayed.snapshot());
+
+        // Keeping a different elite in the last generation changes the outcome
+        let mut altered = log;
+        let elite = altered
+            .iter_mut()
+            .rev()
+            .find(|decision| decision.kind == DecisionKind::Elite { island: 0 })
+            .unwrap();
+        elite.value = (elite.value + 1) % 4;
+        let mut replayed = build();
+        replayed.genetic_engine_mut().replay_decisions(altered);
+        replayed.run_n_generations(3).unwrap();
+        assert_ne!(recorded.snapshot(), replayed.snapshot());
+    }
+
+    #[test]
+    fn replays_fall_back_to_the_generator_when_the_log_does_not_fit() {
+        let logged = |kind, value| Decision {
+            generation: 0,
+            kind,
+            value,
+        };
+        let mut log = DecisionLog::Replaying(
+            vec![
+                logged(DecisionKind::Parent { island: 0 }, 7),
+                logged(DecisionKind::Parent { island: 0 }, 2),
+            ]
+            .into_iter()
+            .peekable(),
+        );
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut draws = 0;
+        let mut decide = |log: &mut DecisionLog, kind| {
+            log.decide(
+                &mut rng,
+                0,
+                kind,
+                |_| {
+                    draws += 1;
+                    100
+                },
+                |value| value < 4,
+            )
+        };
+
+        // A decision of another kind leaves the log where it is, and a rejected value is used up
+        assert_eq!(100, decide(&mut log, DecisionKind::Operator));
+        assert_eq!(100, decide(&mut log, DecisionKind::Parent { island: 0 }));
+        assert_eq!(2, decide(&mut log, DecisionKind::Parent { island: 0 }));
+        assert_eq!(100, decide(&mut log, DecisionKind::Parent { island: 0 }));
+        assert_eq!(4, draws);
+
+        // Taking decisions from an engine that is not recording gives an empty log
+        let mut engine = counting_engine(1);
+        assert!(engine.take_decisions().is_empty());
+        engine.replay_decisions(vec![]);
+        assert!(engine.take_decisions().is_empty());
+    }
+}
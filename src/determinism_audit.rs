@@ -0,0 +1,195 @@
+use crate::{GeneticError, Genetics, World, WorldPhase};
+
+/// Looks for nondeterminism by running two worlds that should be identical side by side, one step at a time, and
+/// comparing them after every step. The factory is called twice and must build the same world both times, including
+/// the seed of the genetic engine. If the worlds ever differ, the audit reports the first step where they did, which
+/// points at the part of the user's code that is not deterministic: the `Genetics` implementation while filling, or
+/// the island engine of the reported island while evaluating.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeterminismAudit {
+    /// The number of generations to compare
+    pub generations: usize,
+}
+
+/// The first step at which the two worlds of a `DeterminismAudit` differed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The number of generations both worlds had completed
+    pub generation: usize,
+
+    /// The first island that differed, or None if the islands matched but the rest of the world state did not
+    pub island: Option<usize>,
+
+    /// What the worlds were doing. `WorldPhase::Evaluating` covers both running and sorting the island, and
+    /// `WorldPhase::Migrating` covers everything that completes a generation, such as statistics and checkpoints.
+    pub phase: WorldPhase,
+}
+
+impl Default for DeterminismAudit {
+    fn default() -> Self {
+        DeterminismAudit { generations: 10 }
+    }
+}
+
+impl DeterminismAudit {
+    /// Runs the audit. Returns the first divergence, or None if both worlds stayed identical for every generation. An
+    /// error is returned if both worlds fail at the same step; if only one of them fails, that is a divergence.
+    pub fn run<G, Factory>(&self, mut factory: Factory) -> Result<Option<Divergence>, GeneticError>
+    where
+        G: Genetics,
+        Factory: FnMut() -> Result<World<G>, GeneticError>,
+    {
+        let mut first = factory()?;
+        let mut second = factory()?;
+
+        while first.generation_count() < self.generations {
+            let generation = first.generation_count();
+            let (phase, running) = first.next_poll_step();
+            let divergence = |island| Divergence {
+                generation,
+                island,
+                phase,
+            };
+
+            let completed = match (first.poll_step(), second.poll_step()) {
+                (Ok(first), Ok(second)) if first == second => first,
+                (Err(error), Err(_)) => return Err(error),
+                _ => return Ok(Some(divergence(running))),
+            };
+            if let Some(island) = first_different_island(&first, &second) {
+                return Ok(Some(divergence(Some(island))));
+            }
+            if completed && first.snapshot() != second.snapshot() {
+                return Ok(Some(divergence(None)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+// Returns the index of the first island whose population or scores differ between the worlds
+fn first_different_island<G: Genetics>(first: &World<G>, second: &World<G>) -> Option<usize> {
+    (0..first.get_number_of_islands()).find(|&index| {
+        let (a, b) = (first.get_island(index), second.get_island(index));
+        match (a, b) {
+            (Some(a), Some(b)) => {
+                a.snapshot() != b.snapshot()
+                    || (0..a.len()).any(|i| a.score_for_individual(i) != b.score_for_individual(i))
+            }
+            _ => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    // Scores individuals by id, adding its bonus once it has run more than one generation of four individuals
+    struct FlakyEngine {
+        bonus: u64,
+        runs: usize,
+    }
+
+    impl IslandEngine for FlakyEngine {
+        fn run_individual(&mut self, _id: u64) {
+            self.runs += 1;
+        }
+
+        fn score_individual(&self, id: u64) -> u64 {
+            if self.runs > 4 {
+                id + self.bonus
+            } else {
+                id
+            }
+        }
+    }
+
+    #[test]
+    fn audit_finds_the_first_divergent_island() {
+        let audit = DeterminismAudit { generations: 3 };
+        let steady = audit.run(|| {
+            world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .build()
+        });
+        assert_eq!(None, steady.unwrap());
+
+        let mut worlds_built = 0;
+        let divergence = audit
+            .run(|| {
+                worlds_built += 1;
+                let mut builder = world_builder(&["a"])
+                    .with_individuals_per_island(4)
+                    .with_migrating_individuals(1);
+                builder.add_island(
+                    "flaky",
+                    Box::new(FlakyEngine {
+                        bonus: worlds_built,
+                        runs: 0,
+                    }),
+                );
+                builder.build()
+            })
+            .unwrap();
+        assert_eq!(
+            Some(Divergence {
+                generation: 1,
+                island: Some(1),
+                phase: WorldPhase::Evaluating,
+            }),
+            divergence
+        );
+    }
+
+    #[test]
+    fn failures_are_errors_only_when_both_worlds_fail() {
+        // Auditing no generations never builds a step
+        assert_eq!(
+            None,
+            DeterminismAudit { generations: 0 }
+                .run(|| world_builder(&["a"]).build())
+                .unwrap()
+        );
+
+        let audit = DeterminismAudit { generations: 3 };
+        let failing = audit.run(|| {
+            let mut builder = world_builder(&["a"]).with_migrating_individuals(0);
+            builder.add_island("b", Box::new(FailingEngine));
+            builder.build()
+        });
+        assert!(
+            matches!(failing, Err(GeneticError::EvaluationFailed { island, .. }) if island == "b")
+        );
+
+        // Only the first world fails, in its second generation
+        let mut worlds_built = 0;
+        let divergence = audit
+            .run(|| {
+                worlds_built += 1;
+                let mut builder = world_builder(&["a"])
+                    .with_individuals_per_island(4)
+                    .with_migrating_individuals(0);
+                let fail_on = if worlds_built == 1 { 6 } else { 0 };
+                builder.add_island("b", Box::new(FailOnceEngine { fail_on, runs: 0 }));
+                builder.build()
+            })
+            .unwrap();
+        assert_eq!(
+            Some(Divergence {
+                generation: 1,
+                island: Some(1),
+                phase: WorldPhase::Evaluating,
+            }),
+            divergence
+        );
+
+        // A world the factory cannot build is an error too
+        assert!(audit
+            .run(|| world_builder(&["a"]).with_individuals_per_island(0).build())
+            .is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Blackboard, IslandEngine, MaybeSend, MaybeSync, ScratchArena};
+
+/// Maps the genome of an individual to the structure that is actually evaluated, its phenome, so that the two can
+/// differ. A grammar-based encoding, for example, evolves a list of codons and develops each genome into the program
+/// the grammar derives from them. Use it through a `DevelopingEngine`.
+pub trait Development: MaybeSend + MaybeSync {
+    /// The structure an island engine evaluates
+    type Phenome: MaybeSend + MaybeSync;
+
+    /// Develops the genome of an individual into its phenome. Called at most once per individual while it stays on the
+    /// island, so it may be expensive.
+    fn develop(&self, genome: u64) -> Self::Phenome;
+}
+
+/// An island engine that runs developed phenomes rather than genomes. Every other `IslandEngine` method works as
+/// usual; `run_individual` is not called by a `DevelopingEngine` and may do nothing.
+pub trait PhenomeEngine<P>: IslandEngine {
+    /// Runs the phenome of one individual. Called instead of `IslandEngine::try_run_individual`, with the same meaning
+    /// for errors.
+    fn run_phenome(&mut self, id: u64, phenome: &P) -> Result<(), String>;
+}
+
+/// Wraps an island engine so that each individual is developed into its phenome before it is run. Phenomes are cached
+/// for as long as their individual stays on the island, so elites and migrants that are run again in a later
+/// generation are not developed again.
+pub struct DevelopingEngine<D: Development, E> {
+    development: D,
+    engine: E,
+    phenomes: HashMap<u64, D::Phenome>,
+}
+
+impl<D, E> DevelopingEngine<D, E>
+where
+    D: Development,
+    E: PhenomeEngine<D::Phenome>,
+{
+    pub fn new(development: D, engine: E) -> Self {
+        DevelopingEngine {
+            development,
+            engine,
+            phenomes: HashMap::new(),
+        }
+    }
+
+    /// Borrows the development stage
+    pub fn development(&self) -> &D {
+        &self.development
+    }
+
+    /// Borrows the wrapped engine
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+
+    /// Borrows the wrapped engine for modification
+    pub fn engine_mut(&mut self) -> &mut E {
+        &mut self.engine
+    }
+
+    /// Returns the cached phenome of an individual, or None if it has not been developed or has left the island
+    pub fn phenome(&self, id: u64) -> Option<&D::Phenome> {
+        self.phenomes.get(&id)
+    }
+}
+
+impl<D, E> IslandEngine for DevelopingEngine<D, E>
+where
+    D: Development,
+    E: PhenomeEngine<D::Phenome>,
+{
+    fn read_blackboard(&mut self, blackboard: &Blackboard) {
+        self.engine.read_blackboard(blackboard);
+    }
+
+    fn write_blackboard(&mut self, blackboard: &mut Blackboard) {
+        self.engine.write_blackboard(blackboard);
+    }
+
+    fn curriculum_stage_started(&mut self, stage: usize, name: &str) {
+        self.engine.curriculum_stage_started(stage, name);
+    }
+
+    fn pre_generation_run(&mut self, individuals: &[u64]) {
+        // Drop the phenomes of individuals that have left the island
+        let individuals_set: HashSet<u64> = individuals.iter().copied().collect();
+        self.phenomes.retain(|id, _| individuals_set.contains(id));
+        self.engine.pre_generation_run(individuals);
+    }
+
+    fn post_generation_run(&mut self, individuals: &[u64]) {
+        self.engine.post_generation_run(individuals);
+    }
+
+    fn run_individual(&mut self, id: u64) {
+        // Failures cannot be reported from here; the island calls `run_individual_with_scratch` instead
+        let _ = self.try_run_individual(id);
+    }
+
+    fn try_run_individual(&mut self, id: u64) -> Result<(), String> {
+        let development = &self.development;
+        let phenome = self
+            .phenomes
+            .entry(id)
+            .or_insert_with(|| development.develop(id));
+        self.engine.run_phenome(id, phenome)
+    }
+
+    fn run_individual_with_scratch(
+        &mut self,
+        id: u64,
+        _scratch: &mut ScratchArena,
+    ) -> Result<(), String> {
+        self.try_run_individual(id)
+    }
+
+    fn is_transient_failure(&self, message: &str) -> bool {
+        self.engine.is_transient_failure(message)
+    }
+
+    fn sort_individuals(&self, a: u64, b: u64) -> std::cmp::Ordering {
+        self.engine.sort_individuals(a, b)
+    }
+
+    fn score_individual(&self, id: u64) -> u64 {
+        self.engine.score_individual(id)
+    }
+
+    fn ranks_by_score(&self) -> bool {
+        self.engine.ranks_by_score()
+    }
+
+    fn score_generation(&self, individuals: &[u64]) -> Option<Vec<u64>> {
+        self.engine.score_generation(individuals)
+    }
+
+    fn validation_score(&self, id: u64) -> Option<u64> {
+        self.engine.validation_score(id)
+    }
+
+    fn quick_score(&mut self, id: u64) -> Option<u64> {
+        self.engine.quick_score(id)
+    }
+
+    fn normalized_score(&self, id: u64) -> Option<f64> {
+        self.engine.normalized_score(id)
+    }
+
+    fn constraint_violation(&self, id: u64) -> f64 {
+        self.engine.constraint_violation(id)
+    }
+
+    fn objectives(&self, id: u64) -> Vec<f64> {
+        self.engine.objectives(id)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    // Develops each genome into the binary digits of its id
+    struct BinaryDevelopment {
+        developed: Arc<AtomicUsize>,
+    }
+
+    impl Development for BinaryDevelopment {
+        type Phenome = String;
+
+        fn develop(&self, genome: u64) -> String {
+            self.developed.fetch_add(1, Ordering::Relaxed);
+            format!("{:b}", genome)
+        }
+    }
+
+    // Scores an individual by the number of ones in its phenome, and fails on phenomes longer than 16 digits
+    #[derive(Default)]
+    struct OnesEngine {
+        scores: HashMap<u64, u64>,
+    }
+
+    impl IslandEngine for OnesEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            self.scores[&id]
+        }
+    }
+
+    impl PhenomeEngine<String> for OnesEngine {
+        fn run_phenome(&mut self, id: u64, phenome: &String) -> Result<(), String> {
+            if phenome.len() > 16 {
+                return Err(format!("{} is too long", phenome));
+            }
+            self.scores
+                .insert(id, phenome.chars().filter(|&c| c == '1').count() as u64);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn phenomes_are_developed_once_per_individual() {
+        let developed = Arc::new(AtomicUsize::new(0));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_elite_individuals(2)
+            .with_migrating_individuals(1);
+        builder.add_island(
+            "a",
+            Box::new(DevelopingEngine::new(
+                BinaryDevelopment {
+                    developed: developed.clone(),
+                },
+                OnesEngine::default(),
+            )),
+        );
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(3).unwrap();
+
+        // The elites carried into each later generation keep their phenomes
+        let stats = world.stats_history();
+        let runs: usize = stats.iter().map(|stats| stats.islands[0].evaluations).sum();
+        assert_eq!(12, runs);
+        assert_eq!(8, developed.load(Ordering::Relaxed));
+
+        let champion = world.most_fit_individual().unwrap();
+        assert_eq!(
+            champion.score,
+            format!("{:b}", champion.id).matches('1').count() as u64
+        );
+    }
+
+    #[test]
+    fn phenomes_are_dropped_when_individuals_leave() {
+        let developed = Arc::new(AtomicUsize::new(0));
+        let mut engine = DevelopingEngine::new(
+            BinaryDevelopment {
+                developed: developed.clone(),
+            },
+            OnesEngine::default(),
+        );
+        engine.try_run_individual(5).unwrap();
+        engine.try_run_individual(6).unwrap();
+        assert_eq!(Some("101"), engine.phenome(5).map(String::as_str));
+
+        // Only the individual that stayed keeps its phenome, so the one that left is developed again if it returns
+        engine.pre_generation_run(&[6]);
+        assert_eq!(None, engine.phenome(5));
+        engine.try_run_individual(6).unwrap();
+        engine.try_run_individual(5).unwrap();
+        assert_eq!(3, developed.load(Ordering::Relaxed));
+
+        // Failures of the wrapped engine are passed on, and the phenome is still kept for a retry
+        assert_eq!(
+            Err(format!("{:b} is too long", 1 << 20)),
+            engine.try_run_individual(1 << 20)
+        );
+        assert!(engine.phenome(1 << 20).is_some());
+        assert_eq!(2, engine.score_individual(6));
+    }
+}
@@ -0,0 +1,102 @@
+/// How the elites copied into the next generation of an island are scored there. This decides whether an elite has
+/// to earn its place again in every generation, which matters most when the fitness is noisy: an elite that was
+/// evaluated again loses a lucky score, while one that keeps its score can hold its place on that luck alone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EliteHandling {
+    /// Elites are run and scored again like every other individual. With `NoisyEvaluation`, `resample_elites` decides
+    /// whether their old samples are discarded first.
+    #[default]
+    CopyAndReevaluate,
+
+    /// Elites are not run again and are ranked by the score they had when they were selected, saving their evaluations.
+    /// An elite selected again in the following generation keeps that same score.
+    CopyWithCachedScore,
+
+    /// Elites are run again, so that the island engine sees the whole generation, such as when individuals are scored
+    /// against each other, but they are still ranked by the score they had when they were selected.
+    Pin,
+}
+
+impl EliteHandling {
+    /// Returns true if elites are ranked by the score they had when they were selected
+    pub(crate) fn keeps_score(&self) -> bool {
+        *self != EliteHandling::CopyAndReevaluate
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn elite_handling_decides_how_elites_are_scored() {
+        // Every generation scores 1000 higher than the one before it
+        struct InflatingEngine {
+            generation: u64,
+        }
+
+        impl IslandEngine for InflatingEngine {
+            fn pre_generation_run(&mut self, _individuals: &[u64]) {
+                self.generation += 1;
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_individual(&self, id: u64) -> u64 {
+                self.generation * 1000 + id
+            }
+        }
+
+        let run = |handling: EliteHandling| {
+            let mut builder = WorldBuilder::new()
+                .with_genetic_engine(counting_engine(1234))
+                .with_individuals_per_island(4)
+                .with_elite_individuals(1)
+                .with_migrating_individuals(0)
+                .with_elite_handling(handling);
+            builder.add_island("a", Box::new(InflatingEngine { generation: 0 }));
+            let mut world = builder.build().unwrap();
+            world.run_n_generations(2).unwrap();
+
+            let stats = &world.stats_history()[1].islands[0];
+            let elite = stats.elites[0];
+            let score = world.get_island(0).unwrap().score_of(elite).unwrap();
+            (score - elite, stats.evaluations, stats.cached_evaluations)
+        };
+
+        assert_eq!((2000, 4, 0), run(EliteHandling::CopyAndReevaluate));
+        assert_eq!((1000, 3, 1), run(EliteHandling::CopyWithCachedScore));
+        assert_eq!((1000, 4, 0), run(EliteHandling::Pin));
+    }
+
+    #[test]
+    fn elite_handling_only_changes_the_evaluations_of_elites() {
+        let run = |elites: usize, handling: EliteHandling| {
+            let mut world = world_builder(&["a"])
+                .with_individuals_per_island(4)
+                .with_elite_individuals(elites)
+                .with_migrating_individuals(0)
+                .with_elite_handling(handling)
+                .build()
+                .unwrap();
+            world.run_n_generations(3).unwrap();
+            let stats = &world.stats_history()[2].islands[0];
+            (stats.evaluations, stats.cached_evaluations)
+        };
+
+        // Without elites every handling runs the whole island
+        for handling in [
+            EliteHandling::CopyAndReevaluate,
+            EliteHandling::CopyWithCachedScore,
+            EliteHandling::Pin,
+        ] {
+            assert_eq!((4, 0), run(0, handling));
+        }
+
+        // With as many elites as an island allows, only the one bred child is run when elites keep their scores
+        assert_eq!((1, 3), run(3, EliteHandling::CopyWithCachedScore));
+        assert_eq!((4, 0), run(3, EliteHandling::Pin));
+    }
+}
@@ -0,0 +1,107 @@
+/// Limits emigration to the better individuals of each island, so that weak individuals do not spread across the world,
+/// for example during the early generations of a run. Individuals that fall below the threshold are not counted as
+/// available to migrate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmigrationThreshold {
+    /// Only individuals whose score is at least this value may emigrate
+    Score(u64),
+
+    /// Only individuals ranked at or above this fraction of their island may emigrate. 0.0 allows every individual,
+    /// and 0.75 allows the most fit quarter. Must be at least 0.0 and below 1.0.
+    Percentile(f64),
+}
+
+impl EmigrationThreshold {
+    /// Returns true if the threshold is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        match self {
+            EmigrationThreshold::Score(_) => true,
+            EmigrationThreshold::Percentile(percentile) => (0.0..1.0).contains(percentile),
+        }
+    }
+
+    /// Returns the index of the least fit individual that may emigrate, given the scores of a sorted island from least
+    /// to most fit. Every individual from that index up may emigrate.
+    pub(crate) fn first_eligible(&self, scores: &[u64]) -> usize {
+        match self {
+            EmigrationThreshold::Score(threshold) => {
+                let eligible = scores
+                    .iter()
+                    .rev()
+                    .take_while(|&&score| score >= *threshold)
+                    .count();
+                scores.len() - eligible
+            }
+            EmigrationThreshold::Percentile(percentile) => {
+                (scores.len() as f64 * percentile) as usize
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn only_individuals_above_the_threshold_emigrate() {
+        let migrate_with = |threshold: EmigrationThreshold| {
+            let mut world = world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_generations_between_migrations(0)
+                .with_migrating_individuals(4)
+                .with_emigration_threshold(threshold)
+                .build()
+                .unwrap();
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+            let top_half: Vec<Vec<u64>> = (0..2)
+                .map(|index| world.get_island(index).unwrap().snapshot().individuals[2..].to_vec())
+                .collect();
+            world.migrate_individuals_between_islands();
+            (top_half, world.migration_log().to_vec())
+        };
+
+        let (top_half, log) = migrate_with(EmigrationThreshold::Percentile(0.5));
+        for record in log {
+            assert_eq!(2, record.available);
+            assert!(record
+                .migrants
+                .iter()
+                .all(|migrant| top_half[record.source_island].contains(&migrant.id)));
+        }
+
+        let (_, log) = migrate_with(EmigrationThreshold::Score(u64::MAX));
+        assert!(log.iter().all(|record| record.migrants.is_empty()));
+    }
+
+    #[test]
+    fn thresholds_include_individuals_exactly_at_them() {
+        let scores = [1, 5, 5, 9];
+        assert_eq!(1, EmigrationThreshold::Score(5).first_eligible(&scores));
+        assert_eq!(0, EmigrationThreshold::Score(0).first_eligible(&scores));
+        assert_eq!(4, EmigrationThreshold::Score(10).first_eligible(&scores));
+        assert_eq!(0, EmigrationThreshold::Score(5).first_eligible(&[]));
+
+        // A percentile rounds down, so it never excludes more of the island than it asks for
+        assert_eq!(
+            0,
+            EmigrationThreshold::Percentile(0.0).first_eligible(&scores)
+        );
+        assert_eq!(
+            2,
+            EmigrationThreshold::Percentile(0.6).first_eligible(&scores)
+        );
+        assert_eq!(
+            3,
+            EmigrationThreshold::Percentile(0.99).first_eligible(&scores)
+        );
+
+        assert!(EmigrationThreshold::Percentile(0.0).is_valid());
+        for percentile in [-0.1, 1.0, f64::NAN] {
+            assert!(!EmigrationThreshold::Percentile(percentile).is_valid());
+        }
+    }
+}
@@ -20,6 +20,12 @@ pub enum GeneticError {
     #[error("Crossover points must be at least 1 when crossover rate is greater than 0")]
     InvalidCrossoverPoints,
 
+    #[error("A point fraction must be greater than 0 and at most 1")]
+    InvalidPointFraction,
+
+    #[error("At least one of mutation rate or crossover rate must be greater than 0")]
+    NoGeneticOperators,
+
     #[error("individuals_per_island must be greater than 0")]
     InvalidIndividualsPerIsland,
 
@@ -29,9 +35,187 @@ pub enum GeneticError {
     #[error("max_individual_points must be greater than 0")]
     InvalidIndividualPoints,
 
+    #[error("max_child_attempts must be greater than 0")]
+    InvalidChildAttempts,
+
     #[error("number_of_individuals_migrating must not exceed individuals_per_island")]
     InvalidMigrationCount,
 
     #[error("genetic_engine implementation is required")]
     MissingGeneticEngine,
+
+    #[error("Hypermutation requires stagnation_generations greater than 0 and multipliers of at least 1.0")]
+    InvalidHypermutation,
+
+    #[error("At least one island is required")]
+    MissingIslands,
+
+    #[error("Island names must be unique: '{0}' is used more than once")]
+    DuplicateIslandName(String),
+
+    #[error("A tournament requires at least one world")]
+    EmptyTournament,
+
+    #[error("Island '{island}' has {actual} individuals but {expected} were expected")]
+    PopulationMismatch {
+        island: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("Island '{island}' received duplicate child {id} from the genetic engine")]
+    DuplicateIndividual { island: String, id: u64 },
+
+    #[error("Migration fractions and probabilities must be between 0 and 1")]
+    InvalidMigrantCount,
+
+    #[error("Noisy evaluation requires at least one evaluation per individual, and no more than the adaptive maximum")]
+    InvalidNoisyEvaluation,
+
+    #[error(
+        "Island weight for '{0}' must name an existing island and be a finite, non-negative number"
+    )]
+    InvalidIslandWeight(String),
+
+    #[error("No island is named '{0}'")]
+    UnknownIsland(String),
+
+    #[error("Island '{0}' has more seed individuals than individuals_per_island")]
+    TooManySeedIndividuals(String),
+
+    #[error("A run budget needs a generation limit or a target score")]
+    UnboundedRunBudget,
+
+    #[error("An emigration percentile must be at least 0.0 and below 1.0")]
+    InvalidEmigrationThreshold,
+
+    #[error("Curriculum stages need a nonzero advancement criterion, except the last")]
+    InvalidCurriculum,
+
+    #[error("Parent curve blend weights must be finite, non-negative and not all zero")]
+    InvalidParentCurveBlend,
+
+    #[error("Selection curve Custom({0}) has no registered selector")]
+    UnknownSelector(usize),
+
+    #[error("The migrant novelty threshold must be a finite, non-negative number")]
+    InvalidMigrantNoveltyThreshold,
+
+    #[error("The minimum improvement of a champion sink must be a finite, non-negative number")]
+    InvalidChampionSink,
+
+    #[error("A Latin hypercube initialization needs at least one dimension")]
+    InvalidInitialization,
+
+    #[error("Brood size must be at least one")]
+    InvalidBroodSize,
+
+    #[error("Island quarantine requires at least one failure and one generation")]
+    InvalidIslandQuarantine,
+
+    #[error("Generation retry requires at least one retry")]
+    InvalidGenerationRetry,
+
+    #[error("Population viability requires a minimum between one and individuals_per_island")]
+    InvalidPopulationViability,
+
+    #[error("Real vector bounds must be finite, with each lower bound below its upper bound")]
+    InvalidRealBounds,
+
+    #[error("Real vector operators need a positive sigma and a non-negative distribution index")]
+    InvalidRealOperator,
+
+    #[error("Stochastic ranking requires an objective probability between 0.0 and 1.0")]
+    InvalidStochasticRanking,
+
+    #[error("Adaptive migration requires min_migrants <= max_migrants <= individuals_per_island")]
+    InvalidAdaptiveMigration,
+
+    #[error("Mating restriction requires a max_distance of at least 0.0 and at least one attempt")]
+    InvalidMatingRestriction,
+
+    #[error(
+        "A rate limit requires a positive, finite requests_per_second and a burst of at least one"
+    )]
+    InvalidRateLimit,
+
+    #[error("A hall of fame requires a capacity and sample size of at least one and a non-negative recency weight")]
+    InvalidHallOfFame,
+
+    #[error("Linear programs need a register, an operation, unique operation names and arities of at most two")]
+    InvalidLinearProgramSetup,
+
+    #[error("Tree primitives need unique names without spaces or parentheses, and at least one terminal")]
+    InvalidPrimitiveSet,
+
+    #[error("Tree depths must allow the initial depth range, which must not be empty")]
+    InvalidTreeDepth,
+
+    #[error("Cartesian genomes need inputs, outputs, a column, a function and unique function names without spaces")]
+    InvalidCartesianGrid,
+
+    #[error("Invalid grammar: {0}")]
+    InvalidGrammar(String),
+
+    #[error("A score of {0} does not fit in a u64")]
+    ScoreOverflow(u128),
+
+    #[error("Could not produce a valid child in {0} attempts")]
+    CouldNotProduceValidChild(usize),
+
+    #[error("Island '{island}' has {individuals} individuals to breed from, fewer than the minimum of {minimum}")]
+    PopulationNotViable {
+        island: String,
+        individuals: usize,
+        minimum: usize,
+    },
+
+    #[error("Island '{island}' could not create its engine: {message}")]
+    IslandEngineUnavailable { island: String, message: String },
+
+    #[error("Island '{island}' failed to evaluate individual {id}: {message}")]
+    EvaluationFailed {
+        island: String,
+        id: u64,
+        message: String,
+    },
+
+    #[error("Island '{0}' has no sorted generation to select individuals from")]
+    IslandNotSorted(String),
+
+    #[error("Island '{island}' is not sorted by score: index {index} scored {score} but index {next_index} scored {next_score}")]
+    ScoreOrderViolation {
+        island: String,
+        index: usize,
+        score: u64,
+        next_index: usize,
+        next_score: u64,
+    },
+
+    #[error("Checkpoints must be written at least one generation apart")]
+    InvalidCheckpointInterval,
+
+    #[error("At least one checkpoint must be kept")]
+    InvalidCheckpointsToKeep,
+
+    #[error("'{value}' is not a valid {kind}")]
+    InvalidSettingValue { kind: &'static str, value: String },
+
+    #[error("Invalid population: {0}")]
+    InvalidPopulation(String),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Individual {0} of the snapshot is no longer held by the genetics")]
+    MissingIndividual(u64),
+
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+
+    #[error("Invalid event log: {0}")]
+    InvalidEventLog(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
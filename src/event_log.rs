@@ -0,0 +1,536 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{GeneticError, IndividualMetadata, IndividualOrigin, Island};
+
+/// One change to the populations of a world, as recorded in an `EventLog`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RunEvent {
+    /// An individual joined an island for the first time anywhere in the log. `origin` and `parents` come from the
+    /// world's `IndividualMetadata`, and `origin` is None when the world has no record of how it was created, such as
+    /// for an individual restored from a snapshot.
+    Born {
+        generation: usize,
+        island: usize,
+        id: u64,
+        origin: Option<IndividualOrigin>,
+        parents: Vec<u64>,
+    },
+
+    /// An individual that had been on some island before joined this island other than by migration
+    Joined {
+        generation: usize,
+        island: usize,
+        id: u64,
+    },
+
+    /// An individual left an island other than by migration: it was not kept in the next generation, lost its brood,
+    /// or the island was cleared
+    Died {
+        generation: usize,
+        island: usize,
+        id: u64,
+    },
+
+    /// An individual was sent to the next generation of another island. A cloned migrant also stays on its source.
+    Migrated {
+        generation: usize,
+        from: usize,
+        to: usize,
+        id: u64,
+        cloned: bool,
+    },
+
+    /// An island gave an individual a score it had not been logged with on that island before
+    Evaluated {
+        generation: usize,
+        island: usize,
+        id: u64,
+        score: u64,
+    },
+
+    /// Every island finished evaluating the generation. The populations rebuilt from the events up to here are the
+    /// populations that were evaluated in that generation, before any migration that followed it.
+    Generation { generation: usize, islands: usize },
+}
+
+/// A record of every change to the populations of a world, kept when the world is built with
+/// `WorldBuilder::with_event_log`. Replaying the log rebuilds exactly the population every island evaluated in any
+/// generation, along with how each individual was bred, where it migrated and how it scored, which makes it a complete
+/// artifact for debugging or analyzing a run after the fact.
+///
+/// The world compares each island with the replayed log once every island has been evaluated, and logs whatever
+/// changed since, so the log stays correct however the population was changed, including by `World::restore`.
+/// Migrations are logged as they happen. Populations are rebuilt as multisets: the order of individuals within an
+/// island is not recorded, and an individual copied more than once appears more than once.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventLog {
+    events: Vec<RunEvent>,
+}
+
+const EVENT_LOG_HEADER: &str = "rust_genetic_optimizer event log 1";
+
+impl EventLog {
+    /// Returns every event, oldest first
+    pub fn events(&self) -> &[RunEvent] {
+        &self.events
+    }
+
+    /// Returns the number of events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if nothing has been logged
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Rebuilds the population every island evaluated in a generation, each sorted by id. If the generation was
+    /// run more than once, such as after `World::restart`, the most recent run is returned. Returns None if the log
+    /// has no such generation.
+    pub fn population_at(&self, generation: usize) -> Option<Vec<Vec<u64>>> {
+        let mut populations = Populations::default();
+        let mut found = None;
+        for event in self.events.iter() {
+            populations.apply(event);
+            if let RunEvent::Generation { generation: g, .. } = event {
+                if *g == generation {
+                    found = Some(populations.sorted());
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Encodes the log as plain text, one event per line, in the format read by `from_text`
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", EVENT_LOG_HEADER);
+        for event in self.events.iter() {
+            let line = match event {
+                RunEvent::Born {
+                    generation,
+                    island,
+                    id,
+                    origin,
+                    parents,
+                } => {
+                    let origin = origin.map_or("-".to_string(), |origin| format!("{:?}", origin));
+                    let parents: String = parents.iter().map(|id| format!(" {}", id)).collect();
+                    format!(
+                        "born {} {} {} {}{}",
+                        generation, island, id, origin, parents
+                    )
+                }
+                RunEvent::Joined {
+                    generation,
+                    island,
+                    id,
+                } => format!("joined {} {} {}", generation, island, id),
+                RunEvent::Died {
+                    generation,
+                    island,
+                    id,
+                } => format!("died {} {} {}", generation, island, id),
+                RunEvent::Migrated {
+                    generation,
+                    from,
+                    to,
+                    id,
+                    cloned,
+                } => format!(
+                    "migrated {} {} {} {} {}",
+                    generation, from, to, id, *cloned as u8
+                ),
+                RunEvent::Evaluated {
+                    generation,
+                    island,
+                    id,
+                    score,
+                } => format!("evaluated {} {} {} {}", generation, island, id, score),
+                RunEvent::Generation {
+                    generation,
+                    islands,
+                } => format!("generation {} {}", generation, islands),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Decodes a log written by `to_text`
+    pub fn from_text(text: &str) -> Result<EventLog, GeneticError> {
+        let mut lines = text.lines();
+        if lines.next() != Some(EVENT_LOG_HEADER) {
+            return Err(invalid("missing header"));
+        }
+
+        let mut events = vec![];
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let mut words = line.split_whitespace();
+            let kind = words.next().unwrap_or_default();
+            let mut number = || parse_number(words.next(), kind);
+            let event = match kind {
+                "born" => {
+                    let generation = parse_number(words.next(), kind)? as usize;
+                    let island = parse_number(words.next(), kind)? as usize;
+                    let id = parse_number(words.next(), kind)?;
+                    let origin = match words.next() {
+                        Some("-") => None,
+                        Some(origin) => Some(parse_origin(origin)?),
+                        None => return Err(invalid("invalid born event")),
+                    };
+                    let parents = words
+                        .map(|word| word.parse().map_err(|_| invalid("invalid born event")))
+                        .collect::<Result<Vec<u64>, GeneticError>>()?;
+                    RunEvent::Born {
+                        generation,
+                        island,
+                        id,
+                        origin,
+                        parents,
+                    }
+                }
+                "joined" => RunEvent::Joined {
+                    generation: number()? as usize,
+                    island: number()? as usize,
+                    id: number()?,
+                },
+                "died" => RunEvent::Died {
+                    generation: number()? as usize,
+                    island: number()? as usize,
+                    id: number()?,
+                },
+                "migrated" => RunEvent::Migrated {
+                    generation: number()? as usize,
+                    from: number()? as usize,
+                    to: number()? as usize,
+                    id: number()?,
+                    cloned: number()? != 0,
+                },
+                "evaluated" => RunEvent::Evaluated {
+                    generation: number()? as usize,
+                    island: number()? as usize,
+                    id: number()?,
+                    score: number()?,
+                },
+                "generation" => RunEvent::Generation {
+                    generation: number()? as usize,
+                    islands: number()? as usize,
+                },
+                _ => return Err(invalid(&format!("unknown event '{}'", kind))),
+            };
+            events.push(event);
+        }
+
+        Ok(EventLog { events })
+    }
+}
+
+/// Records the events of a world into its `EventLog`, keeping the replayed populations to compare the islands with.
+#[derive(Debug, Default)]
+pub(crate) struct EventRecorder {
+    log: EventLog,
+    populations: Populations,
+    // The last score logged for each individual on each island
+    scores: HashMap<(usize, u64), u64>,
+    // Every individual that has been born in the log
+    born: HashSet<u64>,
+}
+
+impl EventRecorder {
+    pub(crate) fn log(&self) -> &EventLog {
+        &self.log
+    }
+
+    fn record(&mut self, event: RunEvent) {
+        self.populations.apply(&event);
+        self.log.events.push(event);
+    }
+
+    /// Logs a migrant as it is delivered to its destination
+    pub(crate) fn migrated(
+        &mut self,
+        generation: usize,
+        from: usize,
+        to: usize,
+        id: u64,
+        cloned: bool,
+    ) {
+        self.record(RunEvent::Migrated {
+            generation,
+            from,
+            to,
+            id,
+            cloned,
+        });
+    }
+
+    /// Logs every difference between the islands and the replayed populations, every new score, and then the end of
+    /// the generation
+    pub(crate) fn generation_evaluated(
+        &mut self,
+        generation: usize,
+        islands: &[Island],
+        metadata: &HashMap<u64, IndividualMetadata>,
+    ) {
+        self.populations.resize(islands.len());
+        for (index, island) in islands.iter().enumerate() {
+            let mut remaining = counts(island.individuals());
+            let mut logged = counts(&self.populations.0[index]);
+
+            // Anything logged that is not on the island any more has died
+            for id in self.populations.0[index].clone() {
+                if remaining.get(&id).copied().unwrap_or(0) < logged[&id] {
+                    *logged.get_mut(&id).unwrap() -= 1;
+                    self.record(RunEvent::Died {
+                        generation,
+                        island: index,
+                        id,
+                    });
+                }
+            }
+            self.scores
+                .retain(|&(other, id), _| other != index || remaining.contains_key(&id));
+
+            for &id in island.individuals() {
+                let count = logged.entry(id).or_insert(0);
+                if *count > 0 {
+                    *count -= 1;
+                } else if self.born.insert(id) {
+                    let metadata = metadata.get(&id);
+                    self.record(RunEvent::Born {
+                        generation,
+                        island: index,
+                        id,
+                        origin: metadata.map(|metadata| metadata.origin),
+                        parents: metadata
+                            .map(|metadata| metadata.parents.clone())
+                            .unwrap_or_default(),
+                    });
+                } else {
+                    self.record(RunEvent::Joined {
+                        generation,
+                        island: index,
+                        id,
+                    });
+                }
+
+                let remaining = remaining.get_mut(&id).unwrap();
+                *remaining -= 1;
+                let score = island.score(id);
+                if *remaining == 0 && self.scores.insert((index, id), score) != Some(score) {
+                    self.record(RunEvent::Evaluated {
+                        generation,
+                        island: index,
+                        id,
+                        score,
+                    });
+                }
+            }
+        }
+
+        self.record(RunEvent::Generation {
+            generation,
+            islands: islands.len(),
+        });
+    }
+}
+
+// The populations of the islands rebuilt by replaying events
+#[derive(Debug, Default)]
+struct Populations(Vec<Vec<u64>>);
+
+impl Populations {
+    fn resize(&mut self, islands: usize) {
+        if self.0.len() < islands {
+            self.0.resize(islands, vec![]);
+        }
+    }
+
+    fn add(&mut self, island: usize, id: u64) {
+        self.resize(island + 1);
+        self.0[island].push(id);
+    }
+
+    fn remove(&mut self, island: usize, id: u64) {
+        if let Some(population) = self.0.get_mut(island) {
+            if let Some(position) = population.iter().position(|&other| other == id) {
+                population.swap_remove(position);
+            }
+        }
+    }
+
+    fn apply(&mut self, event: &RunEvent) {
+        match *event {
+            RunEvent::Born { island, id, .. } | RunEvent::Joined { island, id, .. } => {
+                self.add(island, id)
+            }
+            RunEvent::Died { island, id, .. } => self.remove(island, id),
+            RunEvent::Migrated {
+                from,
+                to,
+                id,
+                cloned,
+                ..
+            } => {
+                if !cloned {
+                    self.remove(from, id);
+                }
+                self.add(to, id);
+            }
+            RunEvent::Evaluated { .. } => {}
+            RunEvent::Generation { islands, .. } => self.0.resize(islands, vec![]),
+        }
+    }
+
+    fn sorted(&self) -> Vec<Vec<u64>> {
+        self.0
+            .iter()
+            .map(|population| {
+                let mut population = population.clone();
+                population.sort();
+                population
+            })
+            .collect()
+    }
+}
+
+fn counts(ids: &[u64]) -> HashMap<u64, usize> {
+    let mut counts = HashMap::new();
+    for &id in ids {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn parse_number(word: Option<&str>, kind: &str) -> Result<u64, GeneticError> {
+    word.and_then(|word| word.parse().ok())
+        .ok_or_else(|| invalid(&format!("invalid {} event", kind)))
+}
+
+fn parse_origin(text: &str) -> Result<IndividualOrigin, GeneticError> {
+    let origin = match text {
+        "Random" => IndividualOrigin::Random,
+        "Elite" => IndividualOrigin::Elite,
+        "Mutation" => IndividualOrigin::Mutation,
+        "Crossover" => IndividualOrigin::Crossover,
+        "Reproduction" => IndividualOrigin::Reproduction,
+        "Migrant" => IndividualOrigin::Migrant,
+        "Seed" => IndividualOrigin::Seed,
+        _ => {
+            let operator = text
+                .strip_prefix("Operator(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|index| index.parse().ok())
+                .ok_or_else(|| invalid(&format!("unknown origin '{}'", text)))?;
+            IndividualOrigin::Operator(operator)
+        }
+    };
+    Ok(origin)
+}
+
+fn invalid(reason: &str) -> GeneticError {
+    GeneticError::InvalidEventLog(reason.to_string())
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn every_generation_is_rebuilt_from_the_event_log() {
+        for clone in [true, false] {
+            let mut world = world_builder(&["a", "b", "c"])
+                .with_individuals_per_island(6)
+                .with_elite_individuals(2)
+                .with_migrating_individuals(2)
+                .with_generations_between_migrations(0)
+                .with_clone_migrated_individuals(clone)
+                .with_event_log(true)
+                .build()
+                .unwrap();
+
+            // Migrating by hand lets the test see each population as it was evaluated, before migrants leave
+            let mut populations = vec![];
+            for _ in 0..5 {
+                world.run_n_generations(1).unwrap();
+                let islands: Vec<Vec<u64>> = world
+                    .snapshot()
+                    .islands
+                    .into_iter()
+                    .map(|island| {
+                        let mut individuals = island.individuals;
+                        individuals.sort();
+                        individuals
+                    })
+                    .collect();
+                populations.push(islands);
+                world.migrate_individuals_between_islands();
+            }
+
+            let log = EventLog::from_text(&world.event_log().unwrap().to_text()).unwrap();
+            assert_eq!(world.event_log().unwrap(), &log);
+            for (generation, islands) in populations.into_iter().enumerate() {
+                assert_eq!(Some(islands), log.population_at(generation + 1));
+            }
+            assert_eq!(None, log.population_at(6));
+
+            let migrations = log
+                .events()
+                .iter()
+                .filter(
+                    |event| matches!(event, RunEvent::Migrated { cloned, .. } if *cloned == clone),
+                )
+                .count();
+            assert_eq!(5 * 3 * 2, migrations);
+        }
+        assert!(world_builder(&["a"]).build().unwrap().event_log().is_none());
+    }
+
+    #[test]
+    fn event_logs_are_read_back_exactly_or_refused() {
+        let text = "rust_genetic_optimizer event log 1\n\
+                    born 1 0 7 Operator(3) 2 5\n\
+                    born 1 1 8 -\n\
+                    \n\
+                    migrated 1 1 0 8 1\n\
+                    generation 1 2\n";
+        let log = EventLog::from_text(text).unwrap();
+        assert_eq!(4, log.len());
+        assert_eq!(
+            RunEvent::Born {
+                generation: 1,
+                island: 0,
+                id: 7,
+                origin: Some(IndividualOrigin::Operator(3)),
+                parents: vec![2, 5],
+            },
+            log.events()[0]
+        );
+        assert_eq!(Some(vec![vec![7, 8], vec![8]]), log.population_at(1));
+        assert_eq!(log, EventLog::from_text(&log.to_text()).unwrap());
+        assert!(EventLog::from_text(&EventLog::default().to_text())
+            .unwrap()
+            .is_empty());
+
+        for invalid in [
+            "",
+            "born 1 0 7 -\n",
+            "rust_genetic_optimizer event log 1\nhatched 1 0 7\n",
+            "rust_genetic_optimizer event log 1\ndied 1 0\n",
+            "rust_genetic_optimizer event log 1\nborn 1 0 7 Operator(x)\n",
+            "rust_genetic_optimizer event log 1\nborn 1 0 7 - parent\n",
+        ] {
+            assert!(matches!(
+                EventLog::from_text(invalid),
+                Err(GeneticError::InvalidEventLog(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Champion, GenerationStats, GeneticError};
+
+const CHECKPOINTS: &str = "checkpoints";
+const METRICS: &str = "metrics";
+const CHAMPIONS: &str = "champions";
+const MANIFEST: &str = "manifest.txt";
+const GENERATIONS_CSV: &str = "generations.csv";
+const GENERATIONS_CSV_HEADER: &str = concat!(
+    "generation,seed,best_score,improved,island,island_best_score,island_worst_score,island_mean_score,",
+    "distinct_parents,evaluations"
+);
+
+/// Manages the files of one run of an experiment in a stable layout:
+///
+/// ```text
+/// <root>/<run id>/manifest.txt
+/// <root>/<run id>/checkpoints/generation-00000100.checkpoint
+/// <root>/<run id>/metrics/generations.csv
+/// <root>/<run id>/champions/generation-00000100-island-2-id-12345.champion
+/// ```
+///
+/// Checkpoints, champions and the manifest are written to a temporary file first and then renamed into place, so an
+/// interrupted run never leaves a partially written file behind under its final name. The contents of checkpoints and
+/// champions are opaque bytes produced by the caller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExperimentDir {
+    run_id: String,
+    path: PathBuf,
+}
+
+impl ExperimentDir {
+    /// Creates the directory of a new run under `root`, with a run id based on the current time
+    pub fn create<P: AsRef<Path>>(root: P) -> Result<ExperimentDir, GeneticError> {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let base = format!("run-{}", seconds);
+        let mut run_id = base.clone();
+        let mut attempt = 1;
+        while root.as_ref().join(&run_id).exists() {
+            attempt += 1;
+            run_id = format!("{}-{}", base, attempt);
+        }
+
+        Self::create_with_run_id(root, run_id)
+    }
+
+    /// Creates the directory of a new run under `root` with the given run id. Returns an error if it already exists.
+    pub fn create_with_run_id<P: AsRef<Path>, S: Into<String>>(
+        root: P,
+        run_id: S,
+    ) -> Result<ExperimentDir, GeneticError> {
+        let run_id = run_id.into();
+        let path = root.as_ref().join(&run_id);
+        fs::create_dir_all(root.as_ref())?;
+        fs::create_dir(&path)?;
+        for directory in [CHECKPOINTS, METRICS, CHAMPIONS] {
+            fs::create_dir(path.join(directory))?;
+        }
+
+        Ok(ExperimentDir { run_id, path })
+    }
+
+    /// Opens the directory of an existing run, for example to resume it from its latest checkpoint
+    pub fn open<P: AsRef<Path>, S: Into<String>>(
+        root: P,
+        run_id: S,
+    ) -> Result<ExperimentDir, GeneticError> {
+        let run_id = run_id.into();
+        let path = root.as_ref().join(&run_id);
+        for directory in [CHECKPOINTS, METRICS, CHAMPIONS] {
+            fs::create_dir_all(path.join(directory))?;
+        }
+
+        Ok(ExperimentDir { run_id, path })
+    }
+
+    /// Returns the run ids found under `root`, sorted
+    pub fn list_runs<P: AsRef<Path>>(root: P) -> Result<Vec<String>, GeneticError> {
+        let mut runs = vec![];
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                runs.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        runs.sort();
+
+        Ok(runs)
+    }
+
+    /// Returns the id of the run
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Returns the directory of the run
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the manifest describing the run, one `key = value` line per entry, replacing any previous manifest
+    pub fn write_manifest(&self, entries: &[(&str, String)]) -> Result<PathBuf, GeneticError> {
+        let mut contents = format!("run_id = {}\n", self.run_id);
+        for (key, value) in entries {
+            contents.push_str(&format!("{} = {}\n", key, value));
+        }
+
+        let path = self.path.join(MANIFEST);
+        write_atomically(&path, contents.as_bytes())?;
+        Ok(path)
+    }
+
+    /// Returns the path of the checkpoint taken after the given generation
+    pub fn checkpoint_path(&self, generation: usize) -> PathBuf {
+        self.path
+            .join(CHECKPOINTS)
+            .join(format!("generation-{:08}.checkpoint", generation))
+    }
+
+    /// Writes the checkpoint taken after the given generation
+    pub fn write_checkpoint(
+        &self,
+        generation: usize,
+        data: &[u8],
+    ) -> Result<PathBuf, GeneticError> {
+        let path = self.checkpoint_path(generation);
+        write_atomically(&path, data)?;
+        Ok(path)
+    }
+
+    /// Returns the generation and path of the most recent checkpoint, if any
+    pub fn latest_checkpoint(&self) -> Result<Option<(usize, PathBuf)>, GeneticError> {
+        let mut latest: Option<(usize, PathBuf)> = None;
+        for entry in fs::read_dir(self.path.join(CHECKPOINTS))? {
+            let path = entry?.path();
+            let generation = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("generation-"))
+                .and_then(|name| name.strip_suffix(".checkpoint"))
+                .and_then(|number| number.parse::<usize>().ok());
+            if let Some(generation) = generation {
+                if latest.as_ref().is_none_or(|(best, _)| generation > *best) {
+                    latest = Some((generation, path));
+                }
+            }
+        }
+
+        Ok(latest)
+    }
+
+    /// Returns the path of a metrics file with the given name
+    pub fn metrics_path(&self, name: &str) -> PathBuf {
+        self.path.join(METRICS).join(format!("{}.csv", name))
+    }
+
+    /// Appends the statistics of one generation to `metrics/generations.csv`, one row per island, writing the header
+    /// first if the file is new
+    pub fn append_generation_stats(
+        &self,
+        stats: &GenerationStats,
+    ) -> Result<PathBuf, GeneticError> {
+        let path = self.path.join(METRICS).join(GENERATIONS_CSV);
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if is_new {
+            writeln!(file, "{}", GENERATIONS_CSV_HEADER)?;
+        }
+
+        for (index, island) in stats.islands.iter().enumerate() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{}",
+                stats.generation,
+                optional(stats.seed),
+                optional(stats.best_score),
+                stats.improved,
+                index,
+                optional(island.best_score),
+                optional(island.worst_score),
+                optional(island.mean_score),
+                island.distinct_parents,
+                island.evaluations,
+            )?;
+        }
+
+        Ok(path)
+    }
+
+    /// Returns the path of an exported champion genome
+    pub fn champion_path(&self, generation: usize, champion: &Champion) -> PathBuf {
+        self.path.join(CHAMPIONS).join(format!(
+            "generation-{:08}-island-{}-id-{}.champion",
+            generation, champion.island, champion.id
+        ))
+    }
+
+    /// Writes a champion genome encoded by the caller
+    pub fn write_champion(
+        &self,
+        generation: usize,
+        champion: &Champion,
+        data: &[u8],
+    ) -> Result<PathBuf, GeneticError> {
+        let path = self.champion_path(generation, champion);
+        write_atomically(&path, data)?;
+        Ok(path)
+    }
+}
+
+// Writes the file next to its final location and renames it into place once complete
+pub(crate) fn write_atomically(path: &Path, data: &[u8]) -> Result<(), GeneticError> {
+    write_rotated(path, data, 1)
+}
+
+// Writes the file atomically like `write_atomically`, first shifting the previous `keep - 1` versions to `<path>.1`,
+// `<path>.2` and so on, so the newest version is always at `path`. The new data is on disk before any rename happens,
+// so a crash at any point leaves every complete version readable.
+pub(crate) fn write_rotated(path: &Path, data: &[u8], keep: usize) -> Result<(), GeneticError> {
+    let temporary = path.with_extension("partial");
+    let mut file = File::create(&temporary)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    for version in (1..keep).rev() {
+        let older = if version == 1 {
+            path.to_path_buf()
+        } else {
+            rotated_path(path, version - 1)
+        };
+        if older.exists() {
+            fs::rename(&older, rotated_path(path, version))?;
+        }
+    }
+    fs::rename(&temporary, path)?;
+    sync_directory(path);
+    Ok(())
+}
+
+// Returns the path `write_rotated` moves the given older version of `path` to
+fn rotated_path(path: &Path, version: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", version));
+    PathBuf::from(rotated)
+}
+
+// Flushes the renames in the directory holding `path` to disk. This is best effort: not every platform can open a
+// directory as a file, and the data itself has already been synced.
+fn sync_directory(path: &Path) {
+    if let Some(directory) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        if let Ok(directory) = File::open(directory) {
+            let _ = directory.sync_all();
+        }
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(String::new, |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::write_rotated;
+    use crate::*;
+
+    #[test]
+    fn runs_use_a_stable_layout() {
+        let root = std::env::temp_dir().join(format!("experiment-dir-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let run = ExperimentDir::create_with_run_id(&root, "first").unwrap();
+        run.write_checkpoint(5, b"five").unwrap();
+        run.write_checkpoint(12, b"twelve").unwrap();
+        run.write_manifest(&[("seed", "1234".to_string())]).unwrap();
+        let champion = Champion {
+            island: 1,
+            id: 42,
+            score: 7,
+            validation_score: None,
+            normalized_score: 7.0,
+            weighted_score: 7.0,
+        };
+        let exported = run.write_champion(12, &champion, b"genome").unwrap();
+
+        let (generation, latest) = run.latest_checkpoint().unwrap().unwrap();
+        assert_eq!(12, generation);
+        assert_eq!(b"twelve".to_vec(), fs::read(latest).unwrap());
+        assert!(exported.ends_with("champions/generation-00000012-island-1-id-42.champion"));
+        assert_eq!(
+            "run_id = first\nseed = 1234\n",
+            fs::read_to_string(run.path().join("manifest.txt")).unwrap()
+        );
+
+        let second = ExperimentDir::create(&root).unwrap();
+        assert!(second.run_id().starts_with("run-"));
+        assert_eq!(2, ExperimentDir::list_runs(&root).unwrap().len());
+        assert!(ExperimentDir::create_with_run_id(&root, "first").is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn rotated_writes_keep_older_versions() {
+        let root = std::env::temp_dir().join(format!("rotated-write-test-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("run.checkpoint");
+
+        for data in [b"first", b"secnd", b"third"] {
+            write_rotated(&path, data, 2).unwrap();
+        }
+        assert_eq!(b"third".to_vec(), fs::read(&path).unwrap());
+        assert_eq!(
+            b"secnd".to_vec(),
+            fs::read(root.join("run.checkpoint.1")).unwrap()
+        );
+        assert!(!root.join("run.checkpoint.2").exists());
+        assert!(!root.join("run.partial").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+/// Retries the evaluation of an island's whole generation when its engine reports a transient failure, such as a lost
+/// connection to a remote evaluator. Without a retry policy, a failed generation is returned as an error or handled by
+/// the `IslandQuarantine`.
+///
+/// A retry starts the generation over rather than resuming it: `pre_generation_run` is called again and every
+/// individual is run again, and the samples a failed attempt added under `NoisyEvaluation` are discarded, so a
+/// generation is never made of a mix of individuals scored before and after the failure. Failures the engine does not
+/// consider transient, see `IslandEngine::is_transient_failure`, are not retried. Once the retries are used up, the
+/// last failure is handled as if there were no retry policy. Observers are told about every retry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationRetry {
+    /// The number of times a generation is evaluated again after a transient failure. Must be greater than zero.
+    pub max_retries: usize,
+}
+
+impl Default for GenerationRetry {
+    fn default() -> Self {
+        GenerationRetry { max_retries: 3 }
+    }
+}
+
+impl GenerationRetry {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.max_retries > 0
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn transient_failures_retry_the_whole_generation() {
+        // Fails on the second individual of an attempt while it has failures left, the last one first
+        struct FlakyEngine {
+            failures: Vec<&'static str>,
+            runs: usize,
+            attempts: Arc<Mutex<usize>>,
+        }
+
+        impl IslandEngine for FlakyEngine {
+            fn pre_generation_run(&mut self, _individuals: &[u64]) {
+                self.runs = 0;
+                *self.attempts.lock().unwrap() += 1;
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn try_run_individual(&mut self, _id: u64) -> Result<(), String> {
+                self.runs += 1;
+                match self.failures.pop() {
+                    Some(message) if self.runs == 2 => Err(message.to_string()),
+                    Some(message) => {
+                        self.failures.push(message);
+                        Ok(())
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            fn is_transient_failure(&self, message: &str) -> bool {
+                message == "timeout"
+            }
+        }
+
+        struct Recorder(Arc<Mutex<Vec<usize>>>);
+
+        impl WorldObserver for Recorder {
+            fn evaluation_retried(&mut self, _: usize, _: usize, attempt: usize, _: &GeneticError) {
+                self.0.lock().unwrap().push(attempt);
+            }
+        }
+
+        let build = |failures: Vec<&'static str>| {
+            let attempts = Arc::new(Mutex::new(0));
+            let retries = Arc::new(Mutex::new(vec![]));
+            let mut builder = WorldBuilder::new()
+                .with_genetic_engine(counting_engine(1234))
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_generation_retry(GenerationRetry { max_retries: 3 })
+                .with_observer(Recorder(retries.clone()));
+            builder.add_island(
+                "flaky",
+                Box::new(FlakyEngine {
+                    failures,
+                    runs: 0,
+                    attempts: attempts.clone(),
+                }),
+            );
+            (builder.build().unwrap(), attempts, retries)
+        };
+
+        let (mut world, attempts, retries) = build(vec!["timeout", "timeout"]);
+        world.run_n_generations(1).unwrap();
+        let stats = &world.stats_history()[0].islands[0];
+        assert_eq!((2, 4), (stats.evaluation_retries, stats.evaluations));
+        assert_eq!(3, *attempts.lock().unwrap());
+        assert_eq!(vec![1, 2], *retries.lock().unwrap());
+
+        // Permanent failures are not retried
+        let (mut world, attempts, retries) = build(vec!["crashed", "timeout"]);
+        let result = world.run_n_generations(1);
+        assert!(
+            matches!(result, Err(GeneticError::EvaluationFailed { message, .. }) if message == "crashed")
+        );
+        assert_eq!(2, *attempts.lock().unwrap());
+        assert_eq!(vec![1], *retries.lock().unwrap());
+
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_generation_retry(GenerationRetry { max_retries: 0 })
+                .build(),
+            Err(GeneticError::InvalidGenerationRetry)
+        ));
+    }
+
+    #[test]
+    fn the_last_transient_failure_is_returned_once_retries_run_out() {
+        struct TimeoutEngine {
+            attempts: Arc<Mutex<usize>>,
+        }
+
+        impl IslandEngine for TimeoutEngine {
+            fn pre_generation_run(&mut self, _individuals: &[u64]) {
+                *self.attempts.lock().unwrap() += 1;
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn try_run_individual(&mut self, _id: u64) -> Result<(), String> {
+                Err("timeout".to_string())
+            }
+
+            fn is_transient_failure(&self, message: &str) -> bool {
+                message == "timeout"
+            }
+        }
+
+        let attempts = Arc::new(Mutex::new(0));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generation_retry(GenerationRetry { max_retries: 2 });
+        builder.add_island(
+            "a",
+            Box::new(TimeoutEngine {
+                attempts: attempts.clone(),
+            }),
+        );
+        let mut world = builder.build().unwrap();
+
+        let result = world.run_n_generations(1);
+        assert!(
+            matches!(result, Err(GeneticError::EvaluationFailed { message, .. }) if message == "timeout")
+        );
+        assert_eq!(3, *attempts.lock().unwrap());
+        assert_eq!(0, world.generation_count());
+    }
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use crate::{ScoreAttribution, ScratchStats};
+
+/// Statistics gathered after every island in the world has run one generation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationStats {
+    /// The generation count of the world after the generation ran
+    pub generation: usize,
+
+    /// The seed the random number generator was reset to when the islands were filled for this generation, or None
+    /// if the generation ran without being filled. Restoring the population as it was before the fill and calling
+    /// `World::set_next_generation_seed` with this value re-executes the generation exactly.
+    pub seed: Option<u64>,
+
+    /// The statistics of each island, in the same order as `World::get_island`
+    pub islands: Vec<IslandStats>,
+
+    /// The highest score of any individual on any island this generation
+    pub best_score: Option<u64>,
+
+    /// The highest validation score of the most fit individuals of the islands this generation
+    pub best_validation_score: Option<u64>,
+
+    /// True if `best_score` is higher than the best score of every previous generation
+    pub improved: bool,
+
+    /// The index of the `CurriculumStage` this generation ran in, or None if the world has no curriculum. A change from
+    /// the previous generation marks a stage boundary.
+    pub curriculum_stage: Option<usize>,
+
+    /// The distances between islands, as returned by `World::island_distances`, if they were measured this generation
+    pub island_distances: Vec<Vec<Option<f64>>>,
+
+    /// The scores of the reference individuals, if they were re-evaluated this generation
+    pub reference_scores: Vec<ReferenceScore>,
+
+    /// Which ways of producing individuals raised the best score this generation
+    pub score_attribution: ScoreAttribution,
+}
+
+/// The score one island gave a reference individual when it was re-evaluated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReferenceScore {
+    /// The name the reference individual was registered with
+    pub name: String,
+
+    /// The index of the island that evaluated it
+    pub island: usize,
+
+    /// The score the island engine gave it
+    pub score: u64,
+}
+
+/// Statistics for one island after it has run one generation.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandStats {
+    /// The score of the most fit individual, or None if the island was empty
+    pub best_score: Option<u64>,
+
+    /// The validation score of the most fit individual, or None if the island was empty or its engine does not
+    /// validate. See `IslandEngine::validation_score`.
+    pub best_validation_score: Option<u64>,
+
+    /// The score of the least fit individual, or None if the island was empty
+    pub worst_score: Option<u64>,
+
+    /// The mean score of all individuals, or None if the island was empty
+    pub mean_score: Option<f64>,
+
+    /// The individuals copied unchanged into this generation as elites, in the order they were selected. The same
+    /// individual may appear more than once. Empty if the island was not filled by the world this generation.
+    pub elites: Vec<u64>,
+
+    /// The number of distinct individuals selected as parents of the children bred into this generation. A value that
+    /// is small compared to the island size is an early warning of premature convergence. Zero if the island was not
+    /// bred by the world this generation.
+    pub distinct_parents: usize,
+
+    /// The number of distinct genomes on the island, as hashed by `Genetics::genome_hash`. With the default hash every
+    /// individual is distinct, so this is only informative for genetics that hash their genomes. A value well below
+    /// the island size means the island is full of copies.
+    pub distinct_genomes: usize,
+
+    /// The mean of the variance between the samples of each individual, or None unless the world uses
+    /// `NoisyEvaluation` with more than one evaluation. A large variance compared to the spread of scores means that
+    /// selection is mostly driven by noise.
+    pub mean_score_variance: Option<f64>,
+
+    /// The number of times an individual was run to evaluate the generation, including repeated noisy evaluations
+    pub evaluations: usize,
+
+    /// The number of individuals that were not run because their score was kept from an earlier generation, which
+    /// happens with `NoisyEvaluation` when `resample_elites` is false. Together with `evaluations` it shows how much
+    /// evaluation the kept scores saved.
+    pub cached_evaluations: usize,
+
+    /// The number of times the generation was evaluated again after a transient failure. See `GenerationRetry`.
+    pub evaluation_retries: usize,
+
+    /// The time the island spent waiting for the world's `RateLimit` while running the generation
+    pub rate_limit_wait: Duration,
+
+    /// How the island's `ScratchArena` was used while running the generation
+    pub scratch: ScratchStats,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn elites_are_recorded_per_island() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(5)
+            .with_elite_individuals(2)
+            .with_generations_between_migrations(0)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+
+        // The first generation is random, so there is nothing to copy
+        world.run_n_generations(1).unwrap();
+        let first = world.stats_history().last().unwrap();
+        assert!(first.islands.iter().all(|island| island.elites.is_empty()));
+
+        let previous = world.snapshot();
+        world.run_n_generations(1).unwrap();
+        let second = world.stats_history().last().unwrap();
+        for (stats, island) in second.islands.iter().zip(previous.islands.iter()) {
+            assert_eq!(2, stats.elites.len());
+            assert!(stats
+                .elites
+                .iter()
+                .all(|id| island.individuals.contains(id)));
+        }
+    }
+
+    #[test]
+    fn distinct_parents_are_counted_per_island() {
+        let mut world = world_builder(&["standard"])
+            .with_individuals_per_island(6)
+            .with_elite_individuals(1)
+            .with_migrating_individuals(0)
+            .with_generations_between_migrations(0)
+            .with_select_as_parent(SelectionCurve::Fair)
+            .build()
+            .unwrap();
+        // The first generation is random, so nobody is a parent
+        world.run_n_generations(1).unwrap();
+        assert_eq!(0, world.stats_history()[0].islands[0].distinct_parents);
+
+        world.run_n_generations(1).unwrap();
+        let parents = world.stats_history()[1].islands[0].distinct_parents;
+        assert!((1..=6).contains(&parents));
+    }
+}
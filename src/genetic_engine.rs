@@ -1,4 +1,9 @@
-use crate::{GeneticEngineBuilder, GeneticError, Genetics};
+use crate::decision_log::DecisionLog;
+use crate::genetic_engine_builder::validate_operators;
+use crate::{
+    Decision, DecisionKind, GeneticEngineBuilder, GeneticEngineSettings, GeneticError, Genetics,
+    IdenticalParents, IndividualOrigin, OversizedOffspring, VariationOperator,
+};
 use rand::Rng;
 use rand::{rngs::StdRng, SeedableRng}; // cspell:disable-line
 
@@ -7,12 +12,23 @@ where
     G: Genetics,
 {
     rng: StdRng,
+    seed: u64,
     mutation_rate: u8,
     crossover_rate: u8,
+    reproduction_rate: u8,
     max_mutation_points: u8,
     max_crossover_points: u8,
     max_individual_points: usize,
+    mutation_point_fraction: Option<f64>,
+    crossover_point_fraction: Option<f64>,
+    oversized_offspring: OversizedOffspring,
+    max_child_attempts: usize,
+    identical_parents: IdenticalParents,
+    operators: Vec<(Box<dyn VariationOperator<G>>, u8)>,
     genetics: G,
+    decisions: DecisionLog,
+    // The generation count of the world, stamped on recorded decisions
+    decision_generation: usize,
 }
 
 impl<G> GeneticEngine<G>
@@ -20,29 +36,325 @@ where
     G: Genetics,
 {
     pub(crate) fn new(builder: GeneticEngineBuilder<G>) -> Self {
-        let rng = match builder.seed {
-            Some(seed) => StdRng::seed_from_u64(seed),
-            None => StdRng::from_rng(&mut rand::rng()),
-        };
+        // Without a seed one is drawn at random, so that the run can still be repeated from its report
+        let seed = builder.seed.unwrap_or_else(|| rand::rng().random());
 
         GeneticEngine {
-            rng,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
             mutation_rate: builder.mutation_rate,
             crossover_rate: builder.crossover_rate,
+            reproduction_rate: builder.reproduction_rate,
             max_mutation_points: builder.max_mutation_points,
             max_crossover_points: builder.max_crossover_points,
             max_individual_points: builder.max_individual_points,
+            mutation_point_fraction: builder.mutation_point_fraction,
+            crossover_point_fraction: builder.crossover_point_fraction,
+            oversized_offspring: builder.oversized_offspring,
+            max_child_attempts: builder.max_child_attempts,
+            identical_parents: builder.identical_parents,
+            operators: builder.operators,
             genetics: builder.genetics.unwrap(),
+            decisions: DecisionLog::Off,
+            decision_generation: 0,
+        }
+    }
+
+    /// Replaces the random number generator with one seeded from `seed`
+    pub(crate) fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns the seed the engine's random number generator started from. If the builder was not given a seed, this
+    /// is the one drawn at random for it.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts recording the operator choices, selections and migration destinations of the world, discarding any log
+    /// being recorded or replayed. Together with `replay_decisions`, this makes it possible to find out why a
+    /// surprising child appeared, or to see what would have happened had one decision gone differently.
+    pub fn record_decisions(&mut self) {
+        self.decisions = DecisionLog::Recording(vec![]);
+    }
+
+    /// Stops recording and returns the decisions recorded since `record_decisions`, oldest first. Returns an empty log
+    /// if the engine was not recording.
+    pub fn take_decisions(&mut self) -> Vec<Decision> {
+        match std::mem::take(&mut self.decisions) {
+            DecisionLog::Recording(decisions) => decisions,
+            _ => vec![],
         }
     }
 
+    /// Makes the following decisions from the log instead of the random number generator. Each decision takes the
+    /// value of the next logged decision of the same kind. A decision that does not match the log, or whose logged
+    /// value is out of range, falls back to the random number generator, as do all decisions after the log runs out.
+    /// The generator is advanced either way, so a log that is replayed unchanged reproduces the recorded run.
+    pub fn replay_decisions(&mut self, decisions: Vec<Decision>) {
+        self.decisions = DecisionLog::Replaying(decisions.into_iter().peekable());
+    }
+
+    /// Makes a random decision of the kind, recording or replaying it as configured. `accept` tells whether a logged
+    /// value is valid for this decision.
+    pub(crate) fn decide<Draw, Accept>(
+        &mut self,
+        kind: DecisionKind,
+        draw: Draw,
+        accept: Accept,
+    ) -> usize
+    where
+        Draw: FnOnce(&mut StdRng) -> usize,
+        Accept: Fn(usize) -> bool,
+    {
+        self.decisions
+            .decide(&mut self.rng, self.decision_generation, kind, draw, accept)
+    }
+
+    /// Sets the generation count stamped on the decisions that follow
+    pub(crate) fn set_decision_generation(&mut self, generation: usize) {
+        self.decision_generation = generation;
+    }
+
+    /// Returns what happens when both parents of a crossover are the same individual
+    pub(crate) fn identical_parents(&self) -> IdenticalParents {
+        self.identical_parents
+    }
+
     /// Allows crate access to the random number generator
     pub(crate) fn rng(&mut self) -> &mut StdRng {
         &mut self.rng
     }
 
-    fn random_zero_to_n(&mut self, n: u8) -> u8 {
-        self.rng.random::<u8>() % n
+    /// Borrows the genetics implementation
+    pub fn genetics(&self) -> &G {
+        &self.genetics
+    }
+
+    /// Returns the current mutation rate
+    pub fn mutation_rate(&self) -> u8 {
+        self.mutation_rate
+    }
+
+    /// Changes the mutation rate for all future children. Returns an error and leaves the engine unchanged if the new
+    /// rate is invalid in combination with the other operator settings, using the same rules as
+    /// `GeneticEngineBuilder::build`.
+    pub fn set_mutation_rate(&mut self, rate: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            rate,
+            self.crossover_rate,
+            self.max_mutation_points,
+            self.max_crossover_points,
+            self.extra_weight(),
+        )?;
+        self.mutation_rate = rate;
+        Ok(())
+    }
+
+    /// Returns the current crossover rate
+    pub fn crossover_rate(&self) -> u8 {
+        self.crossover_rate
+    }
+
+    /// Changes the crossover rate for all future children. Returns an error and leaves the engine unchanged if the new
+    /// rate is invalid in combination with the other operator settings.
+    pub fn set_crossover_rate(&mut self, rate: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            self.mutation_rate,
+            rate,
+            self.max_mutation_points,
+            self.max_crossover_points,
+            self.extra_weight(),
+        )?;
+        self.crossover_rate = rate;
+        Ok(())
+    }
+
+    /// Changes the mutation and crossover rates together, which allows moving between settings that would be
+    /// invalid if the rates were changed one at a time. Returns an error and leaves the engine unchanged if the new
+    /// rates are invalid.
+    pub fn set_rates(&mut self, mutation_rate: u8, crossover_rate: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            mutation_rate,
+            crossover_rate,
+            self.max_mutation_points,
+            self.max_crossover_points,
+            self.extra_weight(),
+        )?;
+        self.mutation_rate = mutation_rate;
+        self.crossover_rate = crossover_rate;
+        Ok(())
+    }
+
+    /// Returns the current maximum number of mutation points
+    pub fn max_mutation_points(&self) -> u8 {
+        self.max_mutation_points
+    }
+
+    /// Changes the maximum number of mutation points for all future children. Returns an error and leaves the engine
+    /// unchanged if the new value is invalid in combination with the other operator settings.
+    pub fn set_max_mutation_points(&mut self, points: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            self.mutation_rate,
+            self.crossover_rate,
+            points,
+            self.max_crossover_points,
+            self.extra_weight(),
+        )?;
+        self.max_mutation_points = points;
+        Ok(())
+    }
+
+    /// Returns the current maximum number of crossover points
+    pub fn max_crossover_points(&self) -> u8 {
+        self.max_crossover_points
+    }
+
+    /// Changes the maximum number of crossover points for all future children. Returns an error and leaves the engine
+    /// unchanged if the new value is invalid in combination with the other operator settings.
+    pub fn set_max_crossover_points(&mut self, points: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            self.mutation_rate,
+            self.crossover_rate,
+            self.max_mutation_points,
+            points,
+            self.extra_weight(),
+        )?;
+        self.max_crossover_points = points;
+        Ok(())
+    }
+
+    /// Returns the maximum number of points of a random individual
+    pub fn max_individual_points(&self) -> usize {
+        self.max_individual_points
+    }
+
+    /// Changes the maximum number of points of all future random individuals. Must be greater than zero.
+    pub fn set_max_individual_points(&mut self, points: usize) -> Result<(), GeneticError> {
+        if points == 0 {
+            return Err(GeneticError::InvalidIndividualPoints);
+        }
+        self.max_individual_points = points;
+        Ok(())
+    }
+
+    /// Returns the fraction of an individual's size that is the most points one mutation changes, if mutation points
+    /// are proportional to size
+    pub fn mutation_point_fraction(&self) -> Option<f64> {
+        self.mutation_point_fraction
+    }
+
+    /// Returns the fraction of an individual's size that is the most points one crossover swaps, if crossover points
+    /// are proportional to size
+    pub fn crossover_point_fraction(&self) -> Option<f64> {
+        self.crossover_point_fraction
+    }
+
+    /// Returns the most points one mutation of the individual changes
+    pub(crate) fn max_mutation_points_of(&self, individual: u64) -> usize {
+        self.proportional_points(individual, self.mutation_point_fraction)
+            .unwrap_or(self.max_mutation_points as usize)
+    }
+
+    // Returns the fraction of the individual's size, rounded up, or None if there is no fraction or no size
+    fn proportional_points(&self, individual: u64, fraction: Option<f64>) -> Option<usize> {
+        let size = self.genetics.size(individual)?;
+        Some(((size as f64 * fraction?).ceil() as usize).max(1))
+    }
+
+    /// Returns what happens to a bred child that is larger than `max_individual_points`
+    pub fn oversized_offspring(&self) -> OversizedOffspring {
+        self.oversized_offspring
+    }
+
+    // Returns true if the individual reports a size over the limit of offspring that are checked
+    fn is_oversized(&self, individual: u64) -> bool {
+        self.oversized_offspring != OversizedOffspring::Allow
+            && self
+                .genetics
+                .size(individual)
+                .is_some_and(|size| size > self.max_individual_points)
+    }
+
+    /// Returns the number of children `rand_child` breeds before giving up on producing a valid one
+    pub fn max_child_attempts(&self) -> usize {
+        self.max_child_attempts
+    }
+
+    /// Returns the reproduction rate
+    pub fn reproduction_rate(&self) -> u8 {
+        self.reproduction_rate
+    }
+
+    /// Changes the reproduction rate for all future children. Returns an error and leaves the engine unchanged if the
+    /// new rate leaves no way to produce a child.
+    pub fn set_reproduction_rate(&mut self, rate: u8) -> Result<(), GeneticError> {
+        validate_operators(
+            self.mutation_rate,
+            self.crossover_rate,
+            self.max_mutation_points,
+            self.max_crossover_points,
+            rate as u32 + self.operator_weight(),
+        )?;
+        self.reproduction_rate = rate;
+        Ok(())
+    }
+
+    /// Returns the total weight of the reproduction rate and the registered `VariationOperator`s, which can produce
+    /// children when the mutation and crossover rates are both zero
+    pub(crate) fn extra_weight(&self) -> u32 {
+        self.reproduction_rate as u32 + self.operator_weight()
+    }
+
+    /// Returns the total weight of the registered `VariationOperator`s
+    pub(crate) fn operator_weight(&self) -> u32 {
+        self.operators
+            .iter()
+            .map(|(_, weight)| *weight as u32)
+            .sum()
+    }
+
+    /// Returns the number of parents the registered `VariationOperator` at the index uses
+    pub(crate) fn operator_parents(&self, index: usize) -> usize {
+        self.operators[index].0.parents()
+    }
+
+    /// Returns a copy of the current operator settings
+    pub fn settings(&self) -> GeneticEngineSettings {
+        GeneticEngineSettings {
+            mutation_rate: self.mutation_rate,
+            crossover_rate: self.crossover_rate,
+            max_mutation_points: self.max_mutation_points,
+            max_crossover_points: self.max_crossover_points,
+            max_individual_points: self.max_individual_points,
+            reproduction_rate: self.reproduction_rate,
+        }
+    }
+
+    /// Replaces every operator setting at once. Returns an error and leaves the engine unchanged if the settings are
+    /// invalid.
+    pub fn apply_settings(&mut self, settings: GeneticEngineSettings) -> Result<(), GeneticError> {
+        validate_operators(
+            settings.mutation_rate,
+            settings.crossover_rate,
+            settings.max_mutation_points,
+            settings.max_crossover_points,
+            settings.reproduction_rate as u32 + self.operator_weight(),
+        )?;
+        if settings.max_individual_points == 0 {
+            return Err(GeneticError::InvalidIndividualPoints);
+        }
+        self.mutation_rate = settings.mutation_rate;
+        self.crossover_rate = settings.crossover_rate;
+        self.max_mutation_points = settings.max_mutation_points;
+        self.max_crossover_points = settings.max_crossover_points;
+        self.max_individual_points = settings.max_individual_points;
+        self.reproduction_rate = settings.reproduction_rate;
+        Ok(())
+    }
+
+    fn random_zero_to_n(&mut self, n: u16) -> u16 {
+        self.rng.random_range(0..n)
     }
 
     /// Produces a random individual of up to the `max_points` number of code items.
@@ -51,19 +363,134 @@ where
             .random_individual(&mut self.rng, self.max_individual_points)
     }
 
-    /// Produces a random child of the two individuals that is either a mutation of the left individual, or the genetic
-    /// crossover of both.
-    pub fn rand_child(&mut self, left: u64, right: u64) -> Result<u64, GeneticError> {
-        let pick = self.random_zero_to_n(self.mutation_rate + self.crossover_rate);
+    /// Produces a new random individual of up to `max_points` code items
+    pub(crate) fn rand_individual_of_size(&mut self, max_points: usize) -> u64 {
+        self.genetics.random_individual(&mut self.rng, max_points)
+    }
 
-        if pick < self.mutation_rate {
-            let points = self.random_zero_to_n(self.max_mutation_points) + 1;
-            Ok(self.genetics.mutate(&mut self.rng, left, points as usize))
+    /// Produces a child by mutating `points` number of code items of the individual, repaired by `Genetics::repair`.
+    /// Returns the individual itself if the child is oversized and the engine does not allow `OversizedOffspring`.
+    pub fn mutate(&mut self, individual: u64, points: usize) -> u64 {
+        let child = self
+            .genetics
+            .mutate(&mut self.rng, individual, points.max(1));
+        let child = self.genetics.repair(&mut self.rng, child);
+        if self.is_oversized(child) {
+            individual
         } else {
-            let points = self.random_zero_to_n(self.max_crossover_points) + 1;
-            Ok(self
-                .genetics
-                .crossover(&mut self.rng, left, right, points as usize))
+            child
+        }
+    }
+
+    /// Produces a random child of the two individuals that is either a mutation of the left individual, the genetic
+    /// crossover of both, an unchanged copy of the left individual, or the child of a registered `VariationOperator`.
+    /// New children are repaired by `Genetics::repair`, handled by the engine's `OversizedOffspring` if they are larger
+    /// than `max_individual_points`, and bred again if `Genetics::is_valid` rejects them. Returns
+    /// `GeneticError::CouldNotProduceValidChild` if no valid child was bred in `max_child_attempts`.
+    pub fn rand_child(&mut self, left: u64, right: u64) -> Result<u64, GeneticError> {
+        self.rand_child_with_origin(left, right)
+            .map(|(child, _)| child)
+    }
+
+    /// Like `rand_child`, but also reports which operator produced the child
+    pub(crate) fn rand_child_with_origin(
+        &mut self,
+        left: u64,
+        right: u64,
+    ) -> Result<(u64, IndividualOrigin), GeneticError> {
+        for _ in 0..self.max_child_attempts {
+            let (child, origin) = self.breed(left, right);
+            if self.is_oversized(child) {
+                match self.oversized_offspring {
+                    OversizedOffspring::CopyParent => {
+                        return Ok((left, IndividualOrigin::Reproduction))
+                    }
+                    _ => continue,
+                }
+            }
+            if self.genetics.is_valid(child) {
+                return Ok((child, origin));
+            }
         }
+
+        // Rejected children are not referenced by the world, so the genetics collects them with its garbage
+        Err(GeneticError::CouldNotProduceValidChild(
+            self.max_child_attempts,
+        ))
+    }
+
+    // Produces one repaired child of the two individuals, which may not be valid
+    fn breed(&mut self, left: u64, right: u64) -> (u64, IndividualOrigin) {
+        // The rates are summed as u16 so that two large u8 rates cannot overflow
+        let (mutation_rate, crossover_rate) =
+            (self.mutation_rate as u16, self.crossover_rate as u16);
+        let reproduction_rate = self.reproduction_rate as u32;
+        let weights: Vec<u32> = self
+            .operators
+            .iter()
+            .map(|(_, weight)| *weight as u32)
+            .collect();
+        let operator_count = weights.len();
+        let operator = self.decide(
+            DecisionKind::Operator,
+            |rng| {
+                if reproduction_rate == 0 && weights.is_empty() {
+                    return (rng.random_range(0..mutation_rate + crossover_rate) >= mutation_rate)
+                        as usize;
+                }
+
+                // Mutation, crossover and reproduction come first, followed by the registered operators in order
+                let rates = [
+                    mutation_rate as u32,
+                    crossover_rate as u32,
+                    reproduction_rate,
+                ];
+                let total: u32 = rates.iter().chain(weights.iter()).sum();
+                let mut draw = rng.random_range(0..total);
+                rates
+                    .iter()
+                    .chain(weights.iter())
+                    .position(|&weight| {
+                        let chosen = draw < weight;
+                        draw = draw.saturating_sub(weight);
+                        chosen
+                    })
+                    .unwrap()
+            },
+            |operator| operator < 3 + operator_count,
+        );
+
+        // Crossing an individual with itself is replaced by a mutation when the engine is set to do so
+        let operator = match operator {
+            1 if left == right && self.identical_parents == IdenticalParents::Mutate => 0,
+            operator => operator,
+        };
+
+        let (child, origin) = match operator {
+            0 => {
+                let points = match self.proportional_points(left, self.mutation_point_fraction) {
+                    Some(max_points) => self.rng.random_range(1..=max_points),
+                    None => self.random_zero_to_n(self.max_mutation_points as u16) as usize + 1,
+                };
+                let child = self.genetics.mutate(&mut self.rng, left, points);
+                (child, IndividualOrigin::Mutation)
+            }
+            1 => {
+                let points = match self.proportional_points(left, self.crossover_point_fraction) {
+                    Some(max_points) => self.rng.random_range(1..=max_points),
+                    None => self.random_zero_to_n(self.max_crossover_points as u16) as usize + 1,
+                };
+                let child = self.genetics.crossover(&mut self.rng, left, right, points);
+                (child, IndividualOrigin::Crossover)
+            }
+            // The copy is the parent itself, so it is not repaired
+            2 => return (left, IndividualOrigin::Reproduction),
+            index => {
+                let (operator, _) = &self.operators[index - 3];
+                let child = operator.vary(&self.genetics, &mut self.rng, left, right);
+                (child, IndividualOrigin::Operator(index - 3))
+            }
+        };
+        (self.genetics.repair(&mut self.rng, child), origin)
     }
 }
@@ -1,4 +1,6 @@
-use crate::{GeneticEngine, GeneticError, Genetics};
+use crate::{
+    GeneticEngine, GeneticError, Genetics, IdenticalParents, OversizedOffspring, VariationOperator,
+};
 
 pub struct GeneticEngineBuilder<G>
 where
@@ -7,9 +9,16 @@ where
     pub seed: Option<u64>,
     pub mutation_rate: u8,
     pub crossover_rate: u8,
+    pub reproduction_rate: u8,
     pub max_mutation_points: u8,
     pub max_crossover_points: u8,
     pub max_individual_points: usize,
+    pub mutation_point_fraction: Option<f64>,
+    pub crossover_point_fraction: Option<f64>,
+    pub oversized_offspring: OversizedOffspring,
+    pub max_child_attempts: usize,
+    pub identical_parents: IdenticalParents,
+    pub operators: Vec<(Box<dyn VariationOperator<G>>, u8)>,
     pub genetics: Option<G>,
 }
 
@@ -22,9 +31,16 @@ where
             seed: None,
             mutation_rate: 1,
             crossover_rate: 9,
+            reproduction_rate: 0,
             max_mutation_points: 3,
             max_crossover_points: 10,
             max_individual_points: 100,
+            mutation_point_fraction: None,
+            crossover_point_fraction: None,
+            oversized_offspring: OversizedOffspring::Allow,
+            max_child_attempts: 10,
+            identical_parents: IdenticalParents::Allow,
+            operators: vec![],
             genetics: None,
         }
     }
@@ -43,7 +59,7 @@ where
     /// Sets the mutation rate. The `mutation_rate` and `crossover_rate` are summed and then a
     /// random value is picked in that range to the final rate is dependant upon both values.
     ///
-    /// Set this to zero to disable mutation entirely.
+    /// Set this to zero to disable mutation entirely. `mutation_rate` and `crossover_rate` cannot both be zero.
     ///
     /// Default: 1 (approximately 10% when using default crossover_rate)
     pub fn mutation_rate(mut self, rate: u8) -> Self {
@@ -54,7 +70,7 @@ where
     /// Sets the crossover rate. The `mutation_rate` and `crossover_rate` are summed and then a
     /// random value is picked in that range to the final rate is dependant upon both values.
     ///
-    /// Set this to zero to disable crossover entirely.
+    /// Set this to zero to disable crossover entirely. `mutation_rate` and `crossover_rate` cannot both be zero.
     ///
     /// Default: 9 (approximately 90% when using default mutation_rate)
     pub fn crossover_rate(mut self, rate: u8) -> Self {
@@ -62,6 +78,15 @@ where
         self
     }
 
+    /// Sets the reproduction rate, which is weighed against the mutation and crossover rates. A child bred by
+    /// reproduction is the first parent itself, copied unchanged into the next generation.
+    ///
+    /// Default: 0 (no reproduction)
+    pub fn reproduction_rate(mut self, rate: u8) -> Self {
+        self.reproduction_rate = rate;
+        self
+    }
+
     /// Sets the maximum number of points that will be mutated when the 'Mutation' operation is
     /// chosen. The actual value is random between one and this number. Must be at least one if
     /// mutation is used at all.
@@ -82,6 +107,27 @@ where
         self
     }
 
+    /// Sets the maximum number of points mutated in an individual as a fraction of its `Genetics::size`, so that
+    /// mutation changes small and large individuals alike by a similar proportion. The actual number is random between
+    /// one and the fraction of the size, rounded up. Individuals whose genetics does not report a size fall back to
+    /// `max_mutation_points`. Must be greater than 0 and at most 1.
+    ///
+    /// Default: None (always use `max_mutation_points`)
+    pub fn mutation_point_fraction(mut self, fraction: f64) -> Self {
+        self.mutation_point_fraction = Some(fraction);
+        self
+    }
+
+    /// Sets the maximum number of points swapped by crossover as a fraction of the `Genetics::size` of the first
+    /// parent, in the same way as `mutation_point_fraction`. Individuals whose genetics does not report a size fall
+    /// back to `max_crossover_points`. Must be greater than 0 and at most 1.
+    ///
+    /// Default: None (always use `max_crossover_points`)
+    pub fn crossover_point_fraction(mut self, fraction: f64) -> Self {
+        self.crossover_point_fraction = Some(fraction);
+        self
+    }
+
     /// Sets the maximum number of points that an individual can have.
     ///
     /// Default: 100
@@ -90,6 +136,44 @@ where
         self
     }
 
+    /// Sets what happens to a bred child whose `Genetics::size` exceeds `max_individual_points`. Mutations bred by
+    /// explorer and exploiter islands cannot be bred again, so unless offspring of any size are allowed, an oversized
+    /// mutation is always replaced by its parent.
+    ///
+    /// Default: OversizedOffspring::Allow
+    pub fn oversized_offspring(mut self, oversized: OversizedOffspring) -> Self {
+        self.oversized_offspring = oversized;
+        self
+    }
+
+    /// Sets the number of children `GeneticEngine::rand_child` breeds before giving up when `Genetics::is_valid`
+    /// rejects every one of them. Must be greater than zero.
+    ///
+    /// Default: 10
+    pub fn max_child_attempts(mut self, attempts: usize) -> Self {
+        self.max_child_attempts = attempts;
+        self
+    }
+
+    /// Sets what happens when a world selects the same individual as both parents of a crossover. Registered
+    /// operators that take two parents are given the same individual twice regardless.
+    ///
+    /// Default: IdenticalParents::Allow
+    pub fn identical_parents(mut self, identical: IdenticalParents) -> Self {
+        self.identical_parents = identical;
+        self
+    }
+
+    /// Registers a `VariationOperator`, chosen for a child in proportion to its weight against the mutation rate, the
+    /// crossover rate and the weights of the other operators. With registered operators, the mutation and crossover
+    /// rates may both be zero.
+    ///
+    /// Default: no operators
+    pub fn operator<O: VariationOperator<G> + 'static>(mut self, operator: O, weight: u8) -> Self {
+        self.operators.push((Box::new(operator), weight));
+        self
+    }
+
     /// Sets the genetics implementation for the genetic engine, which determines how specifically
     /// the individuals are represented and how they are mutated and combined.
     ///
@@ -108,21 +192,292 @@ where
             return Err(GeneticError::MissingGenetics);
         }
 
-        // The max_mutation_points must be at least one if mutation is used at all.
-        if self.max_mutation_points < 1 && self.mutation_rate > 0 {
-            return Err(GeneticError::InvalidMutationPoints);
-        }
-
-        // The max_crossover_points must be at least one if crossover is used at all.
-        if self.max_crossover_points < 1 && self.crossover_rate > 0 {
-            return Err(GeneticError::InvalidCrossoverPoints);
-        }
+        validate_operators(
+            self.mutation_rate,
+            self.crossover_rate,
+            self.max_mutation_points,
+            self.max_crossover_points,
+            self.reproduction_rate as u32
+                + self
+                    .operators
+                    .iter()
+                    .map(|(_, weight)| *weight as u32)
+                    .sum::<u32>(),
+        )?;
 
         // The max_individual_points must be greater than zero
         if self.max_individual_points == 0 {
             return Err(GeneticError::InvalidIndividualPoints);
         }
 
+        let valid_fraction = |fraction: f64| fraction > 0.0 && fraction <= 1.0;
+        if !self
+            .mutation_point_fraction
+            .into_iter()
+            .chain(self.crossover_point_fraction)
+            .all(valid_fraction)
+        {
+            return Err(GeneticError::InvalidPointFraction);
+        }
+
+        if self.max_child_attempts == 0 {
+            return Err(GeneticError::InvalidChildAttempts);
+        }
+
         Ok(GeneticEngine::new(self))
     }
 }
+
+/// Validates the rates and points of the genetic operators, given the total weight of the reproduction rate and the
+/// registered operators. Shared by the builder and the runtime setters of `GeneticEngine`.
+pub(crate) fn validate_operators(
+    mutation_rate: u8,
+    crossover_rate: u8,
+    max_mutation_points: u8,
+    max_crossover_points: u8,
+    extra_weight: u32,
+) -> Result<(), GeneticError> {
+    // With neither mutation, crossover, reproduction nor a registered operator there is no way to produce a child.
+    if mutation_rate == 0 && crossover_rate == 0 && extra_weight == 0 {
+        return Err(GeneticError::NoGeneticOperators);
+    }
+
+    // The max_mutation_points must be at least one if mutation is used at all.
+    if max_mutation_points < 1 && mutation_rate > 0 {
+        return Err(GeneticError::InvalidMutationPoints);
+    }
+
+    // The max_crossover_points must be at least one if crossover is used at all.
+    if max_crossover_points < 1 && crossover_rate > 0 {
+        return Err(GeneticError::InvalidCrossoverPoints);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng; // cspell:disable-line
+
+    use crate::test_support::CountingGenetics;
+    use crate::*;
+
+    #[test]
+    fn zero_mutation_and_crossover_is_a_build_error() {
+        let result = GeneticEngineBuilder::default()
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .crossover_rate(0)
+            .build();
+
+        assert!(matches!(result, Err(GeneticError::NoGeneticOperators)));
+    }
+
+    #[test]
+    fn maximum_rates_do_not_overflow() {
+        let mut engine = GeneticEngineBuilder::default()
+            .seed(1)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(u8::MAX)
+            .crossover_rate(u8::MAX)
+            .max_mutation_points(u8::MAX)
+            .max_crossover_points(u8::MAX)
+            .build()
+            .unwrap();
+
+        let left = engine.rand_individual();
+        let right = engine.rand_individual();
+        for _ in 0..100 {
+            engine.rand_child(left, right).unwrap();
+        }
+    }
+
+    #[test]
+    fn mutation_points_follow_the_size_of_the_parent() {
+        // Every individual has as many points as its id, and mutation records the points it was asked for
+        struct Sized(CountingGenetics, std::cell::RefCell<Vec<usize>>);
+
+        impl Genetics for Sized {
+            fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+                self.1.borrow_mut().push(points);
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(&self, rng: &mut StdRng, a: u64, b: u64, points: usize) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn size(&self, individual: u64) -> Option<usize> {
+                Some(individual as usize)
+            }
+        }
+
+        let mut engine = GeneticEngineBuilder::default()
+            .seed(1)
+            .genetics(Sized(CountingGenetics::default(), Default::default()))
+            .mutation_rate(1)
+            .crossover_rate(0)
+            .max_mutation_points(1)
+            .mutation_point_fraction(0.1)
+            .build()
+            .unwrap();
+        for _ in 0..1000 {
+            engine.rand_individual();
+        }
+        for _ in 0..50 {
+            engine.rand_child(1000, 1000).unwrap();
+        }
+        let points = engine.genetics().1.borrow();
+        assert!(points.iter().all(|&points| (1..=100).contains(&points)));
+        assert!(points.iter().any(|&points| points > 50));
+        assert_eq!(1, engine.max_mutation_points_of(3));
+
+        assert!(matches!(
+            GeneticEngineBuilder::default()
+                .genetics(CountingGenetics::default())
+                .crossover_point_fraction(1.5)
+                .build(),
+            Err(GeneticError::InvalidPointFraction)
+        ));
+    }
+
+    #[test]
+    fn oversized_offspring_are_rejected_or_replaced() {
+        // Each child has as many points as its id, so children quickly outgrow the limit
+        struct Growing(CountingGenetics);
+
+        impl Genetics for Growing {
+            fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(&self, rng: &mut StdRng, a: u64, b: u64, points: usize) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn size(&self, individual: u64) -> Option<usize> {
+                Some(individual as usize)
+            }
+        }
+
+        let engine = |oversized: OversizedOffspring| {
+            GeneticEngineBuilder::default()
+                .seed(1)
+                .genetics(Growing(CountingGenetics::default()))
+                .max_individual_points(3)
+                .oversized_offspring(oversized)
+                .build()
+                .unwrap()
+        };
+        let mut allowing = engine(OversizedOffspring::Allow);
+        let (left, right) = (allowing.rand_individual(), allowing.rand_individual());
+        assert_eq!(2, allowing.rand_child(left, right).unwrap());
+        assert_eq!(3, allowing.rand_child(left, right).unwrap());
+        assert_eq!(4, allowing.rand_child(left, right).unwrap());
+
+        let mut copying = engine(OversizedOffspring::CopyParent);
+        let (left, right) = (copying.rand_individual(), copying.rand_individual());
+        assert_eq!(2, copying.rand_child(left, right).unwrap());
+        assert_eq!(3, copying.rand_child(left, right).unwrap());
+        assert_eq!(
+            (left, IndividualOrigin::Reproduction),
+            copying.rand_child_with_origin(left, right).unwrap()
+        );
+        assert_eq!(left, copying.mutate(left, 1));
+
+        let mut rejecting = engine(OversizedOffspring::Reject);
+        let (left, right) = (rejecting.rand_individual(), rejecting.rand_individual());
+        rejecting.rand_child(left, right).unwrap();
+        rejecting.rand_child(left, right).unwrap();
+        assert!(matches!(
+            rejecting.rand_child(left, right),
+            Err(GeneticError::CouldNotProduceValidChild(10))
+        ));
+    }
+
+    #[test]
+    fn reproduction_copies_the_first_parent() {
+        let mut engine = GeneticEngineBuilder::default()
+            .seed(1)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .crossover_rate(0)
+            .reproduction_rate(1)
+            .build()
+            .unwrap();
+        let (left, right) = (engine.rand_individual(), engine.rand_individual());
+        assert_eq!(
+            (left, IndividualOrigin::Reproduction),
+            engine.rand_child_with_origin(left, right).unwrap()
+        );
+
+        // Reproduction is the only way left to breed a child, so it cannot be turned off
+        assert!(matches!(
+            engine.set_reproduction_rate(0),
+            Err(GeneticError::NoGeneticOperators)
+        ));
+        engine.set_rates(1, 1).unwrap();
+        engine.set_reproduction_rate(0).unwrap();
+        assert_eq!(0, engine.settings().reproduction_rate);
+    }
+
+    #[test]
+    fn invalid_children_are_bred_again() {
+        // Accepts only ids that are a multiple of the modulus
+        struct Rejecting(CountingGenetics, u64);
+
+        impl Genetics for Rejecting {
+            fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(&self, rng: &mut StdRng, a: u64, b: u64, points: usize) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn is_valid(&self, individual: u64) -> bool {
+                individual.is_multiple_of(self.1)
+            }
+        }
+
+        let engine = |modulus: u64| {
+            GeneticEngineBuilder::default()
+                .seed(1)
+                .genetics(Rejecting(CountingGenetics::default(), modulus))
+                .max_child_attempts(3)
+                .build()
+                .unwrap()
+        };
+        let mut accepting = engine(3);
+        let (left, right) = (accepting.rand_individual(), accepting.rand_individual());
+        for _ in 0..5 {
+            assert!(accepting.rand_child(left, right).unwrap().is_multiple_of(3));
+        }
+
+        let mut rejecting = engine(1000);
+        let (left, right) = (rejecting.rand_individual(), rejecting.rand_individual());
+        assert!(matches!(
+            rejecting.rand_child(left, right),
+            Err(GeneticError::CouldNotProduceValidChild(3))
+        ));
+        assert!(matches!(
+            GeneticEngineBuilder::default()
+                .genetics(CountingGenetics::default())
+                .max_child_attempts(0)
+                .build(),
+            Err(GeneticError::InvalidChildAttempts)
+        ));
+    }
+}
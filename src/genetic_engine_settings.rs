@@ -0,0 +1,24 @@
+/// The operator settings of a `GeneticEngine` at some point in time. These can change during a run, for example during
+/// a `Warmup`, a hypermutation burst or a `CurriculumStage`, so they are saved with the rest of a `WorldSnapshot`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneticEngineSettings {
+    /// The mutation rate
+    pub mutation_rate: u8,
+
+    /// The crossover rate
+    pub crossover_rate: u8,
+
+    /// The maximum number of code points changed by one mutation
+    pub max_mutation_points: u8,
+
+    /// The maximum number of code points swapped by one crossover
+    pub max_crossover_points: u8,
+
+    /// The maximum number of code points in a random individual
+    pub max_individual_points: usize,
+
+    /// The reproduction rate
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reproduction_rate: u8,
+}
@@ -1,5 +1,13 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
 use rand::rngs::StdRng; // cspell:disable-line
 
+/// The genetic operators of a representation. Individuals are `u64` ids into storage owned by the implementation, and
+/// every other part of the framework, such as islands, island engines, snapshots and logs, handles only those ids.
+/// Genomes that are ordinary values, such as a `Vec<f64>`, do not need their own storage: implement `ValueGenetics`
+/// and wrap it in `StoredGenetics`, which keeps the genomes and hands out the ids.
 pub trait Genetics {
     /// Produces a random individual of up to the `max_points` number of code items.
     fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64;
@@ -15,4 +23,94 @@ pub trait Genetics {
         individual_b: u64,
         points: usize,
     ) -> u64;
+
+    /// Fixes a child that breaks the hard constraints of the representation, such as a permutation with a repeated
+    /// element or a program that does not type check, so that it is not evaluated as it is. Called by the genetic
+    /// engine on every child of a mutation or crossover. Returns the id of the repaired child, which may be the id it
+    /// was given. The default implementation returns the child unchanged.
+    fn repair(&self, _rng: &mut StdRng, individual: u64) -> u64 {
+        individual
+    }
+
+    /// Returns false for a repaired child that must still not join an island. The genetic engine breeds another child
+    /// instead, up to `GeneticEngineBuilder::max_child_attempts` times. The default implementation accepts every
+    /// individual.
+    fn is_valid(&self, _individual: u64) -> bool {
+        true
+    }
+
+    /// Returns the number of code points in an individual, which lets the genetic engine scale the points of its
+    /// operators to the individual with `GeneticEngineBuilder::mutation_point_fraction` and
+    /// `GeneticEngineBuilder::crossover_point_fraction`. The default implementation returns None, and the engine then
+    /// uses its absolute maxima.
+    fn size(&self, _individual: u64) -> Option<usize> {
+        None
+    }
+
+    /// Returns a hash of the genome of an individual, equal for any two individuals with the same genome even if their
+    /// ids differ. The world uses it to count the distinct genomes of each island in `IslandStats::distinct_genomes`
+    /// and, with `WorldBuilder::with_unique_genomes`, to keep bred duplicates out of an island. Callers may also use
+    /// it as the key of a fitness cache. The default implementation returns the id, so that every individual is
+    /// considered unique.
+    fn genome_hash(&self, individual: u64) -> u64 {
+        individual
+    }
+
+    /// Measures how different two individuals are. Used by the framework wherever individuals must be compared
+    /// without knowing how they are represented, such as deduplicating an `Archive`, sizing an `AdaptiveMigration` or
+    /// pairing parents under a `MatingRestriction`. Larger values mean more different, and the distance from an
+    /// individual to itself should be 0.0.
+    ///
+    /// The default implementation is the discrete metric: 0.0 for the same individual and 1.0 for any two different
+    /// individuals.
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        if individual_a == individual_b {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Writes the genome of an individual as text, so that it can be exported with `Island::export_population` and
+    /// recreated by `decode` on another machine. The default implementation returns None, and exported individuals
+    /// then carry only their id.
+    fn encode(&self, _individual: u64) -> Option<String> {
+        None
+    }
+
+    /// Recreates an individual from a genome written by `encode`, returning its id in this process. Returns None if
+    /// the genome cannot be read. The default implementation always returns None.
+    fn decode(&self, _genome: &str) -> Option<u64> {
+        None
+    }
+
+    /// Creates an individual from a point in the unit hypercube, each coordinate being in the range [0, 1). Used by
+    /// `Initialization::LatinHypercube` to spread the initial population evenly, typically by scaling each coordinate
+    /// to the range of one gene. Returns None if the genome cannot be made from a point, in which case a random
+    /// individual is used instead. The default implementation always returns None.
+    fn individual_from_point(&self, _point: &[f64]) -> Option<u64> {
+        None
+    }
+
+    /// Called by the world after every generation with the id of every individual it still references, unless
+    /// `WorldBuilder::with_garbage_collection` is disabled. Any other individual will never be passed to the genetics
+    /// again by the world, unless it restores a snapshot or checkpoint that holds it, and its genome may be released.
+    /// Genetics that keep their genomes in an `IndividualStore` or allocate ids from an `IdAllocator` can forward this
+    /// to its `collect_garbage`. The default implementation does nothing.
+    fn collect_garbage(&self, _referenced: &HashSet<u64>) {}
+
+    /// Returns false if the genome of an individual is not held by the genetics, for example because it was released
+    /// by `collect_garbage`. `World::restore` and `World::resume_from` check every individual of a snapshot with it,
+    /// and refuse a snapshot that refers to an individual that is gone. The default implementation returns true.
+    fn contains(&self, _individual: u64) -> bool {
+        true
+    }
+}
+
+/// Hashes a genome for `Genetics::genome_hash`. The hasher is created with fixed keys, so the hash of a genome does not
+/// change between runs of the same build.
+pub(crate) fn hash_genome<T: Hash + ?Sized>(genome: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    genome.hash(&mut hasher);
+    hasher.finish()
 }
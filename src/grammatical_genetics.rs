@@ -0,0 +1,494 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{GeneticError, Genetics, IndividualStore};
+
+// The number of random genomes `random_individual` draws while looking for one that maps
+const RANDOM_INDIVIDUAL_ATTEMPTS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Symbol {
+    Terminal(String),
+    NonTerminal(usize),
+}
+
+/// A context-free grammar in Backus-Naur form, which `GrammaticalGenetics` maps genomes through.
+///
+/// Each rule is written on its own line as `<name> ::= alternative | alternative`, and a line starting with `|` adds
+/// alternatives to the rule above it. The symbols of an alternative are separated by whitespace: `<name>` refers to a
+/// rule and anything else is a terminal, so a terminal cannot contain whitespace or `|`. The first rule is the start
+/// symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grammar {
+    names: Vec<String>,
+    rules: Vec<Vec<Vec<Symbol>>>,
+}
+
+impl Grammar {
+    /// Parses a grammar. Returns an error if it has no rules, if a rule is defined twice, has an empty alternative or
+    /// refers to a rule that is not defined, or if a rule can never finish expanding.
+    pub fn parse(bnf: &str) -> Result<Self, GeneticError> {
+        let invalid = |message: String| Err(GeneticError::InvalidGrammar(message));
+        let mut definitions: Vec<(String, Vec<Vec<String>>)> = vec![];
+        for line in bnf.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (alternatives, definition) = if let Some(rest) = line.strip_prefix('|') {
+                match definitions.last_mut() {
+                    Some((_, alternatives)) => (rest, alternatives),
+                    None => return invalid("the first line does not start a rule".to_string()),
+                }
+            } else {
+                let Some((name, rest)) = line.split_once("::=") else {
+                    return invalid(format!("'{}' is not a rule", line));
+                };
+                let name = name.trim();
+                if !is_non_terminal(name) {
+                    return invalid(format!("'{}' is not a rule name", name));
+                }
+                if definitions.iter().any(|(defined, _)| defined == name) {
+                    return invalid(format!("{} is defined more than once", name));
+                }
+                definitions.push((name.to_string(), vec![]));
+                (rest, &mut definitions.last_mut().unwrap().1)
+            };
+            for alternative in alternatives.split('|') {
+                let symbols: Vec<String> =
+                    alternative.split_whitespace().map(String::from).collect();
+                if symbols.is_empty() {
+                    return invalid(format!("'{}' has an empty alternative", line));
+                }
+                definition.push(symbols);
+            }
+        }
+        if definitions.is_empty() {
+            return invalid("there are no rules".to_string());
+        }
+
+        let names: Vec<String> = definitions.iter().map(|(name, _)| name.clone()).collect();
+        let indices: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.as_str(), index))
+            .collect();
+        let mut rules = vec![];
+        for (_, alternatives) in definitions.iter() {
+            let mut rule = vec![];
+            for alternative in alternatives {
+                let mut symbols = vec![];
+                for symbol in alternative {
+                    if !is_non_terminal(symbol) {
+                        symbols.push(Symbol::Terminal(symbol.clone()));
+                    } else if let Some(&index) = indices.get(symbol.as_str()) {
+                        symbols.push(Symbol::NonTerminal(index));
+                    } else {
+                        return invalid(format!("{} is not defined", symbol));
+                    }
+                }
+                rule.push(symbols);
+            }
+            rules.push(rule);
+        }
+
+        let grammar = Grammar { names, rules };
+        if let Some(rule) = grammar.endless_rule() {
+            return invalid(format!("{} never finishes expanding", grammar.names[rule]));
+        }
+        Ok(grammar)
+    }
+
+    /// Returns the names of the rules, starting with the start symbol
+    pub fn rule_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Maps codons through the grammar, reading the codons again from the start up to `max_wraps` times, and returns
+    /// the terminals of the program in order. Returns None if the program is not complete by then. See
+    /// `GrammaticalGenetics`.
+    pub fn map(&self, codons: &[u32], max_wraps: usize) -> Option<Vec<&str>> {
+        let mut terminals = vec![];
+        let mut pending = vec![&Symbol::NonTerminal(0)];
+        let mut used = 0;
+        while let Some(symbol) = pending.pop() {
+            let rule = match symbol {
+                Symbol::Terminal(terminal) => {
+                    terminals.push(terminal.as_str());
+                    continue;
+                }
+                Symbol::NonTerminal(rule) => &self.rules[*rule],
+            };
+            let alternative = if rule.len() == 1 {
+                &rule[0]
+            } else {
+                if codons.is_empty() || used == codons.len() * (max_wraps + 1) {
+                    return None;
+                }
+                let codon = codons[used % codons.len()];
+                used += 1;
+                &rule[codon as usize % rule.len()]
+            };
+            pending.extend(alternative.iter().rev());
+        }
+        Some(terminals)
+    }
+
+    // Returns a rule that cannot be expanded into terminals alone, if there is one
+    fn endless_rule(&self) -> Option<usize> {
+        let mut finishes = vec![false; self.rules.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (rule, alternatives) in self.rules.iter().enumerate() {
+                let finished = alternatives.iter().any(|symbols| {
+                    symbols.iter().all(|symbol| match symbol {
+                        Symbol::Terminal(_) => true,
+                        Symbol::NonTerminal(index) => finishes[*index],
+                    })
+                });
+                if finished && !finishes[rule] {
+                    finishes[rule] = true;
+                    changed = true;
+                }
+            }
+        }
+        finishes.iter().position(|finishes| !finishes)
+    }
+}
+
+fn is_non_terminal(symbol: &str) -> bool {
+    symbol.len() > 2 && symbol.starts_with('<') && symbol.ends_with('>')
+}
+
+/// What `GrammaticalGenetics` does with offspring whose genome cannot be mapped through the grammar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InvalidMapping {
+    /// An invalid child is replaced by a copy of its first parent, and `random_individual` draws again until it finds a
+    /// genome that maps, giving up after 100 attempts. Invalid individuals are still possible, such as decoded ones.
+    #[default]
+    Replace,
+
+    /// Invalid individuals are kept, and island engines must score them when `phenotype` returns None
+    Keep,
+}
+
+/// A ready-made `Genetics` for grammatical evolution. Individuals are strings of integer codons kept in an
+/// `IndividualStore`, and their programs are derived from a `Grammar`: starting from the start symbol, the leftmost
+/// rule is repeatedly replaced by the alternative chosen by the next codon, modulo the number of alternatives. Rules
+/// with a single alternative do not use a codon. Island engines get the resulting terminals from `phenotype`.
+///
+/// When the codons run out before the program is complete, the mapping wraps around and reads the genome again from
+/// the start, up to `max_wraps` times. A genome that still has not finished is invalid; see `InvalidMapping`.
+///
+/// Mutation replaces `points` random codons, and crossover joins the start of one genome to the end of the other at
+/// independently chosen cut points. Genomes hold at least one codon and no more than the length limit, which is the
+/// engine's `max_individual_points` unless it is set with `with_max_length`.
+pub struct GrammaticalGenetics {
+    grammar: Grammar,
+    codon_limit: u32,
+    max_wraps: usize,
+    invalid_mapping: InvalidMapping,
+    max_length: Option<usize>,
+    // The largest `max_points` passed to `random_individual`, which is the engine's `max_individual_points`
+    engine_max_length: Cell<Option<usize>>,
+    store: Arc<IndividualStore<Vec<u32>>>,
+}
+
+impl GrammaticalGenetics {
+    /// Creates genetics for the grammar, with codons below 256 and two wraps
+    pub fn new(grammar: Grammar) -> Self {
+        GrammaticalGenetics {
+            grammar,
+            codon_limit: 256,
+            max_wraps: 2,
+            invalid_mapping: InvalidMapping::default(),
+            max_length: None,
+            engine_max_length: Cell::new(None),
+            store: Arc::new(IndividualStore::new()),
+        }
+    }
+
+    /// Sets the number of distinct codon values. Codons are drawn from 0 up to, but not including, the limit.
+    pub fn with_codon_limit(mut self, limit: u32) -> Self {
+        self.codon_limit = limit.max(1);
+        self
+    }
+
+    /// Sets the number of times the mapping may read the genome again from the start
+    pub fn with_max_wraps(mut self, wraps: usize) -> Self {
+        self.max_wraps = wraps;
+        self
+    }
+
+    pub fn with_invalid_mapping(mut self, invalid_mapping: InvalidMapping) -> Self {
+        self.invalid_mapping = invalid_mapping;
+        self
+    }
+
+    /// Sets the number of codons no genome may exceed, in place of the engine's `max_individual_points`
+    pub fn with_max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length.max(1));
+        self
+    }
+
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Returns a handle to the store of genomes, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<u32>>> {
+        self.store.clone()
+    }
+
+    /// Returns the codons of an individual, or None if it is not stored
+    pub fn codons(&self, id: u64) -> Option<Arc<Vec<u32>>> {
+        self.store.get(id)
+    }
+
+    /// Maps an individual through the grammar, returning the terminals of its program in order. Returns None if the
+    /// individual is not stored or its genome does not map within `max_wraps` wraps.
+    pub fn phenotype(&self, id: u64) -> Option<Vec<&str>> {
+        self.grammar.map(&self.store.get(id)?, self.max_wraps)
+    }
+
+    fn length_limit(&self) -> usize {
+        self.max_length
+            .or(self.engine_max_length.get())
+            .unwrap_or(usize::MAX)
+            .max(1)
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<u32>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    // Stores the child if it maps or invalid individuals are kept, and otherwise a copy of the parent
+    fn insert_valid(&self, child: Vec<u32>, parent: u64) -> u64 {
+        if self.invalid_mapping == InvalidMapping::Keep
+            || self.grammar.map(&child, self.max_wraps).is_some()
+        {
+            self.store.insert(child)
+        } else {
+            let copy = (*self.genome(parent)).clone();
+            self.store.insert(copy)
+        }
+    }
+}
+
+impl Genetics for GrammaticalGenetics {
+    fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+        let largest = self
+            .engine_max_length
+            .get()
+            .map_or(max_points, |length| length.max(max_points));
+        self.engine_max_length.set(Some(largest));
+
+        let max_length = max_points.min(self.length_limit()).max(1);
+        let attempts = match self.invalid_mapping {
+            InvalidMapping::Replace => RANDOM_INDIVIDUAL_ATTEMPTS,
+            InvalidMapping::Keep => 1,
+        };
+        let mut codons = vec![];
+        for _ in 0..attempts {
+            let length = rng.random_range(1..=max_length);
+            codons = (0..length)
+                .map(|_| rng.random_range(0..self.codon_limit))
+                .collect();
+            if self.grammar.map(&codons, self.max_wraps).is_some() {
+                break;
+            }
+        }
+        self.store.insert(codons)
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        for _ in 0..points {
+            let codon = rng.random_range(0..child.len());
+            child[codon] = rng.random_range(0..self.codon_limit);
+        }
+        self.insert_valid(child, individual)
+    }
+
+    // The engine's crossover points are not used
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let cut_a = rng.random_range(1..=a.len());
+        let cut_b = rng.random_range(0..b.len());
+        let end_b = b
+            .len()
+            .min(cut_b + self.length_limit().saturating_sub(cut_a));
+        let mut child = a[..cut_a].to_vec();
+        child.extend_from_slice(&b[cut_b..end_b]);
+        self.insert_valid(child, individual_a)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let differing = a.iter().zip(b.iter()).filter(|(a, b)| a != b).count();
+        (differing + a.len().abs_diff(b.len())) as f64
+    }
+
+    // Genomes are written as their codons separated by spaces, such as `12 7 201`
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |codons| hash_genome(&*codons))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let codons = self.store.get(individual)?;
+        let codons: Vec<String> = codons.iter().map(u32::to_string).collect();
+        Some(codons.join(" "))
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let codons = genome
+            .split_whitespace()
+            .map(|codon| codon.parse().ok().filter(|&codon| codon < self.codon_limit))
+            .collect::<Option<Vec<u32>>>()?;
+        if codons.is_empty() || codons.len() > self.length_limit() {
+            return None;
+        }
+        Some(self.store.insert(codons))
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::seq::IndexedRandom;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    const ARITHMETIC: &str = "
+        <e> ::= add <e> <e> | mul <e> <e>
+              | <v>
+        <v> ::= x | one
+    ";
+
+    // Scores a program by how closely it matches x * x + 1 on a few points, with invalid programs scoring 0
+    struct RegressionEngine {
+        grammar: Grammar,
+        store: Arc<IndividualStore<Vec<u32>>>,
+    }
+
+    fn evaluate(terminals: &[&str], next: &mut usize, x: i64) -> i64 {
+        let terminal = terminals[*next];
+        *next += 1;
+        match terminal {
+            "add" => evaluate(terminals, next, x).saturating_add(evaluate(terminals, next, x)),
+            "mul" => evaluate(terminals, next, x).saturating_mul(evaluate(terminals, next, x)),
+            "x" => x,
+            _ => 1,
+        }
+    }
+
+    impl IslandEngine for RegressionEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let codons = self.store.get(id).unwrap();
+            let Some(terminals) = self.grammar.map(&codons, 2) else {
+                return 0;
+            };
+            let error: u64 = (0..5)
+                .map(|x| evaluate(&terminals, &mut 0, x).abs_diff(x * x + 1))
+                .fold(0, u64::saturating_add);
+            1000u64.saturating_sub(error)
+        }
+    }
+
+    #[test]
+    fn genomes_are_mapped_through_the_grammar() {
+        for invalid in [
+            "<e> ::= x\n<e> ::= y",
+            "<e> ::= <f>",
+            "<e> ::= add <e> <e>",
+            "<e> ::= x |",
+        ] {
+            assert!(matches!(
+                Grammar::parse(invalid),
+                Err(GeneticError::InvalidGrammar(_))
+            ));
+        }
+
+        let grammar = Grammar::parse(ARITHMETIC).unwrap();
+        assert_eq!(["<e>", "<v>"], grammar.rule_names());
+        assert_eq!(
+            Some(vec!["add", "x", "one"]),
+            grammar.map(&[0, 2, 0, 2, 1], 0)
+        );
+
+        // The last variable is chosen by the first codon after wrapping around
+        assert_eq!(Some(vec!["add", "x", "x"]), grammar.map(&[0, 2, 0, 2], 1));
+        assert_eq!(None, grammar.map(&[0, 2, 0, 2], 0));
+        assert_eq!(None, grammar.map(&[1], 2));
+
+        let genetics = GrammaticalGenetics::new(grammar).with_max_length(8);
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut individuals = vec![genetics.decode("0 2 0 2 1").unwrap()];
+        assert_eq!(
+            Some("0 2 0 2 1".to_string()),
+            genetics.encode(individuals[0])
+        );
+        assert_eq!(None, genetics.decode("0 256"));
+        for _ in 0..200 {
+            let a = *individuals.choose(&mut rng).unwrap();
+            let b = *individuals.choose(&mut rng).unwrap();
+            individuals.push(genetics.crossover(&mut rng, a, b, 1));
+            individuals.push(genetics.mutate(&mut rng, a, 2));
+            individuals.push(genetics.random_individual(&mut rng, 8));
+        }
+        for id in individuals {
+            assert!((1..=8).contains(&genetics.codons(id).unwrap().len()));
+            assert!(genetics.phenotype(id).is_some());
+        }
+    }
+
+    #[test]
+    fn a_polynomial_is_found() {
+        let grammar = Grammar::parse(ARITHMETIC).unwrap();
+        let genetics = GrammaticalGenetics::new(grammar.clone());
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .max_individual_points(20)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(50)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(RegressionEngine { grammar, store }));
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(30).unwrap();
+        assert_eq!(Some(1000), world.best_score());
+    }
+}
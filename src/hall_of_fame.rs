@@ -0,0 +1,186 @@
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::IndexedRandom;
+
+/// Keeps the world's champions from past generations as opponents for competitive fitness, such as self-play, where
+/// an individual is scored by playing against others. Scoring only against the current population lets an island
+/// forget how to beat strategies it has moved away from; facing a sample of past champions keeps that pressure on.
+///
+/// After every generation the world's most fit individual joins the hall of fame, unless it is already a member, and
+/// the oldest member leaves once there are more than `capacity`. Before each island runs a generation, the world draws
+/// `sample_size` distinct members for it and passes them to `IslandEngine::read_opponents`. Members are kept alive
+/// for as long as they are in the hall of fame, so the engine can run them as opponents.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HallOfFame {
+    /// The most champions kept. Must be at least one.
+    ///
+    /// Default: 50
+    pub capacity: usize,
+
+    /// The number of members each island is given as opponents every generation, or every member while there are
+    /// fewer. Must be at least one.
+    ///
+    /// Default: 5
+    pub sample_size: usize,
+
+    /// How strongly the sample favors recent members. The newest member has a weight of 1, the one before it
+    /// 1/2^recency_weight, the one before that 1/3^recency_weight and so on. Zero samples every member equally.
+    /// Must not be negative.
+    ///
+    /// Default: 1.0
+    pub recency_weight: f64,
+}
+
+impl Default for HallOfFame {
+    fn default() -> Self {
+        HallOfFame {
+            capacity: 50,
+            sample_size: 5,
+            recency_weight: 1.0,
+        }
+    }
+}
+
+impl HallOfFame {
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
+    pub fn with_recency_weight(mut self, recency_weight: f64) -> Self {
+        self.recency_weight = recency_weight;
+        self
+    }
+
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.capacity > 0
+            && self.sample_size > 0
+            && self.recency_weight >= 0.0
+            && self.recency_weight.is_finite()
+    }
+
+    /// Adds a champion as the newest member, dropping the oldest members beyond the capacity. A champion that is
+    /// already a member keeps its place.
+    pub(crate) fn induct(&self, members: &mut Vec<u64>, champion: u64) {
+        if members.contains(&champion) {
+            return;
+        }
+        members.push(champion);
+        let excess = members.len().saturating_sub(self.capacity);
+        members.drain(..excess);
+    }
+
+    /// Draws distinct opponents from the members, which are ordered oldest first, favoring the newest
+    pub(crate) fn sample(&self, rng: &mut StdRng, members: &[u64]) -> Vec<u64> {
+        let ages: Vec<(usize, u64)> = members.iter().rev().copied().enumerate().collect();
+        ages.choose_multiple_weighted(rng, self.sample_size, |&(age, _)| {
+            (1.0 + age as f64).powf(-self.recency_weight)
+        })
+        .map(|sample| sample.map(|&(_, id)| id).collect())
+        .unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rand::rngs::StdRng; // cspell:disable-line
+    use rand::SeedableRng;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn islands_are_given_opponents_from_the_hall_of_fame() {
+        // Scores by id, and remembers the opponents it was given each generation
+        struct Competing(Arc<Mutex<Vec<Vec<u64>>>>);
+
+        impl IslandEngine for Competing {
+            fn read_opponents(&mut self, opponents: &[u64]) {
+                let mut opponents = opponents.to_vec();
+                opponents.sort();
+                self.0.lock().unwrap().push(opponents);
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_individual(&self, id: u64) -> u64 {
+                id
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(vec![]));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_hall_of_fame(HallOfFame::default().with_capacity(2));
+        builder.add_island("a", Box::new(Competing(seen.clone())));
+        let mut world = builder.build().unwrap();
+        let mut champions = vec![];
+        for _ in 0..3 {
+            world.run_n_generations(1).unwrap();
+            champions.push(world.most_fit_individual().unwrap().id);
+        }
+
+        // Each generation faces the champions of the generations before it, up to the capacity
+        assert_eq!(&champions[1..], world.hall_of_fame());
+        assert_eq!(
+            vec![vec![], vec![champions[0]], champions[..2].to_vec()],
+            *seen.lock().unwrap()
+        );
+        assert!(world.referenced_individuals().contains(&champions[1]));
+
+        world.restart(RestartKeep::nothing()).unwrap();
+        assert!(world.hall_of_fame().is_empty());
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_hall_of_fame(HallOfFame::default().with_recency_weight(-1.0))
+                .build(),
+            Err(GeneticError::InvalidHallOfFame)
+        ));
+    }
+
+    #[test]
+    fn members_and_samples_stay_within_their_limits() {
+        let hall = HallOfFame::default().with_capacity(1).with_sample_size(3);
+        let mut members = vec![];
+        hall.induct(&mut members, 4);
+        hall.induct(&mut members, 4);
+        assert_eq!(vec![4], members);
+        hall.induct(&mut members, 9);
+        assert_eq!(vec![9], members);
+
+        // A sample is every member while there are fewer than the sample size, and is never repeated
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(hall.sample(&mut rng, &[]).is_empty());
+        let mut sample = hall.sample(&mut rng, &[1, 2]);
+        sample.sort();
+        assert_eq!(vec![1, 2], sample);
+
+        // With a strong recency weight the newest member is nearly always drawn
+        let recent = HallOfFame::default()
+            .with_sample_size(1)
+            .with_recency_weight(20.0);
+        let newest = (0..100)
+            .filter(|_| recent.sample(&mut rng, &[1, 2, 3]) == vec![3])
+            .count();
+        assert!(newest > 95);
+
+        for hall in [
+            HallOfFame::default().with_capacity(0),
+            HallOfFame::default().with_sample_size(0),
+            HallOfFame::default().with_recency_weight(f64::INFINITY),
+        ] {
+            assert!(!hall.is_valid());
+        }
+        assert!(HallOfFame::default().with_recency_weight(0.0).is_valid());
+    }
+}
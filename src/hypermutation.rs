@@ -0,0 +1,142 @@
+/// Temporarily boosts mutation when the world stagnates. Once `stagnation_generations` pass without a new best score,
+/// the mutation rate and maximum mutation points of the genetic engine are multiplied for `burst_generations`, and
+/// then decay linearly back to their original values over `decay_generations`.
+///
+/// While a burst is active the world owns the mutation rate and maximum mutation points of the genetic engine. Values
+/// set during the burst are overwritten, and the values in place when the burst started are restored when it ends.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hypermutation {
+    /// The number of generations without a new best score that triggers a burst. Must be greater than zero.
+    pub stagnation_generations: usize,
+
+    /// The factor applied to the mutation rate at the peak of a burst. Must be at least 1.0.
+    pub mutation_rate_multiplier: f64,
+
+    /// The factor applied to the maximum mutation points at the peak of a burst. Must be at least 1.0.
+    pub mutation_points_multiplier: f64,
+
+    /// The number of generations the full multipliers are applied
+    pub burst_generations: usize,
+
+    /// The number of generations over which the multipliers return to 1.0 after the full burst
+    pub decay_generations: usize,
+}
+
+impl Default for Hypermutation {
+    fn default() -> Self {
+        Hypermutation {
+            stagnation_generations: 20,
+            mutation_rate_multiplier: 5.0,
+            mutation_points_multiplier: 2.0,
+            burst_generations: 5,
+            decay_generations: 5,
+        }
+    }
+}
+
+impl Hypermutation {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.stagnation_generations > 0
+            && self.mutation_rate_multiplier >= 1.0
+            && self.mutation_points_multiplier >= 1.0
+    }
+
+    /// Returns how far into the burst `elapsed` generations are: 1.0 during the full burst, falling linearly to 0.0 at
+    /// the end of the decay. Returns None once the burst is over.
+    pub(crate) fn intensity(&self, elapsed: usize) -> Option<f64> {
+        if elapsed < self.burst_generations {
+            Some(1.0)
+        } else if elapsed < self.burst_generations + self.decay_generations {
+            let decayed = elapsed - self.burst_generations + 1;
+            Some(1.0 - decayed as f64 / (self.decay_generations + 1) as f64)
+        } else {
+            None
+        }
+    }
+
+    /// Scales `base` by the multiplier at the given intensity, saturating at u8::MAX
+    pub(crate) fn scale(base: u8, multiplier: f64, intensity: f64) -> u8 {
+        let factor = 1.0 + (multiplier - 1.0) * intensity;
+        (base as f64 * factor).round().min(u8::MAX as f64) as u8
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn hypermutation_bursts_after_stagnation() {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1))
+            .with_individuals_per_island(10)
+            .with_hypermutation(Hypermutation {
+                stagnation_generations: 2,
+                mutation_rate_multiplier: 4.0,
+                mutation_points_multiplier: 2.0,
+                burst_generations: 2,
+                decay_generations: 1,
+            });
+        builder.add_island("flat", Box::new(UnscoredEngine));
+        let mut world = builder.build().unwrap();
+
+        let mut rates = vec![];
+        let mut points = vec![];
+        for _ in 0..8 {
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+            rates.push(world.genetic_engine().mutation_rate());
+            points.push(world.genetic_engine().max_mutation_points());
+        }
+
+        // The first generation sets the best score, the next two stagnate and trigger a two generation burst that
+        // decays over one generation. After another two stagnant generations the next burst starts.
+        assert_eq!(vec![1, 1, 4, 4, 3, 1, 1, 4], rates);
+        assert_eq!(vec![3, 3, 6, 6, 5, 3, 3, 6], points);
+    }
+
+    #[test]
+    fn bursts_without_decay_end_abruptly_and_scaling_saturates() {
+        let abrupt = Hypermutation {
+            burst_generations: 2,
+            decay_generations: 0,
+            ..Hypermutation::default()
+        };
+        assert_eq!(Some(1.0), abrupt.intensity(1));
+        assert_eq!(None, abrupt.intensity(2));
+
+        // A burst of no generations only decays, and never reaches zero intensity before it ends
+        let decaying = Hypermutation {
+            burst_generations: 0,
+            decay_generations: 3,
+            ..Hypermutation::default()
+        };
+        assert_eq!(Some(0.75), decaying.intensity(0));
+        assert_eq!(Some(0.25), decaying.intensity(2));
+        assert_eq!(None, decaying.intensity(3));
+
+        assert_eq!(u8::MAX, Hypermutation::scale(100, 5.0, 1.0));
+        assert_eq!(100, Hypermutation::scale(100, 5.0, 0.0));
+        assert_eq!(0, Hypermutation::scale(0, 5.0, 1.0));
+
+        assert!(Hypermutation {
+            mutation_rate_multiplier: 1.0,
+            mutation_points_multiplier: 1.0,
+            ..Hypermutation::default()
+        }
+        .is_valid());
+        assert!(!Hypermutation {
+            stagnation_generations: 0,
+            ..Hypermutation::default()
+        }
+        .is_valid());
+        assert!(!Hypermutation {
+            mutation_rate_multiplier: 0.5,
+            ..Hypermutation::default()
+        }
+        .is_valid());
+    }
+}
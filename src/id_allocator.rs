@@ -0,0 +1,165 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::MaybeSend;
+
+#[cfg(not(feature = "send"))]
+type ReleaseCallback = Box<dyn Fn(u64)>;
+
+#[cfg(feature = "send")]
+type ReleaseCallback = Box<dyn Fn(u64) + Send>;
+
+/// Hands out unique ids for `Genetics` implementations that keep their genomes in their own storage, and recycles the
+/// ids of individuals the world has dropped. Like `IndividualStore` it can be used through a shared reference, so the
+/// genetics can own one and allocate from `random_individual`, `mutate` and `crossover`.
+///
+/// Forward `Genetics::collect_garbage` to `IdAllocator::collect_garbage`, and the allocator learns which ids the world
+/// has stopped referencing after each generation; release the user's data for the ids it returns. A recycled id is
+/// handed out again, so ids held outside the world, such as in an old snapshot, may later refer to a new individual.
+/// Forwarding `Genetics::contains` to `is_allocated` only catches ids that have not been handed out again, so do not
+/// restore such snapshots, or disable `WorldBuilder::with_garbage_collection` when runs are rolled back.
+///
+/// Because ids are recycled, they stay below `id_bound`, which is never more than the largest number of individuals
+/// alive at once. The user's data can therefore be kept in a `Vec` indexed by id that stops growing once the
+/// population has, and a callback given to `with_release_callback` can clear each slot as its id is released.
+#[derive(Default)]
+pub struct IdAllocator {
+    allocated: RefCell<HashSet<u64>>,
+    free: RefCell<Vec<u64>>,
+    next_id: Cell<u64>,
+    on_release: Option<ReleaseCallback>,
+}
+
+impl IdAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` with every id that is released, before the id can be handed out again. With the `send` feature
+    /// the callback must be `Send`.
+    pub fn with_release_callback<F: Fn(u64) + MaybeSend + 'static>(mut self, callback: F) -> Self {
+        self.on_release = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns an id that is not in use, recycling a released id if there is one
+    pub fn allocate(&self) -> u64 {
+        let id = self.free.borrow_mut().pop().unwrap_or_else(|| {
+            let id = self.next_id.get();
+            self.next_id.set(id + 1);
+            id
+        });
+        self.allocated.borrow_mut().insert(id);
+        id
+    }
+
+    /// Makes an id available for reuse. Returns false if it was not in use.
+    pub fn release(&self, id: u64) -> bool {
+        let released = self.allocated.borrow_mut().remove(&id);
+        if released {
+            if let Some(on_release) = &self.on_release {
+                on_release(id);
+            }
+            self.free.borrow_mut().push(id);
+        }
+        released
+    }
+
+    /// Returns true if the id has been allocated and not released
+    pub fn is_allocated(&self, id: u64) -> bool {
+        self.allocated.borrow().contains(&id)
+    }
+
+    /// Returns one more than the largest id ever allocated, which is the length a `Vec` indexed by id needs
+    pub fn id_bound(&self) -> u64 {
+        self.next_id.get()
+    }
+
+    /// Returns the number of ids in use
+    pub fn len(&self) -> usize {
+        self.allocated.borrow().len()
+    }
+
+    /// Returns true if no ids are in use
+    pub fn is_empty(&self) -> bool {
+        self.allocated.borrow().is_empty()
+    }
+
+    /// Releases every id that is not in `referenced`, and returns them so that the data behind them can be dropped
+    pub fn collect_garbage(&self, referenced: &HashSet<u64>) -> Vec<u64> {
+        let unreferenced: Vec<u64> = self
+            .allocated
+            .borrow()
+            .iter()
+            .filter(|id| !referenced.contains(id))
+            .copied()
+            .collect();
+        for &id in unreferenced.iter() {
+            self.release(id);
+        }
+        unreferenced
+    }
+}
+
+impl fmt::Debug for IdAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdAllocator")
+            .field("allocated", &self.allocated)
+            .field("free", &self.free)
+            .field("next_id", &self.next_id)
+            .field("on_release", &self.on_release.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn unreferenced_ids_are_recycled() {
+        let allocator = IdAllocator::new();
+        let ids: Vec<u64> = (0..4).map(|_| allocator.allocate()).collect();
+        assert_eq!(vec![0, 1, 2, 3], ids);
+
+        let mut collected = allocator.collect_garbage(&HashSet::from([1, 3]));
+        collected.sort();
+        assert_eq!(vec![0, 2], collected);
+        assert_eq!(2, allocator.len());
+        assert!(!allocator.release(0));
+
+        let mut recycled = vec![allocator.allocate(), allocator.allocate()];
+        recycled.sort();
+        assert_eq!(vec![0, 2], recycled);
+        assert_eq!(4, allocator.allocate());
+        assert!(allocator.is_allocated(2) && !allocator.is_empty());
+        assert_eq!(5, allocator.id_bound());
+    }
+
+    #[test]
+    fn released_ids_are_reported_to_the_user_store() {
+        let store = Arc::new(Mutex::new(Vec::new()));
+        let released = store.clone();
+        let allocator = IdAllocator::new()
+            .with_release_callback(move |id| released.lock().unwrap()[id as usize] = None);
+
+        // The store never grows past the largest population, however many generations are created
+        for generation in 0..10 {
+            let ids: Vec<u64> = (0..3).map(|_| allocator.allocate()).collect();
+            for &id in ids.iter() {
+                let mut store = store.lock().unwrap();
+                if store.len() <= id as usize {
+                    store.resize(id as usize + 1, None);
+                }
+                store[id as usize] = Some(generation);
+            }
+            allocator.collect_garbage(&HashSet::from([ids[0]]));
+        }
+        assert_eq!(4, allocator.id_bound());
+        assert_eq!(4, store.lock().unwrap().len());
+        assert_eq!(1, store.lock().unwrap().iter().flatten().count());
+    }
+}
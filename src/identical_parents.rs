@@ -0,0 +1,109 @@
+/// What happens when the second parent selected for a crossover is the same individual as the first. Crossing an
+/// individual with itself usually gives back a copy of it, spending an evaluation on a genome the island already has.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdenticalParents {
+    /// The individual is crossed with itself
+    #[default]
+    Allow,
+
+    /// The second parent is selected again, up to this many times, until it is a different individual. If every draw
+    /// picks the first parent again, it is crossed with itself.
+    Redraw(usize),
+
+    /// The child is bred by mutating the parent instead of by crossover
+    Mutate,
+}
+
+impl IdenticalParents {
+    /// Returns the number of times the second parent may be selected again
+    pub(crate) fn redraws(&self) -> usize {
+        match self {
+            IdenticalParents::Redraw(redraws) => *redraws,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::Rng;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    // Counts the children crossed from one parent with itself and the children bred by mutation, when parents are
+    // always one of the `candidates` most fit individuals
+    fn identical_crossovers_and_mutations(
+        identical: IdenticalParents,
+        candidates: usize,
+    ) -> (usize, usize) {
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .identical_parents(identical)
+            .build()
+            .unwrap();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0)
+            .with_event_log(true)
+            .with_selector(move |rng: &mut rand::rngs::StdRng, len: usize| {
+                len - 1 - rng.random_range(0..candidates)
+            })
+            .with_select_as_parent(SelectionCurve::Custom(0));
+        builder.add_island("a", Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(5).unwrap();
+
+        let mut counts = (0, 0);
+        for event in world.event_log().unwrap().events() {
+            match event {
+                RunEvent::Born {
+                    origin: Some(IndividualOrigin::Crossover),
+                    parents,
+                    ..
+                } if parents[0] == parents[1] => counts.0 += 1,
+                RunEvent::Born {
+                    origin: Some(IndividualOrigin::Mutation),
+                    ..
+                } => counts.1 += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn identical_parents_are_redrawn_or_mutated() {
+        let (crossed_with_itself, mutated) =
+            identical_crossovers_and_mutations(IdenticalParents::Allow, 2);
+        assert!(crossed_with_itself > 0);
+        assert_eq!(0, mutated);
+        assert_eq!(
+            (0, 0),
+            identical_crossovers_and_mutations(IdenticalParents::Redraw(20), 2)
+        );
+        assert_eq!(
+            (0, crossed_with_itself),
+            identical_crossovers_and_mutations(IdenticalParents::Mutate, 2)
+        );
+    }
+
+    #[test]
+    fn redraws_give_up_when_every_draw_is_the_same_parent() {
+        let allowed = identical_crossovers_and_mutations(IdenticalParents::Allow, 2);
+        assert_eq!(
+            allowed,
+            identical_crossovers_and_mutations(IdenticalParents::Redraw(0), 2)
+        );
+
+        // With a single candidate every crossover is with itself, however many redraws are allowed
+        let (crossed_with_itself, mutated) =
+            identical_crossovers_and_mutations(IdenticalParents::Redraw(5), 1);
+        assert!(crossed_with_itself > 0);
+        assert_eq!(0, mutated);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::IndividualOrigin;
+
+/// How an individual was created, as recorded by the world when it was bred. See `World::individual_metadata`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndividualMetadata {
+    /// The generation count of the world when the individual was created, which is the generation it was first
+    /// evaluated in
+    pub generation: usize,
+
+    /// The individuals it was bred from: one for a mutation, two for a crossover and none for random and seed
+    /// individuals
+    pub parents: Vec<u64>,
+
+    /// The operator that created the individual. Always one of Random, Seed, Mutation, Crossover or
+    /// Operator, as a copy bred by reproduction is the individual it was copied from.
+    pub origin: IndividualOrigin,
+
+    /// The index of the island the individual was created on
+    pub island: usize,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn individuals_carry_their_creation_metadata() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        let first = world.get_island(1).unwrap().individuals().to_vec();
+        for &id in first.iter() {
+            let metadata = world.individual_metadata(id).unwrap();
+            assert_eq!(
+                (0, IndividualOrigin::Random, 1),
+                (metadata.generation, metadata.origin, metadata.island)
+            );
+            assert!(metadata.parents.is_empty());
+        }
+
+        world.run_n_generations(3).unwrap();
+        for &id in world.get_island(0).unwrap().individuals() {
+            let metadata = world.individual_metadata(id).unwrap();
+            let expected_parents = match metadata.origin {
+                IndividualOrigin::Random | IndividualOrigin::Seed => 0,
+                IndividualOrigin::Mutation => 1,
+                IndividualOrigin::Crossover => 2,
+                origin => panic!("{:?} is not a way to create an individual", origin),
+            };
+            assert_eq!(expected_parents, metadata.parents.len());
+            assert!(metadata.parents.iter().all(|&parent| parent < id));
+            assert!(metadata.generation < 4);
+        }
+
+        // Metadata is dropped along with the individuals
+        assert!(first
+            .iter()
+            .any(|&id| world.individual_metadata(id).is_none()));
+    }
+
+    #[test]
+    fn seeds_and_migrants_keep_the_metadata_of_their_creation() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(2)
+            .with_generations_between_migrations(1)
+            .with_seed_individuals("b", vec![1000])
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        let seed = world.individual_metadata(1000).unwrap();
+        assert_eq!(
+            (0, IndividualOrigin::Seed, 1, true),
+            (
+                seed.generation,
+                seed.origin,
+                seed.island,
+                seed.parents.is_empty()
+            )
+        );
+
+        // Migrants are recorded as created on the island they came from, not as bred by migration
+        let log = world.migration_log().to_vec();
+        assert!(!log.is_empty());
+        for record in log {
+            for migrant in record.migrants {
+                let metadata = world.individual_metadata(migrant.id).unwrap();
+                assert_eq!(record.source_island, metadata.island);
+                assert_ne!(IndividualOrigin::Migrant, metadata.origin);
+            }
+        }
+
+        world.restart(RestartKeep::nothing()).unwrap();
+        assert_eq!(None, world.individual_metadata(1000));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Storage for the genomes behind the `u64` ids the framework passes around, for `Genetics` implementations that do
+/// not want to manage their own. A genetics implementation owns a store, inserts each new genome from
+/// `random_individual`, `mutate` and `crossover`, and returns the id it was given. The store can be changed through a
+/// shared reference, as those methods only get `&self`, and it can be shared with island engines on other threads.
+///
+/// Genomes are kept until they are removed. To release the genomes the world no longer references, forward
+/// `Genetics::collect_garbage` to `IndividualStore::collect_garbage`, and `Genetics::contains` to
+/// `IndividualStore::contains`. The world then removes every genome that is not referenced by an island, the archive, a
+/// reference individual, the best validated individual or an unused seed individual after each generation, and
+/// refuses to restore a snapshot whose genomes have been removed since.
+pub struct IndividualStore<T> {
+    genomes: RwLock<HashMap<u64, Arc<T>>>,
+    next_id: AtomicU64,
+}
+
+impl<T> IndividualStore<T> {
+    pub fn new() -> Self {
+        IndividualStore {
+            genomes: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Stores a genome and returns its new id
+    pub fn insert(&self, genome: T) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.genomes.write().unwrap().insert(id, Arc::new(genome));
+        id
+    }
+
+    /// Returns the genome of an individual, or None if it was never stored or has been removed. Genomes are not changed
+    /// once stored, so the genome stays readable even if it is removed from the store in the meantime.
+    pub fn get(&self, id: u64) -> Option<Arc<T>> {
+        self.genomes.read().unwrap().get(&id).cloned()
+    }
+
+    /// Removes and returns the genome of an individual
+    pub fn remove(&self, id: u64) -> Option<Arc<T>> {
+        self.genomes.write().unwrap().remove(&id)
+    }
+
+    /// Returns true if the genome of the individual is stored
+    pub fn contains(&self, id: u64) -> bool {
+        self.genomes.read().unwrap().contains_key(&id)
+    }
+
+    /// Returns the number of stored genomes
+    pub fn len(&self) -> usize {
+        self.genomes.read().unwrap().len()
+    }
+
+    /// Returns true if no genomes are stored
+    pub fn is_empty(&self) -> bool {
+        self.genomes.read().unwrap().is_empty()
+    }
+
+    /// Removes every genome whose id is not in `referenced`, returning how many were removed
+    pub fn collect_garbage(&self, referenced: &HashSet<u64>) -> usize {
+        let mut genomes = self.genomes.write().unwrap();
+        let before = genomes.len();
+        genomes.retain(|id, _| referenced.contains(id));
+        before - genomes.len()
+    }
+}
+
+impl<T> Default for IndividualStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn unreferenced_genomes_are_collected() {
+        let store = IndividualStore::new();
+        let a = store.insert(vec![1.0, 2.0]);
+        let b = store.insert(vec![3.0]);
+        let c = store.insert(vec![]);
+        assert_eq!(vec![3.0], *store.get(b).unwrap());
+
+        assert_eq!(2, store.collect_garbage(&HashSet::from([b])));
+        assert!(!store.contains(a) && store.contains(b) && store.get(c).is_none());
+        assert_eq!(Some(vec![3.0]), store.remove(b).as_deref().cloned());
+        assert!(store.is_empty());
+
+        // Ids are not reused after their genomes are removed
+        assert_eq!(c + 1, store.insert(vec![4.0]));
+    }
+
+    #[test]
+    fn unreferenced_genomes_are_collected_after_each_generation() {
+        #[derive(Default)]
+        struct StoredGenetics {
+            store: IndividualStore<Vec<u8>>,
+        }
+
+        impl Genetics for StoredGenetics {
+            fn random_individual(&self, _: &mut rand::rngs::StdRng, _: usize) -> u64 {
+                self.store.insert(vec![])
+            }
+
+            fn mutate(&self, _: &mut rand::rngs::StdRng, individual: u64, _: usize) -> u64 {
+                let mut genome = (*self.store.get(individual).unwrap()).clone();
+                genome.push(1);
+                self.store.insert(genome)
+            }
+
+            fn crossover(&self, _: &mut rand::rngs::StdRng, a: u64, b: u64, _: usize) -> u64 {
+                let genome = [
+                    self.store.get(a).unwrap().as_slice(),
+                    &self.store.get(b).unwrap(),
+                ]
+                .concat();
+                self.store.insert(genome)
+            }
+
+            fn collect_garbage(&self, referenced: &HashSet<u64>) {
+                self.store.collect_garbage(referenced);
+            }
+        }
+
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(StoredGenetics::default())
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1);
+        world.add_island("a", Box::new(IdScoreEngine));
+        world.add_island("b", Box::new(IdScoreEngine));
+        let mut world = world.build().unwrap();
+        world.run_n_generations(5).unwrap();
+
+        let store = &world.genetic_engine().genetics().store;
+        let referenced = world.referenced_individuals();
+        assert!(referenced.len() <= 8);
+        assert_eq!(referenced.len(), store.len());
+        assert!(referenced.iter().all(|&id| store.contains(id)));
+    }
+}
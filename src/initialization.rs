@@ -0,0 +1,178 @@
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Creates up to the given number of individuals for the initial population of one island. See
+/// `WorldBuilder::with_island_initializer`.
+#[cfg(not(feature = "send"))]
+pub type IslandInitializer = Box<dyn FnMut(&mut StdRng, usize) -> Vec<u64>>;
+
+/// Creates up to the given number of individuals for the initial population of one island. See
+/// `WorldBuilder::with_island_initializer`.
+#[cfg(feature = "send")]
+pub type IslandInitializer = Box<dyn FnMut(&mut StdRng, usize) -> Vec<u64> + Send>;
+
+/// How the world creates the population of an island that has none, on the first generation and whenever the island
+/// has been cleared. Seed individuals are used first, then the island's initializer if it was given one with
+/// `WorldBuilder::with_island_initializer`, and the strategy creates whatever is still missing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Initialization {
+    /// Every individual is created by `Genetics::random_individual` with the engine's `max_individual_points`
+    #[default]
+    Random,
+
+    /// The size limit passed to `Genetics::random_individual` is ramped evenly up to the engine's
+    /// `max_individual_points` across the population, so that small and large trees are present from the start
+    Ramped,
+
+    /// The population is spread over the unit hypercube of this many dimensions by Latin hypercube sampling: along
+    /// every dimension, each of the N individuals lies in a different one of N equal intervals. Each point is turned
+    /// into an individual by `Genetics::individual_from_point`. Must have at least one dimension.
+    LatinHypercube(usize),
+}
+
+impl Initialization {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        *self != Initialization::LatinHypercube(0)
+    }
+}
+
+/// Returns the size limit of the individual at `index` of `count` when ramping up to `max_points`
+pub(crate) fn ramped_points(max_points: usize, index: usize, count: usize) -> usize {
+    (max_points * (index + 1) / count).max(1)
+}
+
+/// Returns `count` points in the unit hypercube of `dimensions` dimensions, each in a different interval of every
+/// dimension
+pub(crate) fn latin_hypercube(rng: &mut StdRng, count: usize, dimensions: usize) -> Vec<Vec<f64>> {
+    let mut points = vec![Vec::with_capacity(dimensions); count];
+    for _ in 0..dimensions {
+        let mut intervals: Vec<usize> = (0..count).collect();
+        intervals.shuffle(rng);
+        for (point, interval) in points.iter_mut().zip(intervals) {
+            point.push((interval as f64 + rng.random::<f64>()) / count as f64);
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn latin_hypercube_points_cover_every_interval() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let points = latin_hypercube(&mut rng, 5, 3);
+        assert_eq!(5, points.len());
+        for dimension in 0..3 {
+            let mut intervals: Vec<usize> = points
+                .iter()
+                .map(|point| (point[dimension] * 5.0) as usize)
+                .collect();
+            intervals.sort();
+            assert_eq!(vec![0, 1, 2, 3, 4], intervals);
+        }
+
+        assert_eq!(
+            vec![25, 50, 75, 100],
+            (0..4)
+                .map(|index| ramped_points(100, index, 4))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, ramped_points(2, 0, 4));
+    }
+
+    #[test]
+    fn initial_populations_follow_the_initialization() {
+        // Records the size limit of random individuals, and creates individuals from points with ids above 1000
+        #[derive(Default)]
+        struct SizedGenetics {
+            sizes: Mutex<Vec<usize>>,
+            points: Mutex<Vec<Vec<f64>>>,
+        }
+
+        impl Genetics for SizedGenetics {
+            fn random_individual(&self, _: &mut rand::rngs::StdRng, max_points: usize) -> u64 {
+                self.sizes.lock().unwrap().push(max_points);
+                max_points as u64
+            }
+
+            fn mutate(&self, _: &mut rand::rngs::StdRng, individual: u64, _: usize) -> u64 {
+                individual
+            }
+
+            fn crossover(&self, _: &mut rand::rngs::StdRng, a: u64, _: u64, _: usize) -> u64 {
+                a
+            }
+
+            fn individual_from_point(&self, point: &[f64]) -> Option<u64> {
+                self.points.lock().unwrap().push(point.to_vec());
+                Some(1000 + self.points.lock().unwrap().len() as u64)
+            }
+        }
+
+        let build = |initialization: Initialization| {
+            let engine = GeneticEngineBuilder::default()
+                .seed(1234)
+                .genetics(SizedGenetics::default())
+                .max_individual_points(100)
+                .build()
+                .unwrap();
+            let mut world = WorldBuilder::new()
+                .with_genetic_engine(engine)
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_initialization(initialization)
+                .with_seed_individuals("a", vec![7])
+                .with_island_initializer("b", |_, count| {
+                    assert_eq!(4, count);
+                    vec![500, 501]
+                });
+            world.add_island("a", Box::new(IdScoreEngine));
+            world.add_island("b", Box::new(IdScoreEngine));
+            let mut world = world.build().unwrap();
+            world.fill_all_islands().unwrap();
+            world
+        };
+
+        let world = build(Initialization::Ramped);
+        assert_eq!(
+            vec![7, 33, 66, 100],
+            world.get_island(0).unwrap().individuals()
+        );
+        assert_eq!(
+            vec![500, 501, 50, 100],
+            world.get_island(1).unwrap().individuals()
+        );
+
+        let world = build(Initialization::LatinHypercube(2));
+        let genetics = world.genetic_engine().genetics();
+        assert!(genetics.sizes.lock().unwrap().is_empty());
+        assert_eq!(
+            vec![7, 1001, 1002, 1003],
+            world.get_island(0).unwrap().individuals()
+        );
+        let mut intervals: Vec<usize> = genetics.points.lock().unwrap()[..3]
+            .iter()
+            .map(|point| (point[1] * 3.0) as usize)
+            .collect();
+        intervals.sort();
+        assert_eq!(vec![0, 1, 2], intervals);
+
+        let invalid = WorldBuilder::<CountingGenetics>::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_initialization(Initialization::LatinHypercube(0))
+            .build();
+        assert!(matches!(invalid, Err(GeneticError::InvalidInitialization)));
+    }
+}
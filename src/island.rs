@@ -1,21 +1,80 @@
-use crate::{IslandEngine, SelectionCurve};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::SeedableRng;
+
+use crate::noisy_evaluation::sample_variance;
+#[cfg(feature = "multi-threaded")]
+use crate::parallel_scoring::score_in_parallel;
+use crate::pareto::crowding_order;
+#[cfg(feature = "async")]
+use crate::rate_limit::Delay;
+use crate::rate_limit::TokenBucket;
+use crate::selection_curve::pick_by_score;
+use crate::{
+    Blackboard, EliteHandling, EmigrationThreshold, GeneticError, IslandEngine, IslandQuarantine,
+    IslandRole, IslandSnapshot, IslandStats, NoisyEvaluation, ScoreNormalization, ScratchArena,
+    ScratchStats, SelectionCurve, StochasticRanking,
+};
+#[cfg(feature = "serde")]
+use crate::{ExportedIndividual, Genetics, PopulationExport};
 
 pub struct Island {
     name: String,
+    role: IslandRole,
     engine: Box<dyn IslandEngine>,
     individuals: Vec<u64>,
     individuals_are_sorted: bool,
     future: Vec<u64>,
+    consecutive_failures: usize,
+    quarantine_remaining: usize,
+    scratch: ScratchArena,
+    scratch_stats: ScratchStats,
+    noisy_evaluation: Option<NoisyEvaluation>,
+    samples: HashMap<u64, Vec<u64>>,
+    elite_handling: EliteHandling,
+    // The scores elites of the current generation are ranked by, when the elite handling keeps them
+    pinned_scores: HashMap<u64, u64>,
+    evaluations_run: usize,
+    cached_evaluations: usize,
+    evaluation_retries: usize,
+    stochastic_ranking: Option<(StochasticRanking, StdRng)>,
+    // The bucket of the world's rate limit, shared by every island
+    rate_limiter: Option<Arc<Mutex<TokenBucket>>>,
+    rate_limit_wait: Duration,
 }
 
 impl Island {
-    pub(crate) fn new<S: Into<String>>(name: S, engine: Box<dyn IslandEngine>) -> Island {
+    pub(crate) fn new<S: Into<String>>(
+        name: S,
+        role: IslandRole,
+        engine: Box<dyn IslandEngine>,
+    ) -> Island {
         Island {
             name: name.into(),
+            role,
             engine,
             individuals: vec![],
             individuals_are_sorted: false,
             future: vec![],
+            consecutive_failures: 0,
+            quarantine_remaining: 0,
+            scratch: ScratchArena::new(),
+            scratch_stats: ScratchStats::default(),
+            noisy_evaluation: None,
+            samples: HashMap::new(),
+            elite_handling: EliteHandling::default(),
+            pinned_scores: HashMap::new(),
+            evaluations_run: 0,
+            cached_evaluations: 0,
+            evaluation_retries: 0,
+            stochastic_ranking: None,
+            rate_limiter: None,
+            rate_limit_wait: Duration::ZERO,
         }
     }
 
@@ -24,15 +83,74 @@ impl Island {
         &self.name
     }
 
+    /// Returns the role of the island
+    pub fn role(&self) -> IslandRole {
+        self.role
+    }
+
+    /// Returns true if the island has been quarantined after repeated evaluation failures and is being skipped
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantine_remaining > 0
+    }
+
+    /// Returns a copy of the current and future generations, suitable for assertions and observers
+    pub fn snapshot(&self) -> IslandSnapshot {
+        IslandSnapshot {
+            name: self.name.clone(),
+            individuals: self.individuals.clone(),
+            sorted: self.individuals_are_sorted,
+            future: self.future.clone(),
+        }
+    }
+
+    /// Writes the current generation as a JSON `PopulationExport`, with each genome encoded by the genetics
+    #[cfg(feature = "serde")]
+    pub fn export_population<G: Genetics>(&self, genetics: &G) -> String {
+        let export = PopulationExport {
+            island: self.name.clone(),
+            sorted: self.individuals_are_sorted,
+            individuals: self
+                .individuals
+                .iter()
+                .map(|&id| ExportedIndividual {
+                    id,
+                    score: self.individuals_are_sorted.then(|| self.score(id)),
+                    genome: genetics.encode(id),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string(&export).expect("a population export is always valid JSON")
+    }
+
+    /// Replaces the current and future generations with the ones in the snapshot
+    pub(crate) fn restore(&mut self, snapshot: &IslandSnapshot) {
+        self.individuals = snapshot.individuals.clone();
+        self.individuals_are_sorted = snapshot.sorted;
+        self.future = snapshot.future.clone();
+        self.samples.clear();
+        self.pinned_scores.clear();
+    }
+
     /// Resets the island to it's 'new' state.
     pub fn clear(&mut self) {
         self.individuals.clear();
         self.individuals_are_sorted = false;
         self.future.clear();
+        self.samples.clear();
+        self.pinned_scores.clear();
+    }
+
+    /// Removes all individuals and forgets the evaluation failures that could quarantine the island
+    pub(crate) fn restart(&mut self) {
+        self.clear();
+        self.consecutive_failures = 0;
+        self.quarantine_remaining = 0;
     }
 
     /// Returns the most fit of all the individuals (the one sorted to the tail by the sorting algorithm). Returns None
     /// if there are no Individuals or if the individuals have not been sorted
+    #[allow(clippy::map_clone)]
     pub fn most_fit_individual(&self) -> Option<u64> {
         if !self.individuals_are_sorted {
             return None;
@@ -42,6 +160,7 @@ impl Island {
 
     /// Returns the least fit of all the individuals (the one sorted to the head by the sorting algorithm). Returns None
     /// if there are no Individuals or if the individuals have not been sorted
+    #[allow(clippy::map_clone)]
     pub fn least_fit_individual(&self) -> Option<u64> {
         if !self.individuals_are_sorted {
             return None;
@@ -49,61 +168,556 @@ impl Island {
         self.individuals.first().map(|x| *x)
     }
 
+    /// Borrows the individuals of the current generation
+    pub(crate) fn individuals(&self) -> &[u64] {
+        &self.individuals
+    }
+
+    /// Borrows the individuals added to the next generation so far
+    pub(crate) fn future_individuals(&self) -> &[u64] {
+        &self.future
+    }
+
     /// Returns one individual by index, or None if the index is out of range
+    #[allow(clippy::map_clone)]
     pub fn get_one_individual(&self, index: usize) -> Option<u64> {
         self.individuals.get(index).map(|x| *x)
     }
 
     /// Uses the specified VM to run one generation of individuals. Calls all of the user-supplied functions from the
-    /// `Island` trait.
+    /// `Island` trait. Returns an error if the engine fails to evaluate an individual.
     #[cfg(not(feature = "async"))]
-    pub fn run_one_generation(&mut self) {
+    pub fn run_one_generation(&mut self) -> Result<(), GeneticError> {
+        self.evaluate_generation()?;
+        self.complete_evaluation();
+        Ok(())
+    }
+
+    /// Runs the current generation without sorting it
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn evaluate_generation(&mut self) -> Result<(), GeneticError> {
         // Allow the island to set up for all runs
         self.engine.pre_generation_run(&self.individuals);
 
         // Run each individual
-        for &id in &self.individuals[..] {
-            self.engine.run_individual(id);
-        }
+        self.run_individuals()?;
 
         // Allow the island to before any cleanup or group analysis tasks
         self.engine.post_generation_run(&self.individuals);
-
-        // Sort the individuals
-        self.sort_individuals();
+        Ok(())
     }
 
     /// Uses the specified VM to run one generation of individuals. Calls all of the user-supplied functions from the
-    /// `Island` trait.
+    /// `Island` trait. Returns an error if the engine fails to evaluate an individual.
+    #[cfg(feature = "async")]
+    pub async fn run_one_generation(&mut self) -> Result<(), GeneticError> {
+        self.evaluate_generation().await?;
+        self.complete_evaluation();
+        Ok(())
+    }
+
+    /// Runs the current generation without sorting it
     #[cfg(feature = "async")]
-    pub async fn run_one_generation(&mut self) {
+    pub(crate) async fn evaluate_generation(&mut self) -> Result<(), GeneticError> {
         // Allow the island to set up for all runs
-        self.engine.pre_generation_run(&self.individuals).await;
+        self.engine.pre_generation_run(&self.individuals);
 
         // Run each individual
-        for &id in &self.individuals[..] {
-            self.engine.run_individual(id);
-        }
+        self.run_individuals().await?;
 
         // Allow the island to before any cleanup or group analysis tasks
-        self.engine.post_generation_run(&self.individuals).await;
+        self.engine.post_generation_run(&self.individuals);
+        Ok(())
+    }
 
-        // Sort the individuals
+    /// Sorts the individuals after a successful evaluation
+    pub(crate) fn complete_evaluation(&mut self) {
         self.sort_individuals();
+        self.consecutive_failures = 0;
+    }
+
+    // Runs every individual of the current generation, stopping at the first failure. With noisy evaluation, each
+    // individual is run and scored several times.
+    #[cfg(not(feature = "async"))]
+    fn run_individuals(&mut self) -> Result<(), GeneticError> {
+        let mut result = Ok(());
+        for id in self.start_runs() {
+            result = self.evaluate(id);
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.resample_ambiguous();
+        }
+        self.scratch_stats = self.scratch.reset();
+
+        result
+    }
+
+    // Runs every individual of the current generation, stopping at the first failure. With noisy evaluation, each
+    // individual is run and scored several times.
+    #[cfg(feature = "async")]
+    async fn run_individuals(&mut self) -> Result<(), GeneticError> {
+        let mut result = Ok(());
+        for id in self.start_runs() {
+            result = self.evaluate(id).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.resample_ambiguous().await;
+        }
+        self.scratch_stats = self.scratch.reset();
+
+        result
+    }
+
+    // Resets the counters of the generation and returns the individuals to run, in order. Individuals whose score is
+    // cached are skipped, and with noisy evaluation every other individual is listed once for each of its runs.
+    fn start_runs(&mut self) -> Vec<u64> {
+        if let Some(noisy) = &self.noisy_evaluation {
+            if noisy.resample_elites {
+                self.samples.clear();
+            } else {
+                let individuals: HashSet<u64> = self.individuals.iter().copied().collect();
+                self.samples.retain(|id, _| individuals.contains(id));
+            }
+        }
+
+        let evaluations = self.evaluations_per_individual();
+        self.evaluations_run = 0;
+        self.cached_evaluations = 0;
+        self.rate_limit_wait = Duration::ZERO;
+        let mut runs = Vec::with_capacity(self.individuals.len() * evaluations);
+        for &id in self.individuals.iter() {
+            if self.elite_handling == EliteHandling::CopyWithCachedScore
+                && self.pinned_scores.contains_key(&id)
+            {
+                self.cached_evaluations += 1;
+                continue;
+            }
+            if self.noisy_evaluation.is_some() && self.samples.contains_key(&id) {
+                self.cached_evaluations += 1;
+                continue;
+            }
+            runs.extend(std::iter::repeat_n(id, evaluations));
+        }
+
+        runs
+    }
+
+    // The number of times each individual is run and scored
+    fn evaluations_per_individual(&self) -> usize {
+        self.noisy_evaluation
+            .as_ref()
+            .map_or(1, |noisy| noisy.evaluations)
+    }
+
+    // Runs one individual once, recording its score as a sample with noisy evaluation
+    #[cfg(not(feature = "async"))]
+    fn evaluate(&mut self, id: u64) -> Result<(), GeneticError> {
+        let wait = self.wait_for_rate_limit();
+        self.evaluate_after_wait(id, wait)
+    }
+
+    // Runs one individual once, recording its score as a sample with noisy evaluation
+    #[cfg(feature = "async")]
+    async fn evaluate(&mut self, id: u64) -> Result<(), GeneticError> {
+        let wait = self.wait_for_rate_limit().await;
+        self.evaluate_after_wait(id, wait)
+    }
+
+    // Completes `evaluate` once the individual's turn with the rate limiter has come
+    fn evaluate_after_wait(&mut self, id: u64, wait: Duration) -> Result<(), GeneticError> {
+        self.evaluations_run += 1;
+        self.rate_limit_wait += wait;
+        self.run_once(id)?;
+        if self.noisy_evaluation.is_some() {
+            let score = self.engine.score_individual(id);
+            self.samples.entry(id).or_default().push(score);
+        }
+
+        Ok(())
+    }
+
+    // Takes a token from the rate limiter, if there is one, and sleeps until it may be used. This blocks the thread
+    // that evaluates the island. Returns how long it waited.
+    #[cfg(not(feature = "async"))]
+    fn wait_for_rate_limit(&self) -> Duration {
+        let wait = self.reserve_run();
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+        wait
+    }
+
+    // Takes a token from the rate limiter, if there is one, and waits until it may be used without blocking the
+    // executor. Returns how long it waited.
+    #[cfg(feature = "async")]
+    async fn wait_for_rate_limit(&self) -> Duration {
+        let wait = self.reserve_run();
+        if !wait.is_zero() {
+            Delay::new(wait).await;
+        }
+        wait
+    }
+
+    // Takes a token from the rate limiter and returns how long to wait before using it
+    fn reserve_run(&self) -> Duration {
+        // The bucket is not held while waiting, so that other islands can reserve their tokens
+        self.rate_limiter
+            .as_ref()
+            .map_or(Duration::ZERO, |rate_limiter| {
+                rate_limiter.lock().unwrap().acquire()
+            })
+    }
+
+    // Runs one individual once through the engine
+    fn run_once(&mut self, id: u64) -> Result<(), GeneticError> {
+        self.engine
+            .run_individual_with_scratch(id, &mut self.scratch)
+            .map_err(|message| GeneticError::EvaluationFailed {
+                island: self.name.clone(),
+                id,
+                message,
+            })
+    }
+
+    // With adaptive resampling, keeps sampling neighbors in the ranking whose order is still ambiguous
+    #[cfg(not(feature = "async"))]
+    fn resample_ambiguous(&mut self) -> Result<(), GeneticError> {
+        loop {
+            let resample = self.ambiguous_individuals();
+            if resample.is_empty() {
+                return Ok(());
+            }
+            for id in resample {
+                self.evaluate(id)?;
+            }
+        }
+    }
+
+    // With adaptive resampling, keeps sampling neighbors in the ranking whose order is still ambiguous
+    #[cfg(feature = "async")]
+    async fn resample_ambiguous(&mut self) -> Result<(), GeneticError> {
+        loop {
+            let resample = self.ambiguous_individuals();
+            if resample.is_empty() {
+                return Ok(());
+            }
+            for id in resample {
+                self.evaluate(id).await?;
+            }
+        }
+    }
+
+    // With adaptive resampling, returns the individuals to sample again because their order against a neighbor in the
+    // ranking is still ambiguous
+    fn ambiguous_individuals(&self) -> HashSet<u64> {
+        let Some(adaptive) = self
+            .noisy_evaluation
+            .as_ref()
+            .and_then(|noisy| noisy.adaptive.as_ref())
+        else {
+            return HashSet::new();
+        };
+
+        // Pinned elites are ranked by their pinned score, so more samples would not change their place
+        let mut ranked: Vec<u64> = self
+            .samples
+            .keys()
+            .filter(|id| !self.pinned_scores.contains_key(id))
+            .copied()
+            .collect();
+        ranked.sort_by_key(|&id| (self.score(id), id));
+
+        let mut resample = HashSet::new();
+        for pair in ranked.windows(2) {
+            let (a, b) = (&self.samples[&pair[0]], &self.samples[&pair[1]]);
+            if !adaptive.is_ambiguous(a, b) {
+                continue;
+            }
+            let fewer = if a.len() <= b.len() { pair[0] } else { pair[1] };
+            if self.samples[&fewer].len() < adaptive.max_evaluations {
+                resample.insert(fewer);
+            }
+        }
+
+        resample
+    }
+
+    /// Returns a copy of the samples kept for noisy evaluation, so that they can be restored if the evaluation of the
+    /// generation has to be retried
+    pub(crate) fn samples(&self) -> HashMap<u64, Vec<u64>> {
+        self.samples.clone()
+    }
+
+    /// Discards the samples added by a failed evaluation
+    pub(crate) fn restore_samples(&mut self, samples: HashMap<u64, Vec<u64>>) {
+        self.samples = samples;
+    }
+
+    /// Returns true if the engine considers the evaluation failure worth retrying
+    pub(crate) fn is_transient_failure(&self, error: &GeneticError) -> bool {
+        match error {
+            GeneticError::EvaluationFailed { message, .. } => {
+                self.engine.is_transient_failure(message)
+            }
+            _ => false,
+        }
+    }
+
+    /// Records how many times the current generation was evaluated again, for the statistics
+    pub(crate) fn set_evaluation_retries(&mut self, retries: usize) {
+        self.evaluation_retries = retries;
+    }
+
+    /// Enables evaluating every individual several times, or disables it with None
+    pub(crate) fn set_noisy_evaluation(&mut self, noisy_evaluation: Option<NoisyEvaluation>) {
+        self.noisy_evaluation = noisy_evaluation;
+        self.samples.clear();
+    }
+
+    /// Sets how the elites copied into the island are scored
+    pub(crate) fn set_elite_handling(&mut self, elite_handling: EliteHandling) {
+        self.elite_handling = elite_handling;
+    }
+
+    /// Keeps the scores of elites that have just joined the current generation, if the elite handling ranks them by
+    /// the score they had when they were selected
+    pub(crate) fn pin_scores(&mut self, scores: Vec<(u64, u64)>) {
+        if self.elite_handling.keeps_score() {
+            self.pinned_scores.extend(scores);
+        }
+    }
+
+    // The score the island ranks an individual by: the pinned score of an elite, the aggregate of its samples with
+    // noisy evaluation, otherwise the engine's score
+    pub(crate) fn score(&self, id: u64) -> u64 {
+        if let Some(&score) = self.pinned_scores.get(&id) {
+            return score;
+        }
+        match (&self.noisy_evaluation, self.samples.get(&id)) {
+            (Some(noisy), Some(samples)) if !samples.is_empty() => {
+                noisy.aggregation.aggregate(samples)
+            }
+            _ => self.engine.score_individual(id),
+        }
+    }
+
+    /// Runs individuals that are not part of the population through the engine, and returns their scores in the same
+    /// order. Each individual is run the way a member of the generation would be, including the rate limiter and noisy
+    /// evaluation, but the generation hooks of the engine are not called and the statistics of the generation are not
+    /// changed. Returns an error if the engine fails to evaluate an individual.
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn evaluate_outside_generation(
+        &mut self,
+        individuals: &[u64],
+    ) -> Result<Vec<u64>, GeneticError> {
+        let mut scores = Vec::with_capacity(individuals.len());
+        let mut result = Ok(());
+        'individuals: for &id in individuals {
+            let mut samples = vec![];
+            for _ in 0..self.evaluations_per_individual() {
+                self.wait_for_rate_limit();
+                result = self.run_once(id);
+                if result.is_err() {
+                    break 'individuals;
+                }
+                samples.push(self.engine.score_individual(id));
+            }
+            scores.push(self.aggregate_samples(&samples));
+        }
+        self.scratch.reset();
+
+        result.map(|()| scores)
+    }
+
+    /// Runs individuals that are not part of the population through the engine, and returns their scores in the same
+    /// order. Each individual is run the way a member of the generation would be, including the rate limiter and noisy
+    /// evaluation, but the generation hooks of the engine are not called and the statistics of the generation are not
+    /// changed. Returns an error if the engine fails to evaluate an individual.
+    #[cfg(feature = "async")]
+    pub(crate) async fn evaluate_outside_generation(
+        &mut self,
+        individuals: &[u64],
+    ) -> Result<Vec<u64>, GeneticError> {
+        let mut scores = Vec::with_capacity(individuals.len());
+        let mut result = Ok(());
+        'individuals: for &id in individuals {
+            let mut samples = vec![];
+            for _ in 0..self.evaluations_per_individual() {
+                self.wait_for_rate_limit().await;
+                result = self.run_once(id);
+                if result.is_err() {
+                    break 'individuals;
+                }
+                samples.push(self.engine.score_individual(id));
+            }
+            scores.push(self.aggregate_samples(&samples));
+        }
+        self.scratch.reset();
+
+        result.map(|()| scores)
+    }
+
+    // The score of an individual evaluated outside the generation: the aggregate of its samples with noisy evaluation,
+    // otherwise its only sample
+    fn aggregate_samples(&self, samples: &[u64]) -> u64 {
+        match &self.noisy_evaluation {
+            Some(noisy) => noisy.aggregation.aggregate(samples),
+            None => samples[0],
+        }
     }
 
     /// Sorts the individuals by calling the sorter function.
     pub fn sort_individuals(&mut self) {
-        self.individuals
-            .sort_by(|a, b| self.engine.sort_individuals(*a, *b));
+        let mut individuals = std::mem::take(&mut self.individuals);
+        let stochastic_ranking = self.stochastic_ranking.take();
+        let scores = self.generation_scores(&individuals);
+        let compare = |a: u64, b: u64| match &scores {
+            Some(scores) => scores[&a].cmp(&scores[&b]),
+            None => self.compare(a, b),
+        };
+        if let Some((ranking, mut rng)) = stochastic_ranking {
+            let mut violations: Vec<f64> = individuals
+                .iter()
+                .map(|&id| self.engine.constraint_violation(id))
+                .collect();
+            ranking.rank(&mut rng, &mut individuals, &mut violations, compare);
+            self.stochastic_ranking = Some((ranking, rng));
+        } else {
+            individuals.sort_by(|&a, &b| compare(a, b));
+        }
+        self.individuals = individuals;
         self.individuals_are_sorted = true;
     }
 
+    // The scores the engine computed for the whole generation at once, if it does that, or that were computed in
+    // parallel for an engine that ranks by score
+    fn generation_scores(&self, individuals: &[u64]) -> Option<HashMap<u64, u64>> {
+        if self.noisy_evaluation.is_some() {
+            return None;
+        }
+        let scores = match self.engine.score_generation(individuals) {
+            Some(scores) => scores,
+            None => self.parallel_scores(individuals)?,
+        };
+        (scores.len() == individuals.len()).then(|| {
+            let mut scores: HashMap<u64, u64> = individuals.iter().copied().zip(scores).collect();
+            scores.extend(&self.pinned_scores);
+            scores
+        })
+    }
+
+    // Scores every individual on the threads of the rayon pool if the engine ranks by score
+    #[cfg(feature = "multi-threaded")]
+    fn parallel_scores(&self, individuals: &[u64]) -> Option<Vec<u64>> {
+        self.engine
+            .ranks_by_score()
+            .then(|| score_in_parallel(self.engine.as_ref(), individuals))
+    }
+
+    // Without the `multi-threaded` feature, engines that rank by score are compared pairwise
+    #[cfg(not(feature = "multi-threaded"))]
+    fn parallel_scores(&self, _individuals: &[u64]) -> Option<Vec<u64>> {
+        None
+    }
+
+    // Orders two individuals by fitness: by their aggregated score with noisy evaluation, otherwise by the engine
+    fn compare(&self, a: u64, b: u64) -> Ordering {
+        let pinned = self.pinned_scores.contains_key(&a) || self.pinned_scores.contains_key(&b);
+        if self.noisy_evaluation.is_some() || pinned {
+            self.score(a).cmp(&self.score(b))
+        } else {
+            self.engine.sort_individuals(a, b)
+        }
+    }
+
+    /// Limits how often the island runs individuals by the bucket of the world's `RateLimit`
+    pub(crate) fn set_rate_limiter(&mut self, rate_limiter: Option<Arc<Mutex<TokenBucket>>>) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    /// Ranks the island by stochastic ranking, or by the usual sort with None
+    pub(crate) fn set_stochastic_ranking(&mut self, ranking: Option<StochasticRanking>) {
+        self.stochastic_ranking = ranking.map(|ranking| (ranking, StdRng::seed_from_u64(0)));
+    }
+
+    /// Restarts the random comparisons of stochastic ranking from the seed, so that they follow the generation
+    pub(crate) fn reseed_stochastic_ranking(&mut self, seed: u64) {
+        if let Some((_, rng)) = &mut self.stochastic_ranking {
+            *rng = StdRng::seed_from_u64(seed);
+        }
+    }
+
+    /// Returns true if the current generation has been sorted since it became the current generation
+    pub fn is_sorted(&self) -> bool {
+        self.individuals_are_sorted
+    }
+
+    /// Calculates the score statistics of the current generation
+    pub(crate) fn stats(&self) -> IslandStats {
+        let scores: Vec<u64> = self.individuals.iter().map(|&id| self.score(id)).collect();
+        if scores.is_empty() {
+            return IslandStats {
+                scratch: self.scratch_stats,
+                evaluations: self.evaluations_run,
+                cached_evaluations: self.cached_evaluations,
+                evaluation_retries: self.evaluation_retries,
+                rate_limit_wait: self.rate_limit_wait,
+                ..IslandStats::default()
+            };
+        }
+
+        IslandStats {
+            best_score: scores.iter().max().copied(),
+            best_validation_score: self
+                .most_fit_individual()
+                .and_then(|id| self.engine.validation_score(id)),
+            worst_score: scores.iter().min().copied(),
+            mean_score: Some(
+                scores.iter().map(|&score| score as f64).sum::<f64>() / scores.len() as f64,
+            ),
+            elites: vec![],
+            distinct_parents: 0,
+            distinct_genomes: 0,
+            scratch: self.scratch_stats,
+            mean_score_variance: self.mean_score_variance(),
+            evaluations: self.evaluations_run,
+            cached_evaluations: self.cached_evaluations,
+            evaluation_retries: self.evaluation_retries,
+            rate_limit_wait: self.rate_limit_wait,
+        }
+    }
+
+    // The mean variance of the samples of each individual in the current generation, if they were sampled repeatedly
+    fn mean_score_variance(&self) -> Option<f64> {
+        let variances: Vec<f64> = self
+            .individuals
+            .iter()
+            .filter_map(|id| self.samples.get(id))
+            .filter_map(|samples| sample_variance(samples))
+            .collect();
+        if variances.is_empty() {
+            return None;
+        }
+
+        Some(variances.iter().sum::<f64>() / variances.len() as f64)
+    }
+
     /// Returns the current number of individuals on the island.
     pub fn len(&self) -> usize {
         self.individuals.len()
     }
 
+    /// Returns true if there are no individuals in the current generation.
+    pub fn is_empty(&self) -> bool {
+        self.individuals.is_empty()
+    }
+
     /// Returns the number of individuals in the next generation
     pub fn len_future_generation(&self) -> usize {
         self.future.len()
@@ -113,11 +727,13 @@ impl Island {
     pub fn advance_generation(&mut self) {
         self.individuals.clear();
         self.individuals_are_sorted = false;
+        self.pinned_scores.clear();
         std::mem::swap(&mut self.individuals, &mut self.future);
     }
 
     /// Select one individual from the island according to the specified SelectionCurve and borrow it.
     /// Returns the individual borrowed or None if the population is zero or not sorted
+    #[allow(clippy::map_clone)]
     pub fn select_one_individual<Rnd: rand::Rng>(
         &self,
         curve: SelectionCurve,
@@ -132,7 +748,7 @@ impl Island {
             None
         } else {
             self.individuals
-                .get(curve.pick_one_index(rng, max))
+                .get(self.pick_in_range(curve, rng, 0..max))
                 .map(|x| *x)
         }
     }
@@ -152,7 +768,192 @@ impl Island {
         if max == 0 {
             None
         } else {
-            Some(self.individuals.remove(curve.pick_one_index(rng, max)))
+            let pick = self.pick_in_range(curve, rng, 0..max);
+            Some(self.individuals.remove(pick))
+        }
+    }
+
+    /// Picks an individual from the range of indexes of the sorted current generation according to the curve, and
+    /// returns its index relative to the start of the range. The range must not be empty.
+    pub(crate) fn pick_in_range<Rnd: rand::Rng>(
+        &self,
+        curve: SelectionCurve,
+        rng: &mut Rnd,
+        range: Range<usize>,
+    ) -> usize {
+        match curve {
+            SelectionCurve::FitnessProportional => {
+                let scores: Vec<u64> = self.individuals[range]
+                    .iter()
+                    .map(|&id| self.score(id))
+                    .collect();
+                pick_by_score(rng, &scores)
+            }
+            _ => curve.pick_one_index(rng, range.len()),
+        }
+    }
+
+    /// Returns the indexes of the sorted individuals that may emigrate: those that pass the threshold, apart from the
+    /// `protected` most fit. Empty if the individuals have not been sorted.
+    pub(crate) fn emigrant_range(
+        &self,
+        threshold: Option<EmigrationThreshold>,
+        protected: usize,
+    ) -> Range<usize> {
+        if !self.individuals_are_sorted {
+            return 0..0;
+        }
+
+        let end = self.individuals.len().saturating_sub(protected);
+        let start = threshold.map_or(0, |threshold| {
+            let scores: Vec<u64> = self.individuals.iter().map(|&id| self.score(id)).collect();
+            threshold.first_eligible(&scores)
+        });
+        start.min(end)..end
+    }
+
+    /// Moves the current generation into the future generation behind any individuals already there, keeping only
+    /// one copy of each individual. The most fit individuals are moved first so that, if the future generation is
+    /// longer than `capacity`, the least fit are the ones dropped.
+    pub(crate) fn carry_forward(&mut self, capacity: usize) {
+        let mut unique = Vec::with_capacity(self.future.len());
+        for id in self.future.drain(..) {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        self.future = unique;
+
+        while let Some(id) = self.individuals.pop() {
+            if self.future.len() >= capacity {
+                break;
+            }
+            if !self.future.contains(&id) {
+                self.future.push(id);
+            }
+        }
+        self.individuals.clear();
+    }
+
+    /// Picks the sibling with the highest quick score. Returns None if the engine cannot quick score every sibling.
+    pub(crate) fn pre_screen(&mut self, brood: &[u64]) -> Option<u64> {
+        let mut best: Option<(u64, u64)> = None;
+        for &id in brood {
+            let score = self.engine.quick_score(id)?;
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((id, score));
+            }
+        }
+
+        best.map(|(id, _)| id)
+    }
+
+    /// Removes every sibling but the most fit from each brood in the current generation. The generation must be sorted.
+    pub(crate) fn keep_best_of_broods(&mut self, broods: &[Vec<u64>]) {
+        let mut losers: HashSet<u64> = HashSet::new();
+        for brood in broods {
+            let best = brood
+                .iter()
+                .filter_map(|id| self.individuals.iter().rposition(|other| other == id))
+                .max()
+                .map(|position| self.individuals[position]);
+            losers.extend(brood.iter().filter(|&&id| Some(id) != best));
+        }
+
+        self.individuals.retain(|id| !losers.contains(id));
+    }
+
+    /// Swaps in a new engine and returns the old one. The individuals keep their places, and the ranking of the current
+    /// generation stays as the old engine sorted it until the next generation is run.
+    pub(crate) fn replace_engine(
+        &mut self,
+        engine: Box<dyn IslandEngine>,
+    ) -> Box<dyn IslandEngine> {
+        self.samples.clear();
+        self.pinned_scores.clear();
+        std::mem::replace(&mut self.engine, engine)
+    }
+
+    /// Creates an island with a copy of this island's role, noise handling and population, evaluated by another engine
+    pub(crate) fn branch<S: Into<String>>(&self, name: S, engine: Box<dyn IslandEngine>) -> Island {
+        let mut island = Island::new(name, self.role, engine);
+        island.set_noisy_evaluation(self.noisy_evaluation.clone());
+        island.set_elite_handling(self.elite_handling);
+        island.set_rate_limiter(self.rate_limiter.clone());
+        island.individuals = self.individuals.clone();
+        island.individuals_are_sorted = self.individuals_are_sorted;
+        island.future = self.future.clone();
+        island
+    }
+
+    /// Tells the engine that the world has entered a new curriculum stage
+    pub(crate) fn curriculum_stage_started(&mut self, stage: usize, name: &str) {
+        self.engine.curriculum_stage_started(stage, name);
+    }
+
+    /// Lets the engine read the data shared by every island
+    pub(crate) fn read_blackboard(&mut self, blackboard: &Blackboard) {
+        self.engine.read_blackboard(blackboard);
+    }
+
+    /// Tells the engine which hall of fame members to evaluate its individuals against
+    pub(crate) fn read_opponents(&mut self, opponents: &[u64]) {
+        self.engine.read_opponents(opponents);
+    }
+
+    /// Lets the engine publish data for every island
+    pub(crate) fn write_blackboard(&mut self, blackboard: &mut Blackboard) {
+        self.engine.write_blackboard(blackboard);
+    }
+
+    /// Discards the current generation after an evaluation failure. Returns true if the failure places the island in
+    /// quarantine.
+    pub(crate) fn record_failure(&mut self, policy: &IslandQuarantine) -> bool {
+        self.clear();
+        self.consecutive_failures += 1;
+        if self.consecutive_failures < policy.failures_before_quarantine {
+            return false;
+        }
+
+        self.consecutive_failures = 0;
+        self.quarantine_remaining = policy.quarantine_generations;
+        true
+    }
+
+    /// Skips one generation of a quarantined island. Returns true if the quarantine is now over.
+    pub(crate) fn serve_quarantine(&mut self) -> bool {
+        self.quarantine_remaining = self.quarantine_remaining.saturating_sub(1);
+        self.quarantine_remaining == 0
+    }
+
+    /// Orders the current generation by Pareto front and crowding distance, most preferred last. Returns None if the
+    /// engine does not report objectives.
+    pub(crate) fn crowding_order(&self) -> Option<Vec<u64>> {
+        let objectives: Vec<Vec<f64>> = self
+            .individuals
+            .iter()
+            .map(|&id| self.engine.objectives(id))
+            .collect();
+        if objectives.iter().all(|objectives| objectives.is_empty()) {
+            return None;
+        }
+
+        let mut order: Vec<u64> = crowding_order(&objectives)
+            .into_iter()
+            .map(|index| self.individuals[index])
+            .collect();
+        order.reverse();
+        Some(order)
+    }
+
+    /// Removes an individual from the current generation. Returns false if it was not found.
+    pub(crate) fn remove_individual(&mut self, id: u64) -> bool {
+        match self.individuals.iter().position(|&other| other == id) {
+            Some(index) => {
+                self.individuals.remove(index);
+                true
+            }
+            None => false,
         }
     }
 
@@ -161,12 +962,160 @@ impl Island {
         self.future.push(id);
     }
 
-    /// Returns the score for the individual specified by index, or None if the index is out of bounds
+    /// Places the individual's score on the scale shared with other islands. The individual does not need to be part
+    /// of the current generation, as long as it was evaluated by this island.
+    pub(crate) fn normalized_score(&self, id: u64, normalization: ScoreNormalization) -> f64 {
+        let score = self.score(id);
+        match normalization {
+            ScoreNormalization::Raw => score as f64,
+            ScoreNormalization::Rank => {
+                let others: Vec<u64> = self
+                    .individuals
+                    .iter()
+                    .filter(|&&other| other != id)
+                    .map(|&other| self.score(other))
+                    .collect();
+                if others.is_empty() {
+                    return 1.0;
+                }
+                let below = others.iter().filter(|&&other| other < score).count();
+                below as f64 / others.len() as f64
+            }
+            ScoreNormalization::MinMax => {
+                let scores = self.individuals.iter().map(|&other| self.score(other));
+                let worst = scores.clone().min().unwrap_or(score).min(score);
+                let best = scores.max().unwrap_or(score).max(score);
+                if best == worst {
+                    return 1.0;
+                }
+                (score - worst) as f64 / (best - worst) as f64
+            }
+            ScoreNormalization::Engine => self.engine.normalized_score(id).unwrap_or(score as f64),
+        }
+    }
+
+    pub(crate) fn validation_score(&self, id: u64) -> Option<u64> {
+        self.engine.validation_score(id)
+    }
+
+    /// Returns the score of an individual in the current generation, or None if it is not on the island. Unlike an
+    /// index, an id stays valid across sorting, so an id from an earlier generation can be looked up as long as the
+    /// individual is still on the island.
+    pub fn score_of(&self, id: u64) -> Option<u64> {
+        self.contains(id).then(|| self.score(id))
+    }
+
+    /// Returns the position of an individual in the sorted current generation, zero being the least fit, or None if
+    /// it is not on the island or the generation has not been sorted
+    pub fn rank_of(&self, id: u64) -> Option<usize> {
+        if !self.individuals_are_sorted {
+            return None;
+        }
+        self.individuals.iter().position(|&other| other == id)
+    }
+
+    /// Returns true if the individual is in the current generation
+    pub fn contains(&self, id: u64) -> bool {
+        self.individuals.contains(&id)
+    }
+
+    /// Returns the score for the individual specified by index, or None if the index is out of bounds. Prefer
+    /// `score_of` when holding an id, as indices change every time the island is sorted.
     pub fn score_for_individual(&self, index: usize) -> Option<u64> {
-        if let Some(individual) = self.get_one_individual(index) {
-            Some(self.engine.score_individual(individual))
-        } else {
-            None
+        self.get_one_individual(index)
+            .map(|individual| self.score(individual))
+    }
+
+    /// Returns true if the individual has already been added to the future generation
+    #[cfg(feature = "strict-checks")]
+    pub(crate) fn future_generation_contains(&self, id: u64) -> bool {
+        self.future.contains(&id)
+    }
+
+    /// Verifies that the current generation holds exactly `expected` individuals.
+    #[cfg(feature = "strict-checks")]
+    pub(crate) fn check_population(&self, expected: usize) -> Result<(), GeneticError> {
+        if self.individuals.len() != expected {
+            return Err(GeneticError::PopulationMismatch {
+                island: self.name.clone(),
+                expected,
+                actual: self.individuals.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the sort order agrees with `score_individual`: the score of each individual must be no greater
+    /// than the score of the individual sorted after it. Only checked when the island ranks by score, either its own
+    /// aggregate with noisy evaluation or an engine whose `IslandEngine::ranks_by_score` is true, and never with
+    /// stochastic ranking, which puts individuals out of score order on purpose.
+    #[cfg(feature = "strict-checks")]
+    pub(crate) fn check_sorted_scores(&self) -> Result<(), GeneticError> {
+        if self.stochastic_ranking.is_some()
+            || (self.noisy_evaluation.is_none() && !self.engine.ranks_by_score())
+        {
+            return Ok(());
         }
+        let scores: Vec<u64> = self.individuals.iter().map(|&id| self.score(id)).collect();
+        for (index, pair) in scores.windows(2).enumerate() {
+            if pair[0] > pair[1] {
+                return Err(GeneticError::ScoreOrderViolation {
+                    island: self.name.clone(),
+                    index,
+                    score: pair[0],
+                    next_index: index + 1,
+                    next_score: pair[1],
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "strict-checks", not(feature = "async")))]
+mod tests {
+    use super::*;
+
+    // Sorts by id, while the score runs the other way
+    struct IdOrderEngine {
+        ranks_by_score: bool,
+    }
+
+    impl IslandEngine for IdOrderEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn sort_individuals(&self, a: u64, b: u64) -> Ordering {
+            a.cmp(&b)
+        }
+
+        fn score_individual(&self, id: u64) -> u64 {
+            100 - id
+        }
+
+        fn ranks_by_score(&self) -> bool {
+            self.ranks_by_score
+        }
+    }
+
+    #[test]
+    fn score_order_is_only_checked_for_engines_that_rank_by_score() {
+        let check = |ranks_by_score: bool| {
+            let engine = IdOrderEngine { ranks_by_score };
+            let mut island = Island::new("a", IslandRole::Standard, Box::new(engine));
+            for id in 0..5 {
+                island.add_individual_to_future_generation(id);
+            }
+            island.advance_generation();
+            island.run_one_generation().unwrap();
+            island.check_sorted_scores()
+        };
+
+        assert!(check(false).is_ok());
+        assert!(matches!(
+            check(true),
+            Err(GeneticError::ScoreOrderViolation { index: 0, .. })
+        ));
     }
 }
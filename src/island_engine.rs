@@ -1,4 +1,36 @@
-pub trait IslandEngine {
+use crate::{Blackboard, MaybeSend, MaybeSync, ScratchArena};
+
+/// Creates the engine of an island once the world is built, or describes why it could not. See
+/// `WorldBuilder::add_island_with_factory`.
+#[cfg(not(feature = "send"))]
+pub type IslandEngineFactory = Box<dyn FnOnce() -> Result<Box<dyn IslandEngine>, String>>;
+
+/// Creates the engine of an island once the world is built, or describes why it could not. See
+/// `WorldBuilder::add_island_with_factory`.
+#[cfg(feature = "send")]
+pub type IslandEngineFactory = Box<dyn FnOnce() -> Result<Box<dyn IslandEngine>, String> + Send>;
+
+pub trait IslandEngine: MaybeSend + MaybeSync {
+    /// Trait implementations can use this callback to read data shared by other islands, such as a pool of opponents or
+    /// normalization constants. Called once before `pre_generation_run`. The default implementation does nothing.
+    fn read_blackboard(&mut self, _blackboard: &Blackboard) {}
+
+    /// Trait implementations can use this callback to publish data for other islands. Called once after the generation
+    /// has been run and sorted, unless the run failed. The default implementation does nothing.
+    fn write_blackboard(&mut self, _blackboard: &mut Blackboard) {}
+
+    /// Trait implementations can use this callback to learn which members of the world's `HallOfFame` every individual
+    /// should be evaluated against this generation. Called once before `pre_generation_run`, after `read_blackboard`,
+    /// and only when the world was built with a hall of fame. The list is empty until the first champion has been
+    /// inducted. The default implementation does nothing.
+    fn read_opponents(&mut self, _opponents: &[u64]) {}
+
+    /// Trait implementations can use this callback to switch test cases or scoring when the world enters a new
+    /// `CurriculumStage`. `stage` is the index of the stage in the curriculum. Called once for the first stage when the
+    /// world is built, and then before the first generation of every later stage. The default implementation does
+    /// nothing.
+    fn curriculum_stage_started(&mut self, _stage: usize, _name: &str) {}
+
     /// Trait implementations can use this callback to configure any data that will apply to all individuals in this
     /// generation. Called once before any individuals are run. The default implementation does nothing.
     fn pre_generation_run(&mut self, _individuals: &[u64]) {}
@@ -31,10 +63,38 @@ pub trait IslandEngine {
     /// calculated in a previous run.
     fn run_individual(&mut self, id: u64);
 
+    /// Run the virtual machine for a single individual, reporting whether the evaluation succeeded. The island calls
+    /// this method (through `run_individual_with_scratch`) rather than `run_individual` directly, so engines whose
+    /// simulation can fail should implement it and return a description of the problem. The remaining individuals of
+    /// the generation are not run after a failure. The default implementation calls `run_individual` and never fails.
+    fn try_run_individual(&mut self, id: u64) -> Result<(), String> {
+        self.run_individual(id);
+        Ok(())
+    }
+
+    /// Run the virtual machine for a single individual using buffers from the island's scratch arena, which lives
+    /// across generations. Implement this instead of `try_run_individual` to avoid allocating temporary data for every
+    /// individual. The default implementation calls `try_run_individual`.
+    fn run_individual_with_scratch(
+        &mut self,
+        id: u64,
+        _scratch: &mut ScratchArena,
+    ) -> Result<(), String> {
+        self.try_run_individual(id)
+    }
+
+    /// Decides whether a failure reported by `try_run_individual` is worth retrying, such as a timeout from a remote
+    /// evaluator, as opposed to a problem with the individual itself. Only consulted when the world has a
+    /// `GenerationRetry` policy. The default implementation treats every failure as transient.
+    fn is_transient_failure(&self, _message: &str) -> bool {
+        true
+    }
+
     /// Compare two individuals. The sort order is least fit to most fit. Called multiple times by the sorting algorithm
     /// after all individuals have been run. The default implementation sorts based on the score of the two individuals.
     /// You should implement your own sorting function if the order of individual is based upon multiple criteria or a
-    /// simple score is impossible to calculate.
+    /// simple score is impossible to calculate. If the order does not follow `score_individual`, also implement
+    /// `ranks_by_score`.
     fn sort_individuals(&self, a: u64, b: u64) -> std::cmp::Ordering {
         self.score_individual(a).cmp(&self.score_individual(b))
     }
@@ -49,4 +109,62 @@ pub trait IslandEngine {
     fn score_individual(&self, _id: u64) -> u64 {
         0
     }
+
+    /// Returns true if `sort_individuals` orders individuals by `score_individual`, so that a higher score is never
+    /// sorted before a lower one. With the `strict-checks` feature, the island verifies this order after every sort.
+    /// Engines whose `sort_individuals` uses other criteria should return false. The default implementation returns
+    /// true, matching the default `sort_individuals`.
+    fn ranks_by_score(&self) -> bool {
+        true
+    }
+
+    /// Scores every individual of the generation at once, returning the scores in the same order, so that the island
+    /// can sort by scores computed once per individual rather than on every comparison. When this returns Some, the
+    /// island ranks by these scores and does not call `sort_individuals`, so only implement it for engines that rank
+    /// by score. Not used with `NoisyEvaluation`. The default implementation returns None.
+    ///
+    /// With the `multi-threaded` feature, an island whose engine returns None here but `ranks_by_score` calls
+    /// `score_individual` once per individual on the threads of the rayon pool, and ranks by those scores instead of
+    /// calling `sort_individuals`. This is why engines must be `Sync` under that feature.
+    fn score_generation(&self, _individuals: &[u64]) -> Option<Vec<u64>> {
+        None
+    }
+
+    /// Scores one individual on held-out validation data that it was not selected on, such as the validation split of
+    /// a machine learning problem. The validation score never affects selection; it is reported in the statistics and
+    /// on champions, and can stop a run early with `RunBudget::validation_patience`, so that overfitting to the
+    /// training data shows up as a training score that keeps rising while the validation score does not. Called for
+    /// the most fit individual of the island after each generation. The default implementation returns None.
+    fn validation_score(&self, _id: u64) -> Option<u64> {
+        None
+    }
+
+    /// Estimates the fitness of a freshly bred individual before it has been run, as cheaply as possible. When brood
+    /// selection is enabled and every sibling of a brood has a quick score, only the sibling with the highest quick
+    /// score enters the future generation and the rest are never run. The default implementation returns None, so
+    /// every sibling is run and the brood is judged on its real scores.
+    fn quick_score(&mut self, _id: u64) -> Option<u64> {
+        None
+    }
+
+    /// Maps the score of one individual onto a scale shared with the other islands, for worlds built with
+    /// `ScoreNormalization::Engine`. The default implementation returns None, meaning the raw score is used.
+    fn normalized_score(&self, _id: u64) -> Option<f64> {
+        None
+    }
+
+    /// Measures how badly one individual breaks the constraints of the problem, for worlds that rank islands with
+    /// `StochasticRanking`. Zero or less means the individual is feasible, and larger values are worse. Called after
+    /// every individual of the generation has been run. The default implementation returns 0.0, so every individual is
+    /// feasible.
+    fn constraint_violation(&self, _id: u64) -> f64 {
+        0.0
+    }
+
+    /// Reports the objective values of one individual for multi-objective selection, where every objective is
+    /// maximized. The default implementation returns an empty Vec, meaning the island only has a single objective
+    /// and is ranked by `sort_individuals`. All individuals on an island must report the same number of objectives.
+    fn objectives(&self, _id: u64) -> Vec<f64> {
+        vec![]
+    }
 }
@@ -0,0 +1,125 @@
+/// Keeps a world running when the engine of one island keeps failing. Without a quarantine policy, the first
+/// evaluation error ends `World::run_one_generation` with `GeneticError::EvaluationFailed`, and the island that failed
+/// is emptied so that the world can carry on with the next generation.
+///
+/// With a policy, a failed generation is discarded instead: the island is emptied and starts over from random
+/// individuals in the next generation. Once an island fails `failures_before_quarantine` generations in a row it is
+/// quarantined, which means it is left empty and is not filled or run for `quarantine_generations` generations.
+/// Observers are told about every failure, quarantine and release.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandQuarantine {
+    /// The number of consecutive failed generations that place an island in quarantine. Must be greater than zero.
+    pub failures_before_quarantine: usize,
+
+    /// The number of generations a quarantined island is skipped. Must be greater than zero.
+    pub quarantine_generations: usize,
+}
+
+impl Default for IslandQuarantine {
+    fn default() -> Self {
+        IslandQuarantine {
+            failures_before_quarantine: 3,
+            quarantine_generations: 10,
+        }
+    }
+}
+
+impl IslandQuarantine {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.failures_before_quarantine > 0 && self.quarantine_generations > 0
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn evaluation_errors_fail_the_world_without_quarantine() {
+        let mut builder = world_builder(&["good"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1);
+        builder.add_island("bad", Box::new(FailingEngine));
+        let mut world = builder.build().unwrap();
+
+        world.fill_all_islands().unwrap();
+        let result = world.run_one_generation();
+
+        assert!(
+            matches!(result, Err(GeneticError::EvaluationFailed { island, .. }) if island == "bad")
+        );
+    }
+
+    #[test]
+    fn failing_islands_are_quarantined() {
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl WorldObserver for Recorder {
+            fn evaluation_failed(&mut self, generation: usize, island: usize, _: &GeneticError) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} failed {}", generation, island));
+            }
+
+            fn island_quarantined(&mut self, generation: usize, island: usize, _: &GeneticError) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} quarantined {}", generation, island));
+            }
+
+            fn island_released(&mut self, generation: usize, island: usize) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} released {}", generation, island));
+            }
+        }
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut builder = world_builder(&["good"])
+            .with_individuals_per_island(4)
+            .with_elite_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_migrating_individuals(1)
+            .with_island_quarantine(IslandQuarantine {
+                failures_before_quarantine: 2,
+                quarantine_generations: 3,
+            })
+            .with_observer(Recorder(events.clone()));
+        builder.add_island("bad", Box::new(FailingEngine));
+        let mut world = builder.build().unwrap();
+
+        let mut quarantined = vec![];
+        for _ in 0..7 {
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+            quarantined.push(world.get_island(1).unwrap().is_quarantined());
+        }
+
+        // The healthy island keeps evolving the whole time
+        assert_eq!(4, world.get_island(0).unwrap().len());
+        assert_eq!(
+            vec![false, true, true, true, false, false, true],
+            quarantined
+        );
+        assert_eq!(
+            vec![
+                "0 failed 1",
+                "1 failed 1",
+                "1 quarantined 1",
+                "4 released 1",
+                "5 failed 1",
+                "6 failed 1",
+                "6 quarantined 1",
+            ],
+            *events.lock().unwrap()
+        );
+    }
+}
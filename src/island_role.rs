@@ -0,0 +1,149 @@
+use crate::SelectionCurve;
+
+/// Gives an island a coherent bundle of breeding behavior that differs from the world-level configuration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IslandRole {
+    /// Breeds using the world configuration
+    #[default]
+    Standard,
+
+    /// Searches broadly. Parents are selected fairly, every child is a mutation using the maximum number of mutation
+    /// points, and one in every `EXPLORER_RANDOM_IMMIGRANT_RATE` children is replaced by a brand new random individual.
+    Explorer,
+
+    /// Refines the best individuals. Parents are selected with a strong preference for fitness and every child is a
+    /// single point mutation of its parent.
+    Exploiter,
+
+    /// Stores the best individuals it receives. An archive never breeds and never emigrates; its population is
+    /// carried forward each generation along with any immigrants, keeping one copy of each individual. When there are
+    /// more than `individuals_per_island`, the least fit residents are dropped. Elitism does not apply.
+    Archive,
+}
+
+/// One out of this many children on an explorer island is a random individual
+pub(crate) const EXPLORER_RANDOM_IMMIGRANT_RATE: u32 = 5;
+
+impl IslandRole {
+    /// Returns the curve used to select parents on an island with this role
+    pub(crate) fn parent_curve(&self, configured: SelectionCurve) -> SelectionCurve {
+        match self {
+            IslandRole::Explorer => SelectionCurve::Fair,
+            IslandRole::Exploiter => SelectionCurve::StrongPreferenceForFit,
+            IslandRole::Standard | IslandRole::Archive => configured,
+        }
+    }
+
+    /// Returns true if individuals on an island with this role may migrate to other islands
+    pub(crate) fn emigrates(&self) -> bool {
+        *self != IslandRole::Archive
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn archive_islands_only_receive_migrants() {
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(3)
+            .with_generations_between_migrations(1);
+        builder.add_island_with_role("archive", IslandRole::Archive, Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+
+        let mut sizes = vec![];
+        for _ in 0..5 {
+            world.fill_all_islands().unwrap();
+            sizes.push(world.get_island(1).unwrap().len());
+            world.run_one_generation().unwrap();
+
+            let log = world.migration_log();
+            assert_eq!(1, log.len());
+            assert_eq!(0, log[0].source_island);
+        }
+
+        // The archive starts empty and grows by at most three immigrants per migration. Elites can migrate more than
+        // once, but the archive keeps only one copy of each.
+        assert_eq!(0, sizes[0]);
+        for pair in sizes.windows(2) {
+            assert!(pair[0] <= pair[1] && pair[1] <= pair[0] + 3);
+        }
+        let mut archived = world.get_island(1).unwrap().snapshot().individuals;
+        archived.dedup();
+        assert_eq!(sizes[4], archived.len());
+    }
+
+    #[test]
+    fn explorers_and_exploiters_only_breed_by_mutation() {
+        let mut builder = world_builder(&[])
+            .with_individuals_per_island(20)
+            .with_elite_individuals(0)
+            .with_migrating_individuals(0);
+        builder.add_island_with_role("explorer", IslandRole::Explorer, Box::new(IdScoreEngine));
+        builder.add_island_with_role("exploiter", IslandRole::Exploiter, Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(3).unwrap();
+
+        let origins = |index: usize| -> Vec<IndividualOrigin> {
+            let island = world.get_island(index).unwrap();
+            island
+                .individuals()
+                .iter()
+                .map(|&id| world.individual_metadata(id).unwrap().origin)
+                .collect()
+        };
+        let explorer = origins(0);
+        assert!(explorer.contains(&IndividualOrigin::Mutation));
+        assert!(explorer.iter().all(|origin| matches!(
+            origin,
+            IndividualOrigin::Mutation | IndividualOrigin::Random
+        )));
+        assert!(origins(1)
+            .iter()
+            .all(|&origin| origin == IndividualOrigin::Mutation));
+
+        assert_eq!(
+            SelectionCurve::Fair,
+            IslandRole::Explorer.parent_curve(SelectionCurve::StrongPreferenceForFit)
+        );
+        assert_eq!(
+            SelectionCurve::Fair,
+            IslandRole::Archive.parent_curve(SelectionCurve::Fair)
+        );
+        assert!(!IslandRole::Archive.emigrates() && IslandRole::Exploiter.emigrates());
+    }
+
+    #[test]
+    fn archive_islands_drop_their_least_fit_residents_when_full() {
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_elite_individuals(0)
+            .with_migrating_individuals(3)
+            .with_generations_between_migrations(1);
+        builder.add_island_with_role("archive", IslandRole::Archive, Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+
+        let mut received = vec![];
+        for _ in 0..6 {
+            world.run_n_generations(1).unwrap();
+            received.extend(
+                world.migration_log()[0]
+                    .migrants
+                    .iter()
+                    .map(|migrant| migrant.id),
+            );
+        }
+
+        // Once the last immigrants are evaluated, only the best four of everything received are kept
+        world.run_n_generations(1).unwrap();
+        received.sort();
+        received.dedup();
+        let mut archived = world.get_island(1).unwrap().snapshot().individuals;
+        archived.sort();
+        assert_eq!(received[received.len() - 4..], archived[..]);
+    }
+}
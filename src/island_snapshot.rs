@@ -0,0 +1,17 @@
+/// A point-in-time copy of the population of one island. Taking a snapshot is cheap: it copies the individual ids, not
+/// the individuals themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandSnapshot {
+    /// The name of the island
+    pub name: String,
+
+    /// The current generation. When `sorted` is true, this is in rank order from least fit to most fit.
+    pub individuals: Vec<u64>,
+
+    /// True if the current generation has been sorted since it became the current generation
+    pub sorted: bool,
+
+    /// The individuals that have been added to the next generation so far
+    pub future: Vec<u64>,
+}
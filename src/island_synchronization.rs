@@ -0,0 +1,84 @@
+/// Controls how far islands may drift apart when they are advanced independently with `World::advance_island`.
+/// `World::run_one_generation` always runs every island together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IslandSynchronization {
+    /// An island may only run its next generation once every other island has completed the current one
+    #[default]
+    Lockstep,
+
+    /// An island may run up to this many generations ahead of the slowest island. Migration still happens when the
+    /// slowest island completes the generation it is due in, so faster islands exchange individuals from their latest
+    /// generation.
+    Bounded(usize),
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn islands_advance_within_their_synchronization_bound() {
+        let build = |synchronization: IslandSynchronization| {
+            world_builder(&["fast", "slow"])
+                .with_individuals_per_island(4)
+                .with_generations_between_migrations(1)
+                .with_migrating_individuals(1)
+                .with_island_synchronization(synchronization)
+                .build()
+                .unwrap()
+        };
+
+        let mut lockstep = build(IslandSynchronization::Lockstep);
+        assert!(lockstep.advance_island(0).unwrap());
+        assert!(!lockstep.advance_island(0).unwrap());
+        assert_eq!(0, lockstep.generation_count());
+        assert!(lockstep.advance_island(1).unwrap());
+        assert_eq!(1, lockstep.generation_count());
+
+        let mut bounded = build(IslandSynchronization::Bounded(2));
+        for _ in 0..3 {
+            assert!(bounded.advance_island(0).unwrap());
+        }
+        assert!(!bounded.advance_island(0).unwrap());
+        assert_eq!(Some(3), bounded.island_generation(0));
+        assert_eq!(0, bounded.generation_count());
+
+        // Catching up completes each generation in turn, migrating at every one of them
+        assert!(bounded.advance_island(1).unwrap());
+        assert_eq!(1, bounded.generation_count());
+        assert_eq!(2, bounded.migration_log().len());
+        assert!(bounded.advance_island(1).unwrap());
+        assert_eq!(2, bounded.generation_count());
+        assert_eq!(2, bounded.stats_history().len());
+    }
+
+    #[test]
+    fn islands_that_cannot_advance_leave_the_world_unchanged() {
+        // A bound of zero is the same as lockstep
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_island_synchronization(IslandSynchronization::Bounded(0))
+            .build()
+            .unwrap();
+        assert!(world.advance_island(0).unwrap());
+        let snapshot = world.snapshot();
+        assert!(!world.advance_island(0).unwrap());
+        assert!(!world.advance_island(2).unwrap());
+        assert_eq!(snapshot, world.snapshot());
+        assert_eq!(None, world.island_generation(2));
+
+        // A lone island completes a generation of the world every time it advances
+        let mut alone = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+        for generation in 1..=3 {
+            assert!(alone.advance_island(0).unwrap());
+            assert_eq!(generation, alone.generation_count());
+        }
+    }
+}
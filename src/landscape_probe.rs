@@ -0,0 +1,210 @@
+use crate::{GeneticEngine, Genetics, IslandEngine};
+
+/// Estimates how rugged a fitness landscape is before committing to a long run. A smooth landscape (high
+/// autocorrelation, strongly negative fitness-distance correlation) rewards strong selection and small mutations,
+/// while a rugged one calls for more exploration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LandscapeProbe {
+    /// The number of steps in the random walk. Each step is a single point mutation of the previous step.
+    pub walk_length: usize,
+
+    /// The largest lag for which the random-walk autocorrelation is reported
+    pub max_lag: usize,
+
+    /// The number of random individuals sampled for the fitness-distance correlation
+    pub samples: usize,
+}
+
+/// The measurements taken by a `LandscapeProbe`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LandscapeReport {
+    /// The autocorrelation of scores along the random walk for lags 1 through `max_lag`. Values near 1.0 indicate a
+    /// smooth landscape and values near 0.0 a rugged one. None where the walk was too short or had no variance.
+    pub autocorrelation: Vec<Option<f64>>,
+
+    /// The number of mutation steps after which scores become unrelated, estimated as `-1 / ln(|r(1)|)`
+    pub correlation_length: Option<f64>,
+
+    /// The correlation between the score of each sample and its `Genetics::distance` to the best sample. Values near
+    /// -1.0 mean that individuals closer to the best are fitter, which makes the problem easy for selection.
+    pub fitness_distance_correlation: Option<f64>,
+}
+
+impl Default for LandscapeProbe {
+    fn default() -> Self {
+        LandscapeProbe {
+            walk_length: 1000,
+            max_lag: 10,
+            samples: 100,
+        }
+    }
+}
+
+impl LandscapeProbe {
+    /// Probes the landscape defined by the genetics of `genetic_engine` and the scores of `island_engine`. The
+    /// probes are evaluated the same way an island evaluates a generation.
+    pub fn run<G: Genetics>(
+        &self,
+        genetic_engine: &mut GeneticEngine<G>,
+        island_engine: &mut dyn IslandEngine,
+    ) -> LandscapeReport {
+        // Random walk: each step is a single point mutation of the previous step
+        let mut walk = Vec::with_capacity(self.walk_length);
+        if self.walk_length > 0 {
+            walk.push(genetic_engine.rand_individual());
+            while walk.len() < self.walk_length {
+                let next = genetic_engine.mutate(*walk.last().unwrap(), 1);
+                walk.push(next);
+            }
+        }
+        let walk_scores = evaluate(island_engine, &walk);
+        let autocorrelation: Vec<Option<f64>> = (1..=self.max_lag)
+            .map(|lag| autocorrelation(&walk_scores, lag))
+            .collect();
+        let correlation_length = autocorrelation
+            .first()
+            .copied()
+            .flatten()
+            .map(|r| r.abs())
+            .filter(|&r| r > 0.0 && r < 1.0)
+            .map(|r| -1.0 / r.ln());
+
+        // Fitness-distance correlation against the best of the random samples
+        let samples: Vec<u64> = (0..self.samples)
+            .map(|_| genetic_engine.rand_individual())
+            .collect();
+        let sample_scores = evaluate(island_engine, &samples);
+        let fitness_distance_correlation = sample_scores
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .and_then(|(best, _)| {
+                let distances: Vec<f64> = samples
+                    .iter()
+                    .map(|&id| genetic_engine.genetics().distance(samples[best], id))
+                    .collect();
+                correlation(&sample_scores, &distances)
+            });
+
+        LandscapeReport {
+            autocorrelation,
+            correlation_length,
+            fitness_distance_correlation,
+        }
+    }
+}
+
+// Runs the individuals through the island engine as a single generation and returns their scores
+fn evaluate(engine: &mut dyn IslandEngine, individuals: &[u64]) -> Vec<f64> {
+    engine.pre_generation_run(individuals);
+    for &id in individuals {
+        engine.run_individual(id);
+    }
+    engine.post_generation_run(individuals);
+
+    individuals
+        .iter()
+        .map(|&id| engine.score_individual(id) as f64)
+        .collect()
+}
+
+fn autocorrelation(values: &[f64], lag: usize) -> Option<f64> {
+    if values.len() <= lag {
+        return None;
+    }
+
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance: f64 = values.iter().map(|v| (v - mean) * (v - mean)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    let covariance: f64 = values
+        .windows(lag + 1)
+        .map(|window| (window[0] - mean) * (window[lag] - mean))
+        .sum();
+
+    Some(covariance / variance)
+}
+
+fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+    let mut covariance = 0.0;
+    let mut variance_a = 0.0;
+    let mut variance_b = 0.0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        covariance += (x - mean_a) * (y - mean_b);
+        variance_a += (x - mean_a) * (x - mean_a);
+        variance_b += (y - mean_b) * (y - mean_b);
+    }
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_a * variance_b).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn steadily_improving_walk_is_smooth() {
+        let mut genetic_engine = counting_engine(1);
+        let probe = LandscapeProbe {
+            walk_length: 100,
+            max_lag: 3,
+            samples: 10,
+        };
+
+        let report = probe.run(&mut genetic_engine, &mut IdScoreEngine);
+
+        // Every step of the walk scores one higher than the last
+        assert_eq!(3, report.autocorrelation.len());
+        assert!(report.autocorrelation[0].unwrap() > 0.9);
+        assert!(report.correlation_length.unwrap() > 10.0);
+
+        // Only the best sample is at distance zero from itself, and it has the highest score
+        assert!(report.fitness_distance_correlation.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn probes_without_enough_variance_report_nothing() {
+        let mut genetic_engine = counting_engine(1);
+        let empty = LandscapeProbe {
+            walk_length: 0,
+            max_lag: 2,
+            samples: 0,
+        }
+        .run(&mut genetic_engine, &mut IdScoreEngine);
+        assert_eq!(
+            LandscapeReport {
+                autocorrelation: vec![None, None],
+                correlation_length: None,
+                fitness_distance_correlation: None,
+            },
+            empty
+        );
+
+        // Lags as long as the walk cannot be measured
+        let short = LandscapeProbe {
+            walk_length: 3,
+            max_lag: 3,
+            samples: 1,
+        }
+        .run(&mut genetic_engine, &mut IdScoreEngine);
+        assert!(short.autocorrelation[..2].iter().all(Option::is_some));
+        assert_eq!(None, short.autocorrelation[2]);
+        assert_eq!(None, short.fitness_distance_correlation);
+
+        // A landscape where every individual scores the same has no variance to correlate
+        let flat = LandscapeProbe::default().run(&mut genetic_engine, &mut UnscoredEngine);
+        assert!(flat.autocorrelation.iter().all(Option::is_none));
+        assert_eq!(
+            (None, None),
+            (flat.correlation_length, flat.fitness_distance_correlation)
+        );
+    }
+}
@@ -1,21 +1,191 @@
+//! The basic operations for running genetic optimization across a world of islands.
+//!
+//! # Threads and `Send`
+//!
+//! A `World` owns its island engines, observers and callbacks as trait objects. The `send` feature requires all of
+//! them to be `Send`, through `MaybeSend`, so that a `World<G>` whose genetics is `Send` can be moved to a worker
+//! thread or into an async task and driven from there. The `multi-threaded` and `async` features both enable `send`,
+//! since both run the world's work away from the thread that built it. Without any of these features, engines may hold
+//! an `Rc` or other state that cannot leave its thread, and the world must stay on the thread that built it.
+//!
+//! Under `multi-threaded`, island engines must also be `Sync`, through `MaybeSync`, because an island scores the
+//! individuals of an engine that ranks by score on several threads at once.
+
+mod adaptive_migration;
+mod archive;
+mod bit_string_genetics;
+mod blackboard;
+mod cartesian_genetics;
+mod champion;
+#[cfg(feature = "serde")]
+mod champion_export;
+mod champion_sink;
+#[cfg(feature = "config")]
+mod config;
+mod curriculum;
+mod decision_log;
+#[cfg(not(feature = "async"))]
+mod determinism_audit;
+mod development;
+mod elite_handling;
+mod emigration_threshold;
 mod error;
+mod event_log;
+mod experiment_dir;
+mod generation_retry;
+mod generation_stats;
 mod genetic_engine;
 mod genetic_engine_builder;
+mod genetic_engine_settings;
 mod genetics;
+mod grammatical_genetics;
+mod hall_of_fame;
+mod hypermutation;
+mod id_allocator;
+mod identical_parents;
+mod individual_metadata;
+mod individual_store;
+mod initialization;
 mod island;
 mod island_engine;
+mod island_quarantine;
+mod island_role;
+mod island_snapshot;
+mod island_synchronization;
+mod landscape_probe;
+mod linear_genetics;
+mod mating_restriction;
+mod maybe_send;
+mod migrant_count;
 mod migration_algorithm;
+mod migration_record;
+mod migration_shortfall;
+mod noisy_evaluation;
+mod oversized_offspring;
+#[cfg(feature = "multi-threaded")]
+mod parallel_scoring;
+mod pareto;
+mod permutation_genetics;
+#[cfg(feature = "serde")]
+mod population_export;
+mod population_viability;
+mod rate_limit;
+mod real_vector_genetics;
+mod restart_keep;
+mod run_budget;
+mod run_report;
+mod run_summary;
+mod score_accumulator;
+mod score_attribution;
+mod score_normalization;
+mod scratch_arena;
 mod selection_curve;
+mod selector;
+#[cfg(not(feature = "async"))]
+mod sensitivity_analysis;
+mod stochastic_ranking;
+#[cfg(test)]
+// Most tests drive the sync API, so some of the helpers go unused under `async`
+#[cfg_attr(feature = "async", allow(dead_code))]
+mod test_support;
+mod threading_model;
+#[cfg(not(feature = "async"))]
+mod tournament;
+mod tree_genetics;
+mod value_genetics;
+mod variation_operator;
+mod warmup;
 mod world;
 mod world_builder;
+#[cfg(not(feature = "async"))]
+mod world_comparison;
+mod world_observer;
+mod world_phase;
+mod world_snapshot;
 
+pub use adaptive_migration::AdaptiveMigration;
+pub use archive::{Archive, ArchivedIndividual};
+pub use bit_string_genetics::{BitStringCrossover, BitStringGenetics};
+pub use blackboard::Blackboard;
+pub use cartesian_genetics::{CartesianGenetics, CartesianGenome, CartesianNode};
+pub use champion::Champion;
+#[cfg(feature = "serde")]
+pub use champion_export::{ChampionExport, ChampionSource, ExportedChampion};
+pub use champion_sink::ChampionSink;
+#[cfg(feature = "config")]
+pub use config::{GeneticEngineConfig, WorldConfig};
+pub use curriculum::{CurriculumStage, StageAdvancement};
+pub use decision_log::{Decision, DecisionKind};
+#[cfg(not(feature = "async"))]
+pub use determinism_audit::{DeterminismAudit, Divergence};
+pub use development::{DevelopingEngine, Development, PhenomeEngine};
+pub use elite_handling::EliteHandling;
+pub use emigration_threshold::EmigrationThreshold;
 pub use error::GeneticError;
+pub use event_log::{EventLog, RunEvent};
+pub use experiment_dir::ExperimentDir;
+pub use generation_retry::GenerationRetry;
+pub use generation_stats::{GenerationStats, IslandStats, ReferenceScore};
 pub use genetic_engine::GeneticEngine;
 pub use genetic_engine_builder::GeneticEngineBuilder;
+pub use genetic_engine_settings::GeneticEngineSettings;
 pub use genetics::Genetics;
+pub use grammatical_genetics::{Grammar, GrammaticalGenetics, InvalidMapping};
+pub use hall_of_fame::HallOfFame;
+pub use hypermutation::Hypermutation;
+pub use id_allocator::IdAllocator;
+pub use identical_parents::IdenticalParents;
+pub use individual_metadata::IndividualMetadata;
+pub use individual_store::IndividualStore;
+pub use initialization::{Initialization, IslandInitializer};
 pub use island::Island;
-pub use island_engine::IslandEngine;
+pub use island_engine::{IslandEngine, IslandEngineFactory};
+pub use island_quarantine::IslandQuarantine;
+pub use island_role::IslandRole;
+pub use island_snapshot::IslandSnapshot;
+pub use island_synchronization::IslandSynchronization;
+pub use landscape_probe::{LandscapeProbe, LandscapeReport};
+pub use linear_genetics::{Instruction, LinearGenetics, Operation};
+pub use mating_restriction::MatingRestriction;
+pub use maybe_send::{MaybeSend, MaybeSync};
+pub use migrant_count::MigrantCount;
 pub use migration_algorithm::MigrationAlgorithm;
+pub use migration_record::{Migrant, MigrationRecord};
+pub use migration_shortfall::MigrationShortfall;
+pub use noisy_evaluation::{AdaptiveResampling, NoisyEvaluation, ScoreAggregation};
+pub use oversized_offspring::OversizedOffspring;
+pub use permutation_genetics::{PermutationCrossover, PermutationGenetics, PermutationMutation};
+#[cfg(feature = "serde")]
+pub use population_export::{ExportedIndividual, PopulationExport};
+pub use population_viability::{PopulationViability, ViabilityAction};
+pub use rate_limit::RateLimit;
+pub use real_vector_genetics::{RealMutation, RealVectorGenetics};
+pub use restart_keep::RestartKeep;
+pub use run_budget::RunBudget;
+pub use run_report::{RunConfiguration, RunReport};
+pub use run_summary::RunSummary;
+pub use score_accumulator::{OverflowMode, ScoreAccumulator};
+pub use score_attribution::{IndividualOrigin, ScoreAttribution};
+pub use score_normalization::ScoreNormalization;
+pub use scratch_arena::{ScratchArena, ScratchStats};
 pub use selection_curve::SelectionCurve;
+pub use selector::Selector;
+#[cfg(not(feature = "async"))]
+pub use sensitivity_analysis::{
+    EngineParameter, ParameterSensitivity, SensitivityAnalysis, SensitivityReport,
+};
+pub use stochastic_ranking::StochasticRanking;
+pub use threading_model::ThreadingModel;
+#[cfg(not(feature = "async"))]
+pub use tournament::{Tournament, TournamentStanding};
+pub use tree_genetics::{Primitive, TreeGenetics, TreeMutation};
+pub use value_genetics::{StoredGenetics, ValueGenetics};
+pub use variation_operator::VariationOperator;
+pub use warmup::Warmup;
 pub use world::World;
 pub use world_builder::WorldBuilder;
+#[cfg(not(feature = "async"))]
+pub use world_comparison::{GenerationComparison, WorldComparison, WorldComparisonReport};
+pub use world_observer::WorldObserver;
+pub use world_phase::WorldPhase;
+pub use world_snapshot::WorldSnapshot;
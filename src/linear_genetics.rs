@@ -0,0 +1,512 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{GeneticError, Genetics, IndividualStore};
+
+/// An operation of the register machine that `LinearGenetics` programs run on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Operation {
+    /// The name the operation is written with by `Genetics::encode`
+    pub name: String,
+
+    /// The number of operands the operation reads, at most two
+    pub arity: usize,
+}
+
+impl Operation {
+    pub fn new<S: Into<String>>(name: S, arity: usize) -> Self {
+        Operation {
+            name: name.into(),
+            arity,
+        }
+    }
+}
+
+/// One instruction of a linear program: `destination = operation(operands)`. Operands index the sources of the machine:
+/// first the calculation registers, then the read-only input registers, then the constants. Only the first `arity`
+/// operands of the operation are read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    /// The index of the operation
+    pub operation: usize,
+
+    /// The calculation register the result is written to
+    pub destination: usize,
+
+    /// The sources the operation reads
+    pub operands: [usize; 2],
+}
+
+/// A ready-made `Genetics` for linear genetic programming. Individuals are sequences of register machine
+/// `Instruction`s kept in an `IndividualStore`, which island engines read through `store` and execute in order. The
+/// result of a program is the final value of register 0.
+///
+/// Instructions that cannot change register 0 are introns. The operators know which instructions are effective:
+/// mutation changes or removes effective instructions and inserts instructions that write to a register that is read
+/// later, and crossover replaces a segment that starts at an effective instruction, so that offspring rarely behave
+/// exactly like their parents. Engines can skip introns when executing with `effective_instructions`, and the
+/// distance between two programs compares their effective code only.
+///
+/// Programs hold at least one instruction and no more than the length limit, which is the engine's
+/// `max_individual_points` unless it is set with `with_max_length`.
+pub struct LinearGenetics {
+    operations: Vec<Operation>,
+    registers: usize,
+    inputs: usize,
+    constants: usize,
+    max_length: Option<usize>,
+    // The largest `max_points` passed to `random_individual`, which is the engine's `max_individual_points`
+    engine_max_length: Cell<Option<usize>>,
+    store: Arc<IndividualStore<Vec<Instruction>>>,
+}
+
+impl LinearGenetics {
+    /// Creates genetics for programs of the given operations on a machine with `registers` calculation registers,
+    /// `inputs` input registers and `constants` constants. Returns an error if there is no register or operation, if an
+    /// operation reads more than two operands, or if an operation name is repeated or not a single word.
+    pub fn new(
+        operations: Vec<Operation>,
+        registers: usize,
+        inputs: usize,
+        constants: usize,
+    ) -> Result<Self, GeneticError> {
+        let mut names = HashSet::new();
+        let valid_operations = operations.iter().all(|operation| {
+            operation.arity <= 2
+                && !operation.name.is_empty()
+                && !operation
+                    .name
+                    .contains(|c: char| c.is_whitespace() || c == ';')
+                && names.insert(operation.name.as_str())
+        });
+        if registers == 0 || operations.is_empty() || !valid_operations {
+            return Err(GeneticError::InvalidLinearProgramSetup);
+        }
+
+        Ok(LinearGenetics {
+            operations,
+            registers,
+            inputs,
+            constants,
+            max_length: None,
+            engine_max_length: Cell::new(None),
+            store: Arc::new(IndividualStore::new()),
+        })
+    }
+
+    /// Sets the number of instructions no program may exceed, in place of the engine's `max_individual_points`
+    pub fn with_max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length.max(1));
+        self
+    }
+
+    /// Returns the operation an instruction refers to
+    pub fn operation(&self, index: usize) -> &Operation {
+        &self.operations[index]
+    }
+
+    /// Returns a handle to the store of programs, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<Instruction>>> {
+        self.store.clone()
+    }
+
+    /// Returns the instructions of an individual, or None if it is not stored
+    pub fn program(&self, id: u64) -> Option<Arc<Vec<Instruction>>> {
+        self.store.get(id)
+    }
+
+    /// Returns, for each instruction, whether it can change the final value of register 0
+    pub fn effective_instructions(&self, program: &[Instruction]) -> Vec<bool> {
+        let mut effective = vec![false; program.len()];
+        let mut needed = vec![false; self.registers];
+        needed[0] = true;
+        for (index, instruction) in program.iter().enumerate().rev() {
+            if !needed[instruction.destination] {
+                continue;
+            }
+            effective[index] = true;
+            needed[instruction.destination] = false;
+            for &operand in self.operands(instruction) {
+                if operand < self.registers {
+                    needed[operand] = true;
+                }
+            }
+        }
+        effective
+    }
+
+    fn operands<'a>(&self, instruction: &'a Instruction) -> &'a [usize] {
+        &instruction.operands[..self.operations[instruction.operation].arity]
+    }
+
+    // The registers whose value before the first of the instructions can change the final value of register 0
+    fn needed_registers(&self, program: &[Instruction]) -> Vec<usize> {
+        let mut needed = vec![false; self.registers];
+        needed[0] = true;
+        for instruction in program.iter().rev() {
+            if needed[instruction.destination] {
+                needed[instruction.destination] = false;
+                for &operand in self.operands(instruction) {
+                    if operand < self.registers {
+                        needed[operand] = true;
+                    }
+                }
+            }
+        }
+        (0..self.registers)
+            .filter(|&register| needed[register])
+            .collect()
+    }
+
+    fn effective_positions(&self, program: &[Instruction]) -> Vec<usize> {
+        let effective = self.effective_instructions(program);
+        (0..program.len())
+            .filter(|&index| effective[index])
+            .collect()
+    }
+
+    fn length_limit(&self) -> usize {
+        self.max_length
+            .or(self.engine_max_length.get())
+            .unwrap_or(usize::MAX)
+            .max(1)
+    }
+
+    fn sources(&self) -> usize {
+        self.registers + self.inputs + self.constants
+    }
+
+    fn random_instruction(&self, rng: &mut StdRng, destination: usize) -> Instruction {
+        Instruction {
+            operation: rng.random_range(0..self.operations.len()),
+            destination,
+            operands: [
+                rng.random_range(0..self.sources()),
+                rng.random_range(0..self.sources()),
+            ],
+        }
+    }
+
+    // Changes one field of an instruction
+    fn mutate_instruction(&self, rng: &mut StdRng, instruction: &mut Instruction) {
+        let arity = self.operations[instruction.operation].arity;
+        match rng.random_range(0..3) {
+            0 => instruction.operation = rng.random_range(0..self.operations.len()),
+            1 => instruction.destination = rng.random_range(0..self.registers),
+            _ => {
+                let operand = rng.random_range(0..arity.max(1));
+                instruction.operands[operand] = rng.random_range(0..self.sources());
+            }
+        }
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<Instruction>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    fn source_name(&self, source: usize) -> String {
+        if source < self.registers {
+            format!("r{}", source)
+        } else if source < self.registers + self.inputs {
+            format!("i{}", source - self.registers)
+        } else {
+            format!("c{}", source - self.registers - self.inputs)
+        }
+    }
+
+    fn parse_source(&self, text: &str) -> Option<usize> {
+        let index: usize = text.get(1..)?.parse().ok()?;
+        let (offset, count) = match text.chars().next()? {
+            'r' => (0, self.registers),
+            'i' => (self.registers, self.inputs),
+            'c' => (self.registers + self.inputs, self.constants),
+            _ => return None,
+        };
+        (index < count).then_some(offset + index)
+    }
+
+    fn parse_instruction(&self, text: &str) -> Option<Instruction> {
+        let mut words = text.split_whitespace();
+        let destination = self.parse_source(words.next()?)?;
+        if destination >= self.registers || words.next()? != "=" {
+            return None;
+        }
+        let name = words.next()?;
+        let operation = self
+            .operations
+            .iter()
+            .position(|operation| operation.name == name)?;
+        let mut operands = [0; 2];
+        for operand in operands.iter_mut().take(self.operations[operation].arity) {
+            *operand = self.parse_source(words.next()?)?;
+        }
+        if words.next().is_some() {
+            return None;
+        }
+
+        Some(Instruction {
+            operation,
+            destination,
+            operands,
+        })
+    }
+}
+
+impl Genetics for LinearGenetics {
+    fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+        let largest = self
+            .engine_max_length
+            .get()
+            .map_or(max_points, |length| length.max(max_points));
+        self.engine_max_length.set(Some(largest));
+
+        let length = rng.random_range(1..=max_points.min(self.length_limit()).max(1));
+        let program = (0..length)
+            .map(|_| {
+                let destination = rng.random_range(0..self.registers);
+                self.random_instruction(rng, destination)
+            })
+            .collect();
+        self.store.insert(program)
+    }
+
+    // Each mutation point either inserts or removes an instruction, or changes one field of an effective instruction
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        let max_length = self.length_limit();
+        for _ in 0..points {
+            let effective = self.effective_positions(&child);
+            if rng.random_bool(0.5) {
+                let insert = child.len() < max_length && (child.len() == 1 || rng.random_bool(0.5));
+                if insert {
+                    let position = rng.random_range(0..=child.len());
+                    let needed = self.needed_registers(&child[position..]);
+                    let destination = needed.choose(rng).copied().unwrap_or(0);
+                    let instruction = self.random_instruction(rng, destination);
+                    child.insert(position, instruction);
+                } else if child.len() > 1 {
+                    let position = match effective.choose(rng) {
+                        Some(&position) => position,
+                        None => rng.random_range(0..child.len()),
+                    };
+                    child.remove(position);
+                }
+            } else {
+                let position = match effective.choose(rng) {
+                    Some(&position) => position,
+                    None => rng.random_range(0..child.len()),
+                };
+                self.mutate_instruction(rng, &mut child[position]);
+            }
+        }
+        self.store.insert(child)
+    }
+
+    // Replaces a segment of the first parent, starting at an effective instruction, with a segment of the second. The
+    // engine's crossover points are not used.
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let start_a = match self.effective_positions(&a).choose(rng) {
+            Some(&position) => position,
+            None => rng.random_range(0..a.len()),
+        };
+        let end_a = rng.random_range(start_a + 1..=a.len());
+        let start_b = rng.random_range(0..b.len());
+        let room = self.length_limit() - (a.len() - (end_a - start_a));
+        let end_b = rng
+            .random_range(start_b + 1..=b.len())
+            .min(start_b + room.max(1));
+
+        let mut child = a[..start_a].to_vec();
+        child.extend_from_slice(&b[start_b..end_b]);
+        child.extend_from_slice(&a[end_a..]);
+        self.store.insert(child)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let effective_code = |program: &[Instruction]| -> Vec<Instruction> {
+            let effective = self.effective_instructions(program);
+            program
+                .iter()
+                .zip(effective)
+                .filter(|(_, effective)| *effective)
+                .map(|(instruction, _)| *instruction)
+                .collect()
+        };
+        let a = effective_code(&self.genome(individual_a));
+        let b = effective_code(&self.genome(individual_b));
+        let differing = a.iter().zip(b.iter()).filter(|(a, b)| a != b).count();
+        (differing + a.len().abs_diff(b.len())) as f64
+    }
+
+    // Programs are written one instruction after another, such as `r1 = mul i0 i0; r0 = add r1 c0`
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |program| hash_genome(&*program))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let program = self.store.get(individual)?;
+        let instructions: Vec<String> = program
+            .iter()
+            .map(|instruction| {
+                let mut text = format!(
+                    "{} = {}",
+                    self.source_name(instruction.destination),
+                    self.operations[instruction.operation].name
+                );
+                for &operand in self.operands(instruction) {
+                    text.push(' ');
+                    text.push_str(&self.source_name(operand));
+                }
+                text
+            })
+            .collect();
+        Some(instructions.join("; "))
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let program = genome
+            .split(';')
+            .map(|instruction| self.parse_instruction(instruction))
+            .collect::<Option<Vec<Instruction>>>()?;
+        if program.is_empty() || program.len() > self.length_limit() {
+            return None;
+        }
+        Some(self.store.insert(program))
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    fn arithmetic() -> LinearGenetics {
+        let operations = vec![Operation::new("add", 2), Operation::new("mul", 2)];
+        LinearGenetics::new(operations, 2, 1, 1).unwrap()
+    }
+
+    // Scores a program by how closely it computes x * x + 1, with every register starting at x
+    struct RegressionEngine {
+        store: Arc<IndividualStore<Vec<Instruction>>>,
+    }
+
+    impl IslandEngine for RegressionEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let program = self.store.get(id).unwrap();
+            let error: u64 = (0..5i64)
+                .map(|x| {
+                    let mut registers = [x, x];
+                    for instruction in program.iter() {
+                        let [a, b] = instruction.operands.map(|source| match source {
+                            0 | 1 => registers[source],
+                            2 => x,
+                            _ => 1,
+                        });
+                        registers[instruction.destination] = match instruction.operation {
+                            0 => a.saturating_add(b),
+                            _ => a.saturating_mul(b),
+                        };
+                    }
+                    registers[0].abs_diff(x * x + 1)
+                })
+                .fold(0, u64::saturating_add);
+            1000u64.saturating_sub(error)
+        }
+    }
+
+    #[test]
+    fn operators_work_on_effective_code() {
+        assert!(matches!(
+            LinearGenetics::new(vec![Operation::new("add", 3)], 1, 0, 0),
+            Err(GeneticError::InvalidLinearProgramSetup)
+        ));
+
+        let genetics = arithmetic().with_max_length(8);
+        let program = genetics
+            .decode("r1 = mul i0 i0; r0 = add r1 c0; r1 = add r0 r0")
+            .unwrap();
+        assert_eq!(
+            Some("r1 = mul i0 i0; r0 = add r1 c0; r1 = add r0 r0".to_string()),
+            genetics.encode(program)
+        );
+        assert_eq!(
+            vec![true, true, false],
+            genetics.effective_instructions(&genetics.program(program).unwrap())
+        );
+        assert_eq!(None, genetics.decode("r2 = add r0 r0"));
+        assert_eq!(None, genetics.decode("r0 = add r0"));
+
+        // The trailing intron is ignored by the distance
+        let effective = genetics.decode("r1 = mul i0 i0; r0 = add r1 c0").unwrap();
+        assert_eq!(0.0, genetics.distance(program, effective));
+
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut individuals = vec![program, effective];
+        for _ in 0..200 {
+            let a = *individuals.choose(&mut rng).unwrap();
+            let b = *individuals.choose(&mut rng).unwrap();
+            individuals.push(genetics.crossover(&mut rng, a, b, 1));
+            individuals.push(genetics.mutate(&mut rng, a, 2));
+        }
+        for id in individuals {
+            assert!((1..=8).contains(&genetics.program(id).unwrap().len()));
+            let decoded = genetics.decode(&genetics.encode(id).unwrap()).unwrap();
+            assert_eq!(0.0, genetics.distance(id, decoded));
+        }
+    }
+
+    #[test]
+    fn a_polynomial_is_found() {
+        let genetics = arithmetic();
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .max_individual_points(10)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(50)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(RegressionEngine { store }));
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(30).unwrap();
+        assert_eq!(Some(1000), world.best_score());
+    }
+}
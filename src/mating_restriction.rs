@@ -0,0 +1,103 @@
+/// Restricts crossover to parents that are alike, as measured by `Genetics::distance`, so that an island can hold
+/// several niches without their offspring blending into poor compromises between them.
+///
+/// After the first parent is selected, the second is selected as usual up to `attempts` times, until one is within
+/// `max_distance` of the first. If none is, the last one selected is used, so that breeding never stalls.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatingRestriction {
+    /// The largest distance between two parents that may be crossed over. Must be zero or more.
+    pub max_distance: f64,
+
+    /// The number of times the second parent is selected while looking for one close enough. Must be greater than zero.
+    ///
+    /// Default: 5
+    pub attempts: usize,
+}
+
+impl MatingRestriction {
+    pub fn new(max_distance: f64) -> Self {
+        MatingRestriction {
+            max_distance,
+            attempts: 5,
+        }
+    }
+
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.max_distance >= 0.0 && self.attempts > 0
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    // Returns the number of crossovers whose parents were the same individual, and the number of crossovers
+    fn crossovers(restriction: Option<MatingRestriction>) -> (usize, usize) {
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .build()
+            .unwrap();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0);
+        builder.mating_restriction = restriction;
+        builder.add_island("a", Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(2).unwrap();
+
+        let island = world.get_island(0).unwrap().snapshot().individuals;
+        let parents: Vec<Vec<u64>> = island
+            .iter()
+            .filter_map(|&id| world.individual_metadata(id))
+            .filter(|metadata| metadata.origin == IndividualOrigin::Crossover)
+            .map(|metadata| metadata.parents.clone())
+            .collect();
+        let selfed = parents
+            .iter()
+            .filter(|parents| parents[0] == parents[1])
+            .count();
+        (selfed, parents.len())
+    }
+
+    #[test]
+    fn mating_restriction_pairs_close_parents() {
+        // Under the discrete distance of CountingGenetics only an individual itself is within a distance of 0.0
+        let (selfed, total) = crossovers(None);
+        assert!(total > 0 && selfed < total / 2);
+        let (selfed, total) = crossovers(Some(MatingRestriction::new(0.0).with_attempts(100)));
+        assert!(total > 0 && selfed == total);
+
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_migrating_individuals(1)
+                .with_mating_restriction(MatingRestriction::new(-1.0))
+                .build(),
+            Err(GeneticError::InvalidMatingRestriction)
+        ));
+    }
+
+    #[test]
+    fn restrictions_that_always_accept_the_first_mate_change_nothing() {
+        // Every pair is exactly the maximum distance apart, and a single attempt has no choice but the first mate
+        let unrestricted = crossovers(None);
+        assert_eq!(unrestricted, crossovers(Some(MatingRestriction::new(1.0))));
+        assert_eq!(
+            unrestricted,
+            crossovers(Some(MatingRestriction::new(0.0).with_attempts(1)))
+        );
+
+        assert!(!MatingRestriction::new(0.0).with_attempts(0).is_valid());
+        assert!(!MatingRestriction::new(f64::NAN).is_valid());
+    }
+}
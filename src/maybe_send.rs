@@ -0,0 +1,46 @@
+/// `Send` when the crate is built with the `send` feature, which `multi-threaded` and `async` enable, and implemented
+/// by every type otherwise.
+///
+/// A `World` is only as sendable as everything it owns, and it owns island engines, observers and callbacks as trait
+/// objects. With the `send` feature those are all required to be `Send`, so that a `World<G>` whose genetics is `Send`
+/// can be moved to a worker thread or into a task and driven from there. Without it they may hold an `Rc` or other
+/// state that cannot leave its thread, but the world must stay on the thread that built it.
+#[cfg(not(feature = "send"))]
+pub trait MaybeSend {}
+
+#[cfg(not(feature = "send"))]
+impl<T: ?Sized> MaybeSend for T {}
+
+#[cfg(feature = "send")]
+pub trait MaybeSend: Send {}
+
+#[cfg(feature = "send")]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Sync` when the crate is built with the `multi-threaded` feature, and implemented by every type otherwise. Island
+/// engines are bound by it so that an island can score its individuals on several threads at once through `&self`.
+#[cfg(not(feature = "multi-threaded"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "multi-threaded"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+#[cfg(feature = "multi-threaded")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "multi-threaded")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+// Fails to compile if anything a world owns stops being `Send` under the `send` feature
+#[cfg(feature = "send")]
+#[allow(dead_code)]
+fn world_is_send<G: crate::Genetics + Send>() {
+    fn is_send<T: Send>() {}
+    is_send::<crate::World<G>>();
+    is_send::<crate::WorldBuilder<G>>();
+
+    // The built-in genetics and the store handles their island engines keep
+    is_send::<crate::RealVectorGenetics>();
+    is_send::<std::sync::Arc<crate::IndividualStore<Vec<f64>>>>();
+    is_send::<crate::IdAllocator>();
+}
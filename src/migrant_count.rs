@@ -0,0 +1,101 @@
+use rand::Rng;
+
+/// Decides how many individuals leave an island at each migration, relative to the island's size at the time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MigrantCount {
+    /// The same number of individuals leave every island. Islands with fewer individuals available are handled by the
+    /// `MigrationShortfall`.
+    Fixed(usize),
+
+    /// This fraction of the island's current individuals leave, rounded to the nearest individual. Must be between 0.0
+    /// and 1.0.
+    Fraction(f64),
+
+    /// Each of the island's current individuals leaves with this probability, so the number of migrants varies from
+    /// one migration to the next. Must be between 0.0 and 1.0.
+    Probability(f64),
+}
+
+impl MigrantCount {
+    /// Returns true if the fraction or probability is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        match self {
+            MigrantCount::Fixed(_) => true,
+            MigrantCount::Fraction(value) | MigrantCount::Probability(value) => {
+                (0.0..=1.0).contains(value)
+            }
+        }
+    }
+
+    /// Returns the number of individuals that should leave an island with `available` individuals
+    pub(crate) fn requested<R: Rng>(&self, available: usize, rng: &mut R) -> usize {
+        match *self {
+            MigrantCount::Fixed(count) => count,
+            MigrantCount::Fraction(fraction) => (available as f64 * fraction).round() as usize,
+            MigrantCount::Probability(probability) => (0..available)
+                .filter(|_| rng.random_bool(probability))
+                .count(),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::rngs::StdRng; // cspell:disable-line
+    use rand::SeedableRng;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn migrant_counts_scale_with_the_source_island() {
+        let requested = |count: MigrantCount| {
+            let mut world = world_builder(&["a", "b"])
+                .with_individuals_per_island(6)
+                .with_generations_between_migrations(1)
+                .with_migrant_count(count)
+                .build()
+                .unwrap();
+            world.run_n_generations(1).unwrap();
+            world
+                .migration_log()
+                .iter()
+                .map(|record| record.requested)
+                .collect::<Vec<usize>>()
+        };
+
+        assert_eq!(vec![3, 3], requested(MigrantCount::Fraction(0.5)));
+        assert_eq!(vec![6, 6], requested(MigrantCount::Probability(1.0)));
+        assert_eq!(vec![0, 0], requested(MigrantCount::Probability(0.0)));
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_migrant_count(MigrantCount::Fraction(1.5))
+                .build(),
+            Err(GeneticError::InvalidMigrantCount)
+        ));
+    }
+
+    #[test]
+    fn fractions_round_to_the_nearest_individual() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(2, MigrantCount::Fraction(0.25).requested(6, &mut rng));
+        assert_eq!(1, MigrantCount::Fraction(0.25).requested(5, &mut rng));
+        assert_eq!(0, MigrantCount::Fraction(0.1).requested(4, &mut rng));
+        assert_eq!(7, MigrantCount::Fraction(1.0).requested(7, &mut rng));
+        assert_eq!(0, MigrantCount::Probability(1.0).requested(0, &mut rng));
+
+        // A fixed count is asked for whatever the size of the island
+        assert_eq!(9, MigrantCount::Fixed(9).requested(2, &mut rng));
+
+        assert!(
+            MigrantCount::Fraction(0.0).is_valid() && MigrantCount::Probability(1.0).is_valid()
+        );
+        for count in [
+            MigrantCount::Probability(-0.1),
+            MigrantCount::Fraction(f64::NAN),
+        ] {
+            assert!(!count.is_valid());
+        }
+    }
+}
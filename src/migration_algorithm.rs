@@ -1,5 +1,11 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::GeneticError;
+
 /// Defines the method by which individuals migrate from island to island when it is time for a migration.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MigrationAlgorithm {
     /// The islands are arranged in a circle and individuals always migrate one island clockwise.
     Circular,
@@ -21,3 +27,143 @@ pub enum MigrationAlgorithm {
     /// migrates to that island.
     CompletelyRandom,
 }
+
+impl fmt::Display for MigrationAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for MigrationAlgorithm {
+    type Err = GeneticError;
+
+    /// Parses a variant as written by `Display`, such as `Circular` or `Cyclical(2)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GeneticError::InvalidSettingValue {
+            kind: "migration algorithm",
+            value: s.to_string(),
+        };
+        let (name, argument) = match s.split_once('(') {
+            Some((name, rest)) => {
+                let argument = rest.strip_suffix(')').ok_or_else(invalid)?;
+                (
+                    name,
+                    Some(argument.trim().parse::<usize>().map_err(|_| invalid())?),
+                )
+            }
+            None => (s, None),
+        };
+
+        match (name.trim(), argument) {
+            ("Circular", None) => Ok(MigrationAlgorithm::Circular),
+            ("Cyclical", Some(n)) => Ok(MigrationAlgorithm::Cyclical(n)),
+            ("Incremental", Some(n)) => Ok(MigrationAlgorithm::Incremental(n)),
+            ("RandomCircular", None) => Ok(MigrationAlgorithm::RandomCircular),
+            ("CompletelyRandom", None) => Ok(MigrationAlgorithm::CompletelyRandom),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn configuration_enums_round_trip_through_strings() {
+        for algorithm in [
+            MigrationAlgorithm::Circular,
+            MigrationAlgorithm::Cyclical(3),
+            MigrationAlgorithm::Incremental(1),
+            MigrationAlgorithm::RandomCircular,
+            MigrationAlgorithm::CompletelyRandom,
+        ] {
+            assert_eq!(
+                Ok(algorithm.clone()),
+                algorithm.to_string().parse().map_err(|_| ())
+            );
+        }
+        assert_eq!("Cyclical(3)", MigrationAlgorithm::Cyclical(3).to_string());
+        for curve in [
+            SelectionCurve::Fair,
+            SelectionCurve::StrongPreferenceForUnfit,
+            SelectionCurve::FitnessProportional,
+        ] {
+            assert_eq!(Ok(curve), curve.to_string().parse().map_err(|_| ()));
+        }
+        for model in [ThreadingModel::None, ThreadingModel::ThreadPerIsland] {
+            assert_eq!(Ok(model), model.to_string().parse().map_err(|_| ()));
+        }
+
+        for bad in [
+            "Cyclical",
+            "Circular(1)",
+            "Cyclical(x)",
+            "Cyclical(2",
+            "circular",
+        ] {
+            assert!(matches!(
+                bad.parse::<MigrationAlgorithm>(),
+                Err(GeneticError::InvalidSettingValue { .. })
+            ));
+        }
+        assert!("Unfair".parse::<SelectionCurve>().is_err());
+    }
+
+    #[test]
+    fn completely_random_migration_never_targets_the_source_island() {
+        let mut world = world_builder(&["a", "b", "c"])
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(5)
+            .with_migration_algorithm(MigrationAlgorithm::CompletelyRandom)
+            .build()
+            .unwrap();
+        world.fill_all_islands().unwrap();
+        world.get_island_mut(0).unwrap().sort_individuals();
+        world.get_island_mut(1).unwrap().sort_individuals();
+        world.get_island_mut(2).unwrap().sort_individuals();
+
+        let before = world.snapshot();
+        world.migrate_individuals_between_islands();
+        let after = world.snapshot();
+
+        let mut migrants = 0;
+        for (before, after) in before.islands.iter().zip(after.islands.iter()) {
+            assert!(after
+                .future
+                .iter()
+                .all(|id| !before.individuals.contains(id)));
+            migrants += after.future.len();
+        }
+        assert_eq!(15, migrants);
+    }
+
+    #[test]
+    fn migration_algorithm_follows_the_schedule() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(10)
+            .with_migration_algorithm(MigrationAlgorithm::CompletelyRandom)
+            .with_migration_algorithm_at(3, MigrationAlgorithm::Cyclical(1))
+            .with_migration_algorithm_at(2, MigrationAlgorithm::RandomCircular)
+            .build()
+            .unwrap();
+
+        let mut algorithms = vec![];
+        for _ in 0..4 {
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+            algorithms.push(world.migration_algorithm().clone());
+        }
+
+        assert_eq!(
+            vec![
+                MigrationAlgorithm::CompletelyRandom,
+                MigrationAlgorithm::RandomCircular,
+                MigrationAlgorithm::Cyclical(1),
+                MigrationAlgorithm::Cyclical(1),
+            ],
+            algorithms
+        );
+    }
+}
@@ -0,0 +1,35 @@
+/// Describes the emigration from one island during a migration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationRecord {
+    /// The generation count of the world when the migration happened
+    pub generation: usize,
+
+    /// The index of the island the individuals left
+    pub source_island: usize,
+
+    /// The number of individuals that were configured to migrate
+    pub requested: usize,
+
+    /// The number of individuals the island could provide. When this is less than `requested`, the configured
+    /// `MigrationShortfall` decided how many actually left.
+    pub available: usize,
+
+    /// Every individual that migrated, in the order they left
+    pub migrants: Vec<Migrant>,
+
+    /// The individuals that were chosen to migrate but turned away for being too similar to the population of their
+    /// destination. They stayed on the source island.
+    pub rejected: Vec<Migrant>,
+}
+
+/// One individual that left its island during a migration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Migrant {
+    /// The individual that migrated
+    pub id: u64,
+
+    /// The index of the island whose next generation received the individual
+    pub destination_island: usize,
+}
@@ -0,0 +1,60 @@
+/// Defines what happens when it is time for a migration but an island has fewer individuals available than the
+/// `MigrantCount` requests. This can happen when earlier migrations drained an island, or when the island
+/// has not been sorted since its last generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MigrationShortfall {
+    /// No individuals leave the island during this migration.
+    Skip,
+
+    /// Every available individual migrates, up to the requested number.
+    MigrateAvailable,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    fn drain_twice(shortfall: MigrationShortfall) -> Vec<MigrationRecord> {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(3)
+            .with_clone_migrated_individuals(false)
+            .with_migration_shortfall(shortfall)
+            .build()
+            .unwrap();
+        world.fill_all_islands().unwrap();
+        world.get_island_mut(0).unwrap().sort_individuals();
+        world.get_island_mut(1).unwrap().sort_individuals();
+
+        // The first migration leaves only one individual on each island
+        world.migrate_individuals_between_islands();
+        world.migrate_individuals_between_islands();
+
+        world.migration_log().to_vec()
+    }
+
+    #[test]
+    fn migration_shortfall_migrates_available() {
+        let log = drain_twice(MigrationShortfall::MigrateAvailable);
+
+        assert_eq!(2, log.len());
+        for record in log {
+            assert_eq!(3, record.requested);
+            assert_eq!(1, record.available);
+            assert_eq!(1, record.migrants.len());
+        }
+    }
+
+    #[test]
+    fn migration_shortfall_skips() {
+        let log = drain_twice(MigrationShortfall::Skip);
+
+        assert_eq!(2, log.len());
+        for record in log {
+            assert_eq!(1, record.available);
+            assert!(record.migrants.is_empty());
+        }
+    }
+}
@@ -0,0 +1,191 @@
+/// Evaluates every individual several times for fitness functions that give a different score each time an
+/// individual is run. The island ranks individuals by the aggregate of their samples instead of by a single run.
+///
+/// While noisy evaluation is active, the island ranks by score alone and `IslandEngine::sort_individuals` is not used.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoisyEvaluation {
+    /// The number of times each individual is run and scored. Must be greater than zero.
+    pub evaluations: usize,
+
+    /// How the samples of one individual are combined into the score used for ranking
+    pub aggregation: ScoreAggregation,
+
+    /// If true, individuals that stay on an island for another generation, such as elites, are evaluated again and
+    /// their old samples are discarded, so a lucky sample cannot keep them on top. If false, they keep their samples
+    /// and are not run again. Only applies to elites under `EliteHandling::CopyAndReevaluate`; the other elite
+    /// handlings rank elites by the score they were selected with.
+    pub resample_elites: bool,
+
+    /// When set, individuals whose rank is still ambiguous after `evaluations` samples are sampled further, so the
+    /// budget is spent where it changes the ranking rather than on clear winners and losers.
+    pub adaptive: Option<AdaptiveResampling>,
+}
+
+impl Default for NoisyEvaluation {
+    fn default() -> Self {
+        NoisyEvaluation {
+            evaluations: 5,
+            aggregation: ScoreAggregation::Mean,
+            resample_elites: true,
+            adaptive: None,
+        }
+    }
+}
+
+/// Sequential sampling for `NoisyEvaluation`. After every individual has its initial samples, neighbors in the ranking
+/// are compared. When the difference between their mean scores is smaller than `confidence` standard errors, the pair
+/// is ambiguous and the sibling with fewer samples is evaluated again. This repeats until no ambiguous pair can be
+/// sampled further.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveResampling {
+    /// The most samples any individual receives. Must be at least `NoisyEvaluation::evaluations`.
+    pub max_evaluations: usize,
+
+    /// The number of standard errors that separate two individuals whose order is considered settled. 1.96 is roughly
+    /// 95% confidence for normally distributed noise.
+    pub confidence: f64,
+}
+
+impl Default for AdaptiveResampling {
+    fn default() -> Self {
+        AdaptiveResampling {
+            max_evaluations: 20,
+            confidence: 1.96,
+        }
+    }
+}
+
+impl AdaptiveResampling {
+    /// Returns true if two individuals cannot yet be ordered with confidence
+    pub(crate) fn is_ambiguous(&self, a: &[u64], b: &[u64]) -> bool {
+        let (Some(variance_a), Some(variance_b)) = (sample_variance(a), sample_variance(b)) else {
+            return true;
+        };
+        let standard_error = (variance_a / a.len() as f64 + variance_b / b.len() as f64).sqrt();
+        (mean(a) - mean(b)).abs() < self.confidence * standard_error
+    }
+}
+
+fn mean(samples: &[u64]) -> f64 {
+    samples.iter().map(|&sample| sample as f64).sum::<f64>() / samples.len() as f64
+}
+
+/// Combines several scores of the same individual into one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScoreAggregation {
+    /// The mean of the samples, rounded to the nearest integer
+    #[default]
+    Mean,
+
+    /// The median of the samples, rounding the mean of the two middle samples when there is an even number of them
+    Median,
+
+    /// The lowest sample, which favors individuals that are reliably good
+    Min,
+}
+
+impl ScoreAggregation {
+    /// Combines the samples. There must be at least one sample.
+    pub(crate) fn aggregate(&self, samples: &[u64]) -> u64 {
+        match self {
+            ScoreAggregation::Mean => {
+                let sum: f64 = samples.iter().map(|&sample| sample as f64).sum();
+                (sum / samples.len() as f64).round() as u64
+            }
+            ScoreAggregation::Median => {
+                let mut sorted = samples.to_vec();
+                sorted.sort_unstable();
+                let middle = sorted.len() / 2;
+                if sorted.len() % 2 == 1 {
+                    sorted[middle]
+                } else {
+                    ((sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.0).round() as u64
+                }
+            }
+            ScoreAggregation::Min => samples.iter().copied().min().unwrap(),
+        }
+    }
+}
+
+/// Returns the unbiased variance of the samples, or None if there are fewer than two
+pub(crate) fn sample_variance(samples: &[u64]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let mean = mean(samples);
+    let squares: f64 = samples
+        .iter()
+        .map(|&sample| (sample as f64 - mean).powi(2))
+        .sum();
+    Some(squares / (samples.len() - 1) as f64)
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn noisy_evaluation_aggregates_repeated_scores() {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_elite_individuals(1)
+            .with_migrating_individuals(0)
+            .with_noisy_evaluation(NoisyEvaluation {
+                evaluations: 2,
+                aggregation: ScoreAggregation::Mean,
+                resample_elites: false,
+                adaptive: None,
+            });
+        builder.add_island("noisy", Box::new(NoisyEngine { noise: 3, runs: 0 }));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(2).unwrap();
+
+        // The two samples of every individual are 10 * id + 3 and 10 * id - 3, so the mean is exact
+        let island = world.get_island(0).unwrap();
+        for index in 0..island.len() {
+            let id = island.get_one_individual(index).unwrap();
+            assert_eq!(Some(id * 10), island.score_for_individual(index));
+        }
+        let stats = &world.stats_history()[1].islands[0];
+        assert_eq!(Some(18.0), stats.mean_score_variance);
+
+        // The elite kept its samples from the first generation and was not run again
+        assert_eq!((1, 6), (stats.cached_evaluations, stats.evaluations));
+    }
+
+    #[test]
+    fn adaptive_resampling_spends_evaluations_on_ambiguous_ranks() {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(6)
+            .with_migrating_individuals(0)
+            .with_noisy_evaluation(NoisyEvaluation {
+                evaluations: 2,
+                adaptive: Some(AdaptiveResampling {
+                    max_evaluations: 6,
+                    confidence: 2.0,
+                }),
+                ..NoisyEvaluation::default()
+            });
+        let engine = PartlyNoisyEngine {
+            noise: 20,
+            noisy_below: 3,
+            runs: 0,
+        };
+        builder.add_island("noisy", Box::new(engine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(1).unwrap();
+
+        // Individuals 0, 1 and 2 overlap under the noise and are sampled up to the maximum. The quiet individuals 3, 4
+        // and 5 are clearly separated and keep their two samples, apart from 3 which borders the noisy ones.
+        let evaluations = world.stats_history()[0].islands[0].evaluations;
+        assert!(evaluations > 12);
+        assert!(evaluations <= 6 * 3 + 6 + 2 * 2);
+    }
+}
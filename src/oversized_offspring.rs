@@ -0,0 +1,17 @@
+/// What the genetic engine does with a child whose `Genetics::size` exceeds the engine's `max_individual_points`.
+/// Without a limit on offspring, repeated crossover can grow genomes without bound. Genetics that can shrink a genome
+/// themselves, for example by truncating it, should do so in `Genetics::repair`, which runs before this check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OversizedOffspring {
+    /// Offspring of any size are kept. Genetics that do not report a size are never checked.
+    #[default]
+    Allow,
+
+    /// The child is rejected like an invalid one, and another is bred instead, up to
+    /// `GeneticEngineBuilder::max_child_attempts` times
+    Reject,
+
+    /// The child is replaced by an unchanged copy of its first parent, as if it had been bred by reproduction
+    CopyParent,
+}
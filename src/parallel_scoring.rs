@@ -0,0 +1,73 @@
+use rayon::prelude::*;
+
+use crate::IslandEngine;
+
+/// Scores individuals with the engine on the threads of the rayon pool and returns the scores in the same order
+pub(crate) fn score_in_parallel(engine: &dyn IslandEngine, individuals: &[u64]) -> Vec<u64> {
+    individuals
+        .par_iter()
+        .map(|&id| engine.score_individual(id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    #[cfg(feature = "async")]
+    use crate::test_support::block_on;
+    use crate::*;
+
+    // Scores each individual by its id modulo 7, so that many individuals tie, and counts the scores computed
+    struct CountingScoreEngine {
+        scores: Arc<AtomicUsize>,
+    }
+
+    impl IslandEngine for CountingScoreEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            self.scores.fetch_add(1, Ordering::Relaxed);
+            id % 7
+        }
+    }
+
+    #[test]
+    fn scores_keep_the_order_of_the_individuals() {
+        let engine = CountingScoreEngine {
+            scores: Arc::default(),
+        };
+        let individuals: Vec<u64> = (0..1000).rev().collect();
+        let scores = score_in_parallel(&engine, &individuals);
+        assert_eq!(
+            individuals.iter().map(|id| id % 7).collect::<Vec<_>>(),
+            scores
+        );
+    }
+
+    #[test]
+    fn parallel_scores_sort_islands_like_pairwise_comparisons() {
+        let scores = Arc::new(AtomicUsize::new(0));
+        let engine = CountingScoreEngine {
+            scores: scores.clone(),
+        };
+        let mut island = Island::new("a", IslandRole::Standard, Box::new(engine));
+        let individuals: Vec<u64> = (0..1000).map(|id| id * 7919 % 1000).collect();
+        for &id in individuals.iter() {
+            island.add_individual_to_future_generation(id);
+        }
+        island.advance_generation();
+        #[cfg(not(feature = "async"))]
+        island.run_one_generation().unwrap();
+        #[cfg(feature = "async")]
+        block_on(island.run_one_generation()).unwrap();
+
+        // A stable sort by score, as the default pairwise comparison gives, with each individual scored only once
+        let mut expected = individuals.clone();
+        expected.sort_by_key(|id| id % 7);
+        assert_eq!(expected, island.snapshot().individuals);
+        assert_eq!(1000, scores.load(Ordering::Relaxed));
+    }
+}
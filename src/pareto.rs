@@ -0,0 +1,119 @@
+/// Orders individuals for multi-objective selection, most preferred first. Individuals are ranked by Pareto front
+/// (non-dominated individuals first, with every objective maximized), and within a front by crowding distance, so that
+/// individuals in sparsely populated regions of the front come before those in crowded regions. The result holds the
+/// indices of `objectives`.
+pub(crate) fn crowding_order(objectives: &[Vec<f64>]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(objectives.len());
+    for front in pareto_fronts(objectives) {
+        let distances = crowding_distances(objectives, &front);
+        let mut ranked: Vec<(usize, f64)> = front.into_iter().zip(distances).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        order.extend(ranked.into_iter().map(|(index, _)| index));
+    }
+
+    order
+}
+
+// Returns true if `a` is at least as good as `b` in every objective and better in at least one
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x >= y) && a.iter().zip(b.iter()).any(|(x, y)| x > y)
+}
+
+// Splits the individuals into successive non-dominated fronts
+fn pareto_fronts(objectives: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+    let mut dominated_by_count = vec![0usize; len];
+    let mut dominates_list: Vec<Vec<usize>> = vec![vec![]; len];
+    for a in 0..len {
+        for b in 0..len {
+            if a != b && dominates(&objectives[a], &objectives[b]) {
+                dominates_list[a].push(b);
+                dominated_by_count[b] += 1;
+            }
+        }
+    }
+
+    let mut fronts = vec![];
+    let mut current: Vec<usize> = (0..len).filter(|&i| dominated_by_count[i] == 0).collect();
+    while !current.is_empty() {
+        let mut next = vec![];
+        for &a in current.iter() {
+            for &b in dominates_list[a].iter() {
+                dominated_by_count[b] -= 1;
+                if dominated_by_count[b] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+        fronts.push(current);
+        current = next;
+    }
+
+    fronts
+}
+
+// Calculates the crowding distance of each member of a front. The extremes of every objective are infinitely far
+// from their neighbors.
+fn crowding_distances(objectives: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distances = vec![0.0; front.len()];
+    let objective_count = front.first().map_or(0, |&i| objectives[i].len());
+    let columns: Vec<Vec<f64>> = (0..objective_count)
+        .map(|objective| front.iter().map(|&i| objectives[i][objective]).collect())
+        .collect();
+    for values in columns {
+        let mut sorted: Vec<usize> = (0..front.len()).collect();
+        sorted.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let first = *sorted.first().unwrap();
+        let last = *sorted.last().unwrap();
+        distances[first] = f64::INFINITY;
+        distances[last] = f64::INFINITY;
+        let range = values[last] - values[first];
+        if range <= 0.0 {
+            continue;
+        }
+        for window in sorted.windows(3) {
+            distances[window[1]] += (values[window[2]] - values[window[0]]) / range;
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crowding_order;
+
+    #[test]
+    fn front_members_in_sparse_regions_come_first() {
+        let objectives = vec![
+            vec![1.0, 1.0], // dominated by everything on the front
+            vec![0.0, 10.0],
+            vec![2.0, 8.0],
+            vec![9.0, 1.0],
+            vec![10.0, 0.0],
+        ];
+
+        // The extremes come first, then the front member with the most room around it, then the dominated individual
+        assert_eq!(vec![1, 4, 2, 3, 0], crowding_order(&objectives));
+    }
+
+    #[test]
+    fn ties_and_missing_objectives_keep_their_order() {
+        assert!(crowding_order(&[]).is_empty());
+        assert_eq!(vec![0], crowding_order(&[vec![5.0, 5.0]]));
+
+        // Identical individuals share a front, and only the two kept as its extremes are infinitely far apart
+        assert_eq!(
+            vec![0, 2, 1],
+            crowding_order(&[vec![1.0, 1.0], vec![1.0, 1.0], vec![1.0, 1.0]])
+        );
+
+        // Without objectives nothing is preferred, and a single objective puts every individual on a front of its own
+        assert_eq!(vec![0, 1, 2], crowding_order(&[vec![], vec![], vec![]]));
+        assert_eq!(
+            vec![1, 2, 0],
+            crowding_order(&[vec![1.0], vec![3.0], vec![2.0]])
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{Genetics, IndividualStore};
+
+/// How `PermutationGenetics` combines two parents. Both keep a segment of the first parent in place and take the rest
+/// from the second, so that the child is always a permutation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PermutationCrossover {
+    /// Order crossover (OX): the remaining positions are filled with the missing elements in the order they appear in
+    /// the second parent, which preserves relative order, such as the sequence of a tour
+    #[default]
+    Order,
+
+    /// Partially-mapped crossover (PMX): the remaining positions keep the second parent's elements where possible,
+    /// which preserves absolute positions, such as the slot of a job in a schedule
+    PartiallyMapped,
+}
+
+/// How `PermutationGenetics` changes an individual. The engine's mutation points are the number of moves made.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PermutationMutation {
+    /// Exchanges two random elements
+    #[default]
+    Swap,
+
+    /// Reverses a random segment, which for a tour replaces only the two edges at its ends
+    Inversion,
+}
+
+/// A ready-made `Genetics` for permutations of `0..length`, for routing and scheduling problems such as the travelling
+/// salesman. The distance between two individuals is the number of positions at which they differ. The permutations
+/// are kept in an `IndividualStore`, which island engines can read through `store`.
+pub struct PermutationGenetics {
+    length: usize,
+    crossover: PermutationCrossover,
+    mutation: PermutationMutation,
+    store: Arc<IndividualStore<Vec<usize>>>,
+}
+
+impl PermutationGenetics {
+    /// Creates genetics for permutations of the given length. The engine's `max_individual_points` is ignored.
+    pub fn new(length: usize) -> Self {
+        PermutationGenetics {
+            length,
+            crossover: PermutationCrossover::default(),
+            mutation: PermutationMutation::default(),
+            store: Arc::new(IndividualStore::new()),
+        }
+    }
+
+    pub fn with_crossover(mut self, crossover: PermutationCrossover) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    pub fn with_mutation(mut self, mutation: PermutationMutation) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// Returns the number of elements in every individual
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns a handle to the store of permutations, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<usize>>> {
+        self.store.clone()
+    }
+
+    /// Returns the permutation of an individual, or None if it is not stored
+    pub fn permutation(&self, id: u64) -> Option<Arc<Vec<usize>>> {
+        self.store.get(id)
+    }
+
+    /// Stores a permutation created outside of the genetic operators, such as a seed individual, and returns its id.
+    /// Returns None if it is not a permutation of `0..length`.
+    pub fn insert(&self, permutation: Vec<usize>) -> Option<u64> {
+        let mut seen = vec![false; self.length];
+        let is_permutation = permutation.len() == self.length
+            && permutation.iter().all(|&element| {
+                element < self.length && !std::mem::replace(&mut seen[element], true)
+            });
+        is_permutation.then(|| self.store.insert(permutation))
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<usize>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    // Picks the inclusive bounds of a random segment
+    fn segment(&self, rng: &mut StdRng) -> (usize, usize) {
+        let a = rng.random_range(0..self.length);
+        let b = rng.random_range(0..self.length);
+        (a.min(b), a.max(b))
+    }
+}
+
+impl Genetics for PermutationGenetics {
+    fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> u64 {
+        let mut permutation: Vec<usize> = (0..self.length).collect();
+        permutation.shuffle(rng);
+        self.store.insert(permutation)
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        if self.length > 1 {
+            for _ in 0..points {
+                let (start, end) = self.segment(rng);
+                match self.mutation {
+                    PermutationMutation::Swap => child.swap(start, end),
+                    PermutationMutation::Inversion => child[start..=end].reverse(),
+                }
+            }
+        }
+        self.store.insert(child)
+    }
+
+    // The engine's crossover points are not used; a single segment is kept from the first parent
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        if self.length == 0 {
+            return self.store.insert(vec![]);
+        }
+
+        let (start, end) = self.segment(rng);
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let child = match self.crossover {
+            PermutationCrossover::Order => order_crossover(&a, &b, start, end),
+            PermutationCrossover::PartiallyMapped => partially_mapped_crossover(&a, &b, start, end),
+        };
+        self.store.insert(child)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        a.iter().zip(b.iter()).filter(|(a, b)| a != b).count() as f64
+    }
+
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |permutation| hash_genome(&*permutation))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let permutation = self.store.get(individual)?;
+        Some(
+            permutation
+                .iter()
+                .map(|element| element.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let permutation = if genome.trim().is_empty() {
+            vec![]
+        } else {
+            genome
+                .split(',')
+                .map(|element| element.trim().parse().ok())
+                .collect::<Option<Vec<usize>>>()?
+        };
+        self.insert(permutation)
+    }
+
+    // Random-key decoding: the elements are ordered by their coordinate of the point
+    fn individual_from_point(&self, point: &[f64]) -> Option<u64> {
+        if point.len() != self.length {
+            return None;
+        }
+        let mut permutation: Vec<usize> = (0..self.length).collect();
+        permutation.sort_by(|&a, &b| point[a].total_cmp(&point[b]));
+        self.insert(permutation)
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+// Keeps a[start..=end] in place and fills the other positions, starting after the segment and wrapping around, with the
+// missing elements in the order they follow the segment in b
+fn order_crossover(a: &[usize], b: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let length = a.len();
+    let mut child = vec![usize::MAX; length];
+    let mut used = vec![false; length];
+    for position in start..=end {
+        child[position] = a[position];
+        used[a[position]] = true;
+    }
+
+    let mut position = (end + 1) % length;
+    for offset in 1..=length {
+        let element = b[(end + offset) % length];
+        if !used[element] {
+            child[position] = element;
+            used[element] = true;
+            position = (position + 1) % length;
+        }
+    }
+
+    child
+}
+
+// Keeps a[start..=end] in place and b's elements elsewhere. An element of b displaced by the segment is placed where
+// the mapping between the two segments leads out of the segment.
+fn partially_mapped_crossover(a: &[usize], b: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let mut position_in_b = vec![0; b.len()];
+    for (position, &element) in b.iter().enumerate() {
+        position_in_b[element] = position;
+    }
+
+    let mut child = b.to_vec();
+    child[start..=end].copy_from_slice(&a[start..=end]);
+    for (position, &element) in b.iter().enumerate().take(end + 1).skip(start) {
+        if a[start..=end].contains(&element) {
+            continue;
+        }
+        let mut target = position;
+        while (start..=end).contains(&target) {
+            target = position_in_b[a[target]];
+        }
+        child[target] = element;
+    }
+
+    child
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    // Scores a tour of cities evenly spaced on a line, returning to the start, higher the shorter it is
+    struct TourEngine {
+        store: Arc<IndividualStore<Vec<usize>>>,
+    }
+
+    impl IslandEngine for TourEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let tour = self.store.get(id).unwrap();
+            let length: usize = (0..tour.len())
+                .map(|index| tour[index].abs_diff(tour[(index + 1) % tour.len()]))
+                .sum();
+            1000 - length as u64
+        }
+    }
+
+    #[test]
+    fn children_are_permutations() {
+        assert_eq!(
+            vec![6, 5, 2, 3, 4, 1, 0, 7],
+            order_crossover(&[0, 1, 2, 3, 4, 5, 6, 7], &[7, 6, 5, 4, 3, 2, 1, 0], 2, 4)
+        );
+        assert_eq!(
+            vec![7, 6, 2, 3, 4, 5, 1, 0],
+            partially_mapped_crossover(&[0, 1, 2, 3, 4, 5, 6, 7], &[7, 6, 5, 4, 3, 2, 1, 0], 2, 4)
+        );
+
+        let mut rng = StdRng::seed_from_u64(1234);
+        for (crossover, mutation) in [
+            (PermutationCrossover::Order, PermutationMutation::Swap),
+            (
+                PermutationCrossover::PartiallyMapped,
+                PermutationMutation::Inversion,
+            ),
+        ] {
+            let genetics = PermutationGenetics::new(10)
+                .with_crossover(crossover)
+                .with_mutation(mutation);
+            assert_eq!(None, genetics.decode("0,1,1,2,3,4,5,6,7,8"));
+            let a = genetics.random_individual(&mut rng, 0);
+            let b = genetics.random_individual(&mut rng, 0);
+            for _ in 0..100 {
+                let mutant = genetics.mutate(&mut rng, a, 2);
+                let child = genetics.crossover(&mut rng, mutant, b, 1);
+                let encoded = genetics.encode(child).unwrap();
+                assert!(genetics.decode(&encoded).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn the_shortest_tour_is_found() {
+        let genetics = PermutationGenetics::new(8).with_mutation(PermutationMutation::Inversion);
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(30)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(TourEngine { store }));
+        let mut world = world.build().unwrap();
+
+        // Going out and back along the line covers it twice
+        world.run_n_generations(50).unwrap();
+        assert_eq!(Some(1000 - 14), world.best_score());
+    }
+}
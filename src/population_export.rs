@@ -0,0 +1,103 @@
+/// The current generation of an island in a form that can be inspected offline or moved to another machine. Produced
+/// as JSON by `Island::export_population` and read back by `World::import_population`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PopulationExport {
+    /// The name of the island the population was exported from
+    pub island: String,
+
+    /// True if the individuals are in rank order, from least fit to most fit
+    pub sorted: bool,
+
+    /// The individuals of the current generation
+    pub individuals: Vec<ExportedIndividual>,
+}
+
+/// One individual of a `PopulationExport`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedIndividual {
+    /// The id of the individual in the process that exported it
+    pub id: u64,
+
+    /// The score of the individual, if the island had been sorted
+    pub score: Option<u64>,
+
+    /// The genome written by `Genetics::encode`, or None if the genetics cannot encode individuals
+    pub genome: Option<String>,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn populations_move_between_worlds() {
+        let mut source = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        source.run_n_generations(2).unwrap();
+        let island = source.get_island(0).unwrap();
+        let data = island.export_population(source.genetic_engine().genetics());
+
+        let mut target = world_builder(&["b", "c"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        target.import_population("c", &data).unwrap();
+        let imported = target.get_island(1).unwrap().snapshot();
+        assert_eq!(island.snapshot().individuals, imported.individuals);
+        assert!(!imported.sorted);
+
+        let export: PopulationExport = serde_json::from_str(&data).unwrap();
+        assert_eq!("a", export.island);
+        assert_eq!(island.score_for_individual(3), export.individuals[3].score);
+        assert!(matches!(
+            target.import_population("b", "{}"),
+            Err(GeneticError::InvalidPopulation(_))
+        ));
+        assert!(matches!(
+            target.import_population("a", &data),
+            Err(GeneticError::UnknownIsland(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn undecodable_populations_leave_the_island_unchanged() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+
+        // An island that never ran exports no individuals and no scores
+        let empty = world
+            .get_island(0)
+            .unwrap()
+            .export_population(world.genetic_engine().genetics());
+        let export: PopulationExport = serde_json::from_str(&empty).unwrap();
+        assert!(!export.sorted && export.individuals.is_empty());
+
+        world.run_n_generations(1).unwrap();
+        let before = world.get_island(0).unwrap().snapshot();
+        for genome in ["null", "\"not a number\""] {
+            let data = format!(
+                r#"{{"island": "a", "sorted": false, "individuals": [{{"id": 1, "score": null, "genome": "7"}},
+                {{"id": 2, "score": null, "genome": {}}}]}}"#,
+                genome
+            );
+            assert!(matches!(
+                world.import_population("a", &data),
+                Err(GeneticError::InvalidPopulation(message)) if message == "individual 2 could not be decoded"
+            ));
+            assert_eq!(before, world.get_island(0).unwrap().snapshot());
+        }
+
+        world.import_population("a", &empty).unwrap();
+        assert!(world.get_island(0).unwrap().is_empty());
+    }
+}
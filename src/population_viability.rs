@@ -0,0 +1,125 @@
+/// Checks that an island has enough individuals to breed from before each generation is filled. Without clones,
+/// migration moves individuals away, and an island that loses most of its generation goes on breeding its next one
+/// from the few that are left, mostly by mating them with themselves. Islands that are empty are not checked, as they
+/// start over from new individuals, and neither are quarantined and archive islands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PopulationViability {
+    /// The fewest individuals an island may breed from. Must be at least one and no more than
+    /// `individuals_per_island`.
+    pub min_individuals: usize,
+
+    /// What happens when an island has fewer individuals
+    ///
+    /// Default: ViabilityAction::Warn
+    pub action: ViabilityAction,
+}
+
+/// What a `PopulationViability` check does with an island that has too few individuals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ViabilityAction {
+    /// Observers are told with `WorldObserver::population_below_minimum`, and the island breeds from what it has
+    #[default]
+    Warn,
+
+    /// Filling the island fails with `GeneticError::PopulationNotViable`
+    Error,
+}
+
+impl PopulationViability {
+    pub fn new(min_individuals: usize) -> Self {
+        PopulationViability {
+            min_individuals,
+            action: ViabilityAction::Warn,
+        }
+    }
+
+    pub fn with_action(mut self, action: ViabilityAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Returns true if every field is in its valid range for islands of the given size
+    pub(crate) fn is_valid(&self, individuals_per_island: usize) -> bool {
+        self.min_individuals > 0 && self.min_individuals <= individuals_per_island
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_support::*;
+    use crate::*;
+
+    struct Recorder(Arc<Mutex<Vec<(usize, usize)>>>);
+
+    impl WorldObserver for Recorder {
+        fn population_below_minimum(&mut self, _: usize, island: usize, individuals: usize) {
+            self.0.lock().unwrap().push((island, individuals));
+        }
+    }
+
+    #[test]
+    fn drained_islands_fail_the_viability_check() {
+        let drained = |action: ViabilityAction, events: Arc<Mutex<Vec<(usize, usize)>>>| {
+            let mut world = world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(3)
+                .with_clone_migrated_individuals(false)
+                .with_population_viability(PopulationViability::new(2).with_action(action))
+                .with_observer(Recorder(events))
+                .build()
+                .unwrap();
+            world.fill_all_islands().unwrap();
+            world.get_island_mut(0).unwrap().sort_individuals();
+            world.get_island_mut(1).unwrap().sort_individuals();
+
+            // The migration leaves only one individual on each island to breed from
+            world.migrate_individuals_between_islands();
+            world.fill_all_islands()
+        };
+
+        let events = Arc::new(Mutex::new(vec![]));
+        drained(ViabilityAction::Warn, events.clone()).unwrap();
+        assert_eq!(vec![(0, 1), (1, 1)], *events.lock().unwrap());
+        assert!(matches!(
+            drained(ViabilityAction::Error, events.clone()),
+            Err(GeneticError::PopulationNotViable { island, individuals: 1, minimum: 2 }) if island == "a"
+        ));
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_migrating_individuals(1)
+                .with_population_viability(PopulationViability::new(101))
+                .build(),
+            Err(GeneticError::InvalidPopulationViability)
+        ));
+    }
+
+    #[test]
+    fn islands_at_the_minimum_and_empty_islands_are_viable() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(2)
+            .with_clone_migrated_individuals(false)
+            .with_population_viability(
+                PopulationViability::new(2).with_action(ViabilityAction::Error),
+            )
+            .with_observer(Recorder(events.clone()))
+            .build()
+            .unwrap();
+
+        // The first fill starts from empty islands, and the migration leaves exactly the minimum behind
+        world.fill_all_islands().unwrap();
+        world.get_island_mut(0).unwrap().sort_individuals();
+        world.get_island_mut(1).unwrap().sort_individuals();
+        world.migrate_individuals_between_islands();
+        world.fill_all_islands().unwrap();
+        assert!(events.lock().unwrap().is_empty());
+
+        assert!(PopulationViability::new(4).is_valid(4));
+        assert!(!PopulationViability::new(0).is_valid(4));
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::sync::{Condvar, Mutex, OnceLock};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// Limits how often individuals are run, for island engines whose evaluations call an external service with a quota.
+/// The limit is a token bucket shared by every island of the world: each run takes a token, tokens are added at
+/// `requests_per_second` up to `burst`, and a run that finds the bucket empty waits until a token is available.
+///
+/// Every run of an individual counts, including repeated noisy evaluations and reference individuals. Without the
+/// `async` feature, waiting blocks the thread that evaluates the island. With it, the island awaits the wait instead,
+/// so the executor can run other tasks in the meantime. The time each island spent waiting is reported in
+/// `IslandStats::rate_limit_wait`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimit {
+    /// The sustained number of runs allowed per second. Must be greater than zero.
+    pub requests_per_second: f64,
+
+    /// The number of runs allowed back to back after the world has been idle, which is also the number available when
+    /// the world starts. Must be at least one.
+    ///
+    /// Default: 1
+    pub burst: usize,
+}
+
+impl RateLimit {
+    pub fn new(requests_per_second: f64) -> Self {
+        RateLimit {
+            requests_per_second,
+            burst: 1,
+        }
+    }
+
+    pub fn with_burst(mut self, burst: usize) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        self.requests_per_second > 0.0 && self.requests_per_second.is_finite() && self.burst > 0
+    }
+}
+
+/// The token bucket of a `RateLimit`.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        TokenBucket {
+            tokens: limit.burst as f64,
+            limit,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token and returns how long to wait before using it. A token that is not available yet is reserved, so
+    /// callers that wait their turn in order are never starved.
+    pub(crate) fn acquire(&mut self) -> Duration {
+        let now = Instant::now();
+        let refilled =
+            now.duration_since(self.last_refill).as_secs_f64() * self.limit.requests_per_second;
+        self.tokens = (self.tokens + refilled).min(self.limit.burst as f64);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.limit.requests_per_second)
+        }
+    }
+}
+
+/// A future that completes once its duration has passed, without blocking the executor that polls it. The crate does
+/// not depend on an async runtime, so every delay is woken by a single timer thread shared by the whole process.
+#[cfg(feature = "async")]
+pub(crate) struct Delay {
+    deadline: Instant,
+}
+
+#[cfg(feature = "async")]
+impl Delay {
+    pub(crate) fn new(duration: Duration) -> Self {
+        Delay {
+            deadline: Instant::now() + duration,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        Timer::shared().wake_at(self.deadline, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// The timer behind every `Delay`: a thread that sleeps until the earliest deadline and then wakes the tasks whose
+/// deadlines have passed. It is started by the first delay that has to wait.
+#[cfg(feature = "async")]
+struct Timer {
+    pending: Mutex<Vec<(Instant, Waker)>>,
+    changed: Condvar,
+}
+
+#[cfg(feature = "async")]
+impl Timer {
+    fn shared() -> &'static Timer {
+        static TIMER: OnceLock<&'static Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            let timer: &'static Timer = Box::leak(Box::new(Timer {
+                pending: Mutex::new(vec![]),
+                changed: Condvar::new(),
+            }));
+            std::thread::Builder::new()
+                .name("rate-limit-timer".to_string())
+                .spawn(|| timer.run())
+                .expect("failed to start the rate limit timer thread");
+            timer
+        })
+    }
+
+    // Wakes the task once the deadline has passed
+    fn wake_at(&self, deadline: Instant, waker: Waker) {
+        self.pending.lock().unwrap().push((deadline, waker));
+        self.changed.notify_one();
+    }
+
+    fn run(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            pending.retain(|(deadline, waker)| {
+                let due = *deadline <= now;
+                if due {
+                    waker.wake_by_ref();
+                }
+                !due
+            });
+            pending = match pending.iter().map(|(deadline, _)| *deadline).min() {
+                Some(next) => self.changed.wait_timeout(pending, next - now).unwrap().0,
+                None => self.changed.wait(pending).unwrap(),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn runs_beyond_the_burst_wait_for_tokens() {
+        let mut bucket = TokenBucket::new(RateLimit::new(10.0).with_burst(2));
+        assert_eq!(Duration::ZERO, bucket.acquire());
+        assert_eq!(Duration::ZERO, bucket.acquire());
+
+        // Each further token is reserved a tenth of a second after the one before
+        let first = bucket.acquire();
+        let second = bucket.acquire();
+        assert!(first > Duration::from_millis(90) && first <= Duration::from_millis(100));
+        assert!(second > Duration::from_millis(190) && second <= Duration::from_millis(200));
+        assert!(!RateLimit::new(0.0).is_valid() && !RateLimit::new(1.0).with_burst(0).is_valid());
+    }
+
+    #[test]
+    fn evaluations_are_rate_limited_across_islands() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(3)
+            .with_migrating_individuals(1)
+            .with_rate_limit(RateLimit::new(100.0).with_burst(2))
+            .build()
+            .unwrap();
+        let started = std::time::Instant::now();
+        #[cfg(not(feature = "async"))]
+        world.run_n_generations(1).unwrap();
+        #[cfg(feature = "async")]
+        block_on(world.run_n_generations(1)).unwrap();
+
+        // Six runs through a shared burst of two leave four to wait 10ms each. Each island only reports the time it
+        // waited itself, which is shorter than the whole run when the islands wait at the same time.
+        assert!(started.elapsed() >= std::time::Duration::from_millis(35));
+        let stats = world.stats_history().last().unwrap();
+        let waited: std::time::Duration = stats
+            .islands
+            .iter()
+            .map(|island| island.rate_limit_wait)
+            .sum();
+        assert!(waited > std::time::Duration::ZERO);
+
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_migrating_individuals(1)
+                .with_rate_limit(RateLimit::new(f64::INFINITY))
+                .build(),
+            Err(GeneticError::InvalidRateLimit)
+        ));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn delays_complete_once_their_duration_has_passed() {
+        let started = Instant::now();
+        block_on(Delay::new(Duration::ZERO));
+        assert!(started.elapsed() < Duration::from_millis(10));
+
+        // Delays waiting at the same time share the timer, and each completes at its own deadline
+        let waiting: Vec<_> = [30, 10, 20]
+            .into_iter()
+            .map(|millis| {
+                std::thread::spawn(move || {
+                    let started = Instant::now();
+                    block_on(Delay::new(Duration::from_millis(millis)));
+                    (millis, started.elapsed())
+                })
+            })
+            .collect();
+        for handle in waiting {
+            let (millis, elapsed) = handle.join().unwrap();
+            assert!(elapsed >= Duration::from_millis(millis));
+        }
+    }
+}
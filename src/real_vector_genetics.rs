@@ -0,0 +1,349 @@
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::index::sample;
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{GeneticError, Genetics, IndividualStore};
+
+/// How `RealVectorGenetics` changes the genes picked for mutation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RealMutation {
+    /// Adds normally distributed noise whose standard deviation is this fraction of the gene's range
+    Gaussian { sigma: f64 },
+
+    /// Deb's polynomial mutation. A larger distribution index keeps the mutated gene closer to its old value.
+    Polynomial { eta: f64 },
+}
+
+impl Default for RealMutation {
+    fn default() -> Self {
+        RealMutation::Polynomial { eta: 20.0 }
+    }
+}
+
+impl RealMutation {
+    // Returns true if every field is in its valid range
+    fn is_valid(&self) -> bool {
+        match *self {
+            RealMutation::Gaussian { sigma } => sigma.is_finite() && sigma > 0.0,
+            RealMutation::Polynomial { eta } => eta.is_finite() && eta >= 0.0,
+        }
+    }
+}
+
+/// A ready-made `Genetics` for vectors of real numbers, for continuous optimization problems. Every gene has its own
+/// bounds, and the operators never leave them. Mutation changes as many distinct genes as the engine's mutation points,
+/// and crossover is simulated binary crossover (SBX), which spreads children around their parents like single-point
+/// crossover does for bit strings. The vectors are kept in an `IndividualStore`, which island engines can read through
+/// `store`.
+///
+/// The distance between two individuals is the Euclidean distance after scaling every gene to the unit range, so that
+/// genes with wide bounds do not dominate it.
+pub struct RealVectorGenetics {
+    bounds: Vec<(f64, f64)>,
+    mutation: RealMutation,
+    crossover_eta: f64,
+    store: Arc<IndividualStore<Vec<f64>>>,
+}
+
+impl RealVectorGenetics {
+    /// Creates genetics for vectors with one gene for each of the inclusive `(lower, upper)` bounds. The engine's
+    /// `max_individual_points` is ignored. Returns an error if a bound is not finite or not below its upper bound.
+    pub fn new(bounds: Vec<(f64, f64)>) -> Result<Self, GeneticError> {
+        if bounds
+            .iter()
+            .any(|&(lower, upper)| !lower.is_finite() || !upper.is_finite() || lower >= upper)
+        {
+            return Err(GeneticError::InvalidRealBounds);
+        }
+
+        Ok(RealVectorGenetics {
+            bounds,
+            mutation: RealMutation::default(),
+            crossover_eta: 15.0,
+            store: Arc::new(IndividualStore::new()),
+        })
+    }
+
+    /// Sets how genes are mutated. Default: `RealMutation::Polynomial { eta: 20.0 }`
+    pub fn with_mutation(mut self, mutation: RealMutation) -> Result<Self, GeneticError> {
+        if !mutation.is_valid() {
+            return Err(GeneticError::InvalidRealOperator);
+        }
+        self.mutation = mutation;
+        Ok(self)
+    }
+
+    /// Sets the distribution index of simulated binary crossover. A larger index keeps children closer to their
+    /// parents. Default: 15.0
+    pub fn with_crossover_eta(mut self, eta: f64) -> Result<Self, GeneticError> {
+        if !eta.is_finite() || eta < 0.0 {
+            return Err(GeneticError::InvalidRealOperator);
+        }
+        self.crossover_eta = eta;
+        Ok(self)
+    }
+
+    /// Returns the bounds of every gene
+    pub fn bounds(&self) -> &[(f64, f64)] {
+        &self.bounds
+    }
+
+    /// Returns a handle to the store of vectors, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<f64>>> {
+        self.store.clone()
+    }
+
+    /// Returns the genes of an individual, or None if it is not stored
+    pub fn values(&self, id: u64) -> Option<Arc<Vec<f64>>> {
+        self.store.get(id)
+    }
+
+    /// Stores a vector created outside of the genetic operators, such as a seed individual, and returns its id.
+    /// Returns None if it does not have one gene within the bounds for every dimension.
+    pub fn insert(&self, values: Vec<f64>) -> Option<u64> {
+        let in_bounds = values.len() == self.bounds.len()
+            && values
+                .iter()
+                .zip(self.bounds.iter())
+                .all(|(value, (lower, upper))| (lower..=upper).contains(&value));
+        in_bounds.then(|| self.store.insert(values))
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<f64>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    fn mutate_gene(&self, rng: &mut StdRng, value: f64, (lower, upper): (f64, f64)) -> f64 {
+        let range = upper - lower;
+        let mutated = match self.mutation {
+            RealMutation::Gaussian { sigma } => value + standard_normal(rng) * sigma * range,
+            RealMutation::Polynomial { eta } => {
+                let power = eta + 1.0;
+                let u: f64 = rng.random();
+                let delta = if u < 0.5 {
+                    let below = 1.0 - (value - lower) / range;
+                    (2.0 * u + (1.0 - 2.0 * u) * below.powf(power)).powf(1.0 / power) - 1.0
+                } else {
+                    let above = 1.0 - (upper - value) / range;
+                    1.0 - (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * above.powf(power)).powf(1.0 / power)
+                };
+                value + delta * range
+            }
+        };
+        mutated.clamp(lower, upper)
+    }
+}
+
+impl Genetics for RealVectorGenetics {
+    fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> u64 {
+        self.store.insert(
+            self.bounds
+                .iter()
+                .map(|&(lower, upper)| rng.random_range(lower..=upper))
+                .collect(),
+        )
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        for gene in sample(rng, child.len(), points.min(child.len())) {
+            child[gene] = self.mutate_gene(rng, child[gene], self.bounds[gene]);
+        }
+        self.store.insert(child)
+    }
+
+    // Each gene is crossed with probability one half, and the child takes one of the two SBX offspring at random. The
+    // engine's crossover points are not used.
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let power = 1.0 / (self.crossover_eta + 1.0);
+        let child = a
+            .iter()
+            .zip(b.iter())
+            .zip(self.bounds.iter())
+            .map(|((&a, &b), &(lower, upper))| {
+                if !rng.random_bool(0.5) {
+                    return a;
+                }
+                let u: f64 = rng.random();
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(power)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(power)
+                };
+                let spread = if rng.random() { 1.0 + beta } else { 1.0 - beta };
+                (0.5 * (spread * a + (2.0 - spread) * b)).clamp(lower, upper)
+            })
+            .collect();
+        self.store.insert(child)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        a.iter()
+            .zip(b.iter())
+            .zip(self.bounds.iter())
+            .map(|((a, b), (lower, upper))| ((a - b) / (upper - lower)).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    // Values are hashed by their bits, so 0.0 and -0.0 are different genomes
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store.get(individual).map_or(individual, |values| {
+            hash_genome(
+                &values
+                    .iter()
+                    .map(|value| value.to_bits())
+                    .collect::<Vec<u64>>(),
+            )
+        })
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let values = self.store.get(individual)?;
+        Some(
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let values = genome
+            .split(',')
+            .map(|value| value.trim().parse().ok())
+            .collect::<Option<Vec<f64>>>()?;
+        self.insert(values)
+    }
+
+    fn individual_from_point(&self, point: &[f64]) -> Option<u64> {
+        if point.len() != self.bounds.len() {
+            return None;
+        }
+        self.insert(
+            point
+                .iter()
+                .zip(self.bounds.iter())
+                .map(|(x, (lower, upper))| (lower + x * (upper - lower)).clamp(*lower, *upper))
+                .collect(),
+        )
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+// Draws from the standard normal distribution with the Box-Muller transform
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u: f64 = 1.0 - rng.random::<f64>();
+    let v: f64 = rng.random();
+    (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    // Scores a vector higher the closer it is to the origin
+    struct SphereEngine {
+        store: Arc<IndividualStore<Vec<f64>>>,
+    }
+
+    impl IslandEngine for SphereEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let squares: f64 = self.store.get(id).unwrap().iter().map(|x| x * x).sum();
+            (1_000_000.0 / (1.0 + squares)) as u64
+        }
+    }
+
+    #[test]
+    fn operators_stay_within_the_bounds() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let bounds = vec![(-1.0, 1.0), (0.0, 100.0)];
+        assert!(matches!(
+            RealVectorGenetics::new(vec![(1.0, 1.0)]),
+            Err(GeneticError::InvalidRealBounds)
+        ));
+        assert!(matches!(
+            RealVectorGenetics::new(bounds.clone())
+                .unwrap()
+                .with_mutation(RealMutation::Gaussian { sigma: 0.0 }),
+            Err(GeneticError::InvalidRealOperator)
+        ));
+
+        for mutation in [
+            RealMutation::Gaussian { sigma: 0.5 },
+            RealMutation::Polynomial { eta: 1.0 },
+        ] {
+            let genetics = RealVectorGenetics::new(bounds.clone())
+                .unwrap()
+                .with_mutation(mutation)
+                .unwrap();
+            let low = genetics.decode("-1,0").unwrap();
+            let high = genetics.decode("1, 100").unwrap();
+            assert_eq!(2f64.sqrt(), genetics.distance(low, high));
+            assert_eq!(None, genetics.decode("0,101"));
+
+            for _ in 0..100 {
+                let mutant = genetics.mutate(&mut rng, high, 2);
+                let child = genetics.crossover(&mut rng, low, mutant, 1);
+                for id in [mutant, child] {
+                    let values = genetics.values(id).unwrap();
+                    assert!((-1.0..=1.0).contains(&values[0]));
+                    assert!((0.0..=100.0).contains(&values[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_sphere_is_minimized() {
+        let genetics = RealVectorGenetics::new(vec![(-5.0, 5.0); 3]).unwrap();
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(30)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(SphereEngine { store }));
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(50).unwrap();
+        assert!(world.best_score().unwrap() > 900_000);
+    }
+}
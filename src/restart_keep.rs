@@ -0,0 +1,160 @@
+/// What `World::restart` keeps from the run it ends. The populations and the counters of the run are always cleared,
+/// and the island engines, observers and blackboard are always kept, as are any unused seed individuals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RestartKeep {
+    /// Keep the members of the archive and the hall of fame, so that the best individuals are collected across every
+    /// restart
+    ///
+    /// Default: false
+    pub archive: bool,
+
+    /// Keep the statistics history and the migration log, so that the runs appear one after another
+    ///
+    /// Default: false
+    pub stats_history: bool,
+
+    /// Continue the curriculum and the migration schedule from where they were, instead of starting them and the
+    /// warmup over
+    ///
+    /// Default: false
+    pub schedules: bool,
+
+    /// Keep the operator settings of the genetic engine as they were changed during the run, instead of returning to
+    /// the ones it was built with. Rates changed by a warmup or hypermutation burst are restored either way.
+    ///
+    /// Default: false
+    pub engine_settings: bool,
+}
+
+impl RestartKeep {
+    /// Keeps nothing, so that the world starts over as if it had just been built
+    pub fn nothing() -> Self {
+        Self::default()
+    }
+
+    pub fn with_archive(mut self, keep: bool) -> Self {
+        self.archive = keep;
+        self
+    }
+
+    pub fn with_stats_history(mut self, keep: bool) -> Self {
+        self.stats_history = keep;
+        self
+    }
+
+    pub fn with_schedules(mut self, keep: bool) -> Self {
+        self.schedules = keep;
+        self
+    }
+
+    pub fn with_engine_settings(mut self, keep: bool) -> Self {
+        self.engine_settings = keep;
+        self
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn restarts_clear_the_run_and_keep_what_is_asked() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_archive(2, 0.5)
+            .build()
+            .unwrap();
+        let built_rate = world.genetic_engine().mutation_rate();
+        world.run_n_generations(3).unwrap();
+        world.genetic_engine_mut().set_mutation_rate(5).unwrap();
+
+        world
+            .restart(
+                RestartKeep::nothing()
+                    .with_archive(true)
+                    .with_stats_history(true),
+            )
+            .unwrap();
+        assert_eq!(0, world.generation_count());
+        assert!(world
+            .get_island(0)
+            .unwrap()
+            .snapshot()
+            .individuals
+            .is_empty());
+        assert_eq!(None, world.most_fit_individual());
+        assert_eq!(built_rate, world.genetic_engine().mutation_rate());
+        assert!(!world.archive().unwrap().is_empty());
+        assert_eq!(3, world.stats_history().len());
+
+        // The new run is recorded after the old one
+        world.run_n_generations(1).unwrap();
+        assert_eq!(4, world.stats_history().len());
+        assert_eq!(1, world.stats_history()[3].generation);
+
+        world.genetic_engine_mut().set_mutation_rate(5).unwrap();
+        world
+            .restart(RestartKeep::nothing().with_engine_settings(true))
+            .unwrap();
+        assert_eq!(5, world.genetic_engine().mutation_rate());
+        assert!(world.archive().unwrap().is_empty() && world.stats_history().is_empty());
+    }
+
+    #[test]
+    fn schedules_continue_only_when_kept_and_bursts_always_end() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_curriculum_stage(
+                CurriculumStage::new("first")
+                    .with_advancement(StageAdvancement::Generations(1))
+                    .with_mutation_rate(2),
+            )
+            .with_curriculum_stage(CurriculumStage::new("second").with_mutation_rate(6))
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+        world
+            .restart(RestartKeep::nothing().with_schedules(true))
+            .unwrap();
+        assert_eq!(
+            (Some(1), 6),
+            (
+                world.curriculum_stage(),
+                world.genetic_engine().mutation_rate()
+            )
+        );
+        world.restart(RestartKeep::nothing()).unwrap();
+        assert_eq!(
+            (Some(0), 2),
+            (
+                world.curriculum_stage(),
+                world.genetic_engine().mutation_rate()
+            )
+        );
+
+        // Rates raised by a hypermutation burst are not kept as engine settings
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .with_hypermutation(Hypermutation {
+                stagnation_generations: 1,
+                burst_generations: 10,
+                ..Hypermutation::default()
+            });
+        builder.add_island("flat", Box::new(UnscoredEngine));
+        let mut world = builder.build().unwrap();
+        let built_rate = world.genetic_engine().mutation_rate();
+        world.run_n_generations(3).unwrap();
+        assert!(world.genetic_engine().mutation_rate() > built_rate);
+        world
+            .restart(RestartKeep::nothing().with_engine_settings(true))
+            .unwrap();
+        assert_eq!(built_rate, world.genetic_engine().mutation_rate());
+    }
+}
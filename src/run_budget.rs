@@ -0,0 +1,150 @@
+/// Termination criteria for `World::run_until`. Generation limits are absolute generation counts of the world rather
+/// than counts from the start of the call, so a world that is resumed, for example from a snapshot, continues towards
+/// the same limit instead of starting over. A budget can be extended to keep a finished run going.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunBudget {
+    /// Stop once the world's generation count reaches this value
+    pub max_generations: Option<usize>,
+
+    /// Stop once the best score of the world reaches this value
+    pub target_score: Option<u64>,
+
+    /// Stop once this many generations have passed without a new best validation score, because the individuals have
+    /// started to overfit their training data. Has no effect unless the island engines implement
+    /// `IslandEngine::validation_score`, so it should be combined with a generation limit.
+    pub validation_patience: Option<usize>,
+}
+
+impl RunBudget {
+    /// Creates a budget that runs until the world has completed `generations` generations in total
+    pub fn generations(generations: usize) -> Self {
+        RunBudget {
+            max_generations: Some(generations),
+            target_score: None,
+            validation_patience: None,
+        }
+    }
+
+    pub fn with_target_score(mut self, score: u64) -> Self {
+        self.target_score = Some(score);
+        self
+    }
+
+    pub fn with_validation_patience(mut self, generations: usize) -> Self {
+        self.validation_patience = Some(generations);
+        self
+    }
+
+    /// Allows `generations` more generations than the current limit. A budget without a generation limit is given one
+    /// of `generations`.
+    pub fn extend_generations(&mut self, generations: usize) {
+        self.max_generations = Some(self.max_generations.unwrap_or(0) + generations);
+    }
+
+    /// Replaces the target score, for example with a more ambitious one once the previous target was reached
+    pub fn set_target_score(&mut self, score: Option<u64>) {
+        self.target_score = score;
+    }
+
+    /// Returns true if a world with the given generation count and best score has used up the budget
+    pub fn is_exhausted(&self, generation_count: usize, best_score: Option<u64>) -> bool {
+        let out_of_generations = self
+            .max_generations
+            .is_some_and(|max| generation_count >= max);
+        let reached_target = match (self.target_score, best_score) {
+            (Some(target), Some(best)) => best >= target,
+            _ => false,
+        };
+        out_of_generations || reached_target
+    }
+
+    /// Returns true if a world whose best validation score was set the given number of generations ago should stop
+    /// early. See `World::generations_since_validation_improvement`.
+    pub fn is_overfitting(&self, generations_since_validation_improvement: Option<usize>) -> bool {
+        match (
+            self.validation_patience,
+            generations_since_validation_improvement,
+        ) {
+            (Some(patience), Some(generations)) => generations >= patience,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn run_budgets_can_be_extended() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+
+        let mut budget = RunBudget::generations(3);
+        assert_eq!(3, world.run_until(&budget).unwrap().generations);
+        assert_eq!(0, world.run_until(&budget).unwrap().generations);
+
+        budget.extend_generations(2);
+        assert_eq!(2, world.run_until(&budget).unwrap().generations);
+        assert_eq!(5, world.generation_count());
+        assert_eq!(5, world.stats_history().len());
+
+        // Every child has a larger id than its parents, so a higher target is reached after a few more generations
+        let target = world.best_score().unwrap() + 1;
+        budget.extend_generations(100);
+        budget.set_target_score(Some(target));
+        let summary = world.run_until(&budget).unwrap();
+        assert!(summary.generations < 100);
+        assert!(world.best_score().unwrap() >= target);
+
+        assert!(matches!(
+            world.run_until(&RunBudget::default()),
+            Err(GeneticError::UnboundedRunBudget)
+        ));
+    }
+
+    #[test]
+    fn runs_stop_once_the_validation_score_stops_improving() {
+        // Trains on the id, but validates best at id 20 so that younger individuals overfit
+        struct OverfittingEngine;
+
+        impl IslandEngine for OverfittingEngine {
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_individual(&self, id: u64) -> u64 {
+                id
+            }
+
+            fn validation_score(&self, id: u64) -> Option<u64> {
+                Some(20 - id.abs_diff(20))
+            }
+        }
+
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(OverfittingEngine));
+        let mut world = world.build().unwrap();
+        let summary = world
+            .run_until(&RunBudget::generations(50).with_validation_patience(3))
+            .unwrap();
+        assert!(summary.generations < 50);
+        assert_eq!(Some(3), world.generations_since_validation_improvement());
+
+        let best = world.best_validated_individual().unwrap();
+        assert_eq!(world.best_validation_score(), best.validation_score);
+        assert!(best.score > 10 && best.score < 30);
+        assert!(summary.champion.unwrap().score > best.score);
+        let stats = world.stats_history();
+        assert_eq!(
+            stats[0].islands[0].best_validation_score,
+            stats[0].best_validation_score
+        );
+    }
+}
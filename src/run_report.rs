@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use crate::{
+    AdaptiveMigration, CurriculumStage, EliteHandling, EmigrationThreshold, GeneticEngineSettings,
+    HallOfFame, Hypermutation, Initialization, IslandSynchronization, MatingRestriction,
+    MigrantCount, MigrationAlgorithm, MigrationShortfall, RateLimit, ScoreNormalization,
+    SelectionCurve, StochasticRanking, Warmup,
+};
+
+/// A record of one call to `World::run_generations_while`, with everything needed to repeat the run and compare it
+/// with other experiments.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunReport {
+    /// The seed the genetic engine started from. Building the world again with this seed repeats the run, as long as
+    /// the island engines are deterministic.
+    pub seed: u64,
+
+    /// The configuration of the world when the run started
+    pub configuration: RunConfiguration,
+
+    /// The number of generations that were run
+    pub generations: usize,
+
+    /// The number of times an individual was run during those generations, across all islands
+    pub evaluations: usize,
+
+    /// The best score of each island during the run, in the same order as `World::get_island`, or None for an island
+    /// that never had a scored individual
+    pub island_best_scores: Vec<Option<u64>>,
+
+    /// The wall-clock time the run took
+    pub elapsed: Duration,
+}
+
+/// The effective settings of a world, as returned by `World::configuration`. Each field has the same meaning as the
+/// `WorldBuilder` field of the same name, after any change made while the world ran.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunConfiguration {
+    /// The names of the islands, in order
+    pub islands: Vec<String>,
+
+    pub individuals_per_island: usize,
+    pub elite_individuals_per_generation: usize,
+    pub generations_between_migrations: usize,
+    pub migrant_count: MigrantCount,
+    pub adaptive_migration: Option<AdaptiveMigration>,
+    pub migration_algorithm: MigrationAlgorithm,
+    pub migration_schedule: Vec<(usize, MigrationAlgorithm)>,
+    pub migration_shortfall: MigrationShortfall,
+    pub clone_migrated_individuals: bool,
+    pub protected_from_emigration: usize,
+    pub emigration_threshold: Option<EmigrationThreshold>,
+    pub select_for_migration: SelectionCurve,
+    pub migrate_by_crowding_distance: bool,
+    pub migrant_novelty_threshold: Option<f64>,
+    pub select_as_parent: SelectionCurve,
+    pub parent_curve_blend: Vec<(SelectionCurve, f64)>,
+    pub select_as_elite: SelectionCurve,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elite_handling: EliteHandling,
+    pub brood_size: usize,
+    pub brood_selection: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unique_genomes: bool,
+    pub mating_restriction: Option<MatingRestriction>,
+    pub island_synchronization: IslandSynchronization,
+    pub score_normalization: ScoreNormalization,
+    pub stochastic_ranking: Option<StochasticRanking>,
+    pub rate_limit: Option<RateLimit>,
+    pub hall_of_fame: Option<HallOfFame>,
+    pub initialization: Initialization,
+    pub hypermutation: Option<Hypermutation>,
+    pub warmup: Option<Warmup>,
+    pub curriculum: Vec<CurriculumStage>,
+
+    /// The weight of each island, in the same order as `islands`
+    pub island_weights: Vec<f64>,
+
+    /// The settings of the genetic engine
+    pub genetic_engine: GeneticEngineSettings,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn runs_report_their_configuration_and_results() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+
+        let mut generations = 0;
+        let report = world
+            .run_generations_while(|world| {
+                world.genetic_engine_mut().set_rates(1, 0).unwrap();
+                generations += 1;
+                generations < 3
+            })
+            .unwrap();
+        assert_eq!(1234, report.seed);
+        assert_eq!(3, report.generations);
+        assert_eq!(3 * 2 * 4, report.evaluations);
+        assert_eq!(
+            vec!["a".to_string(), "b".to_string()],
+            report.configuration.islands
+        );
+        assert_eq!(4, report.configuration.individuals_per_island);
+        assert_ne!(
+            world.configuration().genetic_engine,
+            report.configuration.genetic_engine
+        );
+        let history = &world.stats_history()[2..];
+        for (index, best) in report.island_best_scores.iter().enumerate() {
+            let expected = history
+                .iter()
+                .filter_map(|stats| stats.islands[index].best_score)
+                .max();
+            assert_eq!(expected, *best);
+        }
+    }
+
+    #[test]
+    fn runs_report_at_least_one_generation_and_stop_at_errors() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+        let report = world.run_generations_while(|_| false).unwrap();
+        assert_eq!((1, 4), (report.generations, report.evaluations));
+        assert_eq!(world.configuration(), report.configuration);
+        assert_eq!(
+            vec![world.most_fit_individual().map(|champion| champion.score)],
+            report.island_best_scores
+        );
+
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0);
+        builder.add_island("b", Box::new(FailingEngine));
+        let mut failing = builder.build().unwrap();
+        let mut calls = 0;
+        assert!(matches!(
+            failing.run_generations_while(|_| {
+                calls += 1;
+                true
+            }),
+            Err(GeneticError::EvaluationFailed { .. })
+        ));
+        assert_eq!(0, calls);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::Champion;
+
+/// Aggregate results of a batch of generations run by `World::run_n_generations`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunSummary {
+    /// The number of generations that were run
+    pub generations: usize,
+
+    /// The best score the world had seen before the batch started, or None if no generation had been scored
+    pub starting_best_score: Option<u64>,
+
+    /// The best score the world has seen at the end of the batch, including generations before it
+    pub best_score: Option<u64>,
+
+    /// The number of generations in the batch that set a new best score
+    pub improvements: usize,
+
+    /// The number of migrations between islands during the batch
+    pub migrations: usize,
+
+    /// The most fit individual in the world at the end of the batch
+    pub champion: Option<Champion>,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn run_n_generations_summarizes_the_batch() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_generations_between_migrations(2)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+
+        let first = world.run_n_generations(3).unwrap();
+        assert_eq!(3, first.generations);
+        assert_eq!(None, first.starting_best_score);
+        assert_eq!(1, first.migrations);
+        assert_eq!(world.best_score(), first.best_score);
+        assert_eq!(world.most_fit_individual(), first.champion);
+
+        // Every child has a larger id, so every generation improves
+        let second = world.run_n_generations(4).unwrap();
+        assert_eq!(first.best_score, second.starting_best_score);
+        assert_eq!(4, second.improvements);
+        assert_eq!(2, second.migrations);
+        assert_eq!(7, world.generation_count());
+    }
+
+    #[test]
+    fn empty_and_stagnant_batches_report_no_improvements() {
+        let mut world = world_builder(&["a"])
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            RunSummary {
+                generations: 0,
+                starting_best_score: None,
+                best_score: None,
+                improvements: 0,
+                migrations: 0,
+                champion: None,
+            },
+            world.run_n_generations(0).unwrap()
+        );
+        assert_eq!(0, world.generation_count());
+
+        // Only the first generation of a world where every individual scores the same sets a best score
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0);
+        builder.add_island("flat", Box::new(UnscoredEngine));
+        let mut flat = builder.build().unwrap();
+        assert_eq!(1, flat.run_n_generations(3).unwrap().improvements);
+        let stagnant = flat.run_n_generations(3).unwrap();
+        assert_eq!(0, stagnant.improvements);
+        assert_eq!(stagnant.starting_best_score, stagnant.best_score);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::GeneticError;
+
+/// What a `ScoreAccumulator` does with a total that does not fit in a `u64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowMode {
+    /// The total is clamped to `u64::MAX`, so that an overflowing individual ranks at the top instead of wrapping
+    /// around to the bottom. Individuals that all overflow become tied.
+    #[default]
+    Saturate,
+
+    /// The total is reported as `GeneticError::ScoreOverflow`, so that the engine can fail the evaluation or rescale
+    Checked,
+}
+
+/// Sums the partial scores of an individual, such as its score on each test case, without the silent wraparound of
+/// `u64` arithmetic that would turn the best individuals into the worst. The total is kept in a `u128`, so only the
+/// final conversion to a `u64` score can overflow, and that is handled by the `OverflowMode`. Engines typically
+/// accumulate in `run_individual` or `try_run_individual`, returning the error of a checked total as the failure
+/// message, and keep the result for `score_individual`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScoreAccumulator {
+    mode: OverflowMode,
+    total: u128,
+    count: u64,
+}
+
+impl ScoreAccumulator {
+    pub fn new(mode: OverflowMode) -> Self {
+        ScoreAccumulator {
+            mode,
+            total: 0,
+            count: 0,
+        }
+    }
+
+    /// Adds a partial score
+    pub fn add(&mut self, score: u64) {
+        self.add_weighted(score, 1);
+    }
+
+    /// Adds a partial score multiplied by a weight. The product is widened, so it cannot overflow.
+    pub fn add_weighted(&mut self, score: u64, weight: u64) {
+        self.total = self.total.saturating_add(score as u128 * weight as u128);
+        self.count += 1;
+    }
+
+    /// Returns the number of partial scores added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the partial scores, handled by the `OverflowMode` if it does not fit in a `u64`
+    pub fn total(&self) -> Result<u64, GeneticError> {
+        self.fit(self.total)
+    }
+
+    /// Returns the sum of the partial scores divided by `divisor`, which scales a total that is known to exceed a
+    /// `u64` back into range while keeping the order of the individuals. A divisor of zero is treated as one.
+    pub fn scaled_total(&self, divisor: u64) -> Result<u64, GeneticError> {
+        self.fit(self.total / divisor.max(1) as u128)
+    }
+
+    /// Returns the mean of the partial scores, which always fits in a `u64`, or zero if none were added
+    pub fn mean(&self) -> u64 {
+        self.total
+            .checked_div(self.count as u128)
+            .map_or(0, |mean| mean.min(u64::MAX as u128) as u64)
+    }
+
+    fn fit(&self, total: u128) -> Result<u64, GeneticError> {
+        match (u64::try_from(total), self.mode) {
+            (Ok(total), _) => Ok(total),
+            (Err(_), OverflowMode::Saturate) => Ok(u64::MAX),
+            (Err(_), OverflowMode::Checked) => Err(GeneticError::ScoreOverflow(total)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflowing_totals_saturate_or_fail() {
+        let mut saturating = ScoreAccumulator::new(OverflowMode::Saturate);
+        let mut checked = ScoreAccumulator::new(OverflowMode::Checked);
+        for accumulator in [&mut saturating, &mut checked] {
+            accumulator.add(u64::MAX);
+            accumulator.add_weighted(u64::MAX, 3);
+        }
+
+        assert_eq!(u64::MAX, saturating.total().unwrap());
+        assert!(matches!(
+            checked.total(),
+            Err(GeneticError::ScoreOverflow(total)) if total == u64::MAX as u128 * 4
+        ));
+        assert_eq!(u64::MAX, checked.scaled_total(4).unwrap());
+        assert_eq!(u64::MAX, checked.mean());
+        assert_eq!(2, checked.count());
+
+        let mut small = ScoreAccumulator::default();
+        assert_eq!(0, small.mean());
+        small.add(3);
+        small.add_weighted(4, 2);
+        assert_eq!(
+            (11, 5, 2),
+            (
+                small.total().unwrap(),
+                small.mean(),
+                small.scaled_total(5).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn totals_of_exactly_u64_max_still_fit() {
+        let mut checked = ScoreAccumulator::new(OverflowMode::Checked);
+        checked.add(u64::MAX - 1);
+        checked.add_weighted(1, 0);
+        assert_eq!(u64::MAX - 1, checked.total().unwrap());
+        assert_eq!(2, checked.count());
+
+        checked.add(1);
+        assert_eq!(u64::MAX, checked.total().unwrap());
+        assert_eq!(u64::MAX, checked.scaled_total(0).unwrap());
+        checked.add(1);
+        assert!(matches!(
+            checked.total(),
+            Err(GeneticError::ScoreOverflow(_))
+        ));
+        assert_eq!(u64::MAX / 2 + 1, checked.scaled_total(2).unwrap());
+    }
+}
@@ -0,0 +1,162 @@
+/// How an individual came to be part of its island's generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndividualOrigin {
+    /// A new random individual, filling an empty island or immigrating to an explorer island
+    Random,
+
+    /// Copied unchanged from the previous generation as an elite
+    Elite,
+
+    /// Bred from one parent by mutation
+    Mutation,
+
+    /// Bred from two parents by crossover
+    Crossover,
+
+    /// An unchanged copy of its parent, bred by reproduction
+    Reproduction,
+
+    /// Bred by the `VariationOperator` registered at this index of the genetic engine
+    Operator(usize),
+
+    /// Migrated from another island
+    Migrant,
+
+    /// Provided by the user with `WorldBuilder::with_seed_individuals`
+    Seed,
+}
+
+/// How much each way of producing individuals contributed to the best score of a generation. Each gain is how far the
+/// best individual of that origin rose above the best score of every previous generation (or above zero in the first
+/// generation), and is zero for origins that did not beat it. Only individuals bred into the generation by the world
+/// are attributed, so a generation that ran without being filled has no gains.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreAttribution {
+    /// The origin of the individual with the best score of the generation, if its origin is known
+    pub best_origin: Option<IndividualOrigin>,
+
+    /// The gain of new random individuals
+    pub random_gain: u64,
+
+    /// The gain of elites
+    pub elite_gain: u64,
+
+    /// The gain of children bred by mutation
+    pub mutation_gain: u64,
+
+    /// The gain of children bred by crossover
+    pub crossover_gain: u64,
+
+    /// The gain of copies bred by reproduction
+    pub reproduction_gain: u64,
+
+    /// The gain of individuals that migrated in before the generation was filled
+    pub migrant_gain: u64,
+
+    /// The gain of seed individuals
+    pub seed_gain: u64,
+
+    /// The gain of children bred by each registered `VariationOperator`, by index. Operators after the last one with a
+    /// gain are left out.
+    pub operator_gains: Vec<u64>,
+}
+
+impl ScoreAttribution {
+    /// Returns the gain attributed to the origin
+    pub fn gain(&self, origin: IndividualOrigin) -> u64 {
+        match origin {
+            IndividualOrigin::Random => self.random_gain,
+            IndividualOrigin::Elite => self.elite_gain,
+            IndividualOrigin::Mutation => self.mutation_gain,
+            IndividualOrigin::Crossover => self.crossover_gain,
+            IndividualOrigin::Reproduction => self.reproduction_gain,
+            IndividualOrigin::Migrant => self.migrant_gain,
+            IndividualOrigin::Seed => self.seed_gain,
+            IndividualOrigin::Operator(index) => {
+                self.operator_gains.get(index).copied().unwrap_or(0)
+            }
+        }
+    }
+
+    // Raises the gain of the origin to include an individual with the score, given the previous best score
+    pub(crate) fn record(&mut self, origin: IndividualOrigin, score: u64, previous_best: u64) {
+        let raised = score.saturating_sub(previous_best);
+        let gain = match origin {
+            IndividualOrigin::Random => &mut self.random_gain,
+            IndividualOrigin::Elite => &mut self.elite_gain,
+            IndividualOrigin::Mutation => &mut self.mutation_gain,
+            IndividualOrigin::Crossover => &mut self.crossover_gain,
+            IndividualOrigin::Reproduction => &mut self.reproduction_gain,
+            IndividualOrigin::Migrant => &mut self.migrant_gain,
+            IndividualOrigin::Seed => &mut self.seed_gain,
+            IndividualOrigin::Operator(index) => {
+                if self.operator_gains.len() <= index {
+                    if raised == 0 {
+                        return;
+                    }
+                    self.operator_gains.resize(index + 1, 0);
+                }
+                &mut self.operator_gains[index]
+            }
+        };
+        *gain = (*gain).max(raised);
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn score_gains_are_attributed_to_the_origin_of_individuals() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        let first = world.stats_history()[0].score_attribution.clone();
+        assert_eq!(Some(IndividualOrigin::Random), first.best_origin);
+        assert_eq!(world.best_score(), Some(first.random_gain));
+
+        // Children always have larger ids than their parents, so only mutation can raise the best score
+        world.genetic_engine_mut().set_rates(1, 0).unwrap();
+        let previous_best = world.best_score().unwrap();
+        world.run_n_generations(1).unwrap();
+        let second = world.stats_history()[1].score_attribution.clone();
+        assert_eq!(Some(IndividualOrigin::Mutation), second.best_origin);
+        assert_eq!(
+            world.best_score().unwrap() - previous_best,
+            second.mutation_gain
+        );
+        assert_eq!(0, second.gain(IndividualOrigin::Elite));
+        assert_eq!(0, second.crossover_gain);
+
+        world.run_one_generation().unwrap();
+        assert_eq!(
+            ScoreAttribution::default(),
+            world.stats_history()[2].score_attribution
+        );
+    }
+
+    #[test]
+    fn only_scores_above_the_previous_best_are_gains() {
+        let mut attribution = ScoreAttribution::default();
+        attribution.record(IndividualOrigin::Mutation, 15, 10);
+        attribution.record(IndividualOrigin::Mutation, 12, 10);
+        attribution.record(IndividualOrigin::Crossover, 10, 10);
+        assert_eq!(5, attribution.gain(IndividualOrigin::Mutation));
+        assert_eq!(0, attribution.gain(IndividualOrigin::Crossover));
+
+        // Operators without a gain are left out, even when they come after one that has a gain
+        attribution.record(IndividualOrigin::Operator(3), 4, 10);
+        assert!(attribution.operator_gains.is_empty());
+        attribution.record(IndividualOrigin::Operator(1), 11, 10);
+        attribution.record(IndividualOrigin::Operator(3), 9, 10);
+        assert_eq!(vec![0, 1], attribution.operator_gains);
+        assert_eq!(0, attribution.gain(IndividualOrigin::Operator(3)));
+    }
+}
@@ -0,0 +1,100 @@
+/// Puts the scores of different islands on a common scale, so individuals from islands that score on different scales
+/// can be compared. The normalized score is used to find the world's most fit individual and to rank the archive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScoreNormalization {
+    /// Scores are compared as they are. Use this when every island scores on the same scale.
+    #[default]
+    Raw,
+
+    /// An individual scores the fraction of the other individuals on its island that have a lower score, from 0.0
+    /// for the least fit to 1.0 for the most fit.
+    Rank,
+
+    /// An individual's score is rescaled so that the least fit individual on its island scores 0.0 and the most fit
+    /// scores 1.0.
+    MinMax,
+
+    /// The island engine normalizes its own scores through `IslandEngine::normalized_score`. Individuals the engine
+    /// does not normalize are compared by their raw score.
+    Engine,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn normalized_scores_pick_the_champion_across_scales() {
+        let champion = |normalization: ScoreNormalization| {
+            let mut builder = WorldBuilder::new()
+                .with_genetic_engine(counting_engine(1234))
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(0)
+                .with_score_normalization(normalization);
+            builder.add_island("thousands", Box::new(ScaledEngine { scale: 1000 }));
+            builder.add_island("ones", Box::new(ScaledEngine { scale: 1 }));
+            let mut world = builder.build().unwrap();
+            world.run_n_generations(1).unwrap();
+            world.most_fit_individual().unwrap()
+        };
+
+        // The first island holds ids 0..=3 and the second 4..=7
+        let raw = champion(ScoreNormalization::Raw);
+        assert_eq!((0, 3, 3000), (raw.island, raw.id, raw.score));
+        let normalized = champion(ScoreNormalization::Engine);
+        assert_eq!(
+            (1, 7, 7.0),
+            (
+                normalized.island,
+                normalized.id,
+                normalized.normalized_score
+            )
+        );
+        let ranked = champion(ScoreNormalization::Rank);
+        assert_eq!(1.0, ranked.normalized_score);
+    }
+
+    #[test]
+    fn ties_single_individuals_and_flat_islands_normalize_to_the_top() {
+        // Scores half the id, so that neighboring ids tie
+        struct HalfIdEngine;
+
+        impl IslandEngine for HalfIdEngine {
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_individual(&self, id: u64) -> u64 {
+                id / 2
+            }
+        }
+
+        let island = |engine: Box<dyn IslandEngine>, individuals: &[u64]| {
+            let mut island = Island::new("a", IslandRole::Standard, engine);
+            for &id in individuals {
+                island.add_individual_to_future_generation(id);
+            }
+            island.advance_generation();
+            island.run_one_generation().unwrap();
+            island
+        };
+
+        let scored = island(Box::new(HalfIdEngine), &[2, 4, 5, 10]);
+        assert_eq!(
+            1.0 / 3.0,
+            scored.normalized_score(4, ScoreNormalization::Rank)
+        );
+        assert_eq!(0.0, scored.normalized_score(2, ScoreNormalization::Rank));
+        assert_eq!(0.25, scored.normalized_score(4, ScoreNormalization::MinMax));
+        assert_eq!(2.0, scored.normalized_score(4, ScoreNormalization::Engine));
+
+        let alone = island(Box::new(IdScoreEngine), &[7]);
+        assert_eq!(1.0, alone.normalized_score(7, ScoreNormalization::Rank));
+        assert_eq!(1.0, alone.normalized_score(7, ScoreNormalization::MinMax));
+
+        // When every score is the same no individual is below another, but the scale still has a top
+        let flat = island(Box::new(UnscoredEngine), &[1, 2, 3]);
+        assert_eq!(0.0, flat.normalized_score(1, ScoreNormalization::Rank));
+        assert_eq!(1.0, flat.normalized_score(1, ScoreNormalization::MinMax));
+    }
+}
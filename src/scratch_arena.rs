@@ -0,0 +1,131 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::MaybeSend;
+
+#[cfg(not(feature = "send"))]
+type Pool = Box<dyn Any>;
+
+#[cfg(feature = "send")]
+type Pool = Box<dyn Any + Send>;
+
+/// Reusable scratch buffers for evaluating individuals. Each island owns an arena and passes it to
+/// `IslandEngine::run_individual_with_scratch`. Buffers taken from the arena and recycled back into it keep their
+/// capacity, so a hot fitness function stops allocating once the buffers have grown to the size it needs.
+///
+/// The counters are reset after every generation and reported in the island's `IslandStats`.
+#[derive(Default)]
+pub struct ScratchArena {
+    pools: HashMap<TypeId, Pool>,
+    stats: ScratchStats,
+}
+
+/// Counts how a `ScratchArena` was used during one generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScratchStats {
+    /// The number of buffers that had to be newly allocated because no recycled buffer was available
+    pub buffers_allocated: usize,
+
+    /// The number of buffers handed out from the recycled pool
+    pub buffers_reused: usize,
+
+    /// The number of buffers recycled back into the arena
+    pub buffers_recycled: usize,
+}
+
+impl ScratchArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes an empty buffer from the arena. Recycle it with `recycle_vec` when done to avoid allocating next time.
+    pub fn take_vec<T: Any + MaybeSend>(&mut self) -> Vec<T> {
+        match self.pool::<T>().pop() {
+            Some(buffer) => {
+                self.stats.buffers_reused += 1;
+                buffer
+            }
+            None => {
+                self.stats.buffers_allocated += 1;
+                Vec::new()
+            }
+        }
+    }
+
+    /// Clears a buffer and returns it to the arena for reuse
+    pub fn recycle_vec<T: Any + MaybeSend>(&mut self, mut buffer: Vec<T>) {
+        buffer.clear();
+        self.pool::<T>().push(buffer);
+        self.stats.buffers_recycled += 1;
+    }
+
+    /// Returns the counters of the current generation
+    pub fn stats(&self) -> ScratchStats {
+        self.stats
+    }
+
+    /// Ends a generation: returns its counters and starts counting again. Recycled buffers are kept.
+    pub(crate) fn reset(&mut self) -> ScratchStats {
+        std::mem::take(&mut self.stats)
+    }
+
+    fn pool<T: Any + MaybeSend>(&mut self) -> &mut Vec<Vec<T>> {
+        self.pools
+            .entry(TypeId::of::<Vec<T>>())
+            .or_insert_with(|| Box::new(Vec::<Vec<T>>::new()))
+            .downcast_mut::<Vec<Vec<T>>>()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recycled_buffers_keep_their_capacity() {
+        let mut arena = ScratchArena::new();
+        let mut buffer = arena.take_vec::<f64>();
+        buffer.extend([1.0, 2.0, 3.0]);
+        arena.recycle_vec(buffer);
+
+        let buffer = arena.take_vec::<f64>();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 3);
+        let other = arena.take_vec::<u8>();
+        arena.recycle_vec(other);
+
+        assert_eq!(
+            ScratchStats {
+                buffers_allocated: 2,
+                buffers_reused: 1,
+                buffers_recycled: 2,
+            },
+            arena.reset()
+        );
+        assert_eq!(ScratchStats::default(), arena.stats());
+    }
+
+    #[test]
+    fn buffers_are_pooled_by_type_and_kept_across_generations() {
+        let mut arena = ScratchArena::new();
+        arena.recycle_vec(Vec::<u32>::with_capacity(8));
+        arena.recycle_vec(Vec::<u32>::with_capacity(16));
+        arena.reset();
+
+        // A buffer of another type is never handed out from the pool, and the last buffer recycled comes out first
+        assert_eq!(0, arena.take_vec::<i32>().capacity());
+        assert_eq!(16, arena.take_vec::<u32>().capacity());
+        assert_eq!(8, arena.take_vec::<u32>().capacity());
+        assert_eq!(0, arena.take_vec::<u32>().capacity());
+        assert_eq!(
+            ScratchStats {
+                buffers_allocated: 2,
+                buffers_reused: 2,
+                buffers_recycled: 0,
+            },
+            arena.stats()
+        );
+    }
+}
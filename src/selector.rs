@@ -0,0 +1,90 @@
+use rand::rngs::StdRng; // cspell:disable-line
+
+use crate::MaybeSend;
+
+/// A user-defined selection pressure, for domains where none of the built-in `SelectionCurve`s fit. Register selectors
+/// with `WorldBuilder::with_selector` and refer to them as `SelectionCurve::Custom` with the index they were registered
+/// at, anywhere the world takes a selection curve.
+///
+/// Any `Fn(&mut StdRng, usize) -> usize` is a selector.
+pub trait Selector: MaybeSend {
+    /// Picks an index in the range [0 .. number_of_individuals], where the pool is sorted from least fit to most fit.
+    /// The pool is never empty. An index past the end is treated as the most fit individual.
+    fn pick_one_index(&self, rng: &mut StdRng, number_of_individuals: usize) -> usize;
+}
+
+impl<F> Selector for F
+where
+    F: Fn(&mut StdRng, usize) -> usize + MaybeSend,
+{
+    fn pick_one_index(&self, rng: &mut StdRng, number_of_individuals: usize) -> usize {
+        self(rng, number_of_individuals)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn custom_selectors_choose_the_parents() {
+        // Always breeds from the most fit individual, asking for an index past the end
+        let build = |curve: SelectionCurve| {
+            world_builder(&["standard"])
+                .with_individuals_per_island(6)
+                .with_elite_individuals(0)
+                .with_migrating_individuals(0)
+                .with_selector(|_rng: &mut rand::rngs::StdRng, _len: usize| usize::MAX)
+                .with_select_as_parent(curve)
+                .build()
+        };
+        let mut world = build(SelectionCurve::Custom(0)).unwrap();
+        world.run_n_generations(2).unwrap();
+        assert_eq!(1, world.stats_history()[1].islands[0].distinct_parents);
+
+        assert_eq!(SelectionCurve::Custom(0), "Custom(0)".parse().unwrap());
+        assert!(matches!(
+            build(SelectionCurve::Custom(1)),
+            Err(GeneticError::UnknownSelector(1))
+        ));
+    }
+
+    #[test]
+    fn each_curve_uses_the_selector_registered_at_its_index() {
+        // The first selector always picks the least fit individual and the second the most fit
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(6)
+            .with_elite_individuals(1)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_clone_migrated_individuals(true)
+            .with_selector(|_rng: &mut rand::rngs::StdRng, _len: usize| 0)
+            .with_selector(|_rng: &mut rand::rngs::StdRng, len: usize| len - 1)
+            .with_select_for_migration(SelectionCurve::Custom(0))
+            .with_select_as_elite(SelectionCurve::Custom(1))
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        let first: Vec<Vec<u64>> = (0..2)
+            .map(|index| world.get_island(index).unwrap().snapshot().individuals)
+            .collect();
+        for record in world.migration_log() {
+            assert_eq!(first[record.source_island][0], record.migrants[0].id);
+        }
+
+        world.run_n_generations(1).unwrap();
+        assert_eq!(
+            vec![*first[0].last().unwrap()],
+            world.stats_history()[1].islands[0].elites
+        );
+
+        assert_eq!("Custom(12)", SelectionCurve::Custom(12).to_string());
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_select_for_migration(SelectionCurve::Custom(0))
+                .build(),
+            Err(GeneticError::UnknownSelector(0))
+        ));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{GeneticEngine, GeneticError, Genetics, World};
+
+/// Measures how much the outcome of a short run depends on each parameter of the genetic engine. Starting from the
+/// configuration produced by a world factory, each parameter is scaled down and up by `factor` one at a time while
+/// the others keep their baseline values. Every configuration runs `repeats` times for `generations` generations, and
+/// the outcome is the mean best score.
+///
+/// The factory receives the repeat number, so repeat `n` of every configuration can use the same seed. This keeps
+/// the comparison between configurations from being drowned out by the randomness of individual runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensitivityAnalysis {
+    /// The number of generations each run lasts
+    pub generations: usize,
+
+    /// The number of runs of each configuration
+    pub repeats: usize,
+
+    /// Each parameter is divided and multiplied by this factor. Must be greater than 1.0.
+    pub factor: f64,
+}
+
+/// A parameter of the genetic engine that can be perturbed by a `SensitivityAnalysis`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngineParameter {
+    MutationRate,
+    CrossoverRate,
+    MaxMutationPoints,
+    MaxCrossoverPoints,
+}
+
+/// The outcome of perturbing one parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterSensitivity {
+    /// The parameter that was perturbed
+    pub parameter: EngineParameter,
+
+    /// The value of the parameter in the baseline configuration
+    pub baseline: u8,
+
+    /// The lowered value and its mean best score, or None if the lowered value was invalid or the same as the baseline
+    pub lower: Option<(u8, f64)>,
+
+    /// The raised value and its mean best score, or None if the raised value was invalid or the same as the baseline
+    pub higher: Option<(u8, f64)>,
+
+    /// The largest absolute change of the mean best score from the baseline
+    pub sensitivity: f64,
+}
+
+/// The result of a `SensitivityAnalysis`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensitivityReport {
+    /// The mean best score of the baseline configuration
+    pub baseline_score: f64,
+
+    /// Every parameter, most sensitive first
+    pub parameters: Vec<ParameterSensitivity>,
+}
+
+impl Default for SensitivityAnalysis {
+    fn default() -> Self {
+        SensitivityAnalysis {
+            generations: 20,
+            repeats: 3,
+            factor: 2.0,
+        }
+    }
+}
+
+impl EngineParameter {
+    const ALL: [EngineParameter; 4] = [
+        EngineParameter::MutationRate,
+        EngineParameter::CrossoverRate,
+        EngineParameter::MaxMutationPoints,
+        EngineParameter::MaxCrossoverPoints,
+    ];
+
+    fn get<G: Genetics>(&self, engine: &GeneticEngine<G>) -> u8 {
+        match self {
+            EngineParameter::MutationRate => engine.mutation_rate(),
+            EngineParameter::CrossoverRate => engine.crossover_rate(),
+            EngineParameter::MaxMutationPoints => engine.max_mutation_points(),
+            EngineParameter::MaxCrossoverPoints => engine.max_crossover_points(),
+        }
+    }
+
+    fn set<G: Genetics>(
+        &self,
+        engine: &mut GeneticEngine<G>,
+        value: u8,
+    ) -> Result<(), GeneticError> {
+        match self {
+            EngineParameter::MutationRate => engine.set_mutation_rate(value),
+            EngineParameter::CrossoverRate => engine.set_crossover_rate(value),
+            EngineParameter::MaxMutationPoints => engine.set_max_mutation_points(value),
+            EngineParameter::MaxCrossoverPoints => engine.set_max_crossover_points(value),
+        }
+    }
+}
+
+impl SensitivityAnalysis {
+    /// Runs the analysis. `factory` builds the baseline world for the given repeat number.
+    pub fn run<G, Factory>(&self, mut factory: Factory) -> Result<SensitivityReport, GeneticError>
+    where
+        G: Genetics,
+        Factory: FnMut(usize) -> Result<World<G>, GeneticError>,
+    {
+        let baseline_score = self.mean_best_score(&mut factory, None)?;
+        let baseline_world = factory(0)?;
+
+        let mut parameters = Vec::with_capacity(EngineParameter::ALL.len());
+        for parameter in EngineParameter::ALL {
+            let baseline = parameter.get(baseline_world.genetic_engine());
+            let lowered = (baseline as f64 / self.factor).round() as u8;
+            let raised = (baseline as f64 * self.factor).round().min(u8::MAX as f64) as u8;
+
+            let lower = self.perturbed_score(&mut factory, parameter, baseline, lowered)?;
+            let higher = self.perturbed_score(&mut factory, parameter, baseline, raised)?;
+            let sensitivity = [lower, higher]
+                .iter()
+                .flatten()
+                .map(|(_, score)| (score - baseline_score).abs())
+                .fold(0.0, f64::max);
+
+            parameters.push(ParameterSensitivity {
+                parameter,
+                baseline,
+                lower,
+                higher,
+                sensitivity,
+            });
+        }
+        parameters.sort_by(|a, b| b.sensitivity.total_cmp(&a.sensitivity));
+
+        Ok(SensitivityReport {
+            baseline_score,
+            parameters,
+        })
+    }
+
+    // Returns the mean best score with the parameter set to `value`, or None if the value is not a perturbation or is
+    // invalid for the engine
+    fn perturbed_score<G, Factory>(
+        &self,
+        factory: &mut Factory,
+        parameter: EngineParameter,
+        baseline: u8,
+        value: u8,
+    ) -> Result<Option<(u8, f64)>, GeneticError>
+    where
+        G: Genetics,
+        Factory: FnMut(usize) -> Result<World<G>, GeneticError>,
+    {
+        if value == baseline {
+            return Ok(None);
+        }
+
+        match self.mean_best_score(factory, Some((parameter, value))) {
+            Ok(score) => Ok(Some((value, score))),
+            Err(
+                GeneticError::NoGeneticOperators
+                | GeneticError::InvalidMutationPoints
+                | GeneticError::InvalidCrossoverPoints,
+            ) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn mean_best_score<G, Factory>(
+        &self,
+        factory: &mut Factory,
+        perturbation: Option<(EngineParameter, u8)>,
+    ) -> Result<f64, GeneticError>
+    where
+        G: Genetics,
+        Factory: FnMut(usize) -> Result<World<G>, GeneticError>,
+    {
+        let repeats = self.repeats.max(1);
+        let mut total = 0.0;
+        for repeat in 0..repeats {
+            let mut world = factory(repeat)?;
+            if let Some((parameter, value)) = perturbation {
+                parameter.set(world.genetic_engine_mut(), value)?;
+            }
+            world.run_n_generations(self.generations)?;
+            total += world.best_score().unwrap_or(0) as f64;
+        }
+
+        Ok(total / repeats as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn sensitivity_report_covers_every_parameter() {
+        let analysis = SensitivityAnalysis {
+            generations: 3,
+            repeats: 2,
+            factor: 2.0,
+        };
+
+        let report = analysis
+            .run(|_| {
+                world_builder(&["a"])
+                    .with_individuals_per_island(10)
+                    .build()
+            })
+            .unwrap();
+
+        // Every child gets a new id no matter which operator made it, so no parameter changes the outcome
+        assert_eq!(4, report.parameters.len());
+        assert!(report.baseline_score > 0.0);
+        for parameter in report.parameters {
+            assert_eq!(0.0, parameter.sensitivity);
+        }
+    }
+
+    #[test]
+    fn perturbations_the_engine_rejects_are_skipped_and_raised_values_are_capped() {
+        let analysis = SensitivityAnalysis {
+            generations: 1,
+            repeats: 1,
+            factor: 100.0,
+        };
+
+        let report = analysis
+            .run(|_| {
+                world_builder(&["a"])
+                    .with_individuals_per_island(10)
+                    .build()
+            })
+            .unwrap();
+
+        let find = |parameter| {
+            report
+                .parameters
+                .iter()
+                .find(|p| p.parameter == parameter)
+                .unwrap()
+        };
+
+        // The default engine has a mutation rate of 1, a crossover rate of 9 and at most 3 mutation and 10 crossover
+        // points. Lowering either rate to 0 leaves the other operator, but zero points disable an operator in use.
+        let mutation_rate = find(EngineParameter::MutationRate);
+        assert_eq!(1, mutation_rate.baseline);
+        assert_eq!(Some(0), mutation_rate.lower.map(|(value, _)| value));
+        assert_eq!(Some(100), mutation_rate.higher.map(|(value, _)| value));
+
+        let crossover_rate = find(EngineParameter::CrossoverRate);
+        assert_eq!(Some(0), crossover_rate.lower.map(|(value, _)| value));
+        assert_eq!(Some(u8::MAX), crossover_rate.higher.map(|(value, _)| value));
+
+        let max_mutation_points = find(EngineParameter::MaxMutationPoints);
+        assert_eq!(None, max_mutation_points.lower);
+        assert_eq!(
+            Some(u8::MAX),
+            max_mutation_points.higher.map(|(value, _)| value)
+        );
+
+        let max_crossover_points = find(EngineParameter::MaxCrossoverPoints);
+        assert_eq!(None, max_crossover_points.lower);
+        assert_eq!(
+            Some(u8::MAX),
+            max_crossover_points.higher.map(|(value, _)| value)
+        );
+    }
+
+    #[test]
+    fn zero_repeats_still_run_each_configuration_once() {
+        let analysis = SensitivityAnalysis {
+            generations: 1,
+            repeats: 0,
+            factor: 2.0,
+        };
+
+        let mut repeats = vec![];
+        analysis
+            .run(|repeat| {
+                repeats.push(repeat);
+                world_builder(&["a"])
+                    .with_individuals_per_island(10)
+                    .build()
+            })
+            .unwrap();
+
+        // The baseline, the world the baseline values are read from, and every perturbation that differs from its
+        // baseline: the mutation rate of 1 halves back to 1, so it is only raised
+        assert_eq!(2 + 7, repeats.len());
+        assert!(repeats.iter().all(|&repeat| repeat == 0));
+    }
+
+    #[test]
+    fn factory_errors_are_returned() {
+        let analysis = SensitivityAnalysis::default();
+
+        let mut calls = 0;
+        let result = analysis.run(|_| -> Result<World<CountingGenetics>, GeneticError> {
+            calls += 1;
+            Err(GeneticError::InvalidConfig("no world".to_string()))
+        });
+
+        assert!(
+            matches!(result, Err(GeneticError::InvalidConfig(message)) if message == "no world")
+        );
+        assert_eq!(1, calls);
+    }
+}
@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::Rng;
+
+/// Ranks islands for constrained problems by stochastic ranking (Runarsson and Yao, 2000), in place of the usual sort.
+/// Engines report how badly an individual breaks the problem's constraints with `IslandEngine::constraint_violation`.
+///
+/// The ranking is a bubble sort whose comparisons are randomized: two neighbors that are both feasible are compared by
+/// fitness, as usual. Otherwise they are compared by fitness with probability `objective_probability`, and by their
+/// violation, smaller being better, the rest of the time. A little less than one half lets good infeasible
+/// individuals survive next to feasible ones, which helps the search cross infeasible regions, while still steering
+/// the population towards feasibility. Without any violations the ranking is the usual sort.
+///
+/// The random comparisons of each island are drawn from the genetic engine when the island is filled, so a generation
+/// ranks the same way whenever it is run again from the same generation seed.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochasticRanking {
+    /// The probability of comparing two neighbors by fitness when at least one of them is infeasible. Must be between
+    /// 0.0 and 1.0.
+    ///
+    /// Default: 0.45
+    pub objective_probability: f64,
+}
+
+impl Default for StochasticRanking {
+    fn default() -> Self {
+        StochasticRanking {
+            objective_probability: 0.45,
+        }
+    }
+}
+
+impl StochasticRanking {
+    /// Returns true if every field is in its valid range
+    pub(crate) fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.objective_probability)
+    }
+
+    /// Sorts the individuals from least to most fit. `violations` holds the constraint violation of each individual,
+    /// in the same order, and `compare` orders two individuals by fitness alone.
+    pub(crate) fn rank<F>(
+        &self,
+        rng: &mut StdRng,
+        individuals: &mut [u64],
+        violations: &mut [f64],
+        compare: F,
+    ) where
+        F: Fn(u64, u64) -> Ordering,
+    {
+        // As many sweeps as there are individuals, stopping early once a sweep changes nothing
+        for _ in 0..individuals.len() {
+            let mut swapped = false;
+            for index in 1..individuals.len() {
+                let (violation_a, violation_b) = (violations[index - 1], violations[index]);
+                let a_is_fitter = if (violation_a <= 0.0 && violation_b <= 0.0)
+                    || rng.random_bool(self.objective_probability)
+                {
+                    compare(individuals[index - 1], individuals[index]) == Ordering::Greater
+                } else {
+                    violation_a < violation_b
+                };
+                if a_is_fitter {
+                    individuals.swap(index - 1, index);
+                    violations.swap(index - 1, index);
+                    swapped = true;
+                }
+            }
+            if !swapped {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn stochastic_ranking_weighs_constraint_violations() {
+        // Odd individuals break a constraint by their id
+        struct ConstrainedEngine;
+
+        impl IslandEngine for ConstrainedEngine {
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_individual(&self, id: u64) -> u64 {
+                id
+            }
+
+            fn constraint_violation(&self, id: u64) -> f64 {
+                (id % 2 * id) as f64
+            }
+        }
+
+        let ranked = |ranking: Option<StochasticRanking>| {
+            let mut builder = WorldBuilder::new()
+                .with_genetic_engine(counting_engine(1234))
+                .with_individuals_per_island(10)
+                .with_migrating_individuals(0);
+            if let Some(ranking) = ranking {
+                builder = builder.with_stochastic_ranking(ranking);
+            }
+            builder.add_island("a", Box::new(ConstrainedEngine));
+            let mut world = builder.build().unwrap();
+            world.run_n_generations(1).unwrap();
+            world.get_island(0).unwrap().snapshot().individuals
+        };
+
+        assert_eq!((0..10).collect::<Vec<u64>>(), ranked(None));
+
+        // Comparing by violation whenever one is infeasible puts every feasible individual above the infeasible ones
+        let by_violation = StochasticRanking {
+            objective_probability: 0.0,
+        };
+        assert_eq!(
+            vec![9, 7, 5, 3, 1, 0, 2, 4, 6, 8],
+            ranked(Some(by_violation))
+        );
+
+        let mixed = ranked(Some(StochasticRanking::default()));
+        assert_ne!(vec![9, 7, 5, 3, 1, 0, 2, 4, 6, 8], mixed);
+
+        // A generation run again from its seed ranks the same way
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(RandomIdGenetics)
+            .build()
+            .unwrap();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0)
+            .with_stochastic_ranking(StochasticRanking::default());
+        builder.add_island("a", Box::new(ConstrainedEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(1).unwrap();
+        let snapshot = world.snapshot();
+        world.run_n_generations(1).unwrap();
+        let seed = world.generation_seed().unwrap();
+        let ranking = world.get_island(0).unwrap().snapshot().individuals;
+        world.restore(&snapshot).unwrap();
+        world.set_next_generation_seed(seed);
+        world.run_n_generations(1).unwrap();
+        assert_eq!(ranking, world.get_island(0).unwrap().snapshot().individuals);
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_stochastic_ranking(StochasticRanking {
+                    objective_probability: 1.5,
+                })
+                .build(),
+            Err(GeneticError::InvalidStochasticRanking)
+        ));
+    }
+
+    #[test]
+    fn objective_probability_bounds() {
+        let rank = |objective_probability: f64, violations: &[f64]| {
+            let ranking = StochasticRanking {
+                objective_probability,
+            };
+            let mut individuals: Vec<u64> = (0..violations.len() as u64).rev().collect();
+            let mut violations = violations.to_vec();
+            let mut rng = rand::SeedableRng::seed_from_u64(1234);
+            ranking.rank(&mut rng, &mut individuals, &mut violations, |a, b| {
+                a.cmp(&b)
+            });
+            individuals
+        };
+
+        // Always comparing by fitness ignores the violations, and a negative violation counts as feasible
+        assert_eq!(vec![0, 1, 2, 3], rank(1.0, &[1.0, 2.0, 0.0, 3.0]));
+        assert_eq!(vec![0, 1, 2, 3], rank(0.0, &[-1.0, 0.0, -2.0, 0.0]));
+
+        // Never comparing by fitness ranks an infeasible individual below any feasible one. The individuals start as
+        // 3, 2, 1, 0, so 2 and 0 are the infeasible ones.
+        assert_eq!(vec![0, 2, 1, 3], rank(0.0, &[0.0, 1.0, 0.0, 2.0]));
+
+        assert!(rank(0.45, &[]).is_empty());
+        assert_eq!(vec![0], rank(0.45, &[5.0]));
+
+        assert!(StochasticRanking {
+            objective_probability: 0.0
+        }
+        .is_valid());
+        assert!(StochasticRanking {
+            objective_probability: 1.0
+        }
+        .is_valid());
+        for objective_probability in [-0.1, 1.1, f64::NAN] {
+            assert!(!StochasticRanking {
+                objective_probability
+            }
+            .is_valid());
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::Rng;
+
+use crate::*;
+
+/// Genetics that hands out sequential ids. Every operation produces a brand new id, so a larger id is always a younger
+/// individual.
+#[derive(Default)]
+pub(crate) struct CountingGenetics {
+    next: Cell<u64>,
+}
+
+impl CountingGenetics {
+    fn next_id(&self) -> u64 {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        id
+    }
+}
+
+impl Genetics for CountingGenetics {
+    fn random_individual(&self, _rng: &mut StdRng, _max_points: usize) -> u64 {
+        self.next_id()
+    }
+
+    fn mutate(&self, _rng: &mut StdRng, _individual: u64, _points: usize) -> u64 {
+        self.next_id()
+    }
+
+    fn crossover(
+        &self,
+        _rng: &mut StdRng,
+        _individual_a: u64,
+        _individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        self.next_id()
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        Some(individual.to_string())
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        genome.parse().ok()
+    }
+}
+
+/// Genetics that draws every id from the random number generator, so a generation run again from the same seed
+/// produces the same ids
+pub(crate) struct RandomIdGenetics;
+
+impl Genetics for RandomIdGenetics {
+    fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> u64 {
+        rng.random::<u32>() as u64
+    }
+
+    fn mutate(&self, rng: &mut StdRng, _individual: u64, _points: usize) -> u64 {
+        rng.random::<u32>() as u64
+    }
+
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        _individual_a: u64,
+        _individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        rng.random::<u32>() as u64
+    }
+}
+
+/// Island engine that scores each individual by its id
+pub(crate) struct IdScoreEngine;
+
+impl IslandEngine for IdScoreEngine {
+    fn run_individual(&mut self, _id: u64) {}
+
+    fn score_individual(&self, id: u64) -> u64 {
+        id
+    }
+}
+
+/// Island engine that gives every individual the same score
+pub(crate) struct UnscoredEngine;
+
+impl IslandEngine for UnscoredEngine {
+    fn run_individual(&mut self, _id: u64) {}
+}
+
+/// Island engine that scores each individual by its id, and quick scores new individuals the same way while counting
+/// how many individuals were run
+#[derive(Default)]
+pub(crate) struct QuickScoreEngine {
+    pub(crate) runs: Arc<AtomicUsize>,
+}
+
+impl IslandEngine for QuickScoreEngine {
+    fn run_individual(&mut self, _id: u64) {
+        self.runs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn score_individual(&self, id: u64) -> u64 {
+        id
+    }
+
+    fn quick_score(&mut self, id: u64) -> Option<u64> {
+        Some(id)
+    }
+}
+
+/// Island engine whose score alternates between the id plus and minus `noise` on every run
+pub(crate) struct NoisyEngine {
+    pub(crate) noise: u64,
+    pub(crate) runs: u64,
+}
+
+impl IslandEngine for NoisyEngine {
+    fn run_individual(&mut self, _id: u64) {
+        self.runs += 1;
+    }
+
+    fn score_individual(&self, id: u64) -> u64 {
+        if self.runs.is_multiple_of(2) {
+            id * 10 + self.noise
+        } else {
+            (id * 10).saturating_sub(self.noise)
+        }
+    }
+}
+
+/// Island engine where individuals below `noisy_below` alternate between the id plus and minus `noise` on every run,
+/// and the rest always score their id
+pub(crate) struct PartlyNoisyEngine {
+    pub(crate) noise: u64,
+    pub(crate) noisy_below: u64,
+    pub(crate) runs: u64,
+}
+
+impl IslandEngine for PartlyNoisyEngine {
+    fn run_individual(&mut self, _id: u64) {
+        self.runs += 1;
+    }
+
+    fn score_individual(&self, id: u64) -> u64 {
+        if id >= self.noisy_below || self.runs.is_multiple_of(2) {
+            id * 10 + self.noise
+        } else {
+            (id * 10).saturating_sub(self.noise)
+        }
+    }
+}
+
+/// Island engine that scores each individual by its id times `scale`, and normalizes the score back to the id
+pub(crate) struct ScaledEngine {
+    pub(crate) scale: u64,
+}
+
+impl IslandEngine for ScaledEngine {
+    fn run_individual(&mut self, _id: u64) {}
+
+    fn score_individual(&self, id: u64) -> u64 {
+        id * self.scale
+    }
+
+    fn normalized_score(&self, id: u64) -> Option<f64> {
+        Some(id as f64)
+    }
+}
+
+/// Island engine that fails to evaluate every individual
+pub(crate) struct FailingEngine;
+
+impl IslandEngine for FailingEngine {
+    fn run_individual(&mut self, _id: u64) {}
+
+    fn try_run_individual(&mut self, id: u64) -> Result<(), String> {
+        Err(format!("individual {} crashed", id))
+    }
+}
+
+/// Island engine that scores each individual by its id, and fails to evaluate only the `fail_on`th individual it runs
+pub(crate) struct FailOnceEngine {
+    pub(crate) fail_on: usize,
+    pub(crate) runs: usize,
+}
+
+impl IslandEngine for FailOnceEngine {
+    fn run_individual(&mut self, _id: u64) {}
+
+    fn try_run_individual(&mut self, id: u64) -> Result<(), String> {
+        self.runs += 1;
+        if self.runs == self.fail_on {
+            return Err(format!("individual {} crashed", id));
+        }
+        Ok(())
+    }
+
+    fn score_individual(&self, id: u64) -> u64 {
+        id
+    }
+}
+
+/// Creates a seeded genetic engine using `CountingGenetics`
+pub(crate) fn counting_engine(seed: u64) -> GeneticEngine<CountingGenetics> {
+    GeneticEngineBuilder::default()
+        .seed(seed)
+        .genetics(CountingGenetics::default())
+        .build()
+        .unwrap()
+}
+
+/// Creates a world builder with the named islands, each scored by `IdScoreEngine`
+pub(crate) fn world_builder(island_names: &[&str]) -> WorldBuilder<CountingGenetics> {
+    let mut builder = WorldBuilder::new().with_genetic_engine(counting_engine(1234));
+    for &name in island_names {
+        builder.add_island(name, Box::new(IdScoreEngine));
+    }
+
+    builder
+}
+
+/// Runs a future to completion on the current thread, for testing the `async` API without an executor.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::task::Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
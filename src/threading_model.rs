@@ -0,0 +1,143 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::GeneticError;
+
+/// Determines how a world spreads the work of its islands across threads. It is set with
+/// `WorldBuilder::with_threading_model` when the crate is built with the `multi-threaded` feature, and only has an
+/// effect without the `async` feature, whose islands are always awaited one after another.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThreadingModel {
+    /// Every island runs on the thread that drives the world, one after another
+    #[default]
+    None,
+
+    /// `World::run_one_generation`, and the methods that run several generations with it, run the generation of each
+    /// island on a thread of its own and wait for all of them before the generation is completed. Every island reads
+    /// the blackboard before any of them runs, so an island does not see what the others publish in the same
+    /// generation. `World::advance_island` and `World::poll_generation` still run one island at a time.
+    ThreadPerIsland,
+}
+
+impl fmt::Display for ThreadingModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for ThreadingModel {
+    type Err = GeneticError;
+
+    /// Parses the name of a variant, as written by `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" => Ok(ThreadingModel::None),
+            "ThreadPerIsland" => Ok(ThreadingModel::ThreadPerIsland),
+            _ => Err(GeneticError::InvalidSettingValue {
+                kind: "threading model",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "multi-threaded", not(feature = "async")))]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+    use std::thread::ThreadId;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    // Scores each individual by its id, and records the threads it was run on
+    struct ThreadRecordingEngine {
+        threads: Arc<Mutex<HashSet<ThreadId>>>,
+    }
+
+    impl IslandEngine for ThreadRecordingEngine {
+        fn run_individual(&mut self, _id: u64) {
+            self.threads
+                .lock()
+                .unwrap()
+                .insert(std::thread::current().id());
+        }
+
+        fn score_individual(&self, id: u64) -> u64 {
+            id
+        }
+    }
+
+    fn build(
+        model: ThreadingModel,
+        threads: &Arc<Mutex<HashSet<ThreadId>>>,
+    ) -> World<CountingGenetics> {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(6)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(2)
+            .with_threading_model(model);
+        for name in ["a", "b", "c"] {
+            builder.add_island(
+                name,
+                Box::new(ThreadRecordingEngine {
+                    threads: threads.clone(),
+                }),
+            );
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn islands_run_on_threads_of_their_own() {
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let mut world = build(ThreadingModel::ThreadPerIsland, &threads);
+        assert_eq!(ThreadingModel::ThreadPerIsland, world.threading_model());
+        world.run_n_generations(1).unwrap();
+
+        let threads = threads.lock().unwrap();
+        assert_eq!(3, threads.len());
+        assert!(!threads.contains(&std::thread::current().id()));
+    }
+
+    #[test]
+    fn threads_per_island_evolve_like_a_single_thread() {
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let mut single = build(ThreadingModel::None, &threads);
+        single.run_n_generations(5).unwrap();
+        assert_eq!(1, threads.lock().unwrap().len());
+
+        let mut threaded = build(ThreadingModel::ThreadPerIsland, &threads);
+        threaded.run_n_generations(5).unwrap();
+        let populations = |world: &World<CountingGenetics>| {
+            world
+                .islands()
+                .map(|island| island.snapshot().individuals)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(populations(&single), populations(&threaded));
+        assert_eq!(single.migration_log(), threaded.migration_log());
+    }
+
+    #[test]
+    fn every_island_completes_before_a_failure_is_returned() {
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_threading_model(ThreadingModel::ThreadPerIsland);
+        builder.add_island("b", Box::new(FailingEngine));
+        builder.add_island("c", Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.fill_all_islands().unwrap();
+
+        assert!(matches!(
+            world.run_one_generation(),
+            Err(GeneticError::EvaluationFailed { island, .. }) if island == "b"
+        ));
+        let sorted: Vec<bool> = world.islands().map(|island| island.is_sorted()).collect();
+        assert_eq!(vec![true, false, true], sorted);
+        assert!(world.get_island(1).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp::Ordering;
+
+use crate::{Champion, GeneticError, Genetics, World};
+
+/// Runs several worlds against each other using successive halving. Every round, each world that is still in the
+/// tournament runs a budget of generations. Their champions are then compared pairwise, and the half of the worlds
+/// with the fewest wins is eliminated. The generation budget doubles every round, so the remaining compute is
+/// reallocated toward the better performing configurations. The tournament ends when one world remains.
+pub struct Tournament<G>
+where
+    G: Genetics,
+{
+    generations_per_round: usize,
+    entries: Vec<TournamentEntry<G>>,
+    rounds_run: usize,
+}
+
+/// The standing of one world in a tournament.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentStanding {
+    /// The name the world was entered with
+    pub name: String,
+
+    /// The total number of generations the world ran during the tournament
+    pub generations_run: usize,
+
+    /// The round the world was eliminated in (starting at zero), or None if it is still in the tournament
+    pub eliminated_in_round: Option<usize>,
+}
+
+struct TournamentEntry<G>
+where
+    G: Genetics,
+{
+    name: String,
+    world: World<G>,
+    standing: TournamentStanding,
+}
+
+impl<G> Tournament<G>
+where
+    G: Genetics,
+{
+    /// Creates an empty tournament. The worlds run `generations_per_round` generations in the first round, and twice
+    /// as many as the previous round in every round after that.
+    pub fn new(generations_per_round: usize) -> Self {
+        Tournament {
+            generations_per_round: generations_per_round.max(1),
+            entries: vec![],
+            rounds_run: 0,
+        }
+    }
+
+    /// Enters a world in the tournament
+    pub fn add_world<S: Into<String>>(&mut self, name: S, world: World<G>) -> &mut Self {
+        let name = name.into();
+        self.entries.push(TournamentEntry {
+            name: name.clone(),
+            world,
+            standing: TournamentStanding {
+                name,
+                generations_run: 0,
+                eliminated_in_round: None,
+            },
+        });
+        self
+    }
+
+    /// Borrows a world by the name it was entered with
+    pub fn get_world(&self, name: &str) -> Option<&World<G>> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| &entry.world)
+    }
+
+    /// Returns the standing of every world, in the order they were entered
+    pub fn standings(&self) -> Vec<TournamentStanding> {
+        self.entries
+            .iter()
+            .map(|entry| entry.standing.clone())
+            .collect()
+    }
+
+    /// Runs rounds until one world remains and returns its name. `compare` receives two worlds along with their
+    /// champions and returns `Ordering::Greater` if the first champion beats the second. A world without a champion
+    /// loses every comparison.
+    pub fn run<Compare>(&mut self, mut compare: Compare) -> Result<&str, GeneticError>
+    where
+        Compare: FnMut(&World<G>, Champion, &World<G>, Champion) -> Ordering,
+    {
+        if self.entries.is_empty() {
+            return Err(GeneticError::EmptyTournament);
+        }
+
+        while self.remaining().len() > 1 {
+            self.run_one_round(&mut compare)?;
+        }
+
+        Ok(&self.entries[self.remaining()[0]].name)
+    }
+
+    /// Runs a single round: every remaining world runs this round's generation budget and then the worlds with the
+    /// fewest wins are eliminated. Returns the number of worlds that remain.
+    pub fn run_one_round<Compare>(&mut self, mut compare: Compare) -> Result<usize, GeneticError>
+    where
+        Compare: FnMut(&World<G>, Champion, &World<G>, Champion) -> Ordering,
+    {
+        let remaining = self.remaining();
+        let generations = self.generations_per_round << self.rounds_run.min(32);
+        for &index in remaining.iter() {
+            let entry = &mut self.entries[index];
+            entry.world.run_n_generations(generations)?;
+            entry.standing.generations_run += generations;
+        }
+
+        // Every remaining world meets every other remaining world once
+        let mut wins = vec![0usize; remaining.len()];
+        for a in 0..remaining.len() {
+            for b in (a + 1)..remaining.len() {
+                let world_a = &self.entries[remaining[a]].world;
+                let world_b = &self.entries[remaining[b]].world;
+                let outcome = match (world_a.most_fit_individual(), world_b.most_fit_individual()) {
+                    (Some(champion_a), Some(champion_b)) => {
+                        compare(world_a, champion_a, world_b, champion_b)
+                    }
+                    (Some(_), None) => Ordering::Greater,
+                    (None, Some(_)) => Ordering::Less,
+                    (None, None) => Ordering::Equal,
+                };
+                match outcome {
+                    Ordering::Greater => wins[a] += 1,
+                    Ordering::Less => wins[b] += 1,
+                    Ordering::Equal => {}
+                }
+            }
+        }
+
+        // Keep the better half, rounding up. Ties are broken in favor of the world entered first.
+        let mut ranked: Vec<usize> = (0..remaining.len()).collect();
+        ranked.sort_by(|&a, &b| wins[b].cmp(&wins[a]).then(a.cmp(&b)));
+        let keep = remaining.len().div_ceil(2);
+        for &loser in ranked[keep..].iter() {
+            self.entries[remaining[loser]].standing.eliminated_in_round = Some(self.rounds_run);
+        }
+        self.rounds_run += 1;
+
+        Ok(keep)
+    }
+
+    // The indices of the worlds that have not been eliminated
+    fn remaining(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.standing.eliminated_in_round.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn successive_halving_keeps_the_best_world() {
+        let mut tournament = Tournament::new(2);
+        for (name, size) in [("small", 5), ("large", 20), ("medium", 10)] {
+            let world = world_builder(&["a", "b"])
+                .with_individuals_per_island(size)
+                .with_migrating_individuals(2)
+                .build()
+                .unwrap();
+            tournament.add_world(name, world);
+        }
+
+        // The counting genetics hands out larger ids to larger islands, and ids are the scores
+        let winner = tournament
+            .run(|_, a, _, b| a.score.cmp(&b.score))
+            .unwrap()
+            .to_string();
+
+        assert_eq!("large", winner);
+        let standings = tournament.standings();
+        assert_eq!(Some(0), standings[0].eliminated_in_round);
+        assert_eq!(None, standings[1].eliminated_in_round);
+        assert_eq!(6, standings[1].generations_run);
+        assert_eq!(Some(1), standings[2].eliminated_in_round);
+    }
+
+    #[test]
+    fn tournaments_of_zero_or_one_world_run_no_rounds() {
+        let mut tournament: Tournament<CountingGenetics> = Tournament::new(1);
+        assert!(matches!(
+            tournament.run(|_, _, _, _| std::cmp::Ordering::Equal),
+            Err(GeneticError::EmptyTournament)
+        ));
+
+        tournament.add_world("only", world_builder(&["a"]).build().unwrap());
+        assert_eq!("only", tournament.run(|_, _, _, _| unreachable!()).unwrap());
+        assert_eq!(0, tournament.standings()[0].generations_run);
+    }
+
+    #[test]
+    fn tied_worlds_are_kept_in_the_order_they_were_entered() {
+        // A round budget of zero still runs a generation
+        let mut tournament = Tournament::new(0);
+        for name in ["first", "second", "third"] {
+            tournament.add_world(name, world_builder(&["a"]).build().unwrap());
+        }
+
+        assert_eq!(
+            2,
+            tournament
+                .run_one_round(|_, _, _, _| std::cmp::Ordering::Equal)
+                .unwrap()
+        );
+        let standings = tournament.standings();
+        assert_eq!(
+            vec![1, 1, 1],
+            standings
+                .iter()
+                .map(|s| s.generations_run)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(0), standings[2].eliminated_in_round);
+
+        assert_eq!(
+            "first",
+            tournament
+                .run(|_, _, _, _| std::cmp::Ordering::Equal)
+                .unwrap()
+        );
+        assert_eq!(3, tournament.get_world("first").unwrap().generation_count());
+    }
+}
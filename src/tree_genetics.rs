@@ -0,0 +1,492 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::genetics::hash_genome;
+use crate::{GeneticError, Genetics, IndividualStore};
+
+/// A function or terminal that trees of `TreeGenetics` are built from, or a function computed by the nodes of
+/// `CartesianGenetics`. Terminals have an arity of zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Primitive {
+    /// The name the primitive is written with by `Genetics::encode`
+    pub name: String,
+
+    /// The number of children of a node with this primitive
+    pub arity: usize,
+}
+
+impl Primitive {
+    pub fn function<S: Into<String>>(name: S, arity: usize) -> Self {
+        Primitive {
+            name: name.into(),
+            arity,
+        }
+    }
+
+    pub fn terminal<S: Into<String>>(name: S) -> Self {
+        Primitive::function(name, 0)
+    }
+}
+
+/// How `TreeGenetics` changes an individual.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeMutation {
+    /// Replaces a random subtree with a new random tree of up to `TreeGenetics::with_mutation_depth` levels. The
+    /// engine's mutation points are not used.
+    #[default]
+    Subtree,
+
+    /// Replaces as many random nodes as the engine's mutation points with other primitives of the same arity, keeping
+    /// the shape of the tree
+    Point,
+}
+
+/// A ready-made `Genetics` for tree-based genetic programming. Trees are built from a set of `Primitive`s and kept in
+/// an `IndividualStore` in prefix order: each node is the index of its primitive, followed by the subtrees of its
+/// children. Island engines read the trees through `store` and evaluate them by walking the nodes in that order.
+///
+/// Random individuals are created by ramped half-and-half: each tree is grown or fully built to a random depth within
+/// `initial_depth`. Crossover replaces a random subtree of the first parent with a random subtree of the second.
+/// Offspring deeper than `max_depth` or larger than the size limit are discarded in favor of a copy of the first
+/// parent, which keeps bloat in check. The size limit is the engine's `max_individual_points` unless it is set with
+/// `with_max_size`. The distance between two trees is the number of positions at which their nodes differ.
+pub struct TreeGenetics {
+    primitives: Vec<Primitive>,
+    functions: Vec<usize>,
+    terminals: Vec<usize>,
+    initial_depth: RangeInclusive<usize>,
+    max_depth: usize,
+    max_size: Option<usize>,
+    mutation: TreeMutation,
+    mutation_depth: usize,
+    // The largest `max_points` passed to `random_individual`, which is the engine's `max_individual_points`
+    engine_max_size: Cell<Option<usize>>,
+    store: Arc<IndividualStore<Vec<usize>>>,
+}
+
+impl TreeGenetics {
+    /// Creates genetics for trees of the given primitives. Returns an error if there is no terminal, or if a name is
+    /// empty, repeated, or contains whitespace or parentheses.
+    pub fn new(primitives: Vec<Primitive>) -> Result<Self, GeneticError> {
+        let mut names = HashSet::new();
+        let valid_names = primitives.iter().all(|primitive| {
+            !primitive.name.is_empty()
+                && !primitive
+                    .name
+                    .contains(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                && names.insert(primitive.name.as_str())
+        });
+        let (terminals, functions): (Vec<usize>, Vec<usize>) =
+            (0..primitives.len()).partition(|&index| primitives[index].arity == 0);
+        if !valid_names || terminals.is_empty() {
+            return Err(GeneticError::InvalidPrimitiveSet);
+        }
+
+        Ok(TreeGenetics {
+            primitives,
+            functions,
+            terminals,
+            initial_depth: 2..=6,
+            max_depth: 17,
+            max_size: None,
+            mutation: TreeMutation::default(),
+            mutation_depth: 4,
+            engine_max_size: Cell::new(None),
+            store: Arc::new(IndividualStore::new()),
+        })
+    }
+
+    /// Sets the range of depths random individuals are created with. A tree of a single node has depth zero.
+    /// Default: 2..=6
+    pub fn with_initial_depth(
+        mut self,
+        depth: RangeInclusive<usize>,
+    ) -> Result<Self, GeneticError> {
+        if depth.is_empty() || *depth.end() > self.max_depth {
+            return Err(GeneticError::InvalidTreeDepth);
+        }
+        self.initial_depth = depth;
+        Ok(self)
+    }
+
+    /// Sets the depth no offspring may exceed. Default: 17
+    pub fn with_max_depth(mut self, depth: usize) -> Result<Self, GeneticError> {
+        if depth < *self.initial_depth.end() {
+            return Err(GeneticError::InvalidTreeDepth);
+        }
+        self.max_depth = depth;
+        Ok(self)
+    }
+
+    /// Sets the number of nodes no offspring may exceed, in place of the engine's `max_individual_points`
+    pub fn with_max_size(mut self, size: usize) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    pub fn with_mutation(mut self, mutation: TreeMutation) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// Sets the depth of the random subtrees created by `TreeMutation::Subtree`. Default: 4
+    pub fn with_mutation_depth(mut self, depth: usize) -> Self {
+        self.mutation_depth = depth;
+        self
+    }
+
+    /// Returns the primitive a node refers to
+    pub fn primitive(&self, node: usize) -> &Primitive {
+        &self.primitives[node]
+    }
+
+    /// Returns a handle to the store of trees, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<Vec<usize>>> {
+        self.store.clone()
+    }
+
+    /// Returns the nodes of an individual in prefix order, or None if it is not stored
+    pub fn tree(&self, id: u64) -> Option<Arc<Vec<usize>>> {
+        self.store.get(id)
+    }
+
+    /// Returns the index just past the subtree that starts at `start`
+    pub fn subtree_end(&self, nodes: &[usize], start: usize) -> usize {
+        let mut end = start;
+        let mut open = 1;
+        while open > 0 {
+            open = open + self.primitives[nodes[end]].arity - 1;
+            end += 1;
+        }
+        end
+    }
+
+    /// Returns the depth of a tree, where a tree of a single node has depth zero
+    pub fn depth(&self, nodes: &[usize]) -> usize {
+        // The number of children still expected at each level of the path to the current node
+        let mut path: Vec<usize> = vec![];
+        let mut depth = 0;
+        for &node in nodes {
+            depth = depth.max(path.len());
+            if let Some(remaining) = path.last_mut() {
+                *remaining -= 1;
+            }
+            path.push(self.primitives[node].arity);
+            while path.last() == Some(&0) {
+                path.pop();
+            }
+        }
+        depth
+    }
+
+    fn size_limit(&self) -> usize {
+        self.max_size
+            .or(self.engine_max_size.get())
+            .unwrap_or(usize::MAX)
+    }
+
+    fn fits(&self, nodes: &[usize], max_size: usize) -> bool {
+        nodes.len() <= max_size && self.depth(nodes) <= self.max_depth
+    }
+
+    // Appends a random tree of up to `depth` levels. A full tree only has terminals at the deepest level.
+    fn generate(&self, rng: &mut StdRng, depth: usize, full: bool, nodes: &mut Vec<usize>) {
+        let terminal_chance = self.terminals.len() as f64 / self.primitives.len() as f64;
+        let terminal =
+            depth == 0 || self.functions.is_empty() || (!full && rng.random_bool(terminal_chance));
+        let choices = if terminal {
+            &self.terminals
+        } else {
+            &self.functions
+        };
+        let node = *choices.choose(rng).unwrap();
+        nodes.push(node);
+        for _ in 0..self.primitives[node].arity {
+            self.generate(rng, depth - 1, full, nodes);
+        }
+    }
+
+    fn genome(&self, id: u64) -> Arc<Vec<usize>> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+
+    // Stores the child if it is within the limits, and otherwise a copy of the parent
+    fn insert_within_limits(&self, child: Vec<usize>, parent: u64) -> u64 {
+        if self.fits(&child, self.size_limit()) {
+            self.store.insert(child)
+        } else {
+            let copy = (*self.genome(parent)).clone();
+            self.store.insert(copy)
+        }
+    }
+
+    fn write(&self, nodes: &[usize], start: usize, text: &mut String) -> usize {
+        let primitive = &self.primitives[nodes[start]];
+        if primitive.arity == 0 {
+            text.push_str(&primitive.name);
+            return start + 1;
+        }
+
+        text.push('(');
+        text.push_str(&primitive.name);
+        let mut next = start + 1;
+        for _ in 0..primitive.arity {
+            text.push(' ');
+            next = self.write(nodes, next, text);
+        }
+        text.push(')');
+        next
+    }
+
+    fn parse<'a, I: Iterator<Item = &'a str>>(
+        &self,
+        tokens: &mut I,
+        nodes: &mut Vec<usize>,
+    ) -> Option<()> {
+        let token = tokens.next()?;
+        let name = if token == "(" { tokens.next()? } else { token };
+        let node = self
+            .primitives
+            .iter()
+            .position(|primitive| primitive.name == name)?;
+        let arity = self.primitives[node].arity;
+        // Functions are written in parentheses and terminals without
+        if (token == "(") != (arity > 0) {
+            return None;
+        }
+
+        nodes.push(node);
+        for _ in 0..arity {
+            self.parse(tokens, nodes)?;
+        }
+        if arity > 0 && tokens.next()? != ")" {
+            return None;
+        }
+        Some(())
+    }
+}
+
+impl Genetics for TreeGenetics {
+    fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+        let largest = self
+            .engine_max_size
+            .get()
+            .map_or(max_points, |size| size.max(max_points));
+        self.engine_max_size.set(Some(largest));
+
+        let max_size = max_points.min(self.size_limit()).max(1);
+        let mut depth = rng.random_range(self.initial_depth.clone());
+        let full = rng.random_bool(0.5);
+        loop {
+            let mut nodes = vec![];
+            self.generate(rng, depth, full, &mut nodes);
+            if depth == 0 || self.fits(&nodes, max_size) {
+                return self.store.insert(nodes);
+            }
+            depth -= 1;
+        }
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let mut child = (*self.genome(individual)).clone();
+        match self.mutation {
+            TreeMutation::Subtree => {
+                let start = rng.random_range(0..child.len());
+                let end = self.subtree_end(&child, start);
+                let mut subtree = vec![];
+                self.generate(rng, self.mutation_depth, false, &mut subtree);
+                child.splice(start..end, subtree);
+            }
+            TreeMutation::Point => {
+                for _ in 0..points {
+                    let position = rng.random_range(0..child.len());
+                    let arity = self.primitives[child[position]].arity;
+                    let alternatives: Vec<usize> = (0..self.primitives.len())
+                        .filter(|&node| {
+                            node != child[position] && self.primitives[node].arity == arity
+                        })
+                        .collect();
+                    if let Some(&node) = alternatives.choose(rng) {
+                        child[position] = node;
+                    }
+                }
+            }
+        }
+        self.insert_within_limits(child, individual)
+    }
+
+    // The engine's crossover points are not used; a single subtree is exchanged
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        _points: usize,
+    ) -> u64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let start_a = rng.random_range(0..a.len());
+        let start_b = rng.random_range(0..b.len());
+        let mut child = (*a).clone();
+        child.splice(
+            start_a..self.subtree_end(&a, start_a),
+            b[start_b..self.subtree_end(&b, start_b)].iter().copied(),
+        );
+        self.insert_within_limits(child, individual_a)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        let (a, b) = (self.genome(individual_a), self.genome(individual_b));
+        let differing = a.iter().zip(b.iter()).filter(|(a, b)| a != b).count();
+        (differing + a.len().abs_diff(b.len())) as f64
+    }
+
+    // Trees are written as S-expressions, such as `(add x (mul x x))`
+    fn size(&self, individual: u64) -> Option<usize> {
+        Some(self.store.get(individual)?.len())
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .map_or(individual, |nodes| hash_genome(&*nodes))
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        let nodes = self.store.get(individual)?;
+        let mut text = String::new();
+        self.write(&nodes, 0, &mut text);
+        Some(text)
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        let spaced = genome.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = spaced.split_whitespace();
+        let mut nodes = vec![];
+        self.parse(&mut tokens, &mut nodes)?;
+        if tokens.next().is_some() {
+            return None;
+        }
+        Some(self.store.insert(nodes))
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::*;
+
+    fn arithmetic() -> TreeGenetics {
+        TreeGenetics::new(vec![
+            Primitive::function("add", 2),
+            Primitive::function("mul", 2),
+            Primitive::terminal("x"),
+            Primitive::terminal("one"),
+        ])
+        .unwrap()
+    }
+
+    // Scores a tree by how closely it matches x * x + 1 on a few points
+    struct RegressionEngine {
+        store: Arc<IndividualStore<Vec<usize>>>,
+    }
+
+    impl RegressionEngine {
+        fn evaluate(nodes: &[usize], next: &mut usize, x: i64) -> i64 {
+            let node = nodes[*next];
+            *next += 1;
+            match node {
+                0 => Self::evaluate(nodes, next, x).saturating_add(Self::evaluate(nodes, next, x)),
+                1 => Self::evaluate(nodes, next, x).saturating_mul(Self::evaluate(nodes, next, x)),
+                2 => x,
+                _ => 1,
+            }
+        }
+    }
+
+    impl IslandEngine for RegressionEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            let nodes = self.store.get(id).unwrap();
+            let error: u64 = (0..5)
+                .map(|x| Self::evaluate(&nodes, &mut 0, x).abs_diff(x * x + 1))
+                .fold(0, u64::saturating_add);
+            1000u64.saturating_sub(error)
+        }
+    }
+
+    #[test]
+    fn offspring_respect_the_limits() {
+        assert!(matches!(
+            TreeGenetics::new(vec![Primitive::function("add", 2)]),
+            Err(GeneticError::InvalidPrimitiveSet)
+        ));
+
+        let genetics = arithmetic().with_max_depth(6).unwrap().with_max_size(20);
+        let tree = genetics.decode("(add x (mul x one))").unwrap();
+        assert_eq!(
+            Some("(add x (mul x one))".to_string()),
+            genetics.encode(tree)
+        );
+        assert_eq!(2, genetics.depth(&genetics.tree(tree).unwrap()));
+        assert_eq!(None, genetics.decode("(add x)"));
+        assert_eq!(None, genetics.decode("(x)"));
+
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut individuals: Vec<u64> = (0..10)
+            .map(|_| genetics.random_individual(&mut rng, 100))
+            .collect();
+        for _ in 0..200 {
+            let a = *individuals.choose(&mut rng).unwrap();
+            let b = *individuals.choose(&mut rng).unwrap();
+            individuals.push(genetics.crossover(&mut rng, a, b, 1));
+            individuals.push(genetics.mutate(&mut rng, a, 2));
+        }
+        for id in individuals {
+            let nodes = genetics.tree(id).unwrap();
+            assert!(genetics.depth(&nodes) <= 6 && nodes.len() <= 20);
+            assert_eq!(nodes.len(), genetics.subtree_end(&nodes, 0));
+        }
+    }
+
+    #[test]
+    fn a_polynomial_is_found() {
+        let genetics = arithmetic();
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .max_individual_points(30)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(50)
+            .with_migrating_individuals(1);
+        world.add_island("a", Box::new(RegressionEngine { store }));
+        let mut world = world.build().unwrap();
+
+        world.run_n_generations(30).unwrap();
+        assert_eq!(Some(1000), world.best_score());
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rand::rngs::StdRng; // cspell:disable-line
+
+use crate::{Genetics, IndividualStore};
+
+/// Genetic operators for genomes that are ordinary values, such as a `Vec<f64>`, rather than `u64` handles into the
+/// user's own storage. Wrap an implementation in `StoredGenetics` to use it in a genetic engine: the genomes are kept
+/// in an `IndividualStore`, and the rest of the framework sees only their ids.
+pub trait ValueGenetics {
+    /// The genome of an individual
+    type Individual;
+
+    /// Produces a random individual of up to the `max_points` number of code items.
+    fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> Self::Individual;
+
+    /// Produces a copy of the individual with `points` number of code items replaced by new random code.
+    fn mutate(
+        &self,
+        rng: &mut StdRng,
+        individual: &Self::Individual,
+        points: usize,
+    ) -> Self::Individual;
+
+    /// Combines the code of two individuals by swapping `points` number of code items between them.
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: &Self::Individual,
+        individual_b: &Self::Individual,
+        points: usize,
+    ) -> Self::Individual;
+
+    /// Measures how different two individuals are. See `Genetics::distance`. The default implementation considers any
+    /// two individuals with different ids to be 1.0 apart, without looking at their genomes.
+    fn distance(&self, _individual_a: &Self::Individual, _individual_b: &Self::Individual) -> f64 {
+        1.0
+    }
+
+    /// Returns the number of code points in an individual. See `Genetics::size`. The default implementation returns
+    /// None.
+    fn size(&self, _individual: &Self::Individual) -> Option<usize> {
+        None
+    }
+
+    /// Returns a hash of the genome of an individual. See `Genetics::genome_hash`. The default implementation returns
+    /// None, and every individual is then considered unique.
+    fn genome_hash(&self, _individual: &Self::Individual) -> Option<u64> {
+        None
+    }
+
+    /// Writes the genome of an individual as text. See `Genetics::encode`. The default implementation returns None.
+    fn encode(&self, _individual: &Self::Individual) -> Option<String> {
+        None
+    }
+
+    /// Recreates a genome written by `encode`. See `Genetics::decode`. The default implementation returns None.
+    fn decode(&self, _genome: &str) -> Option<Self::Individual> {
+        None
+    }
+
+    /// Creates an individual from a point in the unit hypercube. See `Genetics::individual_from_point`. The default
+    /// implementation returns None.
+    fn individual_from_point(&self, _point: &[f64]) -> Option<Self::Individual> {
+        None
+    }
+}
+
+/// Implements `Genetics` for a `ValueGenetics` by keeping every genome in an `IndividualStore` and handing out its id.
+/// Genomes are dropped once the world no longer references them.
+///
+/// The store is shared: island engines that need the genomes to evaluate an individual keep a handle from `store`.
+pub struct StoredGenetics<V: ValueGenetics> {
+    genetics: V,
+    store: Arc<IndividualStore<V::Individual>>,
+}
+
+impl<V: ValueGenetics> StoredGenetics<V> {
+    pub fn new(genetics: V) -> Self {
+        StoredGenetics {
+            genetics,
+            store: Arc::new(IndividualStore::new()),
+        }
+    }
+
+    /// Borrows the wrapped genetics
+    pub fn genetics(&self) -> &V {
+        &self.genetics
+    }
+
+    /// Returns a handle to the store of genomes, which can be given to island engines
+    pub fn store(&self) -> Arc<IndividualStore<V::Individual>> {
+        self.store.clone()
+    }
+
+    /// Returns the genome of an individual, or None if it is not stored
+    pub fn individual(&self, id: u64) -> Option<Arc<V::Individual>> {
+        self.store.get(id)
+    }
+
+    /// Stores a genome created outside of the genetic operators, such as a seed individual, and returns its id
+    pub fn insert(&self, individual: V::Individual) -> u64 {
+        self.store.insert(individual)
+    }
+
+    fn genome(&self, id: u64) -> Arc<V::Individual> {
+        self.store
+            .get(id)
+            .unwrap_or_else(|| panic!("individual {} is not in the store", id))
+    }
+}
+
+impl<V: ValueGenetics> Genetics for StoredGenetics<V> {
+    fn random_individual(&self, rng: &mut StdRng, max_points: usize) -> u64 {
+        self.insert(self.genetics.random_individual(rng, max_points))
+    }
+
+    fn mutate(&self, rng: &mut StdRng, individual: u64, points: usize) -> u64 {
+        let child = self.genetics.mutate(rng, &self.genome(individual), points);
+        self.insert(child)
+    }
+
+    fn crossover(
+        &self,
+        rng: &mut StdRng,
+        individual_a: u64,
+        individual_b: u64,
+        points: usize,
+    ) -> u64 {
+        let child = self.genetics.crossover(
+            rng,
+            &self.genome(individual_a),
+            &self.genome(individual_b),
+            points,
+        );
+        self.insert(child)
+    }
+
+    fn distance(&self, individual_a: u64, individual_b: u64) -> f64 {
+        if individual_a == individual_b {
+            0.0
+        } else {
+            self.genetics
+                .distance(&self.genome(individual_a), &self.genome(individual_b))
+        }
+    }
+
+    fn size(&self, individual: u64) -> Option<usize> {
+        self.genetics.size(&*self.store.get(individual)?)
+    }
+
+    fn genome_hash(&self, individual: u64) -> u64 {
+        self.store
+            .get(individual)
+            .and_then(|genome| self.genetics.genome_hash(&genome))
+            .unwrap_or(individual)
+    }
+
+    fn encode(&self, individual: u64) -> Option<String> {
+        self.genetics.encode(&*self.store.get(individual)?)
+    }
+
+    fn decode(&self, genome: &str) -> Option<u64> {
+        Some(self.insert(self.genetics.decode(genome)?))
+    }
+
+    fn individual_from_point(&self, point: &[f64]) -> Option<u64> {
+        Some(self.insert(self.genetics.individual_from_point(point)?))
+    }
+
+    fn collect_garbage(&self, referenced: &HashSet<u64>) {
+        self.store.collect_garbage(referenced);
+    }
+
+    fn contains(&self, individual: u64) -> bool {
+        self.store.contains(individual)
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::*;
+
+    struct RealGenetics;
+
+    impl ValueGenetics for RealGenetics {
+        type Individual = Vec<f64>;
+
+        fn random_individual(&self, rng: &mut StdRng, _max_points: usize) -> Vec<f64> {
+            (0..3).map(|_| rng.random_range(0.0..10.0)).collect()
+        }
+
+        fn mutate(&self, rng: &mut StdRng, individual: &Vec<f64>, _points: usize) -> Vec<f64> {
+            let mut child = individual.clone();
+            let gene = rng.random_range(0..child.len());
+            child[gene] = rng.random_range(0.0..10.0);
+            child
+        }
+
+        fn crossover(
+            &self,
+            _rng: &mut StdRng,
+            a: &Vec<f64>,
+            b: &Vec<f64>,
+            _points: usize,
+        ) -> Vec<f64> {
+            a.iter().zip(b).map(|(a, b)| (a + b) / 2.0).collect()
+        }
+    }
+
+    // Scores a genome by its sum, read from the shared store
+    struct SumEngine {
+        store: Arc<IndividualStore<Vec<f64>>>,
+    }
+
+    impl IslandEngine for SumEngine {
+        fn run_individual(&mut self, _id: u64) {}
+
+        fn score_individual(&self, id: u64) -> u64 {
+            self.store.get(id).unwrap().iter().sum::<f64>() as u64
+        }
+    }
+
+    #[test]
+    fn value_genomes_are_evaluated_through_the_store() {
+        let genetics = StoredGenetics::new(RealGenetics);
+        let store = genetics.store();
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(genetics)
+            .build()
+            .unwrap();
+        let mut world = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(1);
+        world.add_island(
+            "a",
+            Box::new(SumEngine {
+                store: store.clone(),
+            }),
+        );
+        let mut world = world.build().unwrap();
+        world.run_n_generations(5).unwrap();
+
+        let champion = world.most_fit_individual().unwrap();
+        let genome = world
+            .genetic_engine()
+            .genetics()
+            .individual(champion.id)
+            .unwrap();
+        assert_eq!(champion.score, genome.iter().sum::<f64>() as u64);
+        assert!(store.len() <= 10);
+    }
+
+    #[test]
+    fn defaults_and_missing_genomes() {
+        let genetics = StoredGenetics::new(RealGenetics);
+        let mut rng = rand::SeedableRng::seed_from_u64(1234);
+        let a = genetics.random_individual(&mut rng, 3);
+        let b = genetics.random_individual(&mut rng, 3);
+        let missing = b + 100;
+
+        // An individual is no distance from itself, even when it is not stored, and the default distance sees any two
+        // different individuals as equally far apart
+        assert_eq!(0.0, genetics.distance(missing, missing));
+        assert_eq!(1.0, genetics.distance(a, b));
+
+        // Without a genome hash, or without a genome, an individual is hashed by its id
+        assert_eq!(a, genetics.genome_hash(a));
+        assert_eq!(missing, genetics.genome_hash(missing));
+        assert_eq!(None, genetics.size(a));
+        assert_eq!(None, genetics.encode(missing));
+
+        // Decoding that is not supported stores nothing
+        assert_eq!(None, genetics.decode("1,2,3"));
+        assert_eq!(None, genetics.individual_from_point(&[0.5]));
+        assert_eq!(2, genetics.store().len());
+
+        genetics.collect_garbage(&HashSet::from([b]));
+        assert!(!genetics.contains(a));
+        assert!(genetics.contains(b));
+        assert_eq!(None, genetics.individual(a));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the store")]
+    fn mutating_a_collected_individual_panics() {
+        let genetics = StoredGenetics::new(RealGenetics);
+        let mut rng = rand::SeedableRng::seed_from_u64(1234);
+        let a = genetics.random_individual(&mut rng, 3);
+        genetics.collect_garbage(&HashSet::new());
+
+        genetics.mutate(&mut rng, a, 1);
+    }
+}
@@ -0,0 +1,129 @@
+use rand::rngs::StdRng; // cspell:disable-line
+
+use crate::{Genetics, MaybeSend};
+
+/// A way of breeding a child that the genetic engine chooses alongside its built-in mutation and crossover, such as a
+/// swap mutation, a subtree crossover or a plain copy. Register operators with `GeneticEngineBuilder::operator`, each
+/// with a weight: every child is bred by mutation, crossover, reproduction or one of the operators, chosen at random in
+/// proportion to the engine's rates and the operator weights. Set the rates to zero to breed only with the registered
+/// operators.
+///
+/// Children bred by an operator are repaired and validated like any other child, and their origin is
+/// `IndividualOrigin::Operator` with the index of the operator in the order it was registered.
+///
+/// Any `Fn(&G, &mut StdRng, u64, u64) -> u64` is an operator that uses both parents.
+pub trait VariationOperator<G: Genetics>: MaybeSend {
+    /// Breeds a child of the two parents and returns its id. An operator that needs a single parent uses `left`.
+    fn vary(&self, genetics: &G, rng: &mut StdRng, left: u64, right: u64) -> u64;
+
+    /// Returns the number of parents the operator uses, either one or two, as recorded in the metadata of its
+    /// children. The default implementation returns 2.
+    fn parents(&self) -> usize {
+        2
+    }
+}
+
+impl<G, F> VariationOperator<G> for F
+where
+    G: Genetics,
+    F: Fn(&G, &mut StdRng, u64, u64) -> u64 + MaybeSend,
+{
+    fn vary(&self, genetics: &G, rng: &mut StdRng, left: u64, right: u64) -> u64 {
+        self(genetics, rng, left, right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::CountingGenetics;
+    use crate::*;
+
+    // Breeds a child by copying its only parent
+    struct Copy;
+
+    impl VariationOperator<CountingGenetics> for Copy {
+        fn vary(
+            &self,
+            _genetics: &CountingGenetics,
+            _rng: &mut StdRng,
+            left: u64,
+            _right: u64,
+        ) -> u64 {
+            left
+        }
+
+        fn parents(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn registered_operators_are_chosen_by_weight() {
+        let mut engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .crossover_rate(0)
+            .operator(Copy, 1)
+            .operator(
+                |_: &CountingGenetics, _: &mut StdRng, _: u64, right: u64| right,
+                3,
+            )
+            .build()
+            .unwrap();
+        let (left, right) = (engine.rand_individual(), engine.rand_individual());
+
+        let mut bred = [0, 0];
+        for _ in 0..400 {
+            match engine.rand_child_with_origin(left, right).unwrap() {
+                (child, IndividualOrigin::Operator(0)) if child == left => bred[0] += 1,
+                (child, IndividualOrigin::Operator(1)) if child == right => bred[1] += 1,
+                unexpected => panic!("{:?} was not bred by a registered operator", unexpected),
+            }
+        }
+        assert!(bred[0] > 70 && bred[0] < 130);
+        assert_eq!(
+            (1, 2),
+            (engine.operator_parents(0), engine.operator_parents(1))
+        );
+    }
+
+    #[test]
+    fn operators_without_weight_are_never_chosen() {
+        let right = |_: &CountingGenetics, _: &mut StdRng, _: u64, right: u64| right;
+
+        // An operator without weight cannot breed a child on its own
+        assert!(matches!(
+            GeneticEngineBuilder::default()
+                .genetics(CountingGenetics::default())
+                .mutation_rate(0)
+                .crossover_rate(0)
+                .operator(Copy, 0)
+                .build(),
+            Err(GeneticError::NoGeneticOperators)
+        ));
+
+        // Operators keep the index they were registered with even when an earlier one is never chosen, and the
+        // weights of many heavy operators add up without overflowing
+        let mut builder = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(CountingGenetics::default())
+            .mutation_rate(0)
+            .crossover_rate(0)
+            .operator(Copy, 0);
+        for _ in 0..4 {
+            builder = builder.operator(right, u8::MAX);
+        }
+        let mut engine = builder.build().unwrap();
+        assert_eq!(4 * u8::MAX as u32, engine.operator_weight());
+        let (left, right) = (engine.rand_individual(), engine.rand_individual());
+
+        for _ in 0..200 {
+            match engine.rand_child_with_origin(left, right).unwrap() {
+                (child, IndividualOrigin::Operator(1..=4)) if child == right => {}
+                unexpected => panic!("{:?} was not bred by a weighted operator", unexpected),
+            }
+        }
+    }
+}
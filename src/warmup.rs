@@ -0,0 +1,148 @@
+use crate::SelectionCurve;
+
+/// Overrides parts of the world configuration for the first `generations` generations of a run. Any override left as
+/// None uses the regular configuration. When the warmup ends, the rates of the genetic engine are restored to the
+/// values they had when the world was built.
+///
+/// Hypermutation bursts do not start during the warmup.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warmup {
+    /// The number of generations the warmup lasts
+    pub generations: usize,
+
+    /// Replaces `WorldBuilder::select_as_parent` during the warmup
+    pub select_as_parent: Option<SelectionCurve>,
+
+    /// Replaces `WorldBuilder::select_as_elite` during the warmup
+    pub select_as_elite: Option<SelectionCurve>,
+
+    /// Replaces `WorldBuilder::select_for_migration` during the warmup
+    pub select_for_migration: Option<SelectionCurve>,
+
+    /// Replaces the mutation rate of the genetic engine during the warmup
+    pub mutation_rate: Option<u8>,
+
+    /// Replaces the crossover rate of the genetic engine during the warmup
+    pub crossover_rate: Option<u8>,
+}
+
+impl Default for Warmup {
+    /// Ten generations of fair parent selection with mostly mutation
+    fn default() -> Self {
+        Warmup {
+            generations: 10,
+            select_as_parent: Some(SelectionCurve::Fair),
+            select_as_elite: None,
+            select_for_migration: None,
+            mutation_rate: Some(9),
+            crossover_rate: Some(1),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn warmup_overrides_rates_for_the_first_generations() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(10)
+            .with_warmup(Warmup {
+                generations: 2,
+                ..Warmup::default()
+            })
+            .build()
+            .unwrap();
+
+        let mut rates = vec![];
+        for _ in 0..4 {
+            world.fill_all_islands().unwrap();
+            let engine = world.genetic_engine();
+            rates.push((
+                world.is_warming_up(),
+                engine.mutation_rate(),
+                engine.crossover_rate(),
+            ));
+            world.run_one_generation().unwrap();
+        }
+
+        assert_eq!(
+            vec![(true, 9, 1), (true, 9, 1), (false, 1, 9), (false, 1, 9)],
+            rates
+        );
+    }
+
+    #[test]
+    fn empty_and_partial_warmups() {
+        let warmed_up = |warmup: Warmup| {
+            let mut world = world_builder(&["a"])
+                .with_individuals_per_island(10)
+                .with_warmup(warmup)
+                .build()
+                .unwrap();
+            world.fill_all_islands().unwrap();
+            let engine = world.genetic_engine();
+            (
+                world.is_warming_up(),
+                engine.mutation_rate(),
+                engine.crossover_rate(),
+            )
+        };
+
+        // A warmup of no generations never starts
+        assert_eq!(
+            (false, 1, 9),
+            warmed_up(Warmup {
+                generations: 0,
+                ..Warmup::default()
+            })
+        );
+
+        // Rates that are not overridden keep their regular values
+        assert_eq!(
+            (true, 1, 9),
+            warmed_up(Warmup {
+                generations: 1,
+                mutation_rate: None,
+                crossover_rate: None,
+                ..Warmup::default()
+            })
+        );
+        assert_eq!(
+            (true, 4, 9),
+            warmed_up(Warmup {
+                generations: 1,
+                mutation_rate: Some(4),
+                crossover_rate: None,
+                ..Warmup::default()
+            })
+        );
+    }
+
+    #[test]
+    fn warmup_rates_must_leave_an_operator() {
+        assert!(matches!(
+            world_builder(&["a"])
+                .with_warmup(Warmup {
+                    mutation_rate: Some(0),
+                    crossover_rate: Some(0),
+                    ..Warmup::default()
+                })
+                .build(),
+            Err(GeneticError::NoGeneticOperators)
+        ));
+
+        // A warmup that turns off only one operator is fine
+        assert!(world_builder(&["a"])
+            .with_warmup(Warmup {
+                mutation_rate: Some(0),
+                crossover_rate: None,
+                ..Warmup::default()
+            })
+            .build()
+            .is_ok());
+    }
+}
@@ -1,7 +1,19 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use rand::seq::SliceRandom;
 use rand::Rng;
 
-#[cfg(any(feature = "multi-threaded", feature = "async"))]
+use crate::event_log::EventRecorder;
+use crate::experiment_dir::{write_atomically, write_rotated};
+use crate::initialization::{latin_hypercube, ramped_points};
+use crate::island_role::EXPLORER_RANDOM_IMMIGRANT_RATE;
+use crate::rate_limit::TokenBucket;
+use crate::world_snapshot::WorldRuntime;
+#[cfg(feature = "multi-threaded")]
 use crate::ThreadingModel;
 use crate::*;
 
@@ -13,44 +25,478 @@ where
     individuals_per_island: usize,
     elite_individuals_per_generation: usize,
     generations_between_migrations: usize,
-    number_of_individuals_migrating: usize,
+    migrant_count: MigrantCount,
+    adaptive_migration: Option<AdaptiveMigration>,
     migration_algorithm: MigrationAlgorithm,
+    // The algorithm the world was built with, which a restart returns to before the migration schedule starts over
+    initial_migration_algorithm: MigrationAlgorithm,
+    migration_schedule: Vec<(usize, MigrationAlgorithm)>,
+    migration_shortfall: MigrationShortfall,
     clone_migrated_individuals: bool,
+    protected_from_emigration: usize,
+    emigration_threshold: Option<EmigrationThreshold>,
     select_for_migration: SelectionCurve,
+    migrate_by_crowding_distance: bool,
+    migrant_novelty_threshold: Option<f64>,
     select_as_parent: SelectionCurve,
+    parent_curve_blend: Vec<(SelectionCurve, f64)>,
     select_as_elite: SelectionCurve,
-    #[cfg(any(feature = "multi-threaded", feature = "async"))]
+    elite_handling: EliteHandling,
+    selectors: Vec<Box<dyn Selector>>,
+    brood_size: usize,
+    brood_selection: bool,
+    unique_genomes: bool,
+    mating_restriction: Option<MatingRestriction>,
+    island_synchronization: IslandSynchronization,
+    score_normalization: ScoreNormalization,
+    island_weights: Vec<f64>,
+    hypermutation: Option<Hypermutation>,
+    warmup: Option<Warmup>,
+    curriculum: Vec<CurriculumStage>,
+    reference_individuals: Vec<(String, u64)>,
+    reference_evaluation_interval: usize,
+    island_distance_interval: usize,
+    archive: Option<Archive>,
+    island_quarantine: Option<IslandQuarantine>,
+    population_viability: Option<PopulationViability>,
+    generation_retry: Option<GenerationRetry>,
+    stochastic_ranking: Option<StochasticRanking>,
+    rate_limit: Option<RateLimit>,
+    hall_of_fame: Option<HallOfFame>,
+    // The ids of the hall of fame members, oldest first
+    hall_of_fame_members: Vec<u64>,
+    checkpoint_every: Option<(usize, PathBuf)>,
+    checkpoints_to_keep: usize,
+    garbage_collection: bool,
+    champion_sink: Option<ChampionSink>,
+    event_recorder: Option<EventRecorder>,
+    observers: Vec<Box<dyn WorldObserver>>,
+    phase: WorldPhase,
+    #[cfg(feature = "multi-threaded")]
     threading_model: ThreadingModel,
     genetic_engine: GeneticEngine<G>,
+    // The operator settings the genetic engine was built with, before any curriculum stage or warmup changed them
+    initial_engine_settings: GeneticEngineSettings,
 
     // Runtime state
     islands: Vec<Island>,
     generation_count: usize,
     generations_remaining_before_migration: usize,
+    migration_log: Vec<MigrationRecord>,
+    migration_count: usize,
+    // The number of individuals each island sends in the current migration, when migration is adaptive
+    adaptive_migrant_counts: Vec<usize>,
+    elites: Vec<Vec<u64>>,
+    parents: Vec<HashSet<u64>>,
+    broods: Vec<Vec<Vec<u64>>>,
+    // How each individual of the generation being filled on each island came to be there
+    origins: Vec<HashMap<u64, IndividualOrigin>>,
+    // How each individual still referenced by the world was created
+    metadata: HashMap<u64, IndividualMetadata>,
+    best_validated: Option<Champion>,
+    // The seed individuals each island has not used yet, in reverse order so that the next one can be popped
+    seed_individuals: Vec<Vec<u64>>,
+    initialization: Initialization,
+    island_initializers: Vec<Option<IslandInitializer>>,
+    island_generations: Vec<usize>,
+    // The next island `poll_generation` will evaluate, or None if it has not filled the islands for a generation yet
+    next_polled_island: Option<usize>,
+    blackboard: Blackboard,
+    curriculum_stage: usize,
+    // The generation count when the active curriculum stage started
+    curriculum_stage_start: usize,
+    generation_seed: Option<u64>,
+    next_generation_seed: Option<u64>,
+    next_scheduled_migration_change: usize,
+    stats_history: Vec<GenerationStats>,
+    best_score: Option<u64>,
+    generations_since_improvement: usize,
+    // Generations elapsed in the active burst, plus the mutation rate and points to restore when it ends
+    hypermutation_burst: Option<(usize, u8, u8)>,
+    // The mutation and crossover rates to restore when the warmup ends. Only set while warming up.
+    warmup_restore_rates: Option<(u8, u8)>,
+    // True once a generation has finished since the last shutdown, so that dropping the world flushes its artifacts
+    needs_shutdown: bool,
 }
 
 impl<G> World<G>
 where
     G: Genetics,
 {
-    pub(crate) fn new(builder: WorldBuilder<G>) -> Self {
-        World {
+    pub(crate) fn new(mut builder: WorldBuilder<G>) -> Self {
+        let island_count = builder.islands.len();
+        let rate_limiter = builder
+            .rate_limit
+            .clone()
+            .map(|rate_limit| Arc::new(Mutex::new(TokenBucket::new(rate_limit))));
+        for island in builder.islands.iter_mut() {
+            island.set_rate_limiter(rate_limiter.clone());
+            island.set_noisy_evaluation(builder.noisy_evaluation.clone());
+            island.set_elite_handling(builder.elite_handling);
+            island.set_stochastic_ranking(builder.stochastic_ranking.clone());
+        }
+        let mut island_initializers: Vec<Option<IslandInitializer>> =
+            (0..island_count).map(|_| None).collect();
+        for (name, initializer) in builder.island_initializers.drain(..) {
+            if let Some(index) = builder
+                .islands
+                .iter()
+                .position(|island| island.name() == name)
+            {
+                island_initializers[index] = Some(initializer);
+            }
+        }
+        let mut world = World {
             individuals_per_island: builder.individuals_per_island,
             elite_individuals_per_generation: builder.elite_individuals_per_generation,
             generations_between_migrations: builder.generations_between_migrations,
-            number_of_individuals_migrating: builder.number_of_individuals_migrating,
-            migration_algorithm: builder.migration_algorithm,
+            migrant_count: builder
+                .migrant_count
+                .unwrap_or(MigrantCount::Fixed(builder.number_of_individuals_migrating)),
+            adaptive_migration: builder.adaptive_migration,
+            migration_algorithm: builder.migration_algorithm.clone(),
+            initial_migration_algorithm: builder.migration_algorithm,
+            migration_schedule: {
+                let mut schedule = builder.migration_schedule;
+                schedule.sort_by_key(|(generation, _)| *generation);
+                schedule
+            },
+            migration_shortfall: builder.migration_shortfall,
             clone_migrated_individuals: builder.clone_migrated_individuals,
+            protected_from_emigration: builder.protected_from_emigration,
+            emigration_threshold: builder.emigration_threshold,
             select_for_migration: builder.select_for_migration,
+            migrate_by_crowding_distance: builder.migrate_by_crowding_distance,
+            migrant_novelty_threshold: builder.migrant_novelty_threshold,
             select_as_parent: builder.select_as_parent,
+            parent_curve_blend: builder.parent_curve_blend,
             select_as_elite: builder.select_as_elite,
-            #[cfg(any(feature = "multi-threaded", feature = "async"))]
+            elite_handling: builder.elite_handling,
+            selectors: builder.selectors,
+            brood_size: builder.brood_size,
+            brood_selection: builder.brood_selection,
+            unique_genomes: builder.unique_genomes,
+            mating_restriction: builder.mating_restriction,
+            island_synchronization: builder.island_synchronization,
+            score_normalization: builder.score_normalization,
+            seed_individuals: builder
+                .islands
+                .iter()
+                .map(|island| {
+                    builder
+                        .seed_individuals
+                        .iter()
+                        .filter(|(name, _)| name == island.name())
+                        .flat_map(|(_, individuals)| individuals.iter().copied())
+                        .rev()
+                        .collect()
+                })
+                .collect(),
+            initialization: builder.initialization,
+            island_initializers,
+            island_weights: builder
+                .islands
+                .iter()
+                .map(|island| {
+                    builder
+                        .island_weights
+                        .iter()
+                        .rev()
+                        .find(|(name, _)| name == island.name())
+                        .map_or(1.0, |(_, weight)| *weight)
+                })
+                .collect(),
+            hypermutation: builder.hypermutation,
+            warmup: builder.warmup,
+            curriculum: builder.curriculum,
+            reference_individuals: builder.reference_individuals,
+            reference_evaluation_interval: builder.reference_evaluation_interval,
+            island_distance_interval: builder.island_distance_interval,
+            archive: if builder.archive_capacity > 0 {
+                Some(Archive::new(
+                    builder.archive_capacity,
+                    builder.archive_min_distance,
+                ))
+            } else {
+                None
+            },
+            island_quarantine: builder.island_quarantine,
+            population_viability: builder.population_viability,
+            generation_retry: builder.generation_retry,
+            stochastic_ranking: builder.stochastic_ranking,
+            rate_limit: builder.rate_limit,
+            hall_of_fame: builder.hall_of_fame,
+            hall_of_fame_members: vec![],
+            checkpoint_every: builder.checkpoint_every,
+            checkpoints_to_keep: builder.checkpoints_to_keep,
+            garbage_collection: builder.garbage_collection,
+            champion_sink: builder.champion_sink,
+            event_recorder: builder.event_log.then(EventRecorder::default),
+            observers: builder.observers,
+            phase: WorldPhase::Idle,
+            #[cfg(feature = "multi-threaded")]
             threading_model: builder.threading_model,
+            initial_engine_settings: builder.genetic_engine.as_ref().unwrap().settings(),
             genetic_engine: builder.genetic_engine.unwrap(),
             islands: builder.islands,
             generation_count: 0,
             generations_remaining_before_migration: builder.generations_between_migrations,
+            migration_log: vec![],
+            migration_count: 0,
+            adaptive_migrant_counts: vec![],
+            elites: vec![],
+            parents: vec![],
+            broods: vec![],
+            origins: vec![],
+            metadata: HashMap::new(),
+            best_validated: None,
+            island_generations: vec![0; island_count],
+            next_polled_island: None,
+            blackboard: Blackboard::new(),
+            curriculum_stage: 0,
+            curriculum_stage_start: 0,
+            generation_seed: None,
+            next_generation_seed: None,
+            next_scheduled_migration_change: 0,
+            stats_history: vec![],
+            best_score: None,
+            generations_since_improvement: 0,
+            hypermutation_burst: None,
+            warmup_restore_rates: None,
+            needs_shutdown: false,
+        };
+        if !world.curriculum.is_empty() {
+            world
+                .enter_curriculum_stage(0)
+                .expect("curriculum rates are validated by WorldBuilder::build");
+        }
+        world.start_warmup();
+
+        world
+    }
+
+    // Applies the warmup rates to the genetic engine, remembering the rates to restore afterwards
+    fn start_warmup(&mut self) {
+        let Some(warmup) = self.warmup.as_ref().filter(|warmup| warmup.generations > 0) else {
+            return;
+        };
+
+        let engine = &mut self.genetic_engine;
+        let restore = (engine.mutation_rate(), engine.crossover_rate());
+        engine
+            .set_rates(
+                warmup.mutation_rate.unwrap_or(restore.0),
+                warmup.crossover_rate.unwrap_or(restore.1),
+            )
+            .expect("warmup rates are validated by WorldBuilder::build");
+        self.warmup_restore_rates = Some(restore);
+    }
+
+    // Applies the rate overrides of a curriculum stage and tells the island engines and observers that it started
+    fn enter_curriculum_stage(&mut self, stage: usize) -> Result<(), GeneticError> {
+        self.curriculum_stage = stage;
+        self.curriculum_stage_start = self.generation_count;
+        let CurriculumStage {
+            name,
+            mutation_rate,
+            crossover_rate,
+            ..
+        } = &self.curriculum[stage];
+
+        // A warmup or hypermutation burst owns the rates until it ends, and then restores the ones of the stage
+        if let Some(restore) = self.warmup_restore_rates.as_mut() {
+            *restore = (
+                mutation_rate.unwrap_or(restore.0),
+                crossover_rate.unwrap_or(restore.1),
+            );
+        } else {
+            let mut mutation_rate = *mutation_rate;
+            if let (Some(rate), Some(burst)) = (mutation_rate, self.hypermutation_burst.as_mut()) {
+                burst.1 = rate;
+                mutation_rate = None;
+            }
+            let engine = &mut self.genetic_engine;
+            engine.set_rates(
+                mutation_rate.unwrap_or(engine.mutation_rate()),
+                crossover_rate.unwrap_or(engine.crossover_rate()),
+            )?;
+        }
+
+        for island in self.islands.iter_mut() {
+            island.curriculum_stage_started(stage, name);
+        }
+        for observer in self.observers.iter_mut() {
+            observer.curriculum_stage_started(self.generation_count, stage, name);
+        }
+
+        Ok(())
+    }
+
+    // Adds the champion to the hall of fame
+    fn induct_champion(&mut self) {
+        let champion = self
+            .hall_of_fame
+            .as_ref()
+            .and_then(|_| self.most_fit_individual());
+        if let (Some(hall_of_fame), Some(champion)) = (self.hall_of_fame.as_ref(), champion) {
+            hall_of_fame.induct(&mut self.hall_of_fame_members, champion.id);
+        }
+    }
+
+    // Gives an island its opponents from the hall of fame for the coming generation
+    fn sample_opponents(&mut self, index: usize) {
+        if let Some(hall_of_fame) = self.hall_of_fame.as_ref() {
+            let opponents =
+                hall_of_fame.sample(self.genetic_engine.rng(), &self.hall_of_fame_members);
+            self.islands[index].read_opponents(&opponents);
+        }
+    }
+
+    // Offers the champion to the champion sink, before migration moves it
+    fn export_champion(&mut self) {
+        let champion = self
+            .champion_sink
+            .as_ref()
+            .and_then(|_| self.most_fit_individual());
+        if let (Some(sink), Some(champion)) = (self.champion_sink.as_mut(), champion) {
+            sink.offer(self.generation_count, &champion);
+        }
+    }
+
+    // Moves on to the next curriculum stage once the active one is complete
+    fn advance_curriculum(&mut self) -> Result<(), GeneticError> {
+        let next = self.curriculum_stage + 1;
+        if next >= self.curriculum.len() {
+            return Ok(());
+        }
+        let Some(advancement) = self.curriculum[self.curriculum_stage].advance_when else {
+            return Ok(());
+        };
+
+        let best_score = self.stats_history.last().and_then(|stats| stats.best_score);
+        if advancement.is_met(
+            self.generation_count - self.curriculum_stage_start,
+            best_score,
+        ) {
+            self.enter_curriculum_stage(next)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns what the world is doing. This is `WorldPhase::Idle` whenever the world is not inside one of its own
+    /// methods, so it is mostly useful from observers, engines and callbacks that run while the world works.
+    pub fn phase(&self) -> WorldPhase {
+        self.phase
+    }
+
+    /// Returns how the world spreads the work of its islands across threads
+    #[cfg(feature = "multi-threaded")]
+    pub fn threading_model(&self) -> ThreadingModel {
+        self.threading_model
+    }
+
+    // Records what the world is doing and tells the observers when it changes
+    fn set_phase(&mut self, phase: WorldPhase) {
+        if self.phase != phase {
+            self.phase = phase;
+            for observer in self.observers.iter_mut() {
+                observer.phase_changed(self.generation_count, phase);
+            }
+        }
+    }
+
+    // Returns to the idle phase if a step failed, since the world then waits to be driven again
+    fn idle_on_error<T>(&mut self, result: Result<T, GeneticError>) -> Result<T, GeneticError> {
+        if result.is_err() {
+            self.set_phase(WorldPhase::Idle);
+        }
+        result
+    }
+
+    /// Returns the index of the active `CurriculumStage`, or None if the world has no curriculum
+    pub fn curriculum_stage(&self) -> Option<usize> {
+        (!self.curriculum.is_empty()).then_some(self.curriculum_stage)
+    }
+
+    // Restores the rates of the genetic engine once the warmup generations have all run
+    fn end_warmup_if_complete(&mut self) -> Result<(), GeneticError> {
+        if let (Some(warmup), Some((mutation_rate, crossover_rate))) =
+            (&self.warmup, self.warmup_restore_rates)
+        {
+            if self.generation_count >= warmup.generations {
+                self.genetic_engine
+                    .set_rates(mutation_rate, crossover_rate)?;
+                self.warmup_restore_rates = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true while the world is running the generations of its warmup
+    pub fn is_warming_up(&self) -> bool {
+        self.warmup_restore_rates.is_some()
+    }
+
+    // Returns the warmup configuration while it is in effect
+    fn active_warmup(&self) -> Option<&Warmup> {
+        self.warmup_restore_rates.and(self.warmup.as_ref())
+    }
+
+    // The curve to select the parents of the next brood with. With a parent curve blend, a curve is picked at random.
+    fn parent_curve(&mut self) -> SelectionCurve {
+        if let Some(curve) = self
+            .active_warmup()
+            .and_then(|warmup| warmup.select_as_parent)
+        {
+            return curve;
+        }
+        if self.parent_curve_blend.is_empty() {
+            return self.select_as_parent;
+        }
+
+        let total: f64 = self
+            .parent_curve_blend
+            .iter()
+            .map(|(_, weight)| weight)
+            .sum();
+        let mut pick = self.genetic_engine.rng().random::<f64>() * total;
+        for &(curve, weight) in self.parent_curve_blend.iter() {
+            if pick < weight {
+                return curve;
+            }
+            pick -= weight;
         }
+
+        // Rounding may leave a sliver past the last weight, which belongs to the last curve that can be picked
+        self.parent_curve_blend
+            .iter()
+            .rfind(|(_, weight)| *weight > 0.0)
+            .map_or(self.select_as_parent, |&(curve, _)| curve)
+    }
+
+    fn elite_curve(&self) -> SelectionCurve {
+        self.active_warmup()
+            .and_then(|warmup| warmup.select_as_elite)
+            .unwrap_or(self.select_as_elite)
+    }
+
+    fn migration_curve(&self) -> SelectionCurve {
+        self.active_warmup()
+            .and_then(|warmup| warmup.select_for_migration)
+            .unwrap_or(self.select_for_migration)
+    }
+
+    /// Borrows the genetic engine
+    pub fn genetic_engine(&self) -> &GeneticEngine<G> {
+        &self.genetic_engine
+    }
+
+    /// Mutably borrows the genetic engine, so that its parameters can be adapted between generations. For example,
+    /// the callback of `run_generations_while` can lower the mutation rate as the run converges.
+    pub fn genetic_engine_mut(&mut self) -> &mut GeneticEngine<G> {
+        &mut self.genetic_engine
     }
 
     /// Returns the total number of islands
@@ -73,146 +519,1996 @@ where
         self.islands.iter().find(|island| island.name() == name)
     }
 
-    /// Removes all individuals from all islands
+    /// Iterates over the islands in index order
+    pub fn islands(&self) -> std::slice::Iter<'_, Island> {
+        self.islands.iter()
+    }
+
+    /// Mutably iterates over the islands in index order
+    pub fn islands_mut(&mut self) -> std::slice::IterMut<'_, Island> {
+        self.islands.iter_mut()
+    }
+
+    /// Mutably iterates over the islands on the threads of the rayon pool
+    #[cfg(feature = "multi-threaded")]
+    pub fn par_islands_mut(&mut self) -> rayon::slice::IterMut<'_, Island> {
+        use rayon::prelude::*;
+        self.islands.par_iter_mut()
+    }
+
+    /// Returns the highest scoring of the most fit individuals of each island, or None if no island has been sorted.
+    /// Ties are won by the island with the lowest index.
+    pub fn most_fit_individual(&self) -> Option<Champion> {
+        let mut champion: Option<Champion> = None;
+        for (index, island) in self.islands.iter().enumerate() {
+            if let Some(id) = island.most_fit_individual() {
+                let normalized_score = island.normalized_score(id, self.score_normalization);
+                let weighted_score = normalized_score * self.island_weight(index);
+                if champion.is_none_or(|best| weighted_score > best.weighted_score) {
+                    champion = Some(Champion {
+                        island: index,
+                        id,
+                        score: island.score(id),
+                        validation_score: island.validation_score(id),
+                        normalized_score,
+                        weighted_score,
+                    });
+                }
+            }
+        }
+
+        champion
+    }
+
+    /// Replaces the engine of the named island between generations, keeping its population, and returns the old engine.
+    /// This allows the evaluation environment to change during a run, for example to move on to harder tasks. The
+    /// current generation keeps the ranking given by the old engine, so the next generation is bred from it; the new
+    /// engine first evaluates the generation after that.
+    pub fn replace_island_engine(
+        &mut self,
+        name: &str,
+        engine: Box<dyn IslandEngine>,
+    ) -> Result<Box<dyn IslandEngine>, GeneticError> {
+        let island = self
+            .islands
+            .iter_mut()
+            .find(|island| island.name() == name)
+            .ok_or_else(|| GeneticError::UnknownIsland(name.to_string()))?;
+
+        Ok(island.replace_engine(engine))
+    }
+
+    /// Adds a branch of the source island under a new name, so that an experiment can continue down two paths from
+    /// the same population, and returns the index of the new island. The branch starts with a copy of the source's
+    /// current and future generations, role, weight and unused seed individuals. Island engines cannot be copied, so
+    /// the branch is evaluated by the given engine, typically created by the same code as the source's; it is told the
+    /// current curriculum stage. As with `replace_island_engine`, the copied generation keeps the ranking given by the
+    /// source's engine.
+    pub fn clone_island<S: Into<String>>(
+        &mut self,
+        source: &str,
+        new_name: S,
+        engine: Box<dyn IslandEngine>,
+    ) -> Result<usize, GeneticError> {
+        let new_name = new_name.into();
+        let index = self
+            .islands
+            .iter()
+            .position(|island| island.name() == source)
+            .ok_or_else(|| GeneticError::UnknownIsland(source.to_string()))?;
+        if self.islands.iter().any(|island| island.name() == new_name) {
+            return Err(GeneticError::DuplicateIslandName(new_name));
+        }
+
+        let mut branch = self.islands[index].branch(new_name, engine);
+        branch.set_stochastic_ranking(self.stochastic_ranking.clone());
+        if let Some(stage) = self.curriculum.get(self.curriculum_stage) {
+            branch.curriculum_stage_started(self.curriculum_stage, &stage.name);
+        }
+        self.islands.push(branch);
+        self.island_weights.push(self.island_weights[index]);
+        self.seed_individuals
+            .push(self.seed_individuals[index].clone());
+        self.island_initializers.push(None);
+        self.island_generations.push(self.island_generations[index]);
+
+        Ok(self.islands.len() - 1)
+    }
+
+    /// Returns the importance of the island when choosing the world's most fit individual
+    pub fn island_weight(&self, index: usize) -> f64 {
+        self.island_weights.get(index).copied().unwrap_or(1.0)
+    }
+
+    /// Borrows the archive of the best migrants, or None if the world was built without one
+    pub fn archive(&self) -> Option<&Archive> {
+        self.archive.as_ref()
+    }
+
+    /// Returns the members of the hall of fame, oldest first. Empty if the world was built without one.
+    pub fn hall_of_fame(&self) -> &[u64] {
+        &self.hall_of_fame_members
+    }
+
+    /// Returns how the individual was created, or None if it was not created by this world or is no longer referenced
+    /// by it. Metadata is kept for as long as the world references the individual, such as on an island or in the
+    /// archive, so the parents of an individual may have no metadata of their own any more.
+    pub fn individual_metadata(&self, id: u64) -> Option<&IndividualMetadata> {
+        self.metadata.get(&id)
+    }
+
+    /// Returns a copy of the runtime state of the world and all of its islands, which `restore` can roll back to
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            generation_count: self.generation_count,
+            generations_remaining_before_migration: self.generations_remaining_before_migration,
+            genetic_engine: self.genetic_engine.settings(),
+            islands: self
+                .islands
+                .iter()
+                .map(|island| island.snapshot())
+                .collect(),
+            runtime: WorldRuntime {
+                best_score: self.best_score,
+                generations_since_improvement: self.generations_since_improvement,
+                hypermutation_burst: self.hypermutation_burst,
+                warmup_restore_rates: self.warmup_restore_rates,
+                curriculum_stage: self.curriculum_stage,
+                curriculum_stage_start: self.curriculum_stage_start,
+                migration_algorithm: self.migration_algorithm.clone(),
+                next_scheduled_migration_change: self.next_scheduled_migration_change,
+                migration_count: self.migration_count,
+                stats_history_len: self.stats_history.len(),
+                migration_log_len: self.migration_log.len(),
+            },
+        }
+    }
+
+    /// Rolls the world back to the state in the snapshot, which may have been taken from this world or, for example
+    /// through a checkpoint, from another world built with islands of the same names.
+    ///
+    /// The statistics history and migration log are not saved in a snapshot, only their lengths: they are truncated to
+    /// their length when the snapshot was taken, so a world that never got that far, such as a new world resuming a
+    /// checkpoint, keeps the history it has. The archive, the blackboard and the random number generator are not saved
+    /// either and are left as they are, so the archive keeps the migrants it collected after the snapshot was taken.
+    ///
+    /// Snapshots hold individuals by id only. If `Genetics::contains` reports that any of them is no longer held, for
+    /// example because garbage collection released it after the snapshot was taken, this returns
+    /// `GeneticError::MissingIndividual` and leaves the world unchanged.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) -> Result<(), GeneticError> {
+        if snapshot.islands.len() != self.islands.len() {
+            return Err(GeneticError::InvalidCheckpoint(format!(
+                "expected {} islands, found {}",
+                self.islands.len(),
+                snapshot.islands.len()
+            )));
+        }
+        let indexes = snapshot
+            .islands
+            .iter()
+            .map(|saved| {
+                self.islands
+                    .iter()
+                    .position(|island| island.name() == saved.name)
+                    .ok_or_else(|| GeneticError::UnknownIsland(saved.name.clone()))
+            })
+            .collect::<Result<Vec<usize>, GeneticError>>()?;
+        let genetics = self.genetic_engine.genetics();
+        if let Some(&missing) = snapshot
+            .islands
+            .iter()
+            .flat_map(|saved| saved.individuals.iter().chain(saved.future.iter()))
+            .find(|&&id| !genetics.contains(id))
+        {
+            return Err(GeneticError::MissingIndividual(missing));
+        }
+
+        self.genetic_engine
+            .apply_settings(snapshot.genetic_engine)?;
+        for (saved, index) in snapshot.islands.iter().zip(indexes) {
+            self.islands[index].restore(saved);
+        }
+        self.generation_count = snapshot.generation_count;
+        self.generations_remaining_before_migration =
+            snapshot.generations_remaining_before_migration;
+        self.island_generations = vec![snapshot.generation_count; self.islands.len()];
+        self.next_polled_island = None;
+
+        let runtime = snapshot.runtime.clone();
+        self.best_score = runtime.best_score;
+        self.generations_since_improvement = runtime.generations_since_improvement;
+        self.hypermutation_burst = runtime.hypermutation_burst;
+        self.warmup_restore_rates = runtime.warmup_restore_rates;
+        self.curriculum_stage = runtime.curriculum_stage;
+        self.curriculum_stage_start = runtime.curriculum_stage_start;
+        self.migration_algorithm = runtime.migration_algorithm;
+        self.next_scheduled_migration_change = runtime.next_scheduled_migration_change;
+        self.migration_count = runtime.migration_count;
+        self.stats_history.truncate(runtime.stats_history_len);
+        self.migration_log.truncate(runtime.migration_log_len);
+
+        Ok(())
+    }
+
+    /// Replaces the current generation of the named island with a population written by `Island::export_population`,
+    /// possibly on another machine. Every genome is recreated with `Genetics::decode`, so the exporting genetics must
+    /// have encoded them. The island's future generation is discarded, and the imported individuals are ranked again
+    /// when the island next runs.
+    #[cfg(feature = "serde")]
+    pub fn import_population(&mut self, island: &str, data: &str) -> Result<(), GeneticError> {
+        let index = self
+            .islands
+            .iter()
+            .position(|candidate| candidate.name() == island)
+            .ok_or_else(|| GeneticError::UnknownIsland(island.to_string()))?;
+        let export: PopulationExport = serde_json::from_str(data)
+            .map_err(|error| GeneticError::InvalidPopulation(error.to_string()))?;
+        let individuals = export
+            .individuals
+            .iter()
+            .map(|individual| {
+                individual
+                    .genome
+                    .as_deref()
+                    .and_then(|genome| self.genetic_engine.genetics().decode(genome))
+                    .ok_or_else(|| {
+                        GeneticError::InvalidPopulation(format!(
+                            "individual {} could not be decoded",
+                            individual.id
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<u64>, GeneticError>>()?;
+
+        self.islands[index].restore(&IslandSnapshot {
+            name: island.to_string(),
+            individuals,
+            sorted: false,
+            future: vec![],
+        });
+        Ok(())
+    }
+
+    /// Writes the champions of the world to `writer` as a JSON `ChampionExport`, and returns it. The most fit
+    /// individual of every sorted island is exported in island order, followed by the members of the archive, most fit
+    /// first, so the same world always produces the same artifact. Each genome is written by `codec`, which is given
+    /// the id of the individual and typically calls `Genetics::encode` or the user's own serializer; it returns None
+    /// for individuals it cannot encode.
+    #[cfg(feature = "serde")]
+    pub fn export_champions<W, C>(
+        &self,
+        writer: W,
+        mut codec: C,
+    ) -> Result<ChampionExport, GeneticError>
+    where
+        W: std::io::Write,
+        C: FnMut(u64) -> Option<String>,
+    {
+        let mut champions = vec![];
+        let mut best = None;
+        let most_fit = self.most_fit_individual();
+        for (index, island) in self.islands.iter().enumerate() {
+            if let Some(id) = island.most_fit_individual() {
+                if most_fit.is_some_and(|champion| champion.island == index) {
+                    best = Some(champions.len());
+                }
+                champions.push(ExportedChampion {
+                    source: ChampionSource::Island,
+                    island: island.name().to_string(),
+                    id,
+                    score: island.score(id),
+                    validation_score: island.validation_score(id),
+                    normalized_score: island.normalized_score(id, self.score_normalization),
+                    genome: codec(id),
+                });
+            }
+        }
+        for member in self.archive.iter().flat_map(|archive| archive.members()) {
+            champions.push(ExportedChampion {
+                source: ChampionSource::Archive,
+                island: self.islands[member.island].name().to_string(),
+                id: member.id,
+                score: member.score,
+                validation_score: None,
+                normalized_score: member.normalized_score,
+                genome: codec(member.id),
+            });
+        }
+
+        let export = ChampionExport {
+            generation: self.generation_count,
+            best,
+            champions,
+        };
+        serde_json::to_writer_pretty(writer, &export).map_err(std::io::Error::from)?;
+        Ok(export)
+    }
+
+    /// Writes a checkpoint of the world to the path. The file is written and synced under a temporary name, then
+    /// renamed into place, so an interrupted write never replaces a good checkpoint. Individuals are saved by id, so
+    /// the caller must save whatever the ids refer to as well.
+    pub fn checkpoint_to<P: AsRef<Path>>(&self, path: P) -> Result<(), GeneticError> {
+        write_atomically(path.as_ref(), self.snapshot().to_checkpoint().as_bytes())
+    }
+
+    /// Restores the world from a checkpoint written by `checkpoint_to`, exactly as `restore` does, including its
+    /// refusal of individuals the genetics no longer holds. The world must have been built with islands of the same
+    /// names. Like a snapshot, a checkpoint does not hold the statistics history, migration log or archive, so a new
+    /// world resuming a checkpoint starts them over from the resumed generation, while a world resuming its own earlier
+    /// checkpoint keeps its archive and has its history truncated.
+    pub fn resume_from<P: AsRef<Path>>(&mut self, path: P) -> Result<(), GeneticError> {
+        let text = std::fs::read_to_string(path)?;
+        self.restore(&WorldSnapshot::from_checkpoint(&text)?)
+    }
+
+    /// Brings the artifacts of the run up to date, so that a run stopped at this point still leaves usable results: the
+    /// champion sink is given the current champion if it beats the last export, regardless of its throttle, a final
+    /// automatic checkpoint is written unless the last generation already has one, and observers are told with
+    /// `WorldObserver::world_shut_down`. The world can still be run afterwards.
+    ///
+    /// Dropping a world that has finished a generation since it was last shut down does the same on a best-effort
+    /// basis, ignoring errors.
+    pub fn shutdown(&mut self) -> Result<(), GeneticError> {
+        self.needs_shutdown = false;
+        let champion = self
+            .champion_sink
+            .as_ref()
+            .and_then(|_| self.most_fit_individual());
+        if let (Some(sink), Some(champion)) = (self.champion_sink.as_mut(), champion) {
+            sink.flush(self.generation_count, &champion);
+        }
+
+        let result = match &self.checkpoint_every {
+            Some((generations, path)) if !self.generation_count.is_multiple_of(*generations) => {
+                write_rotated(
+                    path,
+                    self.snapshot().to_checkpoint().as_bytes(),
+                    self.checkpoints_to_keep,
+                )
+            }
+            _ => Ok(()),
+        };
+        for observer in self.observers.iter_mut() {
+            observer.world_shut_down(self.generation_count);
+        }
+
+        result
+    }
+
+    // Writes the automatic checkpoint if one is due after the generation that just finished
+    fn checkpoint_if_due(&self) -> Result<(), GeneticError> {
+        match &self.checkpoint_every {
+            Some((generations, path)) if self.generation_count.is_multiple_of(*generations) => {
+                write_rotated(
+                    path,
+                    self.snapshot().to_checkpoint().as_bytes(),
+                    self.checkpoints_to_keep,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes all individuals from all islands. Use `restart` to also start the run over.
     pub fn reset_all_islands(&mut self) {
         for island in self.islands.iter_mut() {
             island.clear();
         }
     }
 
-    /// Runs the next generation across all islands.
-    #[cfg(not(feature = "async"))]
-    pub fn run_one_generation(&mut self) {
+    /// Starts a new run in the same world, so that several independent runs can be made with the same configuration
+    /// and island engines. Every island is cleared and filled again on the next generation, and the generation count,
+    /// best score, stagnation, migration and quarantine counters, individual metadata and best validated individual
+    /// start over. `keep` chooses whether the archive, the statistics history, the position in the schedules and the
+    /// operator settings carry over into the new run. The champion sink exports the first champion of the new run
+    /// whatever its score.
+    pub fn restart(&mut self, keep: RestartKeep) -> Result<(), GeneticError> {
+        // Undo the temporary rates of a hypermutation burst and a warmup, in the reverse of the order they were applied
+        if let Some((_, mutation_rate, mutation_points)) = self.hypermutation_burst.take() {
+            self.genetic_engine.set_mutation_rate(mutation_rate)?;
+            self.genetic_engine
+                .set_max_mutation_points(mutation_points)?;
+        }
+        if let Some((mutation_rate, crossover_rate)) = self.warmup_restore_rates.take() {
+            self.genetic_engine
+                .set_rates(mutation_rate, crossover_rate)?;
+        }
+        if !keep.engine_settings {
+            self.genetic_engine
+                .apply_settings(self.initial_engine_settings)?;
+        }
+
         for island in self.islands.iter_mut() {
-            island.run_one_generation();
+            island.restart();
+        }
+        self.generation_count = 0;
+        self.island_generations = vec![0; self.islands.len()];
+        self.next_polled_island = None;
+        self.generations_remaining_before_migration = self.generations_between_migrations;
+        self.migration_count = 0;
+        self.adaptive_migrant_counts.clear();
+        self.elites.clear();
+        self.parents.clear();
+        self.broods.clear();
+        self.origins.clear();
+        self.best_validated = None;
+        self.best_score = None;
+        self.generations_since_improvement = 0;
+        self.generation_seed = None;
+        self.set_phase(WorldPhase::Idle);
+        if let Some(sink) = self.champion_sink.as_mut() {
+            sink.reset();
         }
 
-        // See if it is time for a migration
-        if self.generations_between_migrations > 0 {
-            self.generations_remaining_before_migration -= 1;
-            if self.generations_remaining_before_migration == 0 {
-                self.migrate_individuals_between_islands();
-                self.generations_remaining_before_migration = self.generations_between_migrations;
+        if !keep.archive {
+            self.hall_of_fame_members.clear();
+        }
+        match self.archive.as_mut() {
+            Some(archive) if keep.archive => {
+                let archived: HashSet<u64> =
+                    archive.members().iter().map(|member| member.id).collect();
+                self.metadata.retain(|id, _| archived.contains(id));
             }
+            Some(archive) => {
+                archive.clear();
+                self.metadata.clear();
+            }
+            None => self.metadata.clear(),
+        }
+        if !keep.stats_history {
+            self.stats_history.clear();
+            self.migration_log.clear();
         }
+
+        if !keep.schedules {
+            self.migration_algorithm = self.initial_migration_algorithm.clone();
+            self.next_scheduled_migration_change = 0;
+            self.curriculum_stage = 0;
+        }
+        if !self.curriculum.is_empty() {
+            self.enter_curriculum_stage(self.curriculum_stage)?;
+        }
+        if !keep.schedules {
+            self.start_warmup();
+        }
+
+        Ok(())
     }
 
     /// Runs the next generation across all islands.
-    #[cfg(feature = "async")]
-    pub async fn run_one_generation(&mut self) {
-        for island in self.islands.iter_mut() {
-            island.run_one_generation().await;
+    ///
+    /// With the `strict-checks` feature enabled, this returns an error if any island is not sorted in ascending score
+    /// order after the run.
+    #[cfg(not(feature = "async"))]
+    pub fn run_one_generation(&mut self) -> Result<(), GeneticError> {
+        self.evaluate_all_islands()?;
+        self.set_phase(WorldPhase::Idle);
+        self.finish_generation()?;
+        self.catch_up_island_generations();
+        Ok(())
+    }
+
+    /// Fills and runs the next generation of a single island, if the `IslandSynchronization` of the world allows it to
+    /// get that far ahead of the slowest island. Returns false, without changing anything, if the island must wait for
+    /// the others. Once every island has completed a generation, the world completes it too: statistics are recorded
+    /// and any migration that is due takes place. This allows a driver to advance islands independently, for example
+    /// when their engines run at very different speeds.
+    #[cfg(not(feature = "async"))]
+    pub fn advance_island(&mut self, index: usize) -> Result<bool, GeneticError> {
+        if !self.island_may_advance(index) {
+            return Ok(false);
         }
 
-        // See if it is time for a migration
-        if self.generations_between_migrations > 0 {
-            self.generations_remaining_before_migration -= 1;
-            if self.generations_remaining_before_migration == 0 {
-                self.migrate_individuals_between_islands();
-                self.generations_remaining_before_migration = self.generations_between_migrations;
+        self.end_warmup_if_complete()?;
+        let elite_curve = self.elite_curve();
+        self.set_phase(WorldPhase::Filling);
+        let filled = self.fill_island(index, elite_curve);
+        self.idle_on_error(filled)?;
+        self.evaluate_island(index)?;
+        self.set_phase(WorldPhase::Idle);
+        self.island_generations[index] += 1;
+        while self.slowest_island_generation() > self.generation_count {
+            self.finish_generation()?;
+        }
+
+        Ok(true)
+    }
+
+    /// Does part of the work of a generation and returns true once the whole generation is complete, so that a game
+    /// loop or GUI event loop can interleave evolution with rendering without threads. Each call takes at least one
+    /// step: filling every island, running a single island, or completing the generation with its statistics and
+    /// migration. Further steps are taken until `budget` has elapsed, but a step is never interrupted, so an island
+    /// that is slow to run can overrun the budget.
+    ///
+    /// A generation that is being polled must not be advanced by any other method until the poll returns true or an
+    /// error. After an error the generation is abandoned and the next call starts a new one, in which an island that
+    /// failed to evaluate starts over from random individuals.
+    #[cfg(not(feature = "async"))]
+    pub fn poll_generation(&mut self, budget: Duration) -> Result<bool, GeneticError> {
+        let start = Instant::now();
+        loop {
+            let step = self.poll_step();
+            if step.is_err() {
+                self.next_polled_island = None;
+                self.set_phase(WorldPhase::Idle);
+            }
+            if step? {
+                return Ok(true);
+            }
+            if start.elapsed() >= budget {
+                return Ok(false);
             }
         }
     }
 
-    /// Fills all islands with the children of the genetic algorithm, or with random individuals if there was no
-    /// previous generation from which to draw upon.
-    pub fn fill_all_islands(&mut self) -> Result<(), GeneticError> {
-        for id in 0..self.islands.len() {
-            let mut elite_remaining = self.elite_individuals_per_generation;
-            while self.len_island_future_generation(id) < self.individuals_per_island {
-                let island = self.islands.get(id).unwrap();
-                let pick_elite = if elite_remaining > 0 {
-                    elite_remaining -= 1;
-                    true
-                } else {
-                    false
-                };
-                let next = if island.len() == 0 {
-                    self.genetic_engine.rand_individual()
-                } else {
-                    if pick_elite {
-                        let elite = island
-                            .select_one_individual(self.select_as_elite, self.genetic_engine.rng())
-                            .unwrap();
-
-                        elite.clone()
-                    } else {
-                        let left = island
-                            .select_one_individual(self.select_as_parent, self.genetic_engine.rng())
-                            .unwrap();
-                        let right = island
-                            .select_one_individual(self.select_as_parent, self.genetic_engine.rng())
-                            .unwrap();
-                        self.genetic_engine.rand_child(left, right)?
-                    }
-                };
-                self.add_individual_to_island_future_generation(id, next);
+    // Takes the next step of the generation being polled. Returns true if the step completed the generation.
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn poll_step(&mut self) -> Result<bool, GeneticError> {
+        match self.next_polled_island {
+            None => {
+                self.fill_all_islands()?;
+                self.next_polled_island = Some(0);
+                Ok(false)
+            }
+            Some(index) if index < self.islands.len() => {
+                self.evaluate_island(index)?;
+                self.set_phase(WorldPhase::Idle);
+                self.next_polled_island = Some(index + 1);
+                Ok(false)
+            }
+            Some(_) => {
+                self.next_polled_island = None;
+                self.finish_generation()?;
+                self.catch_up_island_generations();
+                Ok(true)
+            }
+        }
+    }
+
+    // Describes the step the next call to `poll_step` will take: the phase it starts in, and the island it runs, if
+    // any. The step that completes a generation is described as migrating.
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn next_poll_step(&self) -> (WorldPhase, Option<usize>) {
+        match self.next_polled_island {
+            None => (WorldPhase::Filling, None),
+            Some(index) if index < self.islands.len() => (WorldPhase::Evaluating, Some(index)),
+            Some(_) => (WorldPhase::Migrating, None),
+        }
+    }
+
+    // Runs the current generation of one island, applying brood selection and the quarantine policy
+    #[cfg(not(feature = "async"))]
+    fn evaluate_island(&mut self, index: usize) -> Result<(), GeneticError> {
+        if !self.begin_island_evaluation(index) {
+            return Ok(());
+        }
+        self.set_phase(WorldPhase::Evaluating);
+        let result = self.evaluate_generation_with_retries(index);
+        self.complete_island_evaluation(index, result)
+    }
+
+    // Runs the current generation of every island, on a thread of its own for each island under
+    // `ThreadingModel::ThreadPerIsland`
+    #[cfg(all(feature = "multi-threaded", not(feature = "async")))]
+    fn evaluate_all_islands(&mut self) -> Result<(), GeneticError> {
+        if self.threading_model == ThreadingModel::ThreadPerIsland {
+            return self.evaluate_islands_on_threads();
+        }
+        for index in 0..self.islands.len() {
+            self.evaluate_island(index)?;
+        }
+        Ok(())
+    }
+
+    // Runs the current generation of every island, one after another
+    #[cfg(not(any(feature = "multi-threaded", feature = "async")))]
+    fn evaluate_all_islands(&mut self) -> Result<(), GeneticError> {
+        for index in 0..self.islands.len() {
+            self.evaluate_island(index)?;
+        }
+        Ok(())
+    }
+
+    // Runs the current generation of every island that is not quarantined, each on a thread of its own. The islands
+    // are prepared before any of them runs and completed in island order once all have run, so the outcome does not
+    // depend on which thread finishes first. Every island is completed before the first error is returned.
+    #[cfg(all(feature = "multi-threaded", not(feature = "async")))]
+    fn evaluate_islands_on_threads(&mut self) -> Result<(), GeneticError> {
+        let running: Vec<usize> = (0..self.islands.len())
+            .filter(|&index| self.begin_island_evaluation(index))
+            .collect();
+        self.set_phase(WorldPhase::Evaluating);
+
+        let retry = self.generation_retry.as_ref();
+        let outcomes: Vec<IslandOutcome> = std::thread::scope(|scope| {
+            let threads: Vec<_> = self
+                .islands
+                .iter_mut()
+                .enumerate()
+                .filter(|(index, _)| running.contains(index))
+                .map(|(index, island)| {
+                    scope.spawn(move || {
+                        let mut retries = vec![];
+                        let result = evaluate_with_retries(island, retry, |attempt, error| {
+                            retries.push((attempt, error))
+                        });
+                        (index, result, retries)
+                    })
+                })
+                .collect();
+            threads
+                .into_iter()
+                .map(|thread| {
+                    thread
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        let mut first_error = None;
+        for (index, result, retries) in outcomes {
+            for (attempt, error) in retries {
+                for observer in self.observers.iter_mut() {
+                    observer.evaluation_retried(self.generation_count, index, attempt, &error);
+                }
+            }
+            if let Err(error) = self.complete_island_evaluation(index, result) {
+                first_error.get_or_insert(error);
+            }
+        }
+        let result = first_error.map_or(Ok(()), Err);
+        self.idle_on_error(result)
+    }
+
+    // Evaluates the current generation of one island, starting it over after transient failures as allowed by the
+    // retry policy
+    #[cfg(not(feature = "async"))]
+    fn evaluate_generation_with_retries(&mut self, index: usize) -> Result<(), GeneticError> {
+        let generation = self.generation_count;
+        let observers = &mut self.observers;
+        evaluate_with_retries(
+            &mut self.islands[index],
+            self.generation_retry.as_ref(),
+            |attempt, error| {
+                for observer in observers.iter_mut() {
+                    observer.evaluation_retried(generation, index, attempt, &error);
+                }
+            },
+        )
+    }
+
+    // Prepares one island to run its current generation: reads the blackboard and the opponents it is evaluated
+    // against. Returns false if the island is quarantined and must not be run.
+    fn begin_island_evaluation(&mut self, index: usize) -> bool {
+        if self.serve_quarantine(index) {
+            return false;
+        }
+        self.islands[index].read_blackboard(&self.blackboard);
+        self.sample_opponents(index);
+        true
+    }
+
+    // Sorts an island that was run successfully, writes its blackboard and keeps the best of its broods, then applies
+    // the quarantine policy to the outcome
+    fn complete_island_evaluation(
+        &mut self,
+        index: usize,
+        result: Result<(), GeneticError>,
+    ) -> Result<(), GeneticError> {
+        if result.is_ok() {
+            self.set_phase(WorldPhase::Sorting);
+            self.islands[index].complete_evaluation();
+            self.islands[index].write_blackboard(&mut self.blackboard);
+        }
+        if result.is_ok() && self.brood_selection {
+            if let Some(broods) = self.broods.get(index) {
+                self.islands[index].keep_best_of_broods(broods);
             }
+        }
+        let handled = self.handle_evaluation_result(index, result);
+        self.idle_on_error(handled)?;
 
-            // Now that the future generation is full, make it the current generation
-            self.advance_island_generation(id);
+        #[cfg(feature = "strict-checks")]
+        {
+            let checked = self.islands[index].check_sorted_scores();
+            self.idle_on_error(checked)?;
         }
 
         Ok(())
     }
 
-    fn len_island_future_generation(&self, index: usize) -> usize {
-        self.islands.get(index).unwrap().len_future_generation()
+    /// Runs the next generation across all islands.
+    ///
+    /// With the `strict-checks` feature enabled, this returns an error if any island is not sorted in ascending score
+    /// order after the run.
+    #[cfg(feature = "async")]
+    pub async fn run_one_generation(&mut self) -> Result<(), GeneticError> {
+        for index in 0..self.islands.len() {
+            self.evaluate_island(index).await?;
+        }
+        self.set_phase(WorldPhase::Idle);
+        self.finish_generation().await?;
+        self.catch_up_island_generations();
+        Ok(())
+    }
+
+    /// Fills and runs the next generation of a single island, if the `IslandSynchronization` of the world allows it to
+    /// get that far ahead of the slowest island. Returns false, without changing anything, if the island must wait for
+    /// the others. Once every island has completed a generation, the world completes it too: statistics are recorded
+    /// and any migration that is due takes place. This allows a driver to advance islands independently, for example
+    /// when their engines run at very different speeds.
+    #[cfg(feature = "async")]
+    pub async fn advance_island(&mut self, index: usize) -> Result<bool, GeneticError> {
+        if !self.island_may_advance(index) {
+            return Ok(false);
+        }
+
+        self.end_warmup_if_complete()?;
+        let elite_curve = self.elite_curve();
+        self.set_phase(WorldPhase::Filling);
+        let filled = self.fill_island(index, elite_curve);
+        self.idle_on_error(filled)?;
+        self.evaluate_island(index).await?;
+        self.set_phase(WorldPhase::Idle);
+        self.island_generations[index] += 1;
+        while self.slowest_island_generation() > self.generation_count {
+            self.finish_generation().await?;
+        }
+
+        Ok(true)
+    }
+
+    // Runs the current generation of one island, applying brood selection and the quarantine policy
+    #[cfg(feature = "async")]
+    async fn evaluate_island(&mut self, index: usize) -> Result<(), GeneticError> {
+        if !self.begin_island_evaluation(index) {
+            return Ok(());
+        }
+        self.set_phase(WorldPhase::Evaluating);
+        let result = self.evaluate_generation_with_retries(index).await;
+        self.complete_island_evaluation(index, result)
+    }
+
+    // Evaluates the current generation of one island, starting it over after transient failures as allowed by the
+    // retry policy
+    #[cfg(feature = "async")]
+    async fn evaluate_generation_with_retries(&mut self, index: usize) -> Result<(), GeneticError> {
+        let mut retries = 0;
+        loop {
+            let samples = self
+                .generation_retry
+                .as_ref()
+                .map(|_| self.islands[index].samples());
+            let result = self.islands[index].evaluate_generation().await;
+            self.islands[index].set_evaluation_retries(retries);
+            match result {
+                Err(error)
+                    if may_retry(
+                        &self.islands[index],
+                        self.generation_retry.as_ref(),
+                        retries,
+                        &error,
+                    ) =>
+                {
+                    retries += 1;
+                    self.prepare_evaluation_retry(index, retries, &error, samples);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the store shared by every island in the world
+    pub fn blackboard(&self) -> &Blackboard {
+        &self.blackboard
+    }
+
+    /// Returns the store shared by every island in the world for modification, for example to seed it with data before
+    /// the first generation
+    pub fn blackboard_mut(&mut self) -> &mut Blackboard {
+        &mut self.blackboard
+    }
+
+    /// Returns the number of generations the island has completed. Islands only differ from the world's generation
+    /// count when they are advanced independently with `advance_island`.
+    pub fn island_generation(&self, index: usize) -> Option<usize> {
+        self.island_generations.get(index).copied()
+    }
+
+    fn slowest_island_generation(&self) -> usize {
+        self.island_generations
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or(self.generation_count)
+    }
+
+    fn island_may_advance(&self, index: usize) -> bool {
+        let lead = match self.island_synchronization {
+            IslandSynchronization::Lockstep => 0,
+            IslandSynchronization::Bounded(lead) => lead,
+        };
+        self.island_generations
+            .get(index)
+            .is_some_and(|&generation| generation <= self.slowest_island_generation() + lead)
+    }
+
+    // Brings every island up to the world's generation count after all islands ran together
+    fn catch_up_island_generations(&mut self) {
+        for generation in self.island_generations.iter_mut() {
+            *generation = (*generation).max(self.generation_count);
+        }
+    }
+
+    // Skips the island for one generation if it is quarantined, notifying observers when the quarantine ends. Returns
+    // true if the island must not be run.
+    fn serve_quarantine(&mut self, index: usize) -> bool {
+        if !self.islands[index].is_quarantined() {
+            return false;
+        }
+
+        if self.islands[index].serve_quarantine() {
+            for observer in self.observers.iter_mut() {
+                observer.island_released(self.generation_count, index);
+            }
+        }
+        true
+    }
+
+    // Discards what the failed attempt added to the island's samples and tells observers about the retry
+    #[cfg(feature = "async")]
+    fn prepare_evaluation_retry(
+        &mut self,
+        index: usize,
+        attempt: usize,
+        error: &GeneticError,
+        samples: Option<HashMap<u64, Vec<u64>>>,
+    ) {
+        if let Some(samples) = samples {
+            self.islands[index].restore_samples(samples);
+        }
+        for observer in self.observers.iter_mut() {
+            observer.evaluation_retried(self.generation_count, index, attempt, error);
+        }
+    }
+
+    // Applies the quarantine policy to the outcome of running one island. Without a policy, errors are returned as-is.
+    // Either way, an island that failed is cleared, since its generation was only partly scored and cannot be bred
+    // from: it starts over from new individuals in the next generation.
+    fn handle_evaluation_result(
+        &mut self,
+        index: usize,
+        result: Result<(), GeneticError>,
+    ) -> Result<(), GeneticError> {
+        let Err(error) = result else {
+            return Ok(());
+        };
+        let Some(quarantine) = &self.island_quarantine else {
+            self.islands[index].clear();
+            return Err(error);
+        };
+
+        let quarantined = self.islands[index].record_failure(quarantine);
+        for observer in self.observers.iter_mut() {
+            observer.evaluation_failed(self.generation_count, index, &error);
+            if quarantined {
+                observer.island_quarantined(self.generation_count, index, &error);
+            }
+        }
+        Ok(())
+    }
+
+    // Called once every island has run a generation. Records statistics, adjusts engine parameters and performs any
+    // migration that is due.
+    #[cfg(not(feature = "async"))]
+    fn finish_generation(&mut self) -> Result<(), GeneticError> {
+        self.generation_count += 1;
+        let reference_scores = self.evaluate_reference_individuals()?;
+        self.complete_generation(reference_scores)
+    }
+
+    // Called once every island has run a generation. Records statistics, adjusts engine parameters and performs any
+    // migration that is due.
+    #[cfg(feature = "async")]
+    async fn finish_generation(&mut self) -> Result<(), GeneticError> {
+        self.generation_count += 1;
+        let reference_scores = self.evaluate_reference_individuals().await?;
+        self.complete_generation(reference_scores)
+    }
+
+    // The part of `finish_generation` that follows the evaluation of the reference individuals
+    fn complete_generation(
+        &mut self,
+        reference_scores: Vec<ReferenceScore>,
+    ) -> Result<(), GeneticError> {
+        self.needs_shutdown = true;
+        self.record_generation_stats(reference_scores);
+        if let Some(recorder) = self.event_recorder.as_mut() {
+            recorder.generation_evaluated(self.generation_count, &self.islands, &self.metadata);
+        }
+        self.export_champion();
+        self.induct_champion();
+        self.advance_curriculum()?;
+        self.update_hypermutation()?;
+        self.apply_migration_schedule();
+
+        // See if it is time for a migration
+        if self.generations_between_migrations > 0 {
+            self.generations_remaining_before_migration -= 1;
+            if self.generations_remaining_before_migration == 0 {
+                self.migrate_individuals_between_islands();
+                self.generations_remaining_before_migration = self.generations_between_migrations;
+            }
+        }
+
+        let referenced = self.referenced_individuals();
+        self.metadata.retain(|id, _| referenced.contains(id));
+        if self.garbage_collection {
+            self.genetic_engine.genetics().collect_garbage(&referenced);
+        }
+        self.checkpoint_if_due()
+    }
+
+    // Returns every individual the world may still use: the populations of the islands, the archive, the hall of fame,
+    // the reference individuals, the best validated individual and the seed individuals that have not been used yet
+    pub(crate) fn referenced_individuals(&self) -> HashSet<u64> {
+        let mut referenced: HashSet<u64> = self
+            .islands
+            .iter()
+            .flat_map(|island| {
+                island
+                    .individuals()
+                    .iter()
+                    .chain(island.future_individuals())
+            })
+            .copied()
+            .collect();
+        if let Some(archive) = self.archive.as_ref() {
+            referenced.extend(archive.members().iter().map(|member| member.id));
+        }
+        referenced.extend(self.hall_of_fame_members.iter());
+        referenced.extend(self.reference_individuals.iter().map(|(_, id)| *id));
+        referenced.extend(self.best_validated.map(|champion| champion.id));
+        referenced.extend(self.seed_individuals.iter().flatten());
+        referenced
+    }
+
+    // Switches to any scheduled migration algorithm whose generation has been reached
+    fn apply_migration_schedule(&mut self) {
+        while let Some((generation, algorithm)) = self
+            .migration_schedule
+            .get(self.next_scheduled_migration_change)
+        {
+            if *generation > self.generation_count {
+                break;
+            }
+            self.migration_algorithm = algorithm.clone();
+            self.next_scheduled_migration_change += 1;
+        }
+    }
+
+    /// Returns the seed the current generation was filled from, or None if the islands have never been filled
+    pub fn generation_seed(&self) -> Option<u64> {
+        self.generation_seed
+    }
+
+    /// Makes the next call to `fill_all_islands` use this seed rather than drawing a new one. Together with a saved
+    /// population, this allows a single generation to be re-executed in isolation.
+    pub fn set_next_generation_seed(&mut self, seed: u64) {
+        self.next_generation_seed = Some(seed);
+    }
+
+    /// Returns the migration algorithm currently in use
+    pub fn migration_algorithm(&self) -> &MigrationAlgorithm {
+        &self.migration_algorithm
+    }
+
+    // The number of different genomes among the individuals of an island, as hashed by `Genetics::genome_hash`
+    fn distinct_genomes(&self, island: &Island) -> usize {
+        let genetics = self.genetic_engine.genetics();
+        island
+            .individuals()
+            .iter()
+            .map(|&individual| genetics.genome_hash(individual))
+            .collect::<HashSet<u64>>()
+            .len()
+    }
+
+    fn record_generation_stats(&mut self, reference_scores: Vec<ReferenceScore>) {
+        let mut elites = std::mem::take(&mut self.elites);
+        elites.resize(self.islands.len(), vec![]);
+        let mut parents = std::mem::take(&mut self.parents);
+        parents.resize(self.islands.len(), HashSet::new());
+        let islands: Vec<IslandStats> = self
+            .islands
+            .iter()
+            .zip(elites)
+            .zip(parents)
+            .map(|((island, elites), parents)| IslandStats {
+                elites,
+                distinct_parents: parents.len(),
+                distinct_genomes: self.distinct_genomes(island),
+                ..island.stats()
+            })
+            .collect();
+        let best_score = islands.iter().filter_map(|stats| stats.best_score).max();
+        let best_validation_score = islands
+            .iter()
+            .filter_map(|stats| stats.best_validation_score)
+            .max();
+        self.update_best_validated();
+        let score_attribution = self.attribute_scores();
+        let improved = match (best_score, self.best_score) {
+            (Some(score), Some(previous)) => score > previous,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if improved {
+            self.best_score = best_score;
+            self.generations_since_improvement = 0;
+        } else {
+            self.generations_since_improvement += 1;
+        }
+
+        self.stats_history.push(GenerationStats {
+            generation: self.generation_count,
+            seed: self.generation_seed,
+            islands,
+            best_score,
+            best_validation_score,
+            improved,
+            curriculum_stage: self.curriculum_stage(),
+            island_distances: if self.island_distance_interval > 0
+                && self
+                    .generation_count
+                    .is_multiple_of(self.island_distance_interval)
+            {
+                self.island_distances()
+            } else {
+                vec![]
+            },
+            reference_scores,
+            score_attribution,
+        });
+    }
+
+    // Keeps the most fit individual of any island if it has the highest validation score seen so far
+    fn update_best_validated(&mut self) {
+        for (index, island) in self.islands.iter().enumerate() {
+            let Some(id) = island.most_fit_individual() else {
+                continue;
+            };
+            let Some(validation_score) = island.validation_score(id) else {
+                continue;
+            };
+            if self
+                .best_validated
+                .is_some_and(|best| best.validation_score >= Some(validation_score))
+            {
+                continue;
+            }
+            let normalized_score = island.normalized_score(id, self.score_normalization);
+            self.best_validated = Some(Champion {
+                island: index,
+                id,
+                score: island.score(id),
+                validation_score: Some(validation_score),
+                normalized_score,
+                weighted_score: normalized_score * self.island_weights[index],
+            });
+        }
+    }
+
+    /// Returns the individual with the highest validation score seen in any generation, as it was when it was seen,
+    /// or None if no island engine validates. This is the individual to keep when later generations overfit their
+    /// training data. It is not rolled back by `restore`.
+    pub fn best_validated_individual(&self) -> Option<Champion> {
+        self.best_validated
+    }
+
+    /// Returns the highest validation score of any generation in the statistics history
+    pub fn best_validation_score(&self) -> Option<u64> {
+        self.stats_history
+            .iter()
+            .filter_map(|stats| stats.best_validation_score)
+            .max()
+    }
+
+    /// Returns the number of generations since the one that set the highest validation score, or None if no
+    /// generation has a validation score
+    pub fn generations_since_validation_improvement(&self) -> Option<usize> {
+        let best = self.best_validation_score()?;
+        self.stats_history
+            .iter()
+            .rev()
+            .position(|stats| stats.best_validation_score == Some(best))
+    }
+
+    // Credits the rise of each individual above the best score so far to the way it was produced. The origins are
+    // used up, so a generation that runs without being filled again has no attribution.
+    fn attribute_scores(&mut self) -> ScoreAttribution {
+        let origins = std::mem::take(&mut self.origins);
+        let previous_best = self.best_score.unwrap_or(0);
+        let mut attribution = ScoreAttribution::default();
+        let mut best = None;
+        for (island, origins) in self.islands.iter().zip(origins.iter()) {
+            for &id in island.individuals() {
+                let score = island.score(id);
+                let origin = origins.get(&id).copied();
+                if let Some(origin) = origin {
+                    attribution.record(origin, score, previous_best);
+                }
+                if best.is_none_or(|(best_score, _)| score > best_score) {
+                    best = Some((score, origin));
+                }
+            }
+        }
+        attribution.best_origin = best.and_then(|(_, origin)| origin);
+
+        attribution
+    }
+
+    // Re-evaluates the reference individuals on every island if it is time
+    #[cfg(not(feature = "async"))]
+    fn evaluate_reference_individuals(&mut self) -> Result<Vec<ReferenceScore>, GeneticError> {
+        let ids = self.reference_individuals_due();
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut scores = Vec::with_capacity(self.islands.len());
+        for island in self.islands.iter_mut() {
+            scores.push(island.evaluate_outside_generation(&ids)?);
+        }
+        Ok(self.reference_scores(scores))
+    }
+
+    // Re-evaluates the reference individuals on every island if it is time
+    #[cfg(feature = "async")]
+    async fn evaluate_reference_individuals(
+        &mut self,
+    ) -> Result<Vec<ReferenceScore>, GeneticError> {
+        let ids = self.reference_individuals_due();
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut scores = Vec::with_capacity(self.islands.len());
+        for island in self.islands.iter_mut() {
+            scores.push(island.evaluate_outside_generation(&ids).await?);
+        }
+        Ok(self.reference_scores(scores))
+    }
+
+    // The reference individuals to evaluate in the current generation, which is none unless it is time
+    fn reference_individuals_due(&self) -> Vec<u64> {
+        if self.reference_evaluation_interval == 0
+            || !self
+                .generation_count
+                .is_multiple_of(self.reference_evaluation_interval)
+        {
+            return vec![];
+        }
+
+        self.reference_individuals
+            .iter()
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    // Names the scores of the reference individuals on each island, given in the order of the islands
+    fn reference_scores(&self, scores: Vec<Vec<u64>>) -> Vec<ReferenceScore> {
+        let mut reference_scores =
+            Vec::with_capacity(self.reference_individuals.len() * scores.len());
+        for (index, island_scores) in scores.into_iter().enumerate() {
+            for ((name, _), score) in self.reference_individuals.iter().zip(island_scores) {
+                reference_scores.push(ReferenceScore {
+                    name: name.clone(),
+                    island: index,
+                    score,
+                });
+            }
+        }
+
+        reference_scores
+    }
+
+    /// Returns the mean `Genetics::distance` between the individuals of every pair of islands, indexed by island. The
+    /// diagonal holds the mean distance between different individuals of the same island, and an entry is None when
+    /// there is no pair to measure. Distances between islands that fall to the distances within them show that
+    /// migration has made the islands alike. Every pair of individuals is compared, so this is expensive for large
+    /// islands.
+    pub fn island_distances(&self) -> Vec<Vec<Option<f64>>> {
+        let genetics = self.genetic_engine.genetics();
+        let mean_distance = |a: &[u64], b: &[u64], same_island: bool| {
+            let mut total = 0.0;
+            let mut pairs = 0usize;
+            for (index, &first) in a.iter().enumerate() {
+                let others = if same_island { &b[index + 1..] } else { b };
+                for &second in others {
+                    total += genetics.distance(first, second);
+                    pairs += 1;
+                }
+            }
+            (pairs > 0).then(|| total / pairs as f64)
+        };
+
+        self.islands
+            .iter()
+            .enumerate()
+            .map(|(row, a)| {
+                self.islands
+                    .iter()
+                    .enumerate()
+                    .map(|(column, b)| {
+                        mean_distance(a.individuals(), b.individuals(), row == column)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Registers an individual to be re-evaluated by every island every `reference_evaluation_interval` generations.
+    /// See `WorldBuilder::reference_individuals`.
+    pub fn add_reference_individual<S: Into<String>>(&mut self, name: S, id: u64) {
+        self.reference_individuals.push((name.into(), id));
+    }
+
+    // Starts, advances or ends a hypermutation burst
+    fn update_hypermutation(&mut self) -> Result<(), GeneticError> {
+        let Some(hypermutation) = &self.hypermutation else {
+            return Ok(());
+        };
+        if self.is_warming_up() {
+            return Ok(());
+        }
+
+        let (elapsed, base_rate, base_points) = match self.hypermutation_burst {
+            Some((elapsed, base_rate, base_points)) => (elapsed + 1, base_rate, base_points),
+            None if self.generations_since_improvement >= hypermutation.stagnation_generations => (
+                0,
+                self.genetic_engine.mutation_rate(),
+                self.genetic_engine.max_mutation_points(),
+            ),
+            None => return Ok(()),
+        };
+
+        match hypermutation.intensity(elapsed) {
+            Some(intensity) => {
+                self.genetic_engine.set_mutation_rate(Hypermutation::scale(
+                    base_rate,
+                    hypermutation.mutation_rate_multiplier,
+                    intensity,
+                ))?;
+                self.genetic_engine
+                    .set_max_mutation_points(Hypermutation::scale(
+                        base_points,
+                        hypermutation.mutation_points_multiplier,
+                        intensity,
+                    ))?;
+                self.hypermutation_burst = Some((elapsed, base_rate, base_points));
+            }
+            None => {
+                // The burst is over. Require a full stagnation period before the next one.
+                self.genetic_engine.set_mutation_rate(base_rate)?;
+                self.genetic_engine.set_max_mutation_points(base_points)?;
+                self.hypermutation_burst = None;
+                self.generations_since_improvement = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the statistics of every generation that has run, oldest first
+    pub fn stats_history(&self) -> &[GenerationStats] {
+        &self.stats_history
+    }
+
+    /// Returns the highest score any individual has achieved in any generation
+    pub fn best_score(&self) -> Option<u64> {
+        self.best_score
+    }
+
+    /// Returns the number of generations that have run since the best score last improved
+    pub fn generations_since_improvement(&self) -> usize {
+        self.generations_since_improvement
+    }
+
+    /// Returns true while a hypermutation burst is boosting the mutation parameters of the genetic engine
+    pub fn is_hypermutating(&self) -> bool {
+        self.hypermutation_burst.is_some()
+    }
+
+    /// Fills all islands with the children of the genetic algorithm, or with random individuals if there was no
+    /// previous generation from which to draw upon.
+    ///
+    /// Before filling, the random number generator of the genetic engine is reset from a new seed drawn from it (or
+    /// from the seed given to `set_next_generation_seed`). The seed is reported by `generation_seed` and in the
+    /// `GenerationStats`, and everything random in the generation, including its migration, follows from it.
+    ///
+    /// With the `strict-checks` feature enabled, this returns an error if the genetic engine produces a child that is
+    /// already part of the island's next generation, or if an island does not end up with exactly
+    /// `individuals_per_island` individuals.
+    pub fn fill_all_islands(&mut self) -> Result<(), GeneticError> {
+        // Every generation runs from its own seed so that it can be re-executed in isolation
+        let seed = match self.next_generation_seed.take() {
+            Some(seed) => seed,
+            None => self.genetic_engine.rng().random(),
+        };
+        self.genetic_engine.reseed(seed);
+        self.generation_seed = Some(seed);
+
+        self.end_warmup_if_complete()?;
+        let elite_curve = self.elite_curve();
+
+        self.set_phase(WorldPhase::Filling);
+        for id in 0..self.islands.len() {
+            let filled = self.fill_island(id, elite_curve);
+            self.idle_on_error(filled)?;
+        }
+        self.set_phase(WorldPhase::Idle);
+
+        Ok(())
+    }
+
+    // Warns about or fails an island that would breed its next generation from too few individuals
+    fn check_population_viability(&mut self, id: usize) -> Result<(), GeneticError> {
+        let Some(viability) = &self.population_viability else {
+            return Ok(());
+        };
+        let individuals = self.islands[id].len();
+        if individuals == 0 || individuals >= viability.min_individuals {
+            return Ok(());
+        }
+
+        match viability.action {
+            ViabilityAction::Warn => {
+                for observer in self.observers.iter_mut() {
+                    observer.population_below_minimum(self.generation_count, id, individuals);
+                }
+                Ok(())
+            }
+            ViabilityAction::Error => Err(GeneticError::PopulationNotViable {
+                island: self.islands[id].name().to_string(),
+                individuals,
+                minimum: viability.min_individuals,
+            }),
+        }
+    }
+
+    // Fills the future generation of one island and makes it the current generation
+    fn fill_island(&mut self, id: usize, elite_curve: SelectionCurve) -> Result<(), GeneticError> {
+        self.genetic_engine
+            .set_decision_generation(self.generation_count);
+        if self.stochastic_ranking.is_some() {
+            // The ranking of the generation follows the genetic engine, so a restored run ranks the same way
+            let seed = self.genetic_engine.rng().random();
+            self.islands[id].reseed_stochastic_ranking(seed);
+        }
+        let island_len = self.islands.len();
+        self.elites.resize(island_len, vec![]);
+        self.parents.resize(island_len, HashSet::new());
+        self.broods.resize(island_len, vec![]);
+        self.origins.resize(island_len, HashMap::new());
+        self.elites[id].clear();
+        self.parents[id].clear();
+        self.broods[id].clear();
+        self.origins[id].clear();
+
+        if self.islands[id].is_quarantined() {
+            // Quarantined islands stay empty, dropping any immigrants they were sent
+            self.islands[id].clear();
+            return Ok(());
+        }
+
+        let role = self.islands.get(id).unwrap().role();
+        if role == IslandRole::Archive {
+            // Archives never breed: the residents join any immigrants, dropping the least fit residents when full
+            let island = self.islands.get_mut(id).unwrap();
+            island.carry_forward(self.individuals_per_island);
+            island.advance_generation();
+            return Ok(());
+        }
+
+        self.check_population_viability(id)?;
+
+        // Anything already in the next generation was sent by another island
+        for &migrant in self.islands[id].future_individuals() {
+            self.origins[id].insert(migrant, IndividualOrigin::Migrant);
+        }
+
+        // An island without a population starts from its seeds, then from its initial individuals
+        let mut initial = vec![];
+        if self.islands[id].is_empty() {
+            let needed = self.individuals_per_island - self.len_island_future_generation(id);
+            initial = self
+                .initial_individuals(id, needed.saturating_sub(self.seed_individuals[id].len()));
+            initial.reverse();
+        }
+
+        // With unique genomes, the genomes already in the next generation and the duplicates bred in a row
+        let mut genomes: HashSet<u64> = HashSet::new();
+        if self.unique_genomes {
+            let genetics = self.genetic_engine.genetics();
+            genomes.extend(
+                self.islands[id]
+                    .future_individuals()
+                    .iter()
+                    .map(|&individual| genetics.genome_hash(individual)),
+            );
+        }
+        let mut duplicates_in_a_row = 0;
+
+        // The scores elites were selected with, which the island may keep ranking them by
+        let mut elite_scores = vec![];
+
+        let mut elite_remaining = self.elite_individuals_per_generation;
+        while self.len_island_future_generation(id) < self.individuals_per_island {
+            let island = self.islands.get(id).unwrap();
+            let pick_elite = if elite_remaining > 0 {
+                elite_remaining -= 1;
+                true
+            } else {
+                false
+            };
+            let is_elite = pick_elite && !island.is_empty();
+            let next = if island.is_empty() {
+                let (individual, origin) = match self.seed_individuals[id].pop() {
+                    Some(seed) => (seed, IndividualOrigin::Seed),
+                    None => (
+                        initial
+                            .pop()
+                            .unwrap_or_else(|| self.genetic_engine.rand_individual()),
+                        IndividualOrigin::Random,
+                    ),
+                };
+                self.record_birth(id, individual, origin, &[]);
+                vec![individual]
+            } else if pick_elite {
+                let kind = DecisionKind::Elite { island: id };
+                vec![self.select_to_breed(id, elite_curve, kind)?]
+            } else {
+                let room = self.individuals_per_island - self.len_island_future_generation(id);
+                if self.brood_selection && self.brood_size > 1 {
+                    // A pre-screened brood only takes one place, so it is always bred in full
+                    let mut brood = self.breed_brood(id, role, self.brood_size)?;
+                    match self.islands[id].pre_screen(&brood) {
+                        Some(best) => vec![best],
+                        None => {
+                            brood.truncate(room);
+                            self.broods[id].push(brood.clone());
+                            brood
+                        }
+                    }
+                } else {
+                    self.breed_brood(id, role, self.brood_size.min(room))?
+                }
+            };
+
+            for child in next {
+                // Elites and migrants may legitimately share an id, but every bred child must be new
+                #[cfg(feature = "strict-checks")]
+                {
+                    let island = self.islands.get(id).unwrap();
+                    if !is_elite && island.future_generation_contains(child) {
+                        return Err(GeneticError::DuplicateIndividual {
+                            island: island.name().to_string(),
+                            id: child,
+                        });
+                    }
+                }
+
+                if self.unique_genomes {
+                    let hash = self.genetic_engine.genetics().genome_hash(child);
+                    if !genomes.insert(hash)
+                        && !is_elite
+                        && duplicates_in_a_row < self.individuals_per_island
+                    {
+                        duplicates_in_a_row += 1;
+                        continue;
+                    }
+                    duplicates_in_a_row = 0;
+                }
+
+                if is_elite {
+                    elite_scores.push((child, self.islands[id].score(child)));
+                    self.elites[id].push(child);
+                    self.origins[id]
+                        .entry(child)
+                        .or_insert(IndividualOrigin::Elite);
+                }
+                self.add_individual_to_island_future_generation(id, child);
+            }
+        }
+
+        for observer in self.observers.iter_mut() {
+            observer.elites_selected(self.generation_count, id, &self.elites[id]);
+        }
+
+        // Now that the future generation is full, make it the current generation
+        self.advance_island_generation(id);
+        self.islands[id].pin_scores(elite_scores);
+
+        #[cfg(feature = "strict-checks")]
+        self.islands
+            .get(id)
+            .unwrap()
+            .check_population(self.individuals_per_island)?;
+
+        Ok(())
+    }
+
+    // Creates up to `count` individuals for the first generation of the island, from its initializer and then the
+    // initialization strategy. With the random strategy, anything the initializer did not create is left to the caller.
+    fn initial_individuals(&mut self, id: usize, count: usize) -> Vec<u64> {
+        let mut individuals = match self.island_initializers[id].as_mut() {
+            Some(initializer) => initializer(self.genetic_engine.rng(), count),
+            None => vec![],
+        };
+        individuals.truncate(count);
+
+        let remaining = count - individuals.len();
+        match self.initialization {
+            Initialization::Random => {}
+            Initialization::Ramped => {
+                let max_points = self.genetic_engine.max_individual_points();
+                for index in 0..remaining {
+                    let points = ramped_points(max_points, index, remaining);
+                    individuals.push(self.genetic_engine.rand_individual_of_size(points));
+                }
+            }
+            Initialization::LatinHypercube(dimensions) => {
+                for point in latin_hypercube(self.genetic_engine.rng(), remaining, dimensions) {
+                    let individual = self.genetic_engine.genetics().individual_from_point(&point);
+                    individuals
+                        .push(individual.unwrap_or_else(|| self.genetic_engine.rand_individual()));
+                }
+            }
+        }
+
+        individuals
+    }
+
+    // Produces a brood of up to `size` new children for the island according to its role, all from the same parents.
+    // The island must not be empty.
+    fn breed_brood(
+        &mut self,
+        index: usize,
+        role: IslandRole,
+        size: usize,
+    ) -> Result<Vec<u64>, GeneticError> {
+        if role == IslandRole::Explorer
+            && self
+                .genetic_engine
+                .rng()
+                .random_ratio(1, EXPLORER_RANDOM_IMMIGRANT_RATE)
+        {
+            let individual = self.genetic_engine.rand_individual();
+            self.record_birth(index, individual, IndividualOrigin::Random, &[]);
+            return Ok(vec![individual]);
+        }
+
+        let curve = role.parent_curve(self.parent_curve());
+        let kind = DecisionKind::Parent { island: index };
+        let left = self.select_to_breed(index, curve, kind)?;
+        self.record_parent(index, left);
+
+        let mut brood = Vec::with_capacity(size);
+        match role {
+            IslandRole::Explorer => {
+                let points = self.genetic_engine.max_mutation_points_of(left);
+                for _ in 0..size {
+                    brood.push(self.genetic_engine.mutate(left, points));
+                }
+                for &child in brood.iter() {
+                    self.record_birth(index, child, IndividualOrigin::Mutation, &[left]);
+                }
+            }
+            IslandRole::Exploiter => {
+                for _ in 0..size {
+                    brood.push(self.genetic_engine.mutate(left, 1));
+                }
+                for &child in brood.iter() {
+                    self.record_birth(index, child, IndividualOrigin::Mutation, &[left]);
+                }
+            }
+            IslandRole::Standard | IslandRole::Archive => {
+                let right = self.select_mate(index, left, curve, kind)?;
+                self.record_parent(index, right);
+                for _ in 0..size {
+                    let (child, origin) =
+                        self.genetic_engine.rand_child_with_origin(left, right)?;
+                    let parents = match origin {
+                        IndividualOrigin::Crossover => vec![left, right],
+                        IndividualOrigin::Operator(operator)
+                            if self.genetic_engine.operator_parents(operator) == 2 =>
+                        {
+                            vec![left, right]
+                        }
+                        _ => vec![left],
+                    };
+                    self.record_birth(index, child, origin, &parents);
+                    brood.push(child);
+                }
+            }
+        }
+
+        Ok(brood)
+    }
+
+    // Selects the second parent for `left`, drawing again while it is `left` if the genetic engine redraws identical
+    // parents. The island must not be empty.
+    fn select_mate(
+        &mut self,
+        index: usize,
+        left: u64,
+        curve: SelectionCurve,
+        kind: DecisionKind,
+    ) -> Result<u64, GeneticError> {
+        let mut right = self.select_restricted_mate(index, left, curve, kind)?;
+        for _ in 0..self.genetic_engine.identical_parents().redraws() {
+            if right != left {
+                break;
+            }
+            right = self.select_restricted_mate(index, left, curve, kind)?;
+        }
+        Ok(right)
+    }
+
+    // Selects the second parent for `left`, looking for one within the mating restriction if there is one. The island
+    // must not be empty.
+    fn select_restricted_mate(
+        &mut self,
+        index: usize,
+        left: u64,
+        curve: SelectionCurve,
+        kind: DecisionKind,
+    ) -> Result<u64, GeneticError> {
+        let attempts = self
+            .mating_restriction
+            .as_ref()
+            .map_or(1, |restriction| restriction.attempts);
+        let mut right = self.select_to_breed(index, curve, kind)?;
+        for _ in 1..attempts {
+            let max_distance = self.mating_restriction.as_ref().unwrap().max_distance;
+            if self.genetic_engine.genetics().distance(left, right) <= max_distance {
+                break;
+            }
+            right = self.select_to_breed(index, curve, kind)?;
+        }
+        Ok(right)
+    }
+
+    // Selects an individual to breed from or to keep as an elite. The island must not be empty, and returns an error
+    // if its current generation was not sorted by a successful evaluation.
+    fn select_to_breed(
+        &mut self,
+        index: usize,
+        curve: SelectionCurve,
+        kind: DecisionKind,
+    ) -> Result<u64, GeneticError> {
+        self.select_individual(index, curve, kind)
+            .ok_or_else(|| GeneticError::IslandNotSorted(self.islands[index].name().to_string()))
+    }
+
+    // Selects one individual of the island's sorted current generation according to the curve, as a decision of the
+    // kind. Returns None if the island is empty or not sorted.
+    fn select_individual(
+        &mut self,
+        index: usize,
+        curve: SelectionCurve,
+        kind: DecisionKind,
+    ) -> Option<u64> {
+        self.select_in_range(index, curve, kind, 0..self.islands[index].len())
+    }
+
+    // Selects one individual from the range of indexes of the island's sorted current generation, treating the start
+    // of the range as the least fit. Returns None if the range is empty or the island is not sorted.
+    fn select_in_range(
+        &mut self,
+        index: usize,
+        curve: SelectionCurve,
+        kind: DecisionKind,
+        range: Range<usize>,
+    ) -> Option<u64> {
+        if range.is_empty() || !self.islands[index].is_sorted() {
+            return None;
+        }
+
+        let len = range.len();
+        let selectors = &self.selectors;
+        let island = &self.islands[index];
+        let pick = self.genetic_engine.decide(
+            kind,
+            |rng| match curve {
+                SelectionCurve::Custom(selector) => {
+                    selectors[selector].pick_one_index(rng, len).min(len - 1)
+                }
+                _ => island.pick_in_range(curve, rng, range.clone()),
+            },
+            |pick| pick < len,
+        );
+        self.islands[index].get_one_individual(range.start + pick)
+    }
+
+    // Remembers how a child bred on the island this generation was produced, and the metadata of its creation
+    fn record_birth(&mut self, index: usize, id: u64, origin: IndividualOrigin, parents: &[u64]) {
+        if let Some(origins) = self.origins.get_mut(index) {
+            origins.entry(id).or_insert(origin);
+        }
+        self.metadata
+            .entry(id)
+            .or_insert_with(|| IndividualMetadata {
+                generation: self.generation_count,
+                parents: parents.to_vec(),
+                origin,
+                island: index,
+            });
+    }
+
+    // Remembers that an individual was selected as a parent on the island this generation
+    fn record_parent(&mut self, index: usize, id: u64) {
+        if let Some(parents) = self.parents.get_mut(index) {
+            parents.insert(id);
+        }
+    }
+
+    fn len_island_future_generation(&self, index: usize) -> usize {
+        self.islands.get(index).unwrap().len_future_generation()
+    }
+
+    fn add_individual_to_island_future_generation(&mut self, index: usize, id: u64) {
+        self.islands
+            .get_mut(index)
+            .unwrap()
+            .add_individual_to_future_generation(id)
+    }
+
+    fn advance_island_generation(&mut self, index: usize) {
+        self.islands.get_mut(index).unwrap().advance_generation()
+    }
+
+    /// Fills, runs and migrates `generations` generations, then summarizes how the world progressed over the batch.
+    #[cfg(not(feature = "async"))]
+    pub fn run_n_generations(&mut self, generations: usize) -> Result<RunSummary, GeneticError> {
+        let start = self.begin_run();
+        for _ in 0..generations {
+            self.fill_all_islands()?;
+            self.run_one_generation()?;
+        }
+
+        Ok(self.summarize_run(start, generations))
+    }
+
+    /// Fills, runs and migrates `generations` generations, then summarizes how the world progressed over the batch.
+    #[cfg(feature = "async")]
+    pub async fn run_n_generations(
+        &mut self,
+        generations: usize,
+    ) -> Result<RunSummary, GeneticError> {
+        let start = self.begin_run();
+        for _ in 0..generations {
+            self.fill_all_islands()?;
+            self.run_one_generation().await?;
+        }
+
+        Ok(self.summarize_run(start, generations))
+    }
+
+    /// Runs generations until the budget is exhausted, and summarizes the generations that were run. Nothing is run if
+    /// the budget was already exhausted. Because the budget is measured against the world's total generation count and
+    /// best score, calling this again with an extended budget continues the run where it stopped.
+    #[cfg(not(feature = "async"))]
+    pub fn run_until(&mut self, budget: &RunBudget) -> Result<RunSummary, GeneticError> {
+        if budget.max_generations.is_none() && budget.target_score.is_none() {
+            return Err(GeneticError::UnboundedRunBudget);
+        }
+
+        let start = self.begin_run();
+        let mut generations = 0;
+        while !budget.is_exhausted(self.generation_count, self.best_score)
+            && !budget.is_overfitting(self.generations_since_validation_improvement())
+        {
+            self.fill_all_islands()?;
+            self.run_one_generation()?;
+            generations += 1;
+        }
+
+        Ok(self.summarize_run(start, generations))
+    }
+
+    /// Runs generations until the budget is exhausted, and summarizes the generations that were run. Nothing is run if
+    /// the budget was already exhausted. Because the budget is measured against the world's total generation count and
+    /// best score, calling this again with an extended budget continues the run where it stopped.
+    #[cfg(feature = "async")]
+    pub async fn run_until(&mut self, budget: &RunBudget) -> Result<RunSummary, GeneticError> {
+        if budget.max_generations.is_none() && budget.target_score.is_none() {
+            return Err(GeneticError::UnboundedRunBudget);
+        }
+
+        let start = self.begin_run();
+        let mut generations = 0;
+        while !budget.is_exhausted(self.generation_count, self.best_score)
+            && !budget.is_overfitting(self.generations_since_validation_improvement())
+        {
+            self.fill_all_islands()?;
+            self.run_one_generation().await?;
+            generations += 1;
+        }
+
+        Ok(self.summarize_run(start, generations))
     }
 
-    fn add_individual_to_island_future_generation(&mut self, index: usize, id: u64) {
-        self.islands
-            .get_mut(index)
-            .unwrap()
-            .add_individual_to_future_generation(id)
+    // Captures the state a RunSummary is measured against: the stats history length, migration count and best score
+    fn begin_run(&self) -> (usize, usize, Option<u64>) {
+        (
+            self.stats_history.len(),
+            self.migration_count,
+            self.best_score,
+        )
     }
 
-    fn advance_island_generation(&mut self, index: usize) {
-        self.islands.get_mut(index).unwrap().advance_generation()
+    fn summarize_run(&self, start: (usize, usize, Option<u64>), generations: usize) -> RunSummary {
+        let (history_len, migration_count, starting_best_score) = start;
+        RunSummary {
+            generations,
+            starting_best_score,
+            best_score: self.best_score,
+            improvements: self.stats_history[history_len..]
+                .iter()
+                .filter(|stats| stats.improved)
+                .count(),
+            migrations: self.migration_count - migration_count,
+            champion: self.most_fit_individual(),
+        }
     }
 
-    /// Runs generations until the specified function returns false
+    /// Runs generations until the specified function returns false, and reports on the run. The function may change
+    /// the world between generations, for example to adapt the parameters of the genetic engine.
     #[cfg(not(feature = "async"))]
-    pub fn run_generations_while<While>(&mut self, mut while_fn: While) -> Result<(), GeneticError>
+    pub fn run_generations_while<While>(
+        &mut self,
+        mut while_fn: While,
+    ) -> Result<RunReport, GeneticError>
     where
-        While: FnMut(&World<G>) -> bool,
+        While: FnMut(&mut World<G>) -> bool,
     {
+        let configuration = self.configuration();
+        let history_len = self.stats_history.len();
+        let start = Instant::now();
+
         // Always run at least one generation
         let mut running = true;
         while running {
             self.fill_all_islands()?;
-            self.run_one_generation();
+            self.run_one_generation()?;
             running = while_fn(self);
         }
 
-        Ok(())
+        Ok(self.report_run(configuration, history_len, start.elapsed()))
     }
 
-    /// Runs generations until the specified function returns false
+    /// Runs generations until the specified function returns false, and reports on the run. The function may change
+    /// the world between generations, for example to adapt the parameters of the genetic engine.
     #[cfg(feature = "async")]
     pub async fn run_generations_while<While>(
         &mut self,
         mut while_fn: While,
-    ) -> Result<(), GeneticError>
+    ) -> Result<RunReport, GeneticError>
     where
-        While: FnMut(&World<G>) -> bool,
+        While: FnMut(&mut World<G>) -> bool,
     {
+        let configuration = self.configuration();
+        let history_len = self.stats_history.len();
+        let start = Instant::now();
+
         // Always run at least one generation
         let mut running = true;
         while running {
             self.fill_all_islands()?;
-            self.run_one_generation().await;
+            self.run_one_generation().await?;
             running = while_fn(self);
         }
 
-        Ok(())
+        Ok(self.report_run(configuration, history_len, start.elapsed()))
     }
 
+    // Builds the report of a run that started with the configuration when the statistics history had `history_len`
+    // entries
+    fn report_run(
+        &self,
+        configuration: RunConfiguration,
+        history_len: usize,
+        elapsed: Duration,
+    ) -> RunReport {
+        let history = &self.stats_history[history_len..];
+        RunReport {
+            seed: self.genetic_engine.seed(),
+            configuration,
+            generations: history.len(),
+            evaluations: history
+                .iter()
+                .flat_map(|stats| stats.islands.iter())
+                .map(|island| island.evaluations)
+                .sum(),
+            island_best_scores: (0..self.islands.len())
+                .map(|index| {
+                    history
+                        .iter()
+                        .filter_map(|stats| stats.islands.get(index)?.best_score)
+                        .max()
+                })
+                .collect(),
+            elapsed,
+        }
+    }
+
+    /// Returns the settings the world is running with, including any changes made since it was built
+    pub fn configuration(&self) -> RunConfiguration {
+        RunConfiguration {
+            islands: self
+                .islands
+                .iter()
+                .map(|island| island.name().to_string())
+                .collect(),
+            individuals_per_island: self.individuals_per_island,
+            elite_individuals_per_generation: self.elite_individuals_per_generation,
+            generations_between_migrations: self.generations_between_migrations,
+            migrant_count: self.migrant_count,
+            adaptive_migration: self.adaptive_migration.clone(),
+            migration_algorithm: self.migration_algorithm.clone(),
+            migration_schedule: self.migration_schedule.clone(),
+            migration_shortfall: self.migration_shortfall,
+            clone_migrated_individuals: self.clone_migrated_individuals,
+            protected_from_emigration: self.protected_from_emigration,
+            emigration_threshold: self.emigration_threshold,
+            select_for_migration: self.select_for_migration,
+            migrate_by_crowding_distance: self.migrate_by_crowding_distance,
+            migrant_novelty_threshold: self.migrant_novelty_threshold,
+            select_as_parent: self.select_as_parent,
+            parent_curve_blend: self.parent_curve_blend.clone(),
+            select_as_elite: self.select_as_elite,
+            elite_handling: self.elite_handling,
+            brood_size: self.brood_size,
+            brood_selection: self.brood_selection,
+            unique_genomes: self.unique_genomes,
+            mating_restriction: self.mating_restriction.clone(),
+            island_synchronization: self.island_synchronization,
+            score_normalization: self.score_normalization,
+            stochastic_ranking: self.stochastic_ranking.clone(),
+            rate_limit: self.rate_limit.clone(),
+            hall_of_fame: self.hall_of_fame.clone(),
+            initialization: self.initialization,
+            hypermutation: self.hypermutation.clone(),
+            warmup: self.warmup.clone(),
+            curriculum: self.curriculum.clone(),
+            island_weights: self.island_weights.clone(),
+            genetic_engine: self.genetic_engine.settings(),
+        }
+    }
+
+    /// Moves individuals between islands according to the configured `MigrationAlgorithm`. The number leaving each
+    /// island is decided by the configured `AdaptiveMigration`, or else the `MigrantCount`, and islands that have fewer
+    /// individuals available than requested are handled according to the configured `MigrationShortfall`. What happened
+    /// is recorded in the `migration_log`.
     pub fn migrate_individuals_between_islands(&mut self) {
+        self.set_phase(WorldPhase::Migrating);
+        self.genetic_engine
+            .set_decision_generation(self.generation_count);
+        self.migration_log.clear();
+        self.migration_count += 1;
         let island_len = self.islands.len();
 
         // It only makes sense to migrate if there are at least two islands
         if island_len > 1 {
+            if let Some(adaptive_migration) = &self.adaptive_migration {
+                let distances = self.island_distances();
+                self.adaptive_migrant_counts = (0..island_len)
+                    .map(|island| adaptive_migration.migrants(&distances, island))
+                    .collect();
+            }
             match self.migration_algorithm {
                 MigrationAlgorithm::Circular => self.migrate_all_islands_circular_n(1),
                 MigrationAlgorithm::Cyclical(n) => self.migrate_all_islands_circular_n(n),
@@ -240,51 +2536,201 @@ where
                     }
                 }
                 MigrationAlgorithm::CompletelyRandom => {
-                    let len = self.islands.len();
-
                     // For each migrating individual on each island, pick a random destination that is not the same
                     // island and migrate there.
-                    for source_island_id in 0..len {
-                        for _ in 0..self.number_of_individuals_migrating {
-                            let mut destination_island_id = source_island_id;
-                            while source_island_id != destination_island_id {
-                                destination_island_id =
-                                    self.genetic_engine.rng().random_range(0..len);
-                            }
-                            self.migrate_one_individual_from_island_to_island(
-                                source_island_id,
-                                destination_island_id,
-                            );
-                        }
+                    for source_island_id in 0..island_len {
+                        self.migrate_from_island(source_island_id, None);
                     }
                 }
             }
         }
+        self.set_phase(WorldPhase::Idle);
+    }
+
+    /// Returns a record for each island that emigrated during the most recent migration. Archive islands never
+    /// emigrate and have no record.
+    pub fn migration_log(&self) -> &[MigrationRecord] {
+        &self.migration_log
+    }
+
+    /// Borrows the log of every change to the populations, or None if the world was built without one
+    pub fn event_log(&self) -> Option<&EventLog> {
+        self.event_recorder.as_ref().map(|recorder| recorder.log())
     }
 
-    fn migrate_one_individual_from_island_to_island(
+    // Migrates individuals away from one island and records the outcome in the migration log. When there is no
+    // destination island, each individual picks a random island that is not its home island.
+    fn migrate_from_island(
         &mut self,
         source_island_id: usize,
-        destination_island_id: usize,
+        destination_island_id: Option<usize>,
     ) {
-        let curve = self.select_for_migration;
+        let source_island = self.islands.get(source_island_id).unwrap();
+        if !source_island.role().emigrates() {
+            return;
+        }
+        let eligible = self.emigrant_range(source_island_id);
+        let available = eligible.len();
+        let requested = match self.adaptive_migrant_counts.get(source_island_id) {
+            Some(&count) => count,
+            None => self
+                .migrant_count
+                .requested(available, self.genetic_engine.rng()),
+        };
+        let count = if available >= requested {
+            requested
+        } else {
+            match self.migration_shortfall {
+                MigrationShortfall::Skip => 0,
+                MigrationShortfall::MigrateAvailable => available,
+            }
+        };
 
-        // Get the migrating individual from the source island
-        let source_island = self.islands.get_mut(source_island_id).unwrap();
-        let migrating: u64 = if self.clone_migrated_individuals {
-            source_island
-                .select_one_individual(curve, self.genetic_engine.rng())
-                .unwrap()
-                .clone()
+        let mut crowding_order = if self.migrate_by_crowding_distance {
+            source_island.crowding_order().map(|order| {
+                let eligible_ids = &source_island.individuals()[eligible];
+                order
+                    .into_iter()
+                    .filter(|id| eligible_ids.contains(id))
+                    .collect::<Vec<u64>>()
+            })
         } else {
-            source_island
-                .select_and_remove_one_individual(curve, self.genetic_engine.rng())
-                .unwrap()
+            None
+        };
+
+        let mut migrants = Vec::with_capacity(count);
+        let mut rejected = vec![];
+        for _ in 0..count {
+            let destination_island_id = match destination_island_id {
+                Some(id) => id,
+                None => self.random_destination_island(source_island_id),
+            };
+            let candidate = match crowding_order.as_mut() {
+                Some(order) => order.pop(),
+                None => self.select_migrant(source_island_id),
+            };
+            if let Some(id) = candidate.filter(|&id| !self.admits(destination_island_id, id)) {
+                rejected.push(Migrant {
+                    id,
+                    destination_island: destination_island_id,
+                });
+                continue;
+            }
+            let migrating = candidate.and_then(|id| self.take_migrant(source_island_id, id));
+            if let Some(id) = migrating {
+                self.deliver_migrant(source_island_id, destination_island_id, id);
+                migrants.push(Migrant {
+                    id,
+                    destination_island: destination_island_id,
+                });
+            }
+        }
+
+        self.migration_log.push(MigrationRecord {
+            generation: self.generation_count,
+            source_island: source_island_id,
+            requested,
+            available,
+            migrants,
+            rejected,
+        });
+    }
+
+    // Picks a random island that is not the source island. There must be at least two islands.
+    fn random_destination_island(&mut self, source_island_id: usize) -> usize {
+        let len = self.islands.len();
+        self.genetic_engine.decide(
+            DecisionKind::Destination {
+                island: source_island_id,
+            },
+            |rng| {
+                let mut destination_island_id = source_island_id;
+                while source_island_id == destination_island_id {
+                    destination_island_id = rng.random_range(0..len);
+                }
+                destination_island_id
+            },
+            |destination_island_id| {
+                destination_island_id < len && destination_island_id != source_island_id
+            },
+        )
+    }
+
+    // The number of most fit individuals on each island that migration may not remove
+    fn protected_count(&self) -> usize {
+        if self.clone_migrated_individuals {
+            0
+        } else {
+            self.protected_from_emigration
+        }
+    }
+
+    // The indexes of the individuals on the island that may emigrate
+    fn emigrant_range(&self, source_island_id: usize) -> Range<usize> {
+        self.islands[source_island_id]
+            .emigrant_range(self.emigration_threshold, self.protected_count())
+    }
+
+    // Selects an emigrant using the migration curve, leaving it on the source island
+    fn select_migrant(&mut self, source_island_id: usize) -> Option<u64> {
+        let curve = self.migration_curve();
+        let eligible = self.emigrant_range(source_island_id);
+        let kind = DecisionKind::Emigrant {
+            island: source_island_id,
+        };
+        self.select_in_range(source_island_id, curve, kind, eligible)
+    }
+
+    // Returns true if the migrant is far enough from everything on the destination island to be admitted
+    fn admits(&self, destination_island_id: usize, id: u64) -> bool {
+        let Some(threshold) = self.migrant_novelty_threshold else {
+            return true;
         };
+        let destination = &self.islands[destination_island_id];
+        let genetics = self.genetic_engine.genetics();
+        destination
+            .individuals()
+            .iter()
+            .chain(destination.future_individuals())
+            .all(|&resident| genetics.distance(id, resident) > threshold)
+    }
+
+    // Takes a specific emigrant, removing it from the source island unless migrants are cloned
+    fn take_migrant(&mut self, source_island_id: usize, id: u64) -> Option<u64> {
+        let source_island = self.islands.get_mut(source_island_id)?;
+        if self.clone_migrated_individuals || source_island.remove_individual(id) {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    // Adds an emigrant to the future generation of its destination and offers it to the archive
+    fn deliver_migrant(&mut self, source_island_id: usize, destination_island_id: usize, id: u64) {
+        // Offer it to the archive with the score from its home island
+        if let Some(archive) = self.archive.as_mut() {
+            let source_island = &self.islands[source_island_id];
+            let candidate = ArchivedIndividual {
+                id,
+                score: source_island.score(id),
+                normalized_score: source_island.normalized_score(id, self.score_normalization),
+                island: source_island_id,
+                generation: self.generation_count,
+            };
+            archive.offer(candidate, self.genetic_engine.genetics());
+        }
 
         // Add it to the destination island
-        let destination_island = self.islands.get_mut(destination_island_id).unwrap();
-        destination_island.add_individual_to_future_generation(migrating);
+        self.islands[destination_island_id].add_individual_to_future_generation(id);
+        if let Some(recorder) = self.event_recorder.as_mut() {
+            recorder.migrated(
+                self.generation_count,
+                source_island_id,
+                destination_island_id,
+                id,
+                self.clone_migrated_individuals,
+            );
+        }
     }
 
     // Calculates the ID of the island at a specific distance from the source. Wraps around when we get to the end of
@@ -301,12 +2747,7 @@ where
 
     fn migrate_one_island_circular_n(&mut self, source_island_id: usize, n: usize) {
         let destination_island_id = self.island_at_distance(source_island_id, n);
-        for _ in 0..self.number_of_individuals_migrating {
-            self.migrate_one_individual_from_island_to_island(
-                source_island_id,
-                destination_island_id,
-            );
-        }
+        self.migrate_from_island(source_island_id, Some(destination_island_id));
     }
 
     // Creates a Vec containing the source_id of each island exactly one time
@@ -332,7 +2773,904 @@ where
         distances
     }
 
+    /// Returns the number of generations that have been run
     pub fn generation_count(&self) -> usize {
         self.generation_count
     }
 }
+
+impl<G> Drop for World<G>
+where
+    G: Genetics,
+{
+    fn drop(&mut self) {
+        if self.needs_shutdown {
+            let _ = self.shutdown();
+        }
+    }
+}
+
+// What a thread reports after running the generation of one island: the island's index, the outcome and the attempts
+// that were started over
+#[cfg(all(feature = "multi-threaded", not(feature = "async")))]
+type IslandOutcome = (usize, Result<(), GeneticError>, Vec<(usize, GeneticError)>);
+
+// Evaluates the current generation of an island, starting it over after transient failures as allowed by the retry
+// policy. `retried` is given the number of every attempt that is started over and the failure that ended the one
+// before it.
+#[cfg(not(feature = "async"))]
+fn evaluate_with_retries(
+    island: &mut Island,
+    retry: Option<&GenerationRetry>,
+    mut retried: impl FnMut(usize, GeneticError),
+) -> Result<(), GeneticError> {
+    let mut retries = 0;
+    loop {
+        let samples = retry.map(|_| island.samples());
+        let result = island.evaluate_generation();
+        island.set_evaluation_retries(retries);
+        match result {
+            Err(error) if may_retry(island, retry, retries, &error) => {
+                retries += 1;
+                if let Some(samples) = samples {
+                    island.restore_samples(samples);
+                }
+                retried(retries, error);
+            }
+            result => return result,
+        }
+    }
+}
+
+// Returns true if the retry policy allows another attempt at the island's generation after this failure
+fn may_retry(
+    island: &Island,
+    retry: Option<&GenerationRetry>,
+    retries: usize,
+    error: &GeneticError,
+) -> bool {
+    retry.is_some_and(|retry| retries < retry.max_retries) && island.is_transient_failure(error)
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn single_individual_islands_with_migration() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(1)
+            .with_elite_individuals(0)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_clone_migrated_individuals(false)
+            .build()
+            .unwrap();
+
+        let mut generations = 0;
+        world
+            .run_generations_while(|_| {
+                generations += 1;
+                generations < 10
+            })
+            .unwrap();
+
+        // Each island's only individual has emigrated and is waiting in the other island's future generation
+        for index in 0..world.get_number_of_islands() {
+            let island = world.get_island(index).unwrap();
+            assert_eq!(0, island.len());
+            assert_eq!(1, island.len_future_generation());
+        }
+    }
+
+    #[test]
+    fn single_island_with_migration_enabled() {
+        let mut world = world_builder(&["only"])
+            .with_individuals_per_island(10)
+            .with_generations_between_migrations(1)
+            .build()
+            .unwrap();
+
+        let mut generations = 0;
+        world
+            .run_generations_while(|_| {
+                generations += 1;
+                generations < 5
+            })
+            .unwrap();
+
+        let island = world.get_island(0).unwrap();
+        assert_eq!(10, island.len());
+        assert_eq!(0, island.len_future_generation());
+    }
+
+    #[test]
+    fn parameters_can_change_between_generations() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(10)
+            .build()
+            .unwrap();
+
+        world
+            .run_generations_while(|world| {
+                let engine = world.genetic_engine_mut();
+                engine.set_crossover_rate(0).unwrap();
+                engine.set_mutation_rate(0).is_ok()
+            })
+            .unwrap();
+
+        // Crossover was disabled first, so mutation could not be disabled as well
+        assert_eq!(0, world.genetic_engine().crossover_rate());
+        assert_eq!(1, world.genetic_engine().mutation_rate());
+        assert!(matches!(
+            world.genetic_engine_mut().set_mutation_rate(0),
+            Err(GeneticError::NoGeneticOperators)
+        ));
+    }
+
+    #[test]
+    fn generation_seeds_are_reported_and_can_be_chosen() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(10)
+            .build()
+            .unwrap();
+
+        world.fill_all_islands().unwrap();
+        world.run_one_generation().unwrap();
+        world.set_next_generation_seed(42);
+        world.fill_all_islands().unwrap();
+        world.run_one_generation().unwrap();
+
+        let stats = world.stats_history();
+        assert_eq!(world.generation_seed(), stats[1].seed);
+        assert_eq!(Some(42), stats[1].seed);
+        assert!(stats[0].seed.is_some());
+    }
+
+    #[test]
+    fn reference_individuals_are_scored_periodically() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(10)
+            .with_reference_individual("old champion", 7)
+            .with_reference_evaluation_interval(2)
+            .build()
+            .unwrap();
+
+        for _ in 0..4 {
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+        }
+
+        let stats = world.stats_history();
+        assert!(stats[0].reference_scores.is_empty());
+        assert!(stats[2].reference_scores.is_empty());
+        for generation in [1, 3] {
+            assert_eq!(
+                vec![
+                    ReferenceScore {
+                        name: "old champion".to_string(),
+                        island: 0,
+                        score: 7
+                    },
+                    ReferenceScore {
+                        name: "old champion".to_string(),
+                        island: 1,
+                        score: 7
+                    },
+                ],
+                stats[generation].reference_scores
+            );
+        }
+    }
+
+    #[test]
+    fn reference_individuals_are_evaluated_like_the_population() {
+        // Fails to run the reference individual and counts the generations it was told about
+        struct RejectingEngine {
+            generations: Arc<Mutex<usize>>,
+        }
+
+        impl IslandEngine for RejectingEngine {
+            fn pre_generation_run(&mut self, _individuals: &[u64]) {
+                *self.generations.lock().unwrap() += 1;
+            }
+
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn try_run_individual(&mut self, id: u64) -> Result<(), String> {
+                match id {
+                    1000 => Err("reference crashed".to_string()),
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        let generations = Arc::new(Mutex::new(0));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(10)
+            .with_reference_individual("old champion", 1000)
+            .with_reference_evaluation_interval(2);
+        builder.add_island(
+            "a",
+            Box::new(RejectingEngine {
+                generations: generations.clone(),
+            }),
+        );
+        let mut world = builder.build().unwrap();
+
+        world.fill_all_islands().unwrap();
+        world.run_one_generation().unwrap();
+        world.fill_all_islands().unwrap();
+        let error = world.run_one_generation().unwrap_err();
+
+        assert!(matches!(
+            error,
+            GeneticError::EvaluationFailed { id: 1000, ref message, .. } if message == "reference crashed"
+        ));
+        assert_eq!(2, *generations.lock().unwrap());
+    }
+
+    #[test]
+    fn duplicate_genomes_are_kept_out_of_an_island() {
+        // Individuals whose ids have the same quotient by the divisor share a genome
+        struct SharedGenomes(CountingGenetics, u64);
+
+        impl Genetics for SharedGenomes {
+            fn random_individual(&self, rng: &mut rand::rngs::StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut rand::rngs::StdRng, individual: u64, points: usize) -> u64 {
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(
+                &self,
+                rng: &mut rand::rngs::StdRng,
+                a: u64,
+                b: u64,
+                points: usize,
+            ) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn genome_hash(&self, individual: u64) -> u64 {
+                individual / self.1
+            }
+        }
+
+        let run = |unique: bool, divisor: u64| {
+            let engine = GeneticEngineBuilder::default()
+                .seed(1234)
+                .genetics(SharedGenomes(CountingGenetics::default(), divisor))
+                .build()
+                .unwrap();
+            let mut builder = WorldBuilder::new()
+                .with_genetic_engine(engine)
+                .with_individuals_per_island(6)
+                .with_migrating_individuals(0)
+                .with_unique_genomes(unique);
+            builder.add_island("a", Box::new(IdScoreEngine));
+            let mut world = builder.build().unwrap();
+            world.run_n_generations(1).unwrap();
+            assert_eq!(6, world.get_island(0).unwrap().len());
+            world.stats_history()[0].islands[0].distinct_genomes
+        };
+
+        // Ids come in pairs with the same genome, so only every other one is kept
+        assert_eq!(3, run(false, 2));
+        assert_eq!(6, run(true, 2));
+
+        // An island that can only hold copies is still filled
+        assert_eq!(1, run(true, u64::MAX));
+    }
+
+    #[test]
+    fn brood_selection_keeps_the_best_sibling() {
+        let run = |selection: bool| {
+            let mut world = world_builder(&["a"])
+                .with_individuals_per_island(7)
+                .with_elite_individuals(1)
+                .with_migrating_individuals(0)
+                .with_generations_between_migrations(0)
+                .with_brood_size(3)
+                .with_brood_selection(selection)
+                .build()
+                .unwrap();
+            world.run_n_generations(2).unwrap();
+            world.get_island(0).unwrap().snapshot().individuals
+        };
+
+        // Without selection every sibling stays in the generation
+        assert_eq!(7, run(false).len());
+
+        // One elite plus the best of the broods 7..=9 and 10..=12. Younger siblings have larger ids, so they win.
+        let survivors = run(true);
+        assert_eq!(3, survivors.len());
+        assert!(survivors[0] < 7);
+        assert_eq!(vec![9, 12], survivors[1..].to_vec());
+    }
+
+    #[test]
+    fn quick_scores_pre_screen_broods() {
+        let engine = QuickScoreEngine::default();
+        let runs = engine.runs.clone();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(3)
+            .with_elite_individuals(1)
+            .with_migrating_individuals(0)
+            .with_generations_between_migrations(0)
+            .with_brood_size(3)
+            .with_brood_selection(true);
+        builder.add_island("a", Box::new(engine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(2).unwrap();
+
+        // Only the winners of the broods 3..=5 and 6..=8 are run, so the island stays full
+        let individuals = world.get_island(0).unwrap().snapshot().individuals;
+        assert_eq!(3, individuals.len());
+        assert_eq!(vec![5, 8], individuals[1..].to_vec());
+        assert_eq!(6, runs.load(std::sync::atomic::Ordering::Relaxed));
+    }
+
+    #[test]
+    fn offspring_are_repaired() {
+        // Marks every child it repairs with a high bit
+        struct Repairing(CountingGenetics);
+
+        impl Genetics for Repairing {
+            fn random_individual(&self, rng: &mut rand::rngs::StdRng, max_points: usize) -> u64 {
+                self.0.random_individual(rng, max_points)
+            }
+
+            fn mutate(&self, rng: &mut rand::rngs::StdRng, individual: u64, points: usize) -> u64 {
+                self.0.mutate(rng, individual, points)
+            }
+
+            fn crossover(
+                &self,
+                rng: &mut rand::rngs::StdRng,
+                a: u64,
+                b: u64,
+                points: usize,
+            ) -> u64 {
+                self.0.crossover(rng, a, b, points)
+            }
+
+            fn repair(&self, _rng: &mut rand::rngs::StdRng, individual: u64) -> u64 {
+                individual | 1 << 40
+            }
+        }
+
+        let engine = GeneticEngineBuilder::default()
+            .seed(1234)
+            .genetics(Repairing(CountingGenetics::default()))
+            .build()
+            .unwrap();
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(engine)
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0);
+        builder.add_island("a", Box::new(IdScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(2).unwrap();
+
+        let island = world.get_island(0).unwrap().snapshot().individuals;
+        for id in island {
+            let origin = world.individual_metadata(id).unwrap().origin;
+            let bred = matches!(
+                origin,
+                IndividualOrigin::Mutation | IndividualOrigin::Crossover
+            );
+            assert_eq!(bred, id & 1 << 40 != 0);
+        }
+    }
+
+    #[test]
+    fn individuals_are_looked_up_by_id() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_elite_individuals(0)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+        let first = world.get_island(0).unwrap().snapshot().individuals;
+        world.run_n_generations(1).unwrap();
+
+        let island = world.get_island(0).unwrap();
+        for index in 0..island.len() {
+            let id = island.get_one_individual(index).unwrap();
+            assert!(island.contains(id));
+            assert_eq!(island.score_for_individual(index), island.score_of(id));
+            assert_eq!(Some(index), island.rank_of(id));
+        }
+
+        // Without elites, nobody from the first generation is left to look up
+        for id in first {
+            assert!(!island.contains(id));
+            assert_eq!((None, None), (island.score_of(id), island.rank_of(id)));
+        }
+    }
+
+    #[test]
+    fn islands_are_iterated_in_index_order() {
+        let mut world = world_builder(&["a", "b", "c"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+
+        let names: Vec<&str> = world.islands().map(|island| island.name()).collect();
+        assert_eq!(vec!["a", "b", "c"], names);
+        assert!(world.islands().all(|island| island.len() == 4));
+
+        for island in world.islands_mut().skip(1) {
+            island.clear();
+        }
+        let sizes: Vec<usize> = world.islands().map(|island| island.len()).collect();
+        assert_eq!(vec![4, 0, 0], sizes);
+    }
+
+    #[test]
+    fn islands_sort_by_scores_of_the_whole_generation() {
+        // Ranks lower ids higher, and must not be compared pairwise
+        struct GenerationScoreEngine;
+
+        impl IslandEngine for GenerationScoreEngine {
+            fn run_individual(&mut self, _id: u64) {}
+
+            fn score_generation(&self, individuals: &[u64]) -> Option<Vec<u64>> {
+                Some(
+                    individuals
+                        .iter()
+                        .map(|&id| self.score_individual(id))
+                        .collect(),
+                )
+            }
+
+            fn score_individual(&self, id: u64) -> u64 {
+                1000 - id
+            }
+
+            fn sort_individuals(&self, _a: u64, _b: u64) -> std::cmp::Ordering {
+                panic!("islands with generation scores are not sorted pairwise");
+            }
+        }
+
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0);
+        builder.add_island("a", Box::new(GenerationScoreEngine));
+        let mut world = builder.build().unwrap();
+        world.run_n_generations(1).unwrap();
+
+        assert_eq!(
+            (0..10).rev().collect::<Vec<u64>>(),
+            world.get_island(0).unwrap().snapshot().individuals
+        );
+    }
+
+    #[test]
+    fn island_engines_can_be_replaced_between_generations() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(0)
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+        let population = world.get_island(0).unwrap().snapshot().individuals;
+
+        world
+            .replace_island_engine("a", Box::new(ScaledEngine { scale: 10 }))
+            .unwrap();
+        assert_eq!(
+            population,
+            world.get_island(0).unwrap().snapshot().individuals
+        );
+
+        world.run_n_generations(1).unwrap();
+        let island = world.get_island(0).unwrap();
+        let best = island.most_fit_individual().unwrap();
+        assert_eq!(Some(best * 10), world.best_score());
+        assert!(matches!(
+            world.replace_island_engine("missing", Box::new(IdScoreEngine)),
+            Err(GeneticError::UnknownIsland(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn shutdown_flushes_the_run_artifacts() {
+        struct Recorder(Arc<Mutex<Vec<usize>>>);
+
+        impl WorldObserver for Recorder {
+            fn world_shut_down(&mut self, generation: usize) {
+                self.0.lock().unwrap().push(generation);
+            }
+        }
+
+        let directory = std::env::temp_dir().join(format!("shutdown-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let checkpoint = directory.join("automatic.checkpoint");
+        let checkpointed_generation = || {
+            let text = std::fs::read_to_string(&checkpoint).unwrap();
+            WorldSnapshot::from_checkpoint(&text)
+                .unwrap()
+                .generation_count
+        };
+        let exported = Arc::new(Mutex::new(vec![]));
+        let shutdowns = Arc::new(Mutex::new(vec![]));
+        let sink_exported = exported.clone();
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_checkpoint_every(2, &checkpoint)
+            .with_champion_sink(
+                ChampionSink::new(move |champion| {
+                    sink_exported.lock().unwrap().push(champion.score)
+                })
+                .with_min_generations_between_exports(100),
+            )
+            .with_observer(Recorder(shutdowns.clone()))
+            .build()
+            .unwrap();
+
+        // The throttled sink holds back the improvements after the first export until the shutdown
+        world.run_n_generations(3).unwrap();
+        assert_eq!(1, exported.lock().unwrap().len());
+        world.shutdown().unwrap();
+        assert_eq!(2, exported.lock().unwrap().len());
+        assert_eq!(world.best_score(), exported.lock().unwrap().last().copied());
+        assert_eq!(3, checkpointed_generation());
+        assert_eq!(vec![3], *shutdowns.lock().unwrap());
+
+        // Dropping the world shuts it down again only if it ran since
+        world.run_n_generations(2).unwrap();
+        drop(world);
+        assert_eq!(5, checkpointed_generation());
+        assert_eq!(vec![3, 5], *shutdowns.lock().unwrap());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn island_distances_are_measured_periodically() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_island_distance_interval(2)
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+
+        // With the discrete distance of CountingGenetics every two individuals are 1.0 apart unless they are the same
+        let distances = &world.stats_history()[1].island_distances;
+        assert!(world.stats_history()[0].island_distances.is_empty());
+        assert_eq!(2, distances.len());
+        assert!(distances
+            .iter()
+            .flatten()
+            .all(|distance| distance.is_some_and(|d| (0.0..=1.0).contains(&d))));
+        assert_eq!(distances[0][1], distances[1][0]);
+
+        let empty = world_builder(&["a", "b"]).build().unwrap();
+        assert_eq!(
+            vec![vec![None, None], vec![None, None]],
+            empty.island_distances()
+        );
+    }
+
+    #[test]
+    fn protected_individuals_do_not_emigrate() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_generations_between_migrations(0)
+            .with_migrating_individuals(4)
+            .with_clone_migrated_individuals(false)
+            .with_protected_from_emigration(2)
+            .build()
+            .unwrap();
+        world.fill_all_islands().unwrap();
+        world.run_one_generation().unwrap();
+        let protected: Vec<Vec<u64>> = (0..2)
+            .map(|index| world.get_island(index).unwrap().snapshot().individuals[2..].to_vec())
+            .collect();
+
+        world.migrate_individuals_between_islands();
+        for record in world.migration_log() {
+            assert_eq!(2, record.available);
+            assert_eq!(2, record.migrants.len());
+            let island = world.get_island(record.source_island).unwrap();
+            assert_eq!(
+                protected[record.source_island],
+                island.snapshot().individuals
+            );
+        }
+    }
+
+    #[test]
+    fn polled_generations_match_generations_run_at_once() {
+        let build = || {
+            world_builder(&["a", "b", "c"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_generations_between_migrations(1)
+                .build()
+                .unwrap()
+        };
+        let mut polled = build();
+        for _ in 0..2 {
+            // Filling, one step per island, then completing the generation
+            let mut polls = 1;
+            while !polled.poll_generation(Duration::ZERO).unwrap() {
+                polls += 1;
+            }
+            assert_eq!(5, polls);
+        }
+        assert!(polled.poll_generation(Duration::from_secs(60)).unwrap());
+
+        let mut world = build();
+        world.run_n_generations(3).unwrap();
+        assert_eq!(world.snapshot(), polled.snapshot());
+        assert_eq!(world.stats_history(), polled.stats_history());
+    }
+
+    #[test]
+    fn generations_can_be_run_again_after_an_evaluation_error() {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(10)
+            .with_elite_individuals(2)
+            .with_migrating_individuals(0);
+        builder.add_island(
+            "a",
+            Box::new(FailOnceEngine {
+                fail_on: 15,
+                runs: 0,
+            }),
+        );
+        let mut world = builder.build().unwrap();
+
+        // The second generation fails half way through, leaving the island to start over
+        let result = world.run_n_generations(3);
+        assert!(matches!(
+            result,
+            Err(GeneticError::EvaluationFailed { island, .. }) if island == "a"
+        ));
+        assert_eq!(1, world.generation_count());
+        assert!(world.get_island(0).unwrap().is_empty());
+
+        world.run_n_generations(2).unwrap();
+        assert_eq!(3, world.generation_count());
+        let island = world.get_island(0).unwrap();
+        assert_eq!(10, island.len());
+        assert!(island.is_sorted());
+    }
+
+    #[test]
+    fn polling_starts_a_new_generation_after_an_evaluation_error() {
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(0);
+        builder.add_island(
+            "b",
+            Box::new(FailOnceEngine {
+                fail_on: 15,
+                runs: 0,
+            }),
+        );
+        let mut world = builder.build().unwrap();
+
+        let mut errors = 0;
+        while world.generation_count() < 3 {
+            match world.poll_generation(Duration::ZERO) {
+                Ok(_) => {}
+                Err(GeneticError::EvaluationFailed { island, .. }) if island == "b" => errors += 1,
+                Err(error) => panic!("unexpected error: {}", error),
+            }
+        }
+        assert_eq!(1, errors);
+        assert_eq!(WorldPhase::Idle, world.phase());
+        assert!(world.islands().all(|island| island.len() == 10));
+    }
+
+    #[test]
+    fn islands_only_admit_novel_migrants() {
+        let migrate_with = |threshold: f64| {
+            let mut world = world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_generations_between_migrations(0)
+                .with_migrating_individuals(3)
+                .with_clone_migrated_individuals(false)
+                .with_migrant_novelty_threshold(threshold)
+                .build()
+                .unwrap();
+            world.fill_all_islands().unwrap();
+            world.run_one_generation().unwrap();
+            world.migrate_individuals_between_islands();
+            world
+        };
+
+        // Every pair of different individuals is 1.0 apart, so only identical copies are too close at 0.5
+        let world = migrate_with(0.5);
+        for record in world.migration_log() {
+            assert_eq!(3, record.migrants.len());
+            assert!(record.rejected.is_empty());
+        }
+
+        let world = migrate_with(1.0);
+        for record in world.migration_log() {
+            assert!(record.migrants.is_empty());
+            assert_eq!(3, record.rejected.len());
+            assert_eq!(4, world.get_island(record.source_island).unwrap().len());
+        }
+
+        let invalid = world_builder(&["a"])
+            .with_migrating_individuals(1)
+            .with_migrant_novelty_threshold(-1.0)
+            .build();
+        assert!(matches!(
+            invalid,
+            Err(GeneticError::InvalidMigrantNoveltyThreshold)
+        ));
+    }
+
+    #[test]
+    fn islands_start_from_their_seed_individuals() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_seed_individuals("b", vec![1000, 1001])
+            .with_seed_individuals("b", vec![1002])
+            .build()
+            .unwrap();
+        world.fill_all_islands().unwrap();
+        assert!(world.get_island(0).unwrap().snapshot().individuals[0] < 1000);
+        assert_eq!(
+            vec![1000, 1001, 1002],
+            world.get_island(1).unwrap().snapshot().individuals[..3]
+        );
+
+        world.run_one_generation().unwrap();
+        let attribution = &world.stats_history()[0].score_attribution;
+        assert_eq!(Some(IndividualOrigin::Seed), attribution.best_origin);
+        assert_eq!(1002, attribution.seed_gain);
+
+        let too_many = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_seed_individuals("a", vec![1, 2, 3, 4, 5])
+            .build();
+        assert!(matches!(too_many, Err(GeneticError::TooManySeedIndividuals(name)) if name == "a"));
+        let unknown = world_builder(&["a"])
+            .with_migrating_individuals(1)
+            .with_seed_individuals("z", vec![1])
+            .build();
+        assert!(matches!(unknown, Err(GeneticError::UnknownIsland(name)) if name == "z"));
+    }
+
+    #[test]
+    fn islands_branch_from_a_copy_of_their_population() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_island_weight("a", 2.0)
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+
+        let index = world
+            .clone_island("a", "a-branch", Box::new(UnscoredEngine))
+            .unwrap();
+        assert_eq!(2, index);
+        let source = world.get_island(0).unwrap().snapshot();
+        let branch = world.get_island_by_name("a-branch").unwrap().snapshot();
+        assert_eq!(source.individuals, branch.individuals);
+        assert_eq!(source.sorted, branch.sorted);
+        assert_eq!(2.0, world.island_weight(2));
+        assert_eq!(Some(2), world.island_generation(2));
+
+        world.run_n_generations(2).unwrap();
+        assert_eq!(3, world.stats_history().last().unwrap().islands.len());
+        assert!(matches!(
+            world.clone_island("z", "c", Box::new(IdScoreEngine)),
+            Err(GeneticError::UnknownIsland(name)) if name == "z"
+        ));
+        assert!(matches!(
+            world.clone_island("a", "b", Box::new(IdScoreEngine)),
+            Err(GeneticError::DuplicateIslandName(name)) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn parent_curves_are_drawn_from_the_blend() {
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_parent_curve_blend(SelectionCurve::Fair, 0.0)
+            .with_parent_curve_blend(SelectionCurve::StrongPreferenceForUnfit, 1.0)
+            .build()
+            .unwrap();
+        assert!((0..100).all(|_| world.parent_curve() == SelectionCurve::StrongPreferenceForUnfit));
+        assert_eq!(2, world.configuration().parent_curve_blend.len());
+
+        let mut world = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_parent_curve_blend(SelectionCurve::Fair, 1.0)
+            .with_parent_curve_blend(SelectionCurve::StrongPreferenceForFit, 1.0)
+            .build()
+            .unwrap();
+        let curves: Vec<SelectionCurve> = (0..100).map(|_| world.parent_curve()).collect();
+        assert!(curves.contains(&SelectionCurve::Fair));
+        assert!(curves.contains(&SelectionCurve::StrongPreferenceForFit));
+
+        let zero = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_parent_curve_blend(SelectionCurve::Fair, 0.0)
+            .build();
+        assert!(matches!(zero, Err(GeneticError::InvalidParentCurveBlend)));
+        let negative = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_parent_curve_blend(SelectionCurve::Fair, 1.0)
+            .with_parent_curve_blend(SelectionCurve::PreferenceForFit, -1.0)
+            .build();
+        assert!(matches!(
+            negative,
+            Err(GeneticError::InvalidParentCurveBlend)
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod async_tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn generations_run_to_completion_when_awaited() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(10)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        block_on(world.run_n_generations(3)).unwrap();
+        assert_eq!(3, world.generation_count());
+        assert!(world.get_island(0).unwrap().is_sorted());
+        assert!(world.get_island(1).unwrap().is_sorted());
+    }
+
+    #[test]
+    fn awaited_generations_can_be_run_again_after_an_evaluation_error() {
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(10)
+            .with_elite_individuals(2)
+            .with_migrating_individuals(0);
+        builder.add_island(
+            "a",
+            Box::new(FailOnceEngine {
+                fail_on: 15,
+                runs: 0,
+            }),
+        );
+        let mut world = builder.build().unwrap();
+
+        let result = block_on(world.run_n_generations(3));
+        assert!(matches!(result, Err(GeneticError::EvaluationFailed { .. })));
+        assert_eq!(1, world.generation_count());
+
+        block_on(world.run_n_generations(2)).unwrap();
+        assert_eq!(3, world.generation_count());
+        assert_eq!(10, world.get_island(0).unwrap().len());
+    }
+}
@@ -1,9 +1,19 @@
+use std::path::PathBuf;
+
+use rand::rngs::StdRng; // cspell:disable-line
+
+use crate::curriculum::is_valid_curriculum;
+use crate::genetic_engine_builder::validate_operators;
 use crate::{
-    GeneticEngine, GeneticError, Genetics, Island, IslandEngine, MigrationAlgorithm,
-    SelectionCurve, World,
+    AdaptiveMigration, ChampionSink, CurriculumStage, EliteHandling, EmigrationThreshold,
+    GenerationRetry, GeneticEngine, GeneticError, Genetics, HallOfFame, Hypermutation,
+    Initialization, Island, IslandEngine, IslandEngineFactory, IslandInitializer, IslandQuarantine,
+    IslandRole, IslandSynchronization, MatingRestriction, MaybeSend, MigrantCount,
+    MigrationAlgorithm, MigrationShortfall, NoisyEvaluation, PopulationViability, RateLimit,
+    ScoreNormalization, SelectionCurve, Selector, StochasticRanking, Warmup, World, WorldObserver,
 };
 
-#[cfg(any(feature = "multi-threaded", feature = "async"))]
+#[cfg(feature = "multi-threaded")]
 use crate::ThreadingModel;
 
 pub struct WorldBuilder<G>
@@ -12,57 +22,331 @@ where
 {
     /// The number of individuals on each island. Before running a generation, the island will be filled with the
     /// children of genetic selection if there was a previous generation, or new random individuals if there was no
-    /// previous generation.
+    /// previous generation. Must be at least one. With a single individual, every child is bred from that individual
+    /// and itself.
     ///
     /// Default: 100
     pub individuals_per_island: usize,
 
     /// The number of individuals whose code will be copied as-is to the next generation. This can help preserve highly
-    /// fit code. Set to zero to disable elitism. ref https://en.wikipedia.org/wiki/Genetic_algorithm#Elitism
+    /// fit code. Set to zero to disable elitism. Must be less than `individuals_per_island` so that every generation
+    /// breeds at least one new child. ref https://en.wikipedia.org/wiki/Genetic_algorithm#Elitism
     ///
     /// Default: 2
     pub elite_individuals_per_generation: usize,
 
     /// After this many generations across all islands, some of the individual will migrate to new islands. Set to zero
-    /// to disable automatic migrations.
+    /// to disable automatic migrations. Migration is always skipped when the world has a single island.
     ///
     /// Default: 10
     pub generations_between_migrations: usize,
 
-    /// The number of individuals that will migrate from one island to another.
+    /// The number of individuals that will migrate from one island to another. Must not exceed
+    /// `individuals_per_island`.
     ///
     /// Default: 10
     pub number_of_individuals_migrating: usize,
 
+    /// Sizes each migration relative to the source island instead of using `number_of_individuals_migrating`, so the
+    /// configuration scales with islands of different or changing sizes.
+    ///
+    /// Default: None (MigrantCount::Fixed(number_of_individuals_migrating))
+    pub migrant_count: Option<MigrantCount>,
+
+    /// When set, each migration is sized by how far the islands have drifted apart, in place of the migrant count.
+    ///
+    /// Default: None
+    pub adaptive_migration: Option<AdaptiveMigration>,
+
     /// When it is time for a migration, a new island will be selected for the individual according to the specified
     /// algorithm.
     ///
     /// Default: MigrationAlgorithm::Circular
     pub migration_algorithm: MigrationAlgorithm,
 
+    /// Changes to the migration algorithm that take effect during the run. Each entry replaces the migration algorithm
+    /// once the world has run the specified number of generations, starting with the migration at the end of that
+    /// generation. Entries are applied in generation order.
+    ///
+    /// Default: empty
+    pub migration_schedule: Vec<(usize, MigrationAlgorithm)>,
+
+    /// Decides what happens when an island has fewer individuals available than `number_of_individuals_migrating`
+    /// at the time of a migration.
+    ///
+    /// Default: MigrationShortfall::MigrateAvailable
+    pub migration_shortfall: MigrationShortfall,
+
     /// If false, individuals selected for migration are removed from their home island. If true, the selected
     /// individuals are cloned and the clone is moved.
     ///
     /// Default: true
     pub clone_migrated_individuals: bool,
 
+    /// The number of most fit individuals on each island that cannot be removed by migration. Only applies when
+    /// `clone_migrated_individuals` is false; cloned migrants leave their originals behind, so nothing needs
+    /// protecting. The protected individuals are not counted as available to migrate.
+    ///
+    /// Default: 0
+    pub protected_from_emigration: usize,
+
+    /// Only individuals that pass the threshold may emigrate. Set to None to let any individual emigrate.
+    ///
+    /// Default: None
+    pub emigration_threshold: Option<EmigrationThreshold>,
+
     /// The SelectionCurve that will be used when choosing which individual will participate in migration.
     ///
     /// Default: SelectionCurve::PreferenceForFit
     pub select_for_migration: SelectionCurve,
 
+    /// If true, islands whose engine reports `IslandEngine::objectives` choose their emigrants from the least crowded
+    /// regions of their Pareto front instead of using `select_for_migration`. This spreads the whole front across the
+    /// world rather than only its most fit corner. Islands without objectives still use `select_for_migration`.
+    ///
+    /// Default: false
+    pub migrate_by_crowding_distance: bool,
+
+    /// When set, an island only admits a migrant whose `Genetics::distance` to every individual of its current
+    /// population, and to every migrant it has already admitted, is greater than this. Migrants that are turned away
+    /// stay on their home island, so migration injects diversity rather than near copies of what the destination
+    /// already has. Must be finite and not negative.
+    ///
+    /// Default: None
+    pub migrant_novelty_threshold: Option<f64>,
+
     /// The SelectionCurve that will be used when choosing a fit parent for genetic operations.
     ///
     /// Default: SelectionCurve::PreferenceForFit
     pub select_as_parent: SelectionCurve,
 
+    /// When not empty, parents are chosen with a weighted mixture of curves instead of `select_as_parent`. For each
+    /// brood, one curve is picked at random in proportion to its weight, and both parents are selected with it. For
+    /// example, `PreferenceForFit` with a weight of 0.7 and `Fair` with a weight of 0.3 mostly breeds from the fit but
+    /// keeps some uniform exploration. Weights must be finite and not negative, and at least one must be positive.
+    ///
+    /// Default: empty
+    pub parent_curve_blend: Vec<(SelectionCurve, f64)>,
+
     /// The SelectionCurve used when choosing an elite individual to preserve for the next generation.
     ///
     /// Default: SelectionCurve::StrongPreferenceForFit
     pub select_as_elite: SelectionCurve,
 
-    #[cfg(any(feature = "multi-threaded", feature = "async"))]
-    /// Determine how the world runs with regards to multi-threading.
+    /// How elites are scored in the generation they are copied into: evaluated again, or ranked by the score they were
+    /// selected with. See `EliteHandling`.
+    ///
+    /// Default: EliteHandling::CopyAndReevaluate
+    pub elite_handling: EliteHandling,
+
+    /// The selectors that `SelectionCurve::Custom` refers to, by index.
+    ///
+    /// Default: empty
+    pub selectors: Vec<Box<dyn Selector>>,
+
+    /// The number of children bred from each selected pair of parents (or single parent, for mutation-only island
+    /// roles). The last brood of a generation is smaller if the island does not have room for all of it. Must be at
+    /// least one.
+    ///
+    /// Default: 1
+    pub brood_size: usize,
+
+    /// If true, every brood is evaluated and then only its most fit sibling is kept in the current generation, so the
+    /// rest can never become parents, elites or migrants. The island shrinks by the removed siblings until it is
+    /// refilled. Engines that implement `IslandEngine::quick_score` pick the best sibling before evaluation instead,
+    /// so the island stays full and the rejected siblings are never run. Has no effect when `brood_size` is one.
+    ///
+    /// Default: false
+    pub brood_selection: bool,
+
+    /// If true, a bred child whose genome, as hashed by `Genetics::genome_hash`, is already in the next generation of
+    /// its island is dropped and another child is bred in its place. Elites and migrants are always kept. If the
+    /// genetics keep breeding duplicates, a duplicate is accepted after as many children in a row as the island holds,
+    /// so that a converged island can still be filled.
+    ///
+    /// Default: false
+    pub unique_genomes: bool,
+
+    /// When set, the second parent of each crossover is looked for among individuals close to the first.
+    ///
+    /// Default: None
+    pub mating_restriction: Option<MatingRestriction>,
+
+    /// When set, every individual is run and scored several times and ranked by the aggregate of its scores, for
+    /// fitness functions that are noisy.
+    ///
+    /// Default: None
+    pub noisy_evaluation: Option<NoisyEvaluation>,
+
+    /// When set, islands are ranked by stochastic ranking, which balances fitness against the constraint violations
+    /// reported by `IslandEngine::constraint_violation`, instead of by fitness alone.
+    ///
+    /// Default: None
+    pub stochastic_ranking: Option<StochasticRanking>,
+
+    /// When set, limits how often individuals are run across all islands, for engines that call an external service.
+    ///
+    /// Default: None
+    pub rate_limit: Option<RateLimit>,
+
+    /// When set, the world keeps its past champions and gives each island a sample of them as opponents every
+    /// generation, for competitive fitness.
+    ///
+    /// Default: None
+    pub hall_of_fame: Option<HallOfFame>,
+
+    /// How scores from different islands are compared when finding the world's most fit individual and ranking the
+    /// archive.
+    ///
+    /// Default: ScoreNormalization::Raw
+    pub score_normalization: ScoreNormalization,
+
+    /// The importance of each named island when choosing the world's most fit individual. Each island's normalized
+    /// score is multiplied by its weight, so that an island whose objective matters more can win against islands with
+    /// better scores on their own terms. Islands that are not listed have a weight of 1.0.
+    ///
+    /// Default: empty
+    pub island_weights: Vec<(String, f64)>,
+
+    /// Known-good individuals to start each named island from, such as the champions of a previous run or the output of
+    /// a heuristic. The first time an island is filled, its seed individuals are used in order before any random
+    /// individuals. An island may not have more seeds than `individuals_per_island`.
+    ///
+    /// Default: empty
+    pub seed_individuals: Vec<(String, Vec<u64>)>,
+
+    /// How the individuals of an island's first generation are created, after any seed individuals and the output of
+    /// its initializer.
+    ///
+    /// Default: Initialization::Random
+    pub initialization: Initialization,
+
+    /// Functions that create the first generation of each named island, in place of the `initialization` strategy.
+    /// Each is given the random number generator of the genetic engine and the number of individuals still needed
+    /// after the seed individuals. Individuals beyond that number are ignored, and any shortfall is made up by the
+    /// strategy.
+    ///
+    /// Default: empty
+    pub island_initializers: Vec<(String, IslandInitializer)>,
+
+    /// Functions that create the engines of the named islands when the world is built. See `add_island_with_factory`.
+    ///
+    /// Default: empty
+    pub island_engine_factories: Vec<(String, IslandEngineFactory)>,
+
+    /// How far islands may drift apart when they are advanced one at a time with `World::advance_island`.
+    ///
+    /// Default: IslandSynchronization::Lockstep
+    pub island_synchronization: IslandSynchronization,
+
+    /// When set, the mutation rate and points of the genetic engine are temporarily boosted after the world stops
+    /// finding better individuals.
+    ///
+    /// Default: None
+    pub hypermutation: Option<Hypermutation>,
+
+    /// When set, the first generations of the run use different selection curves and engine rates.
+    ///
+    /// Default: None
+    pub warmup: Option<Warmup>,
+
+    /// The stages of a curriculum, in the order the world moves through them. The world starts in the first stage and
+    /// records the active stage in its `GenerationStats`. Leave empty to run without a curriculum.
+    ///
+    /// Default: empty
+    pub curriculum: Vec<CurriculumStage>,
+
+    /// Named individuals, typically champions of earlier runs, that are re-evaluated by every island every
+    /// `reference_evaluation_interval` generations. Their scores are recorded in the `GenerationStats`, which shows
+    /// when a changing fitness environment stops rewarding solutions that used to be good.
+    ///
+    /// Default: empty
+    pub reference_individuals: Vec<(String, u64)>,
+
+    /// The number of generations between re-evaluations of the reference individuals. Set to zero to disable
+    /// re-evaluation.
+    ///
+    /// Default: 10
+    pub reference_evaluation_interval: usize,
+
+    /// The number of generations between measurements of the distances between islands, which are recorded in the
+    /// `GenerationStats`. See `World::island_distances`. Set to zero to disable the measurements.
+    ///
+    /// Default: 0
+    pub island_distance_interval: usize,
+
+    /// The maximum number of individuals in the world's `Archive`, which collects the best migrants from every
+    /// island. Set to zero to disable the archive.
+    ///
+    /// Default: 0
+    pub archive_capacity: usize,
+
+    /// Archived individuals closer than this distance, as measured by `Genetics::distance`, are considered duplicates
+    /// and only the most fit is kept.
+    ///
+    /// Default: 0.5 (with the default `Genetics::distance`, only copies of the same individual are duplicates)
+    pub archive_min_distance: f64,
+
+    /// Keeps the world running when an island repeatedly fails to evaluate its individuals. When None, the first
+    /// evaluation error is returned from `World::run_one_generation`, and the island starts over from random
+    /// individuals in the next generation.
+    ///
+    /// Default: None
+    pub island_quarantine: Option<IslandQuarantine>,
+
+    /// Checks that every island has enough individuals to breed from before its next generation is filled. When None,
+    /// islands breed from however many individuals they have.
+    ///
+    /// Default: None
+    pub population_viability: Option<PopulationViability>,
+
+    /// Evaluates an island's generation again after a transient failure, before the failure is returned or handled by
+    /// the `island_quarantine`. When None, failures are never retried.
+    ///
+    /// Default: None
+    pub generation_retry: Option<GenerationRetry>,
+
+    /// Writes a checkpoint of the world to the path after every this many generations. Resume from it with
+    /// `World::resume_from`. Set to None to disable.
+    ///
+    /// Default: None
+    pub checkpoint_every: Option<(usize, PathBuf)>,
+
+    /// The number of automatic checkpoints to keep. The newest is always at the configured path, and older ones are
+    /// moved to the same path with `.1`, `.2` and so on appended, so a checkpoint that turns out to be bad can be
+    /// skipped. Must be at least one.
+    ///
+    /// Default: 1
+    pub checkpoints_to_keep: usize,
+
+    /// If true, the world calls `Genetics::collect_garbage` after every generation, so that genetics which keep their
+    /// genomes in an `IndividualStore` or allocate ids from an `IdAllocator` can release the individuals the world no
+    /// longer references. Snapshots and checkpoints save individuals by id only, so a snapshot taken before some of
+    /// its individuals were collected can no longer be restored, and `World::restore` returns
+    /// `GeneticError::MissingIndividual` for it. Disable collection when runs are rolled back to older snapshots.
+    ///
+    /// Default: true
+    pub garbage_collection: bool,
+
+    /// Receives the champion of the world each time it improves, subject to the sink's throttling. Set to None to
+    /// disable.
+    ///
+    /// Default: None
+    pub champion_sink: Option<ChampionSink>,
+
+    /// If true, the world keeps an `EventLog` of every change to its populations, which can rebuild the population of
+    /// any generation. The log grows with every generation, so it is meant for runs that will be analyzed afterwards.
+    ///
+    /// Default: false
+    pub event_log: bool,
+
+    /// Observers notified of notable events while the world runs.
+    ///
+    /// Default: empty
+    pub observers: Vec<Box<dyn WorldObserver>>,
+
+    #[cfg(feature = "multi-threaded")]
+    /// Determine how the world runs with regards to multi-threading. See `ThreadingModel`.
     ///
     /// Default: ThreadingModel::None
     pub threading_model: ThreadingModel,
@@ -70,7 +354,7 @@ where
     /// The genetic engine that will be used to perform genetic operations.
     pub genetic_engine: Option<GeneticEngine<G>>,
 
-    /// The islands that exist in the world. At least one is required.
+    /// The islands that exist in the world. At least one is required and every island name must be unique.
     pub islands: Vec<Island>,
 }
 
@@ -84,12 +368,55 @@ where
             elite_individuals_per_generation: 2,
             generations_between_migrations: 10,
             number_of_individuals_migrating: 10,
+            migrant_count: None,
+            adaptive_migration: None,
             migration_algorithm: MigrationAlgorithm::Circular,
+            migration_schedule: vec![],
+            migration_shortfall: MigrationShortfall::MigrateAvailable,
             clone_migrated_individuals: true,
+            protected_from_emigration: 0,
+            emigration_threshold: None,
             select_for_migration: SelectionCurve::PreferenceForFit,
+            migrate_by_crowding_distance: false,
+            migrant_novelty_threshold: None,
             select_as_parent: SelectionCurve::PreferenceForFit,
+            parent_curve_blend: vec![],
             select_as_elite: SelectionCurve::StrongPreferenceForFit,
-            #[cfg(any(feature = "multi-threaded", feature = "async"))]
+            elite_handling: EliteHandling::default(),
+            selectors: vec![],
+            brood_size: 1,
+            brood_selection: false,
+            unique_genomes: false,
+            mating_restriction: None,
+            noisy_evaluation: None,
+            stochastic_ranking: None,
+            rate_limit: None,
+            hall_of_fame: None,
+            score_normalization: ScoreNormalization::Raw,
+            island_weights: vec![],
+            seed_individuals: vec![],
+            initialization: Initialization::Random,
+            island_initializers: vec![],
+            island_engine_factories: vec![],
+            island_synchronization: IslandSynchronization::Lockstep,
+            hypermutation: None,
+            warmup: None,
+            curriculum: vec![],
+            reference_individuals: vec![],
+            reference_evaluation_interval: 10,
+            island_distance_interval: 0,
+            archive_capacity: 0,
+            archive_min_distance: 0.5,
+            island_quarantine: None,
+            population_viability: None,
+            generation_retry: None,
+            checkpoint_every: None,
+            checkpoints_to_keep: 1,
+            garbage_collection: true,
+            champion_sink: None,
+            event_log: false,
+            observers: vec![],
+            #[cfg(feature = "multi-threaded")]
             threading_model: ThreadingModel::None,
             genetic_engine: None,
             islands: vec![],
@@ -125,32 +452,254 @@ where
         self
     }
 
+    pub fn with_migrant_count(mut self, count: MigrantCount) -> Self {
+        self.migrant_count = Some(count);
+        self
+    }
+
+    pub fn with_adaptive_migration(mut self, adaptive_migration: AdaptiveMigration) -> Self {
+        self.adaptive_migration = Some(adaptive_migration);
+        self
+    }
+
     pub fn with_migration_algorithm(mut self, algorithm: MigrationAlgorithm) -> Self {
         self.migration_algorithm = algorithm;
         self
     }
 
+    /// Switches to `algorithm` once the world has run `generation` generations
+    pub fn with_migration_algorithm_at(
+        mut self,
+        generation: usize,
+        algorithm: MigrationAlgorithm,
+    ) -> Self {
+        self.migration_schedule.push((generation, algorithm));
+        self
+    }
+
+    pub fn with_migration_shortfall(mut self, shortfall: MigrationShortfall) -> Self {
+        self.migration_shortfall = shortfall;
+        self
+    }
+
     pub fn with_clone_migrated_individuals(mut self, clone: bool) -> Self {
         self.clone_migrated_individuals = clone;
         self
     }
 
+    pub fn with_protected_from_emigration(mut self, individuals: usize) -> Self {
+        self.protected_from_emigration = individuals;
+        self
+    }
+
+    pub fn with_emigration_threshold(mut self, threshold: EmigrationThreshold) -> Self {
+        self.emigration_threshold = Some(threshold);
+        self
+    }
+
     pub fn with_select_for_migration(mut self, curve: SelectionCurve) -> Self {
         self.select_for_migration = curve;
         self
     }
 
+    pub fn with_migrate_by_crowding_distance(mut self, enabled: bool) -> Self {
+        self.migrate_by_crowding_distance = enabled;
+        self
+    }
+
+    pub fn with_migrant_novelty_threshold(mut self, threshold: f64) -> Self {
+        self.migrant_novelty_threshold = Some(threshold);
+        self
+    }
+
     pub fn with_select_as_parent(mut self, curve: SelectionCurve) -> Self {
         self.select_as_parent = curve;
         self
     }
 
+    pub fn with_parent_curve_blend(mut self, curve: SelectionCurve, weight: f64) -> Self {
+        self.parent_curve_blend.push((curve, weight));
+        self
+    }
+
     pub fn with_select_as_elite(mut self, curve: SelectionCurve) -> Self {
         self.select_as_elite = curve;
         self
     }
 
-    #[cfg(any(feature = "multi-threaded", feature = "async"))]
+    pub fn with_elite_handling(mut self, elite_handling: EliteHandling) -> Self {
+        self.elite_handling = elite_handling;
+        self
+    }
+
+    /// Registers a selector, which is used wherever the world is given `SelectionCurve::Custom` with the number of
+    /// selectors registered before it
+    pub fn with_selector<S: Selector + 'static>(mut self, selector: S) -> Self {
+        self.selectors.push(Box::new(selector));
+        self
+    }
+
+    pub fn with_brood_size(mut self, size: usize) -> Self {
+        self.brood_size = size;
+        self
+    }
+
+    pub fn with_brood_selection(mut self, enabled: bool) -> Self {
+        self.brood_selection = enabled;
+        self
+    }
+
+    pub fn with_unique_genomes(mut self, enabled: bool) -> Self {
+        self.unique_genomes = enabled;
+        self
+    }
+
+    pub fn with_mating_restriction(mut self, restriction: MatingRestriction) -> Self {
+        self.mating_restriction = Some(restriction);
+        self
+    }
+
+    pub fn with_noisy_evaluation(mut self, noisy_evaluation: NoisyEvaluation) -> Self {
+        self.noisy_evaluation = Some(noisy_evaluation);
+        self
+    }
+
+    pub fn with_stochastic_ranking(mut self, ranking: StochasticRanking) -> Self {
+        self.stochastic_ranking = Some(ranking);
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    pub fn with_hall_of_fame(mut self, hall_of_fame: HallOfFame) -> Self {
+        self.hall_of_fame = Some(hall_of_fame);
+        self
+    }
+
+    pub fn with_score_normalization(mut self, normalization: ScoreNormalization) -> Self {
+        self.score_normalization = normalization;
+        self
+    }
+
+    pub fn with_island_weight<S: Into<String>>(mut self, island: S, weight: f64) -> Self {
+        self.island_weights.push((island.into(), weight));
+        self
+    }
+
+    pub fn with_seed_individuals<S: Into<String>>(
+        mut self,
+        island: S,
+        individuals: Vec<u64>,
+    ) -> Self {
+        self.seed_individuals.push((island.into(), individuals));
+        self
+    }
+
+    pub fn with_initialization(mut self, initialization: Initialization) -> Self {
+        self.initialization = initialization;
+        self
+    }
+
+    pub fn with_island_initializer<S, F>(mut self, island: S, initializer: F) -> Self
+    where
+        S: Into<String>,
+        F: FnMut(&mut StdRng, usize) -> Vec<u64> + MaybeSend + 'static,
+    {
+        self.island_initializers
+            .push((island.into(), Box::new(initializer)));
+        self
+    }
+
+    pub fn with_island_synchronization(mut self, synchronization: IslandSynchronization) -> Self {
+        self.island_synchronization = synchronization;
+        self
+    }
+
+    pub fn with_hypermutation(mut self, hypermutation: Hypermutation) -> Self {
+        self.hypermutation = Some(hypermutation);
+        self
+    }
+
+    pub fn with_warmup(mut self, warmup: Warmup) -> Self {
+        self.warmup = Some(warmup);
+        self
+    }
+
+    pub fn with_curriculum_stage(mut self, stage: CurriculumStage) -> Self {
+        self.curriculum.push(stage);
+        self
+    }
+
+    pub fn with_island_distance_interval(mut self, generations: usize) -> Self {
+        self.island_distance_interval = generations;
+        self
+    }
+
+    pub fn with_checkpoint_every<P: Into<PathBuf>>(mut self, generations: usize, path: P) -> Self {
+        self.checkpoint_every = Some((generations, path.into()));
+        self
+    }
+
+    pub fn with_checkpoints_to_keep(mut self, checkpoints: usize) -> Self {
+        self.checkpoints_to_keep = checkpoints;
+        self
+    }
+
+    pub fn with_garbage_collection(mut self, enabled: bool) -> Self {
+        self.garbage_collection = enabled;
+        self
+    }
+
+    pub fn with_champion_sink(mut self, sink: ChampionSink) -> Self {
+        self.champion_sink = Some(sink);
+        self
+    }
+
+    pub fn with_event_log(mut self, enabled: bool) -> Self {
+        self.event_log = enabled;
+        self
+    }
+
+    pub fn with_reference_individual<S: Into<String>>(mut self, name: S, id: u64) -> Self {
+        self.reference_individuals.push((name.into(), id));
+        self
+    }
+
+    pub fn with_reference_evaluation_interval(mut self, generations: usize) -> Self {
+        self.reference_evaluation_interval = generations;
+        self
+    }
+
+    pub fn with_archive(mut self, capacity: usize, min_distance: f64) -> Self {
+        self.archive_capacity = capacity;
+        self.archive_min_distance = min_distance;
+        self
+    }
+
+    pub fn with_island_quarantine(mut self, quarantine: IslandQuarantine) -> Self {
+        self.island_quarantine = Some(quarantine);
+        self
+    }
+
+    pub fn with_population_viability(mut self, viability: PopulationViability) -> Self {
+        self.population_viability = Some(viability);
+        self
+    }
+
+    pub fn with_generation_retry(mut self, retry: GenerationRetry) -> Self {
+        self.generation_retry = Some(retry);
+        self
+    }
+
+    pub fn with_observer<O: WorldObserver + 'static>(mut self, observer: O) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    #[cfg(feature = "multi-threaded")]
     pub fn with_threading_model(mut self, model: ThreadingModel) -> Self {
         self.threading_model = model;
         self
@@ -166,11 +715,36 @@ where
         name: S,
         engine: Box<dyn IslandEngine>,
     ) -> &mut Self {
-        self.islands.push(Island::new(name, engine));
+        self.add_island_with_role(name, IslandRole::Standard, engine)
+    }
+
+    /// Adds an island whose breeding behavior is determined by its role rather than the world configuration
+    pub fn add_island_with_role<S: Into<String>>(
+        &mut self,
+        name: S,
+        role: IslandRole,
+        engine: Box<dyn IslandEngine>,
+    ) -> &mut Self {
+        self.islands.push(Island::new(name, role, engine));
         self
     }
 
-    pub fn build(self) -> Result<World<G>, GeneticError> {
+    /// Adds an island whose engine is created by `factory` only once `build` has validated the configuration, for
+    /// engines that cannot be constructed up front, such as ones that need remote workers to connect first or a
+    /// resource pool sized for the whole world. If the factory fails, `build` returns
+    /// `GeneticError::IslandEngineUnavailable`.
+    pub fn add_island_with_factory<S, F>(&mut self, name: S, factory: F) -> &mut Self
+    where
+        S: Into<String>,
+        F: FnOnce() -> Result<Box<dyn IslandEngine>, String> + MaybeSend + 'static,
+    {
+        let name = name.into();
+        self.island_engine_factories
+            .push((name.clone(), Box::new(factory)));
+        self.add_island(name, Box::new(PendingEngine))
+    }
+
+    pub fn build(mut self) -> Result<World<G>, GeneticError> {
         // Validate configuration
         if self.individuals_per_island == 0 {
             return Err(GeneticError::InvalidIndividualsPerIsland);
@@ -180,14 +754,324 @@ where
             return Err(GeneticError::InvalidEliteCount);
         }
 
-        if self.number_of_individuals_migrating > self.individuals_per_island {
-            return Err(GeneticError::InvalidMigrationCount);
+        match self.migrant_count {
+            None if self.number_of_individuals_migrating > self.individuals_per_island => {
+                return Err(GeneticError::InvalidMigrationCount);
+            }
+            Some(MigrantCount::Fixed(count)) if count > self.individuals_per_island => {
+                return Err(GeneticError::InvalidMigrationCount);
+            }
+            Some(count) if !count.is_valid() => return Err(GeneticError::InvalidMigrantCount),
+            _ => {}
         }
 
-        if self.genetic_engine.is_none() {
+        if let Some(adaptive_migration) = &self.adaptive_migration {
+            if !adaptive_migration.is_valid()
+                || adaptive_migration.max_migrants > self.individuals_per_island
+            {
+                return Err(GeneticError::InvalidAdaptiveMigration);
+            }
+        }
+
+        if let Some(noisy) = &self.noisy_evaluation {
+            let adaptive_is_valid = noisy.adaptive.as_ref().is_none_or(|adaptive| {
+                adaptive.max_evaluations >= noisy.evaluations && adaptive.confidence >= 0.0
+            });
+            if noisy.evaluations == 0 || !adaptive_is_valid {
+                return Err(GeneticError::InvalidNoisyEvaluation);
+            }
+        }
+
+        if let Some(restriction) = &self.mating_restriction {
+            if !restriction.is_valid() {
+                return Err(GeneticError::InvalidMatingRestriction);
+            }
+        }
+
+        if let Some(rate_limit) = &self.rate_limit {
+            if !rate_limit.is_valid() {
+                return Err(GeneticError::InvalidRateLimit);
+            }
+        }
+
+        if let Some(hall_of_fame) = &self.hall_of_fame {
+            if !hall_of_fame.is_valid() {
+                return Err(GeneticError::InvalidHallOfFame);
+            }
+        }
+
+        if let Some(ranking) = &self.stochastic_ranking {
+            if !ranking.is_valid() {
+                return Err(GeneticError::InvalidStochasticRanking);
+            }
+        }
+
+        if self.brood_size == 0 {
+            return Err(GeneticError::InvalidBroodSize);
+        }
+
+        if let Some(hypermutation) = &self.hypermutation {
+            if !hypermutation.is_valid() {
+                return Err(GeneticError::InvalidHypermutation);
+            }
+        }
+
+        if let Some(quarantine) = &self.island_quarantine {
+            if !quarantine.is_valid() {
+                return Err(GeneticError::InvalidIslandQuarantine);
+            }
+        }
+
+        if let Some(viability) = &self.population_viability {
+            if !viability.is_valid(self.individuals_per_island) {
+                return Err(GeneticError::InvalidPopulationViability);
+            }
+        }
+
+        if let Some(retry) = &self.generation_retry {
+            if !retry.is_valid() {
+                return Err(GeneticError::InvalidGenerationRetry);
+            }
+        }
+
+        if self
+            .checkpoint_every
+            .as_ref()
+            .is_some_and(|(generations, _)| *generations == 0)
+        {
+            return Err(GeneticError::InvalidCheckpointInterval);
+        }
+
+        if self.checkpoints_to_keep == 0 {
+            return Err(GeneticError::InvalidCheckpointsToKeep);
+        }
+
+        if self
+            .emigration_threshold
+            .is_some_and(|threshold| !threshold.is_valid())
+        {
+            return Err(GeneticError::InvalidEmigrationThreshold);
+        }
+
+        if !self.parent_curve_blend.is_empty() {
+            let weights_are_valid = self
+                .parent_curve_blend
+                .iter()
+                .all(|(_, weight)| weight.is_finite() && *weight >= 0.0);
+            if !weights_are_valid
+                || self
+                    .parent_curve_blend
+                    .iter()
+                    .all(|(_, weight)| *weight == 0.0)
+            {
+                return Err(GeneticError::InvalidParentCurveBlend);
+            }
+        }
+
+        let warmup_curves = self.warmup.iter().flat_map(|warmup| {
+            [
+                warmup.select_as_parent,
+                warmup.select_as_elite,
+                warmup.select_for_migration,
+            ]
+        });
+        let curves = [
+            self.select_for_migration,
+            self.select_as_parent,
+            self.select_as_elite,
+        ]
+        .into_iter()
+        .chain(self.parent_curve_blend.iter().map(|&(curve, _)| curve))
+        .chain(warmup_curves.flatten());
+        for curve in curves {
+            if let SelectionCurve::Custom(index) = curve {
+                if index >= self.selectors.len() {
+                    return Err(GeneticError::UnknownSelector(index));
+                }
+            }
+        }
+
+        if self
+            .migrant_novelty_threshold
+            .is_some_and(|threshold| !threshold.is_finite() || threshold < 0.0)
+        {
+            return Err(GeneticError::InvalidMigrantNoveltyThreshold);
+        }
+
+        if !self.initialization.is_valid() {
+            return Err(GeneticError::InvalidInitialization);
+        }
+
+        if let Some(sink) = &self.champion_sink {
+            if !sink.is_valid() {
+                return Err(GeneticError::InvalidChampionSink);
+            }
+        }
+
+        let Some(engine) = &self.genetic_engine else {
             return Err(GeneticError::MissingGeneticEngine);
+        };
+
+        if !is_valid_curriculum(&self.curriculum) {
+            return Err(GeneticError::InvalidCurriculum);
+        }
+        // Each stage keeps the rates of the one before it unless it overrides them
+        let mut first_stage_rates = None;
+        let (mut mutation_rate, mut crossover_rate) =
+            (engine.mutation_rate(), engine.crossover_rate());
+        for stage in self.curriculum.iter() {
+            mutation_rate = stage.mutation_rate.unwrap_or(mutation_rate);
+            crossover_rate = stage.crossover_rate.unwrap_or(crossover_rate);
+            validate_operators(
+                mutation_rate,
+                crossover_rate,
+                engine.max_mutation_points(),
+                engine.max_crossover_points(),
+                engine.extra_weight(),
+            )?;
+            first_stage_rates.get_or_insert((mutation_rate, crossover_rate));
+        }
+
+        // The warmup starts from the rates of the first curriculum stage
+        if let Some(warmup) = &self.warmup {
+            let (mutation_rate, crossover_rate) =
+                first_stage_rates.unwrap_or((engine.mutation_rate(), engine.crossover_rate()));
+            validate_operators(
+                warmup.mutation_rate.unwrap_or(mutation_rate),
+                warmup.crossover_rate.unwrap_or(crossover_rate),
+                engine.max_mutation_points(),
+                engine.max_crossover_points(),
+                engine.extra_weight(),
+            )?;
+        }
+
+        if self.islands.is_empty() {
+            return Err(GeneticError::MissingIslands);
+        }
+
+        for (index, island) in self.islands.iter().enumerate() {
+            if self.islands[..index]
+                .iter()
+                .any(|other| other.name() == island.name())
+            {
+                return Err(GeneticError::DuplicateIslandName(island.name().to_string()));
+            }
+        }
+
+        for (name, weight) in self.island_weights.iter() {
+            let known = self.islands.iter().any(|island| island.name() == name);
+            if !known || !weight.is_finite() || *weight < 0.0 {
+                return Err(GeneticError::InvalidIslandWeight(name.clone()));
+            }
+        }
+
+        for island in self.islands.iter() {
+            let seeds: usize = self
+                .seed_individuals
+                .iter()
+                .filter(|(name, _)| name == island.name())
+                .map(|(_, individuals)| individuals.len())
+                .sum();
+            if seeds > self.individuals_per_island {
+                return Err(GeneticError::TooManySeedIndividuals(
+                    island.name().to_string(),
+                ));
+            }
+        }
+        let is_unknown = |name: &String| !self.islands.iter().any(|island| island.name() == name);
+        let unknown = self
+            .seed_individuals
+            .iter()
+            .map(|(name, _)| name)
+            .chain(self.island_initializers.iter().map(|(name, _)| name))
+            .find(|name| is_unknown(name));
+        if let Some(name) = unknown {
+            return Err(GeneticError::UnknownIsland(name.clone()));
+        }
+
+        for (name, factory) in self.island_engine_factories.drain(..) {
+            let engine = factory().map_err(|message| GeneticError::IslandEngineUnavailable {
+                island: name.clone(),
+                message,
+            })?;
+            if let Some(island) = self.islands.iter_mut().find(|island| island.name() == name) {
+                island.replace_engine(engine);
+            }
         }
 
         Ok(World::new(self))
     }
 }
+
+// Stands in for the engine of an island until its factory is called by `build`
+struct PendingEngine;
+
+impl IslandEngine for PendingEngine {
+    fn run_individual(&mut self, _id: u64) {
+        unreachable!("the island's engine factory has not been called");
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn at_least_one_island_is_required() {
+        let result = world_builder(&[]).build();
+
+        assert!(matches!(result, Err(GeneticError::MissingIslands)));
+    }
+
+    #[test]
+    fn island_names_must_be_unique() {
+        let result = world_builder(&["a", "b", "a"]).build();
+
+        assert!(matches!(result, Err(GeneticError::DuplicateIslandName(name)) if name == "a"));
+    }
+
+    #[test]
+    fn elites_must_leave_room_for_children() {
+        let result = world_builder(&["a"])
+            .with_individuals_per_island(1)
+            .with_elite_individuals(1)
+            .build();
+
+        assert!(matches!(result, Err(GeneticError::InvalidEliteCount)));
+    }
+
+    #[test]
+    fn island_engine_factories_are_called_by_build() {
+        let created = Arc::new(AtomicBool::new(false));
+        let mut builder = world_builder(&["a"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1);
+        let flag = created.clone();
+        builder.add_island_with_factory("deferred", move || {
+            flag.store(true, Ordering::Relaxed);
+            Ok(Box::new(IdScoreEngine) as Box<dyn IslandEngine>)
+        });
+        assert!(!created.load(Ordering::Relaxed));
+
+        let mut world = builder.build().unwrap();
+        assert!(created.load(Ordering::Relaxed));
+        world.run_n_generations(1).unwrap();
+        assert_eq!(
+            Some("deferred"),
+            world.get_island(1).map(|island| island.name())
+        );
+        assert!(world.get_island(1).unwrap().most_fit_individual().is_some());
+
+        let mut builder = world_builder(&["a"]);
+        builder.add_island_with_factory("remote", || Err("no workers connected".to_string()));
+        assert!(matches!(
+            builder.build(),
+            Err(GeneticError::IslandEngineUnavailable { island, message })
+                if island == "remote" && message == "no workers connected"
+        ));
+    }
+}
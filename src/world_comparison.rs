@@ -0,0 +1,231 @@
+use rand::rngs::StdRng; // cspell:disable-line
+use rand::{Rng, SeedableRng};
+
+use crate::{GeneticError, Genetics, World};
+
+/// Compares two configurations of a world by running them side by side under the same randomness. Every generation of
+/// both worlds is filled from the same generation seed, drawn from `seed`, and by default the candidate starts from
+/// the baseline's first generation, so that differences in the best scores come from the configurations rather than
+/// from luck.
+///
+/// Sharing the first generation copies individual ids from the baseline to the candidate, so the two worlds must
+/// understand the same ids: either their genetics share the genome storage, or they are deterministic and create the
+/// same individuals from the same seed. Candidate islands are matched to baseline islands by name, and candidate
+/// islands without a match are filled normally.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorldComparison {
+    /// The number of generations to run each world
+    ///
+    /// Default: 10
+    pub generations: usize,
+
+    /// The seed the generation seeds of both worlds are drawn from
+    ///
+    /// Default: 0
+    pub seed: u64,
+
+    /// Whether the candidate starts from a copy of the baseline's first generation
+    ///
+    /// Default: true
+    pub share_initial_population: bool,
+}
+
+/// The best scores of both worlds after one generation of a `WorldComparison`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenerationComparison {
+    /// The generation count of both worlds after the generation ran
+    pub generation: usize,
+
+    /// The seed both worlds were filled from
+    pub seed: u64,
+
+    /// The best score of the baseline world this generation
+    pub baseline_best: Option<u64>,
+
+    /// The best score of the candidate world this generation
+    pub candidate_best: Option<u64>,
+}
+
+/// The result of a `WorldComparison`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorldComparisonReport {
+    /// Every generation that was compared, in order
+    pub generations: Vec<GenerationComparison>,
+}
+
+impl Default for WorldComparison {
+    fn default() -> Self {
+        WorldComparison {
+            generations: 10,
+            seed: 0,
+            share_initial_population: true,
+        }
+    }
+}
+
+impl WorldComparison {
+    pub fn with_generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_share_initial_population(mut self, share: bool) -> Self {
+        self.share_initial_population = share;
+        self
+    }
+
+    /// Runs both worlds in lockstep and reports their best score after every generation. Both worlds are left as they
+    /// were after the last generation, so that they can be inspected further.
+    pub fn run<G, H>(
+        &self,
+        baseline: &mut World<G>,
+        candidate: &mut World<H>,
+    ) -> Result<WorldComparisonReport, GeneticError>
+    where
+        G: Genetics,
+        H: Genetics,
+    {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut report = WorldComparisonReport::default();
+        for generation in 0..self.generations {
+            let seed = rng.random();
+            baseline.set_next_generation_seed(seed);
+            baseline.fill_all_islands()?;
+            candidate.set_next_generation_seed(seed);
+            candidate.fill_all_islands()?;
+            if generation == 0 && self.share_initial_population {
+                share_population(baseline, candidate);
+            }
+            baseline.run_one_generation()?;
+            candidate.run_one_generation()?;
+
+            report.generations.push(GenerationComparison {
+                generation: baseline.generation_count(),
+                seed,
+                baseline_best: baseline
+                    .stats_history()
+                    .last()
+                    .and_then(|stats| stats.best_score),
+                candidate_best: candidate
+                    .stats_history()
+                    .last()
+                    .and_then(|stats| stats.best_score),
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+impl WorldComparisonReport {
+    /// Returns the first generation where the best scores of the two worlds differed
+    pub fn first_divergence(&self) -> Option<usize> {
+        self.generations
+            .iter()
+            .find(|comparison| comparison.baseline_best != comparison.candidate_best)
+            .map(|comparison| comparison.generation)
+    }
+
+    /// Returns the number of generations in which the candidate had the better best score
+    pub fn candidate_leads(&self) -> usize {
+        self.generations
+            .iter()
+            .filter(|comparison| comparison.candidate_best > comparison.baseline_best)
+            .count()
+    }
+
+    /// Returns the number of generations in which the baseline had the better best score
+    pub fn baseline_leads(&self) -> usize {
+        self.generations
+            .iter()
+            .filter(|comparison| comparison.baseline_best > comparison.candidate_best)
+            .count()
+    }
+}
+
+// Gives every candidate island the population of the baseline island of the same name
+fn share_population<G: Genetics, H: Genetics>(baseline: &World<G>, candidate: &mut World<H>) {
+    for index in 0..candidate.get_number_of_islands() {
+        let island = candidate.get_island_mut(index).unwrap();
+        if let Some(source) = baseline.get_island_by_name(island.name()) {
+            island.restore(&source.snapshot());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn worlds_are_compared_under_the_same_randomness() {
+        let build = |elites: usize| {
+            world_builder(&["a", "b"])
+                .with_individuals_per_island(6)
+                .with_migrating_individuals(1)
+                .with_elite_individuals(elites)
+                .build()
+                .unwrap()
+        };
+        let comparison = WorldComparison::default().with_generations(5);
+
+        let report = comparison.run(&mut build(2), &mut build(2)).unwrap();
+        assert_eq!(5, report.generations.len());
+        assert_eq!(None, report.first_divergence());
+        assert_eq!((0, 0), (report.baseline_leads(), report.candidate_leads()));
+
+        // Both worlds start from the same population, and only diverge once the candidate breeds more new, and so
+        // higher scoring, children without elites
+        let report = comparison.run(&mut build(2), &mut build(0)).unwrap();
+        assert_eq!(Some(2), report.first_divergence());
+        assert_eq!((0, 4), (report.baseline_leads(), report.candidate_leads()));
+    }
+
+    #[test]
+    fn empty_comparisons_failures_and_missing_scores() {
+        let mut baseline = world_builder(&["a"]).build().unwrap();
+        let mut candidate = world_builder(&["a"]).build().unwrap();
+
+        // Comparing no generations leaves both worlds untouched
+        let report = WorldComparison::default()
+            .with_generations(0)
+            .run(&mut baseline, &mut candidate)
+            .unwrap();
+        assert_eq!(WorldComparisonReport::default(), report);
+        assert_eq!(None, report.first_divergence());
+        assert_eq!((0, 0), (report.baseline_leads(), report.candidate_leads()));
+        assert_eq!(0, baseline.generation_count());
+
+        // A candidate that fails to evaluate stops the comparison
+        let mut builder = WorldBuilder::new().with_genetic_engine(counting_engine(1234));
+        builder.add_island("a", Box::new(FailingEngine));
+        let mut failing = builder.build().unwrap();
+        assert!(matches!(
+            WorldComparison::default().run(&mut baseline, &mut failing),
+            Err(GeneticError::EvaluationFailed { .. })
+        ));
+
+        // A generation without a best score trails any generation with one
+        let comparison = |generation, baseline_best, candidate_best| GenerationComparison {
+            generation,
+            seed: 0,
+            baseline_best,
+            candidate_best,
+        };
+        let report = WorldComparisonReport {
+            generations: vec![
+                comparison(1, None, None),
+                comparison(2, None, Some(0)),
+                comparison(3, Some(0), None),
+            ],
+        };
+        assert_eq!(Some(2), report.first_divergence());
+        assert_eq!((1, 1), (report.baseline_leads(), report.candidate_leads()));
+    }
+}
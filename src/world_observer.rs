@@ -0,0 +1,52 @@
+use crate::{GeneticError, MaybeSend, WorldPhase};
+
+/// Receives notifications about notable events while a world runs. Every method has a default implementation that does
+/// nothing, so observers only implement the events they care about. Observers are added with
+/// `WorldBuilder::with_observer`.
+pub trait WorldObserver: MaybeSend {
+    /// Called after an island has been filled, with the individuals that were copied into the new generation as elites.
+    /// `generation` is the number of generations completed before the new one.
+    fn elites_selected(&mut self, _generation: usize, _island: usize, _elites: &[u64]) {}
+
+    /// Called when an island fails to evaluate its generation and an `IslandQuarantine` policy kept the world running.
+    /// `generation` is the number of generations completed before the failed one.
+    fn evaluation_failed(&mut self, _generation: usize, _island: usize, _error: &GeneticError) {}
+
+    /// Called when an island's generation failed with a transient error and a `GenerationRetry` policy is about to
+    /// evaluate it again. `attempt` counts the retries of this generation, starting at 1.
+    fn evaluation_retried(
+        &mut self,
+        _generation: usize,
+        _island: usize,
+        _attempt: usize,
+        _error: &GeneticError,
+    ) {
+    }
+
+    /// Called when an island is placed in quarantine after repeated evaluation failures
+    fn island_quarantined(&mut self, _generation: usize, _island: usize, _error: &GeneticError) {}
+
+    /// Called each time the world moves to a different phase. `generation` is the number of generations completed.
+    fn phase_changed(&mut self, _generation: usize, _phase: WorldPhase) {}
+
+    /// Called when the world enters a new `CurriculumStage`, including the first stage when the world is built.
+    /// `generation` is the number of generations completed before the stage starts.
+    fn curriculum_stage_started(&mut self, _generation: usize, _stage: usize, _name: &str) {}
+
+    /// Called when the world is shut down with `World::shutdown`, or dropped after running, once the champion sink and
+    /// the final checkpoint have been flushed. Observers that write metrics should flush them here.
+    fn world_shut_down(&mut self, _generation: usize) {}
+
+    /// Called when an island is about to breed its next generation from fewer individuals than the minimum of a
+    /// `PopulationViability` check that warns. `individuals` is the size of its current generation.
+    fn population_below_minimum(
+        &mut self,
+        _generation: usize,
+        _island: usize,
+        _individuals: usize,
+    ) {
+    }
+
+    /// Called when an island has served its quarantine. It starts over from random individuals in the next generation.
+    fn island_released(&mut self, _generation: usize, _island: usize) {}
+}
@@ -0,0 +1,99 @@
+/// What a world is doing. Observers are told about every change through `WorldObserver::phase_changed`, and the current
+/// phase is available from `World::phase`, which allows a monitoring UI to show the progress of a long-running world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorldPhase {
+    /// The world is between steps, waiting to be driven
+    #[default]
+    Idle,
+
+    /// The next generation of the islands is being bred
+    Filling,
+
+    /// The individuals of an island are being run by its engine
+    Evaluating,
+
+    /// The individuals of an island are being ranked after they ran
+    Sorting,
+
+    /// Individuals are moving between islands
+    Migrating,
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_support::*;
+    use crate::*;
+
+    struct Recorder(Arc<Mutex<Vec<WorldPhase>>>);
+
+    impl WorldObserver for Recorder {
+        fn phase_changed(&mut self, _: usize, phase: WorldPhase) {
+            self.0.lock().unwrap().push(phase);
+        }
+    }
+
+    #[test]
+    fn observers_follow_the_world_phase() {
+        let phases = Arc::new(Mutex::new(vec![]));
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_observer(Recorder(phases.clone()))
+            .build()
+            .unwrap();
+        world.run_n_generations(1).unwrap();
+
+        use WorldPhase::*;
+        let expected = vec![
+            Filling, Idle, Evaluating, Sorting, Evaluating, Sorting, Idle, Migrating, Idle,
+        ];
+        assert_eq!(expected, *phases.lock().unwrap());
+        assert_eq!(Idle, world.phase());
+    }
+
+    #[test]
+    fn failed_generations_return_to_idle() {
+        let phases = Arc::new(Mutex::new(vec![]));
+        let mut builder = WorldBuilder::new()
+            .with_genetic_engine(counting_engine(1234))
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_observer(Recorder(phases.clone()));
+        builder.add_island("a", Box::new(FailingEngine));
+        let mut world = builder.build().unwrap();
+
+        assert!(world.run_n_generations(1).is_err());
+        assert_eq!(WorldPhase::Idle, world.phase());
+
+        use WorldPhase::*;
+        assert_eq!(
+            vec![Filling, Idle, Evaluating, Idle],
+            *phases.lock().unwrap()
+        );
+
+        phases.lock().unwrap().clear();
+        assert!(world.advance_island(0).is_err());
+        assert_eq!(WorldPhase::Idle, world.phase());
+        assert_eq!(vec![Filling, Evaluating, Idle], *phases.lock().unwrap());
+    }
+
+    #[test]
+    fn worlds_without_migration_never_migrate() {
+        let phases = Arc::new(Mutex::new(vec![]));
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(0)
+            .with_observer(Recorder(phases.clone()))
+            .build()
+            .unwrap();
+        world.run_n_generations(3).unwrap();
+
+        assert!(!phases.lock().unwrap().contains(&WorldPhase::Migrating));
+        assert_eq!(WorldPhase::Idle, world.phase());
+    }
+}
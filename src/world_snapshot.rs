@@ -0,0 +1,497 @@
+use crate::{GeneticEngineSettings, GeneticError, IslandSnapshot, MigrationAlgorithm};
+
+/// A point-in-time copy of the runtime state of a world, including a snapshot of every island in index order. Passing
+/// it to `World::restore` rolls the world back to this point, which allows branching the search and abandoning a branch
+/// whose fitness regresses. A world cannot be serialized directly because its island engines and genetics are user
+/// types, so with the `serde` feature a long-running optimization is persisted by serializing its snapshot.
+///
+/// Besides the public fields, a snapshot holds bookkeeping such as the progress of a hypermutation burst or curriculum
+/// stage. It can only be created by `World::snapshot` or `from_checkpoint`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldSnapshot {
+    /// The number of generations the world has run
+    pub generation_count: usize,
+
+    /// The number of generations left to run before the next automatic migration
+    pub generations_remaining_before_migration: usize,
+
+    /// The operator settings of the genetic engine
+    pub genetic_engine: GeneticEngineSettings,
+
+    /// A snapshot of each island, in the same order as `World::get_island`
+    pub islands: Vec<IslandSnapshot>,
+
+    pub(crate) runtime: WorldRuntime,
+}
+
+/// The part of a world's runtime state that only the world itself needs to read
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct WorldRuntime {
+    pub(crate) best_score: Option<u64>,
+    pub(crate) generations_since_improvement: usize,
+    pub(crate) hypermutation_burst: Option<(usize, u8, u8)>,
+    pub(crate) warmup_restore_rates: Option<(u8, u8)>,
+    pub(crate) curriculum_stage: usize,
+    pub(crate) curriculum_stage_start: usize,
+    pub(crate) migration_algorithm: MigrationAlgorithm,
+    pub(crate) next_scheduled_migration_change: usize,
+    pub(crate) migration_count: usize,
+    pub(crate) stats_history_len: usize,
+    pub(crate) migration_log_len: usize,
+}
+
+const CHECKPOINT_HEADER: &str = "rust_genetic_optimizer checkpoint 1";
+
+impl WorldSnapshot {
+    /// Encodes the snapshot as the plain text checkpoint format read by `from_checkpoint`. Individuals are saved by
+    /// id, so whatever the ids refer to must be saved alongside the checkpoint.
+    pub fn to_checkpoint(&self) -> String {
+        let engine = &self.genetic_engine;
+        let mut text = format!(
+            "{}\ngeneration_count {}\ngenerations_remaining_before_migration {}\ngenetic_engine {} {} {} {} {} {}\n",
+            CHECKPOINT_HEADER,
+            self.generation_count,
+            self.generations_remaining_before_migration,
+            engine.mutation_rate,
+            engine.crossover_rate,
+            engine.max_mutation_points,
+            engine.max_crossover_points,
+            engine.max_individual_points,
+            engine.reproduction_rate,
+        );
+        let runtime = &self.runtime;
+        let burst = runtime
+            .hypermutation_burst
+            .map(|(elapsed, rate, points)| vec![elapsed, rate as usize, points as usize]);
+        let warmup = runtime
+            .warmup_restore_rates
+            .map(|(mutation, crossover)| vec![mutation as usize, crossover as usize]);
+        let (algorithm, distance) = match runtime.migration_algorithm {
+            MigrationAlgorithm::Circular => ("circular", None),
+            MigrationAlgorithm::Cyclical(n) => ("cyclical", Some(n)),
+            MigrationAlgorithm::Incremental(n) => ("incremental", Some(n)),
+            MigrationAlgorithm::RandomCircular => ("random_circular", None),
+            MigrationAlgorithm::CompletelyRandom => ("completely_random", None),
+        };
+        for (key, values) in [
+            (
+                "best_score",
+                runtime
+                    .best_score
+                    .map(|score| vec![score])
+                    .unwrap_or_default(),
+            ),
+            (
+                "generations_since_improvement",
+                vec![runtime.generations_since_improvement as u64],
+            ),
+            ("hypermutation_burst", to_u64(burst)),
+            ("warmup_restore_rates", to_u64(warmup)),
+            (
+                "curriculum",
+                vec![
+                    runtime.curriculum_stage as u64,
+                    runtime.curriculum_stage_start as u64,
+                ],
+            ),
+            (
+                "migration",
+                vec![
+                    runtime.next_scheduled_migration_change as u64,
+                    runtime.migration_count as u64,
+                ],
+            ),
+            (
+                "history",
+                vec![
+                    runtime.stats_history_len as u64,
+                    runtime.migration_log_len as u64,
+                ],
+            ),
+        ] {
+            text.push_str(&format!("{}{}\n", key, ids_to_text(&values)));
+        }
+        text.push_str(&format!(
+            "migration_algorithm {}{}\n",
+            algorithm,
+            distance.map(|n| format!(" {}", n)).unwrap_or_default()
+        ));
+        for island in self.islands.iter() {
+            text.push_str(&format!(
+                "island {} {}\nindividuals{}\nfuture{}\n",
+                island.sorted as u8,
+                island.name,
+                ids_to_text(&island.individuals),
+                ids_to_text(&island.future),
+            ));
+        }
+
+        text
+    }
+
+    /// Decodes a checkpoint written by `to_checkpoint`
+    pub fn from_checkpoint(text: &str) -> Result<WorldSnapshot, GeneticError> {
+        let mut lines = text.lines();
+        if lines.next() != Some(CHECKPOINT_HEADER) {
+            return Err(invalid("missing header"));
+        }
+
+        let generation_count = parse_value(lines.next(), "generation_count")?;
+        let generations_remaining_before_migration =
+            parse_value(lines.next(), "generations_remaining_before_migration")?;
+        let mut engine: Vec<usize> = parse_values(lines.next(), "genetic_engine")?;
+        // Checkpoints written before the reproduction rate existed have five values
+        if engine.len() == 5 {
+            engine.push(0);
+        }
+        let [mutation_rate, crossover_rate, mutation_points, crossover_points, individual_points, reproduction_rate] =
+            engine[..]
+        else {
+            return Err(invalid("genetic_engine needs six values"));
+        };
+        let rate = |value: usize| {
+            u8::try_from(value).map_err(|_| invalid("genetic_engine value out of range"))
+        };
+        let genetic_engine = GeneticEngineSettings {
+            mutation_rate: rate(mutation_rate)?,
+            crossover_rate: rate(crossover_rate)?,
+            max_mutation_points: rate(mutation_points)?,
+            max_crossover_points: rate(crossover_points)?,
+            max_individual_points: individual_points,
+            reproduction_rate: rate(reproduction_rate)?,
+        };
+
+        let best_score: Vec<u64> = parse_values(lines.next(), "best_score")?;
+        let generations_since_improvement =
+            parse_value(lines.next(), "generations_since_improvement")?;
+        let burst: Vec<usize> = parse_values(lines.next(), "hypermutation_burst")?;
+        let warmup: Vec<usize> = parse_values(lines.next(), "warmup_restore_rates")?;
+        let [curriculum_stage, curriculum_stage_start] = parse_array(lines.next(), "curriculum")?;
+        let [next_scheduled_migration_change, migration_count] =
+            parse_array(lines.next(), "migration")?;
+        let [stats_history_len, migration_log_len] = parse_array(lines.next(), "history")?;
+        let runtime = WorldRuntime {
+            best_score: best_score.first().copied(),
+            generations_since_improvement,
+            hypermutation_burst: match burst[..] {
+                [] => None,
+                [elapsed, base_rate, base_points] => {
+                    Some((elapsed, rate(base_rate)?, rate(base_points)?))
+                }
+                _ => return Err(invalid("hypermutation_burst needs three values")),
+            },
+            warmup_restore_rates: match warmup[..] {
+                [] => None,
+                [mutation, crossover] => Some((rate(mutation)?, rate(crossover)?)),
+                _ => return Err(invalid("warmup_restore_rates needs two values")),
+            },
+            curriculum_stage,
+            curriculum_stage_start,
+            migration_algorithm: parse_migration_algorithm(lines.next())?,
+            next_scheduled_migration_change,
+            migration_count,
+            stats_history_len,
+            migration_log_len,
+        };
+
+        let mut islands = vec![];
+        while let Some(line) = lines.next() {
+            let (sorted, name) = line
+                .strip_prefix("island ")
+                .and_then(|rest| rest.split_once(' '))
+                .ok_or_else(|| invalid("expected an island"))?;
+            islands.push(IslandSnapshot {
+                name: name.to_string(),
+                sorted: sorted == "1",
+                individuals: parse_values(lines.next(), "individuals")?,
+                future: parse_values(lines.next(), "future")?,
+            });
+        }
+
+        Ok(WorldSnapshot {
+            generation_count,
+            generations_remaining_before_migration,
+            genetic_engine,
+            islands,
+            runtime,
+        })
+    }
+}
+
+fn invalid(reason: &str) -> GeneticError {
+    GeneticError::InvalidCheckpoint(reason.to_string())
+}
+
+fn ids_to_text(ids: &[u64]) -> String {
+    ids.iter().map(|id| format!(" {}", id)).collect()
+}
+
+// Parses a line made of a key followed by whitespace separated numbers
+fn parse_values<T: std::str::FromStr>(
+    line: Option<&str>,
+    key: &str,
+) -> Result<Vec<T>, GeneticError> {
+    let mut words = line.unwrap_or_default().split_whitespace();
+    if words.next() != Some(key) {
+        return Err(invalid(&format!("expected {}", key)));
+    }
+    words
+        .map(|word| {
+            word.parse()
+                .map_err(|_| invalid(&format!("invalid value in {}", key)))
+        })
+        .collect()
+}
+
+fn parse_array<const N: usize>(line: Option<&str>, key: &str) -> Result<[usize; N], GeneticError> {
+    parse_values(line, key)?
+        .try_into()
+        .map_err(|_| invalid(&format!("{} needs {} values", key, N)))
+}
+
+fn parse_migration_algorithm(line: Option<&str>) -> Result<MigrationAlgorithm, GeneticError> {
+    let mut words = line.unwrap_or_default().split_whitespace();
+    if words.next() != Some("migration_algorithm") {
+        return Err(invalid("expected migration_algorithm"));
+    }
+    let name = words.next();
+    let distance = words.next().and_then(|word| word.parse::<usize>().ok());
+    match (name, distance) {
+        (Some("circular"), None) => Ok(MigrationAlgorithm::Circular),
+        (Some("cyclical"), Some(n)) => Ok(MigrationAlgorithm::Cyclical(n)),
+        (Some("incremental"), Some(n)) => Ok(MigrationAlgorithm::Incremental(n)),
+        (Some("random_circular"), None) => Ok(MigrationAlgorithm::RandomCircular),
+        (Some("completely_random"), None) => Ok(MigrationAlgorithm::CompletelyRandom),
+        _ => Err(invalid("unknown migration_algorithm")),
+    }
+}
+
+fn to_u64(values: Option<Vec<usize>>) -> Vec<u64> {
+    values
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value as u64)
+        .collect()
+}
+
+fn parse_value(line: Option<&str>, key: &str) -> Result<usize, GeneticError> {
+    match parse_values(line, key)?[..] {
+        [value] => Ok(value),
+        _ => Err(invalid(&format!("{} needs one value", key))),
+    }
+}
+
+#[cfg(all(test, not(feature = "async")))]
+mod tests {
+    use super::WorldRuntime;
+    use crate::test_support::*;
+    use crate::*;
+
+    #[test]
+    fn checkpoints_round_trip() {
+        let snapshot = WorldSnapshot {
+            generation_count: 12,
+            generations_remaining_before_migration: 3,
+            genetic_engine: GeneticEngineSettings {
+                mutation_rate: 1,
+                crossover_rate: 9,
+                max_mutation_points: 3,
+                max_crossover_points: 10,
+                max_individual_points: 100,
+                reproduction_rate: 2,
+            },
+            islands: vec![
+                IslandSnapshot {
+                    name: "first island".to_string(),
+                    individuals: vec![5, 2, 9],
+                    sorted: true,
+                    future: vec![7],
+                },
+                IslandSnapshot {
+                    name: "empty".to_string(),
+                    individuals: vec![],
+                    sorted: false,
+                    future: vec![],
+                },
+            ],
+            runtime: WorldRuntime {
+                best_score: Some(40),
+                generations_since_improvement: 2,
+                hypermutation_burst: Some((1, 1, 3)),
+                warmup_restore_rates: None,
+                curriculum_stage: 1,
+                curriculum_stage_start: 8,
+                migration_algorithm: MigrationAlgorithm::Incremental(2),
+                next_scheduled_migration_change: 1,
+                migration_count: 6,
+                stats_history_len: 12,
+                migration_log_len: 12,
+            },
+        };
+
+        let text = snapshot.to_checkpoint();
+        assert_eq!(snapshot, WorldSnapshot::from_checkpoint(&text).unwrap());
+        assert!(matches!(
+            WorldSnapshot::from_checkpoint(&text.replace("future 7", "future x")),
+            Err(GeneticError::InvalidCheckpoint(_))
+        ));
+    }
+
+    #[test]
+    fn worlds_resume_from_checkpoints() {
+        let directory =
+            std::env::temp_dir().join(format!("checkpoint-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let automatic = directory.join("automatic.checkpoint");
+        let manual = directory.join("manual.checkpoint");
+        let builder = || {
+            world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_generations_between_migrations(2)
+        };
+
+        let mut world = builder()
+            .with_checkpoint_every(2, &automatic)
+            .build()
+            .unwrap();
+        world.run_n_generations(3).unwrap();
+        world.genetic_engine_mut().set_mutation_rate(4).unwrap();
+        world.checkpoint_to(&manual).unwrap();
+        let automatic_text = std::fs::read_to_string(&automatic).unwrap();
+        assert_eq!(
+            2,
+            WorldSnapshot::from_checkpoint(&automatic_text)
+                .unwrap()
+                .generation_count
+        );
+
+        let mut resumed = builder().build().unwrap();
+        resumed.resume_from(&manual).unwrap();
+        let (original, restored) = (world.snapshot(), resumed.snapshot());
+        assert_eq!(original.generation_count, restored.generation_count);
+        assert_eq!(original.genetic_engine, restored.genetic_engine);
+        assert_eq!(original.islands, restored.islands);
+        assert_eq!(world.best_score(), resumed.best_score());
+        resumed.run_n_generations(1).unwrap();
+        assert_eq!(4, resumed.generation_count());
+
+        let mut renamed = world_builder(&["a", "c"]).build().unwrap();
+        assert!(matches!(
+            renamed.resume_from(&manual),
+            Err(GeneticError::UnknownIsland(name)) if name == "b"
+        ));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn worlds_roll_back_to_snapshots() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(2)
+            .build()
+            .unwrap();
+        world.run_n_generations(3).unwrap();
+        let saved = world.snapshot();
+        let saved_stats = world.stats_history().to_vec();
+
+        world.genetic_engine_mut().set_rates(9, 1).unwrap();
+        world.run_n_generations(4).unwrap();
+        world.restore(&saved).unwrap();
+
+        assert_eq!(saved, world.snapshot());
+        assert_eq!(saved_stats, world.stats_history());
+        assert_eq!(1, world.genetic_engine().mutation_rate());
+        world.run_n_generations(1).unwrap();
+        assert_eq!(4, world.stats_history().len());
+    }
+
+    #[test]
+    fn restoring_keeps_the_archive_and_truncates_the_history() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .with_generations_between_migrations(1)
+            .with_archive(20, 0.5)
+            .build()
+            .unwrap();
+        world.run_n_generations(2).unwrap();
+        let saved = world.snapshot();
+        let saved_log = world.migration_log().len();
+        let saved_archive = world.archive().unwrap().members().to_vec();
+
+        world.run_n_generations(3).unwrap();
+        let archive = world.archive().unwrap().members().to_vec();
+        assert!(archive.len() > saved_archive.len());
+        world.restore(&saved).unwrap();
+
+        assert_eq!(archive, world.archive().unwrap().members());
+        assert_eq!(2, world.stats_history().len());
+        assert_eq!(saved_log, world.migration_log().len());
+    }
+
+    #[test]
+    fn resuming_treats_the_archive_and_history_like_restoring() {
+        let checkpoint =
+            std::env::temp_dir().join(format!("archive-checkpoint-{}", std::process::id()));
+        let builder = || {
+            world_builder(&["a", "b"])
+                .with_individuals_per_island(4)
+                .with_migrating_individuals(1)
+                .with_generations_between_migrations(1)
+                .with_archive(20, 0.5)
+        };
+        let mut world = builder().build().unwrap();
+        world.run_n_generations(2).unwrap();
+        world.checkpoint_to(&checkpoint).unwrap();
+        let saved_log = world.migration_log().len();
+
+        // The world that wrote the checkpoint keeps its archive and truncates its history
+        world.run_n_generations(3).unwrap();
+        let archive = world.archive().unwrap().members().to_vec();
+        world.resume_from(&checkpoint).unwrap();
+        assert_eq!(archive, world.archive().unwrap().members());
+        assert_eq!(2, world.stats_history().len());
+        assert_eq!(saved_log, world.migration_log().len());
+
+        // A new world starts its archive and history over
+        let mut resumed = builder().build().unwrap();
+        resumed.resume_from(&checkpoint).unwrap();
+        assert_eq!(2, resumed.generation_count());
+        assert!(resumed.archive().unwrap().is_empty());
+        assert!(resumed.stats_history().is_empty());
+        assert!(resumed.migration_log().is_empty());
+        resumed.run_n_generations(1).unwrap();
+        assert_eq!(1, resumed.stats_history().len());
+        std::fs::remove_file(&checkpoint).unwrap();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshots_round_trip_through_serde() {
+        let mut world = world_builder(&["a", "b"])
+            .with_individuals_per_island(4)
+            .with_migrating_individuals(1)
+            .build()
+            .unwrap();
+        world.run_n_generations(3).unwrap();
+        world.fill_all_islands().unwrap();
+
+        let snapshot = world.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(
+            snapshot,
+            serde_json::from_str::<WorldSnapshot>(&json).unwrap()
+        );
+
+        let stats = world.stats_history().to_vec();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(
+            stats,
+            serde_json::from_str::<Vec<GenerationStats>>(&json).unwrap()
+        );
+    }
+}